@@ -1,15 +1,45 @@
+use std::collections::VecDeque;
 use std::ops::{Index, IndexMut};
 
-use sdl2::audio::AudioCallback;
+use sdl2::audio::{AudioCallback, AudioFormat};
 use thiserror::Error;
 
-use crate::audio::{AudioBuffer, AudioGenerator, AudioSpec, NUM_CHANNELS};
+use crate::audio::{
+	AudioBuffer, AudioGenerator, AudioSpec, NUM_CHANNELS, TARGET_AUDIO_CHANNELS, TARGET_AUDIO_FREQUENCY,
+};
+
+/// The maximum number of [`AudioEvent`]s that will be held by an [`AudioDevice`] waiting to be
+/// taken by the application. Once this limit is reached, the oldest events will be discarded.
+pub const MAX_PENDING_AUDIO_EVENTS: usize = 64;
+
+/// Notifications about changes in playback status of an [`AudioChannel`] which occurred during
+/// audio mixing. These are collected by the [`AudioDevice`] and can be retrieved by the
+/// application via [`AudioDevice::take_events`] or [`crate::audio::AudioQueue::take_events`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum AudioEvent {
+	/// The non-looping channel with the given index reached the end of its data and has stopped.
+	ChannelFinished(usize),
+	/// The looping channel with the given index reached the end of its data and has wrapped
+	/// back around to the start.
+	ChannelLooped(usize),
+}
+
+/// Playback status change that occurred as a result of sampling an [`AudioChannel`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum ChannelStatusChange {
+	Finished,
+	Looped,
+}
 
 /// Represents a "channel" of audio playback that will be mixed together with all of the other
 /// actively playing audio channels to get the final audio playback.
 pub struct AudioChannel {
 	/// Whether the channel is currently playing or not.
 	pub playing: bool,
+	/// Whether playback on this channel is currently paused. A paused channel is still considered
+	/// to be [`playing`] (and so will not be picked as a free channel for new playback), but it
+	/// will not produce any samples and its position will not advance until it is resumed.
+	pub paused: bool,
 	/// Whether this channel is playing on a loop or not. If not, once the end of the [`data`]
 	/// buffer is reached, or the [`AudioGenerator::gen_sample`] method returns `None`, playback
 	/// on this channel will automatically stop and [`playing`] will be changed to `false`.
@@ -27,12 +57,14 @@ pub struct AudioChannel {
 	/// either the (current) size of the [`data`] buffer or dependant on the implementation of this
 	/// channel's current [`generator`] if not `None`.
 	pub position: usize,
+	spec: AudioSpec,
 }
 
 impl std::fmt::Debug for AudioChannel {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		f.debug_struct("AudioChannel")
 			.field("playing", &self.playing)
+			.field("paused", &self.paused)
 			.field("loops", &self.loops)
 			.field("data.len()", &self.data.len())
 			.field(
@@ -44,22 +76,34 @@ impl std::fmt::Debug for AudioChannel {
 			)
 			.field("volume", &self.volume)
 			.field("position", &self.position)
+			.field("spec", &self.spec)
 			.finish_non_exhaustive()
 	}
 }
 
 impl AudioChannel {
+	/// Creates a new, empty, [`AudioChannel`]. The channel takes on the spec of the [`AudioDevice`]
+	/// it belongs to, or of the last [`AudioBuffer`] played on it. Until then, times are
+	/// calculated assuming 8-bit audio at [`TARGET_AUDIO_FREQUENCY`] and [`TARGET_AUDIO_CHANNELS`].
 	pub fn new() -> Self {
 		AudioChannel {
 			playing: false, //
+			paused: false,
 			loops: false,
 			volume: 1.0,
 			position: 0,
 			generator: None,
 			data: Vec::new(),
+			spec: AudioSpec::new(TARGET_AUDIO_FREQUENCY, TARGET_AUDIO_CHANNELS, AudioFormat::U8),
 		}
 	}
 
+	/// Returns the spec of the audio that this channel plays.
+	#[inline]
+	pub fn spec(&self) -> &AudioSpec {
+		&self.spec
+	}
+
 	/// Returns the audio sample for the given position, or `None` if that position is invalid.
 	#[inline]
 	fn data_at(&mut self, position: usize) -> Option<u8> {
@@ -97,18 +141,25 @@ impl AudioChannel {
 	/// instead of 0 to 255).
 	#[inline]
 	pub fn sample(&mut self) -> Option<i16> {
-		if !self.playing {
-			return None;
+		self.sample_with_status().0
+	}
+
+	/// Same as [`AudioChannel::sample`], but also returns any change in playback status that
+	/// occurred as a result of reaching the end of the channel's audio data.
+	#[inline]
+	fn sample_with_status(&mut self) -> (Option<i16>, Option<ChannelStatusChange>) {
+		if !self.playing || self.paused {
+			return (None, None);
 		}
 
 		if let Some(sample) = self.next_sample() {
-			Some((sample as f32 * self.volume) as i16)
+			(Some((sample as f32 * self.volume) as i16), None)
 		} else if self.loops {
 			self.position = 0;
-			None
+			(None, Some(ChannelStatusChange::Looped))
 		} else {
 			self.stop();
-			None
+			(None, Some(ChannelStatusChange::Finished))
 		}
 	}
 
@@ -120,6 +171,7 @@ impl AudioChannel {
 		self.generator = None;
 		self.position = 0;
 		self.playing = false;
+		self.paused = false;
 	}
 
 	/// Copies the data from the given audio buffer into this channel's buffer (clearing it first,
@@ -129,9 +181,11 @@ impl AudioChannel {
 	pub fn play_buffer(&mut self, buffer: &AudioBuffer, loops: bool) {
 		self.data.clear();
 		self.data.extend(&buffer.data);
+		self.spec = *buffer.spec();
 		self.generator = None;
 		self.position = 0;
 		self.playing = true;
+		self.paused = false;
 		self.loops = loops;
 	}
 
@@ -143,6 +197,7 @@ impl AudioChannel {
 		self.generator = Some(generator);
 		self.position = 0;
 		self.playing = true;
+		self.paused = false;
 		self.loops = loops;
 	}
 
@@ -160,6 +215,7 @@ impl AudioChannel {
 		if self.is_playable() {
 			self.position = 0;
 			self.playing = true;
+			self.paused = false;
 			self.loops = loops;
 			true
 		} else {
@@ -171,6 +227,85 @@ impl AudioChannel {
 	#[inline]
 	pub fn stop(&mut self) {
 		self.playing = false;
+		self.paused = false;
+	}
+
+	/// Pauses playback on this channel. The channel's current position is retained, and playback
+	/// can be continued from that point with [`AudioChannel::resume`].
+	#[inline]
+	pub fn pause(&mut self) {
+		if self.playing {
+			self.paused = true;
+		}
+	}
+
+	/// Resumes playback on this channel if it was previously paused.
+	#[inline]
+	pub fn resume(&mut self) {
+		self.paused = false;
+	}
+
+	/// Returns true if this channel is currently playing, but paused.
+	#[inline]
+	pub fn is_paused(&self) -> bool {
+		self.playing && self.paused
+	}
+
+	/// Returns the total length of this channel's audio data, in samples (the same units as
+	/// [`position`]). Returns `None` if this channel is playing from an [`AudioGenerator`], as the
+	/// length of generated audio is not known ahead of time.
+	#[inline]
+	pub fn length(&self) -> Option<usize> {
+		if self.generator.is_some() {
+			None
+		} else {
+			Some(self.data.len())
+		}
+	}
+
+	/// Returns the amount of time that has been played so far on this channel (that is, the time
+	/// corresponding to the current [`position`]), in seconds.
+	#[inline]
+	pub fn elapsed(&self) -> f32 {
+		self.spec.position_to_seconds(self.position)
+	}
+
+	/// Returns the total play time of this channel's audio data, in seconds. Returns `None` if
+	/// this channel is playing from an [`AudioGenerator`], as the length of generated audio is not
+	/// known ahead of time.
+	#[inline]
+	pub fn duration(&self) -> Option<f32> {
+		self.length().map(|length| self.spec.position_to_seconds(length))
+	}
+
+	/// Returns how far playback has progressed through this channel's audio data as a value
+	/// between 0.0 (start) and 1.0 (end). Returns `None` if this channel is playing from an
+	/// [`AudioGenerator`], or if there is no audio data at all.
+	#[inline]
+	pub fn progress(&self) -> Option<f32> {
+		match self.length() {
+			Some(length) if length > 0 => Some((self.position as f32 / length as f32).min(1.0)),
+			_ => None,
+		}
+	}
+
+	/// Moves the current playback position to the given position (index). If this channel is
+	/// playing from audio data (and not an [`AudioGenerator`]), the position is clamped to the
+	/// length of that data.
+	#[inline]
+	pub fn seek(&mut self, position: usize) {
+		self.position = match self.length() {
+			Some(length) => position.min(length),
+			None => position,
+		};
+	}
+
+	/// Moves the current playback position to the position corresponding to the given time, in
+	/// seconds. If this channel is playing from audio data (and not an [`AudioGenerator`]), the
+	/// position is clamped to the length of that data.
+	#[inline]
+	pub fn seek_to_time(&mut self, seconds: f32) {
+		self.seek(self.spec.seconds_to_position(seconds));
 	}
 }
 
@@ -192,6 +327,7 @@ pub enum AudioDeviceError {
 pub struct AudioDevice {
 	spec: AudioSpec,
 	channels: Vec<AudioChannel>,
+	events: VecDeque<AudioEvent>,
 	pub volume: f32,
 }
 
@@ -203,10 +339,21 @@ impl AudioCallback for AudioDevice {
 	fn callback(&mut self, out: &mut [u8]) {
 		for dest in out.iter_mut() {
 			let mut sample: i16 = 0;
-			for channel in self.channels.iter_mut() {
-				if let Some(this_sample) = channel.sample() {
+			for (index, channel) in self.channels.iter_mut().enumerate() {
+				let (this_sample, status_change) = channel.sample_with_status();
+				if let Some(this_sample) = this_sample {
 					sample += this_sample;
 				}
+				if let Some(status_change) = status_change {
+					let event = match status_change {
+						ChannelStatusChange::Finished => AudioEvent::ChannelFinished(index),
+						ChannelStatusChange::Looped => AudioEvent::ChannelLooped(index),
+					};
+					if self.events.len() >= MAX_PENDING_AUDIO_EVENTS {
+						self.events.pop_front();
+					}
+					self.events.push_back(event);
+				}
 			}
 			sample = ((sample as f32) * self.volume) as i16;
 			*dest = (sample.clamp(-128, 127) + 128) as u8;
@@ -219,9 +366,16 @@ impl AudioDevice {
 	pub fn new(spec: AudioSpec) -> Self {
 		let mut channels = Vec::new();
 		for _ in 0..NUM_CHANNELS {
-			channels.push(AudioChannel::new());
+			let mut channel = AudioChannel::new();
+			channel.spec = spec;
+			channels.push(channel);
+		}
+		AudioDevice {
+			spec, //
+			channels,
+			events: VecDeque::with_capacity(MAX_PENDING_AUDIO_EVENTS),
+			volume: 1.0,
 		}
-		AudioDevice { spec, channels, volume: 1.0 }
 	}
 
 	/// Returns the spec that this device is currently set to play. All audio to be played via
@@ -255,6 +409,51 @@ impl AudioDevice {
 		}
 	}
 
+	/// Pauses the specified channel's playback, or does nothing if that channel was not currently
+	/// playing. Playback can be continued from the same position with [`AudioDevice::resume_channel`].
+	pub fn pause_channel(&mut self, channel_index: usize) -> Result<(), AudioDeviceError> {
+		if channel_index >= NUM_CHANNELS {
+			Err(AudioDeviceError::ChannelIndexOutOfRange(channel_index))
+		} else {
+			self.channels[channel_index].pause();
+			Ok(())
+		}
+	}
+
+	/// Resumes the specified channel's playback if it was previously paused.
+	pub fn resume_channel(&mut self, channel_index: usize) -> Result<(), AudioDeviceError> {
+		if channel_index >= NUM_CHANNELS {
+			Err(AudioDeviceError::ChannelIndexOutOfRange(channel_index))
+		} else {
+			self.channels[channel_index].resume();
+			Ok(())
+		}
+	}
+
+	/// Moves the specified channel's playback position to the position corresponding to the
+	/// given time, in seconds.
+	pub fn seek_channel(&mut self, channel_index: usize, seconds: f32) -> Result<(), AudioDeviceError> {
+		if channel_index >= NUM_CHANNELS {
+			Err(AudioDeviceError::ChannelIndexOutOfRange(channel_index))
+		} else {
+			self.channels[channel_index].seek_to_time(seconds);
+			Ok(())
+		}
+	}
+
+	/// Returns true if there are any [`AudioEvent`]s waiting to be taken.
+	#[inline]
+	pub fn has_events(&self) -> bool {
+		!self.events.is_empty()
+	}
+
+	/// Removes and returns all of the [`AudioEvent`]s that have been collected during audio mixing
+	/// since the last time this was called, in the order that they occurred.
+	#[inline]
+	pub fn take_events(&mut self) -> std::collections::vec_deque::Drain<AudioEvent> {
+		self.events.drain(..)
+	}
+
 	/// Tries to play the given [`AudioBuffer`] on the first channel found that is not already
 	/// playing. If a free channel is found, playback will be started by copying the buffer's
 	/// contents to the channel. The index of the channel is returned. If playback was not started
@@ -391,3 +590,104 @@ impl IndexMut<usize> for AudioDevice {
 		self.get_mut(index).unwrap()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use sdl2::audio::AudioFormat;
+
+	use crate::audio::AUDIO_FREQUENCY_22KHZ;
+
+	use super::*;
+
+	fn test_spec() -> AudioSpec {
+		AudioSpec::new(AUDIO_FREQUENCY_22KHZ, 1, AudioFormat::U8)
+	}
+
+	fn test_buffer(length: usize) -> AudioBuffer {
+		let mut buffer = AudioBuffer::new(test_spec());
+		buffer.data.resize(length, 200);
+		buffer
+	}
+
+	#[test]
+	pub fn channel_time_and_seeking() {
+		let mut channel = AudioChannel::new();
+		assert_eq!(None, channel.progress());
+
+		channel.play_buffer(&test_buffer(AUDIO_FREQUENCY_22KHZ as usize * 2), false);
+		assert_eq!(test_spec(), *channel.spec());
+		assert_eq!(Some(2.0), channel.duration());
+		assert_eq!(0.0, channel.elapsed());
+		assert_eq!(Some(0.0), channel.progress());
+
+		channel.seek_to_time(0.5);
+		assert_eq!(AUDIO_FREQUENCY_22KHZ as usize / 2, channel.position);
+		assert_eq!(0.5, channel.elapsed());
+		assert_eq!(Some(0.25), channel.progress());
+
+		channel.seek_to_time(10.0);
+		assert_eq!(AUDIO_FREQUENCY_22KHZ as usize * 2, channel.position);
+		assert_eq!(Some(1.0), channel.progress());
+
+		channel.seek(100);
+		assert_eq!(100, channel.position);
+	}
+
+	#[test]
+	pub fn channel_pause_and_resume() {
+		let mut channel = AudioChannel::new();
+		channel.play_buffer(&test_buffer(8), false);
+
+		assert!(channel.sample().is_some());
+		assert_eq!(1, channel.position);
+
+		channel.pause();
+		assert!(channel.is_paused());
+		assert!(channel.playing);
+		assert_eq!(None, channel.sample());
+		assert_eq!(1, channel.position);
+
+		channel.resume();
+		assert!(!channel.is_paused());
+		assert!(channel.sample().is_some());
+		assert_eq!(2, channel.position);
+
+		channel.stop();
+		channel.pause();
+		assert!(!channel.is_paused());
+	}
+
+	#[test]
+	pub fn device_collects_channel_events() {
+		let mut device = AudioDevice::new(test_spec());
+		let mut out = [0u8; 8];
+
+		device.play_buffer_on_channel(0, &test_buffer(4), false).unwrap();
+		device.play_buffer_on_channel(2, &test_buffer(3), true).unwrap();
+		assert!(!device.has_events());
+
+		device.callback(&mut out);
+		assert!(!device[0].playing);
+		assert!(device[2].playing);
+		let events = device.take_events().collect::<Vec<_>>();
+		assert_eq!(
+			vec![AudioEvent::ChannelLooped(2), AudioEvent::ChannelFinished(0), AudioEvent::ChannelLooped(2)],
+			events
+		);
+		assert!(!device.has_events());
+
+		device.stop_all();
+		device.callback(&mut out);
+		assert!(!device.has_events());
+	}
+
+	#[test]
+	pub fn device_limits_pending_events() {
+		let mut device = AudioDevice::new(test_spec());
+		let mut out = [0u8; 1024];
+
+		device.play_buffer_on_channel(0, &test_buffer(1), true).unwrap();
+		device.callback(&mut out);
+		assert_eq!(MAX_PENDING_AUDIO_EVENTS, device.take_events().count());
+	}
+}
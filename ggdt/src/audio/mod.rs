@@ -63,6 +63,39 @@ impl AudioSpec {
 	pub fn format(&self) -> AudioFormat {
 		self.format
 	}
+
+	/// Returns the size of a single sample for a single channel, in bytes.
+	#[inline]
+	pub fn bytes_per_sample(&self) -> usize {
+		use AudioFormat::*;
+		match self.format {
+			U8 | S8 => 1,
+			U16LSB | U16MSB | S16LSB | S16MSB => 2,
+			S32LSB | S32MSB | F32LSB | F32MSB => 4,
+		}
+	}
+
+	/// Returns the number of bytes of audio data that make up one second of playback.
+	#[inline]
+	pub fn bytes_per_second(&self) -> usize {
+		self.frequency as usize * self.channels as usize * self.bytes_per_sample()
+	}
+
+	/// Converts a position (byte index) within an audio buffer in this spec to a time, in seconds.
+	#[inline]
+	pub fn position_to_seconds(&self, position: usize) -> f32 {
+		position as f32 / self.bytes_per_second() as f32
+	}
+
+	/// Converts a time, in seconds, to a position (byte index) within an audio buffer in this
+	/// spec. The returned position is always aligned to the start of a sample frame. Negative
+	/// times are treated as zero.
+	#[inline]
+	pub fn seconds_to_position(&self, seconds: f32) -> usize {
+		let frame_size = self.channels as usize * self.bytes_per_sample();
+		let frames = (seconds.max(0.0) * self.frequency as f32) as usize;
+		frames * frame_size
+	}
 }
 
 //////////////////////////////////////////////////////////////////////////////////////////////////
@@ -1,11 +1,20 @@
 use std::collections::VecDeque;
 use std::rc::Rc;
 
-use crate::audio::{Audio, AudioBuffer, AudioDevice, AudioDeviceError, AudioGenerator, AudioSpec, NUM_CHANNELS};
+use crate::audio::{
+	Audio, AudioBuffer, AudioDevice, AudioDeviceError, AudioEvent, AudioGenerator, AudioSpec, NUM_CHANNELS,
+};
+use crate::events::EventPublisher;
 
 pub enum AudioCommand {
 	StopChannel(usize),
 	StopAllChannels,
+	PauseChannel(usize),
+	ResumeChannel(usize),
+	SeekChannel {
+		channel: usize, //
+		seconds: f32,
+	},
 	PlayBuffer {
 		buffer: AudioBuffer, //
 		loops: bool,
@@ -41,6 +50,14 @@ impl std::fmt::Debug for AudioCommand {
 		match self {
 			StopChannel(n) => write!(f, "StopChannel({})", n),
 			StopAllChannels => write!(f, "StopAllChannels"),
+			PauseChannel(n) => write!(f, "PauseChannel({})", n),
+			ResumeChannel(n) => write!(f, "ResumeChannel({})", n),
+			SeekChannel { channel, seconds } => {
+				f.debug_struct("SeekChannel") //
+					.field("channel", channel)
+					.field("seconds", seconds)
+					.finish()
+			}
 			PlayBuffer { buffer, loops } => {
 				f.debug_struct("PlayBuffer") //
 					.field("buffer", buffer)
@@ -87,16 +104,25 @@ impl std::fmt::Debug for AudioCommand {
 /// lock the [`AudioDevice`] and then determine what your application needs to do and issue those
 /// commands that time. [`AudioQueue`] lets you play/stop audio in more of a "fire-and-forget"
 /// manner.
+///
+/// Any [`AudioEvent`]s that were collected by the [`AudioDevice`] are also retrieved each time
+/// the queued commands are flushed, and can then be obtained via [`AudioQueue::take_events`] or
+/// [`AudioQueue::publish_events`].
 #[derive(Debug)]
 pub struct AudioQueue {
 	spec: AudioSpec,
 	commands: VecDeque<AudioCommand>,
+	events: VecDeque<AudioEvent>,
 }
 
 impl AudioQueue {
 	/// Creates and returns a new [`AudioQueue`] instance.
 	pub fn new(audio: &Audio) -> Self {
-		AudioQueue { spec: audio.spec, commands: VecDeque::new() }
+		AudioQueue {
+			spec: audio.spec, //
+			commands: VecDeque::new(),
+			events: VecDeque::new(),
+		}
 	}
 
 	/// Returns the spec that this queue is currently set to play. All audio to be played via
@@ -122,6 +148,37 @@ impl AudioQueue {
 		self.commands.push_back(AudioCommand::StopAllChannels);
 	}
 
+	/// Queues a pause command for the given channel.
+	pub fn pause_channel(&mut self, channel_index: usize) -> Result<(), AudioDeviceError> {
+		if channel_index >= NUM_CHANNELS {
+			Err(AudioDeviceError::ChannelIndexOutOfRange(channel_index))
+		} else {
+			self.commands.push_back(AudioCommand::PauseChannel(channel_index));
+			Ok(())
+		}
+	}
+
+	/// Queues a command to resume playback on the given (paused) channel.
+	pub fn resume_channel(&mut self, channel_index: usize) -> Result<(), AudioDeviceError> {
+		if channel_index >= NUM_CHANNELS {
+			Err(AudioDeviceError::ChannelIndexOutOfRange(channel_index))
+		} else {
+			self.commands.push_back(AudioCommand::ResumeChannel(channel_index));
+			Ok(())
+		}
+	}
+
+	/// Queues a command to move the given channel's playback position to the given time, in
+	/// seconds.
+	pub fn seek_channel(&mut self, channel_index: usize, seconds: f32) -> Result<(), AudioDeviceError> {
+		if channel_index >= NUM_CHANNELS {
+			Err(AudioDeviceError::ChannelIndexOutOfRange(channel_index))
+		} else {
+			self.commands.push_back(AudioCommand::SeekChannel { channel: channel_index, seconds });
+			Ok(())
+		}
+	}
+
 	/// Queues a command to play a copy of the given [`AudioBuffer`]'s data. The buffer will be
 	/// played on the first channel found that is not already playing. If all channels are already
 	/// playing, then nothing will be done.
@@ -220,9 +277,34 @@ impl AudioQueue {
 		Ok(())
 	}
 
+	/// Returns true if there are any [`AudioEvent`]s that were retrieved from the [`AudioDevice`]
+	/// which have not been taken yet.
+	#[inline]
+	pub fn has_events(&self) -> bool {
+		!self.events.is_empty()
+	}
+
+	/// Removes and returns all of the [`AudioEvent`]s retrieved from the [`AudioDevice`] the last
+	/// time(s) that queued commands were flushed, in the order that they occurred.
+	#[inline]
+	pub fn take_events(&mut self) -> std::collections::vec_deque::Drain<AudioEvent> {
+		self.events.drain(..)
+	}
+
+	/// Removes all of the [`AudioEvent`]s retrieved from the [`AudioDevice`] the last time(s) that
+	/// queued commands were flushed, and queues them up, in the order that they occurred, in the
+	/// given [`EventPublisher`].
+	pub fn publish_events<EventType: From<AudioEvent>>(&mut self, publisher: &mut EventPublisher<EventType>) {
+		for event in self.events.drain(..) {
+			publisher.queue(event.into());
+		}
+	}
+
 	/// Flushes the queued commands, issuing them in the same order they were created, to the
-	/// given [`AudioDevice`].
+	/// given [`AudioDevice`]. Any [`AudioEvent`]s that the [`AudioDevice`] has collected are
+	/// also retrieved.
 	pub fn apply_to_device(&mut self, device: &mut AudioDevice) -> Result<(), AudioDeviceError> {
+		self.events.extend(device.take_events());
 		loop {
 			if let Some(command) = self.commands.pop_front() {
 				use AudioCommand::*;
@@ -233,6 +315,15 @@ impl AudioQueue {
 					StopAllChannels => {
 						device.stop_all();
 					}
+					PauseChannel(channel_index) => {
+						device.pause_channel(channel_index)?;
+					}
+					ResumeChannel(channel_index) => {
+						device.resume_channel(channel_index)?;
+					}
+					SeekChannel { channel, seconds } => {
+						device.seek_channel(channel, seconds)?;
+					}
 					PlayBuffer { buffer, loops } => {
 						device.play_buffer(&buffer, loops)?;
 					}
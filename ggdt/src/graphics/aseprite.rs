@@ -0,0 +1,1256 @@
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read};
+use std::path::Path;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+use thiserror::Error;
+
+use crate::graphics::{
+	BitmapAtlas, BitmapAtlasError, GeneralBitmap, GeneralBlitMethod, IndexedBitmap, Palette, RgbaBitmap, RGBA,
+};
+use crate::math::Rect;
+
+#[derive(Error, Debug)]
+pub enum AsepriteError {
+	#[error("Bad or unsupported Aseprite file: {0}")]
+	BadFile(String),
+
+	#[error("Frame index {0} is invalid / out of range")]
+	InvalidFrame(usize),
+
+	#[error("Layer index {0} is invalid / out of range")]
+	InvalidLayer(usize),
+
+	#[error("Aseprite file does not use indexed color")]
+	NotIndexed,
+
+	#[error("Aseprite bitmap atlas error")]
+	BitmapAtlasError(#[from] BitmapAtlasError),
+
+	#[error("Aseprite I/O error")]
+	IOError(#[from] std::io::Error),
+}
+
+const HEADER_MAGIC: u16 = 0xa5e0;
+const FRAME_MAGIC: u16 = 0xf1fa;
+const HEADER_SIZE: usize = 128;
+const FRAME_HEADER_SIZE: usize = 16;
+const CHUNK_HEADER_SIZE: usize = 6;
+
+const HEADER_FLAG_LAYER_OPACITY_VALID: u32 = 1;
+
+const CHUNK_OLD_PALETTE: u16 = 0x0004;
+const CHUNK_OLD_PALETTE_6BIT: u16 = 0x0011;
+const CHUNK_LAYER: u16 = 0x2004;
+const CHUNK_CEL: u16 = 0x2005;
+const CHUNK_TAGS: u16 = 0x2018;
+const CHUNK_PALETTE: u16 = 0x2019;
+const CHUNK_SLICE: u16 = 0x2022;
+
+const LAYER_FLAG_VISIBLE: u16 = 1;
+const LAYER_FLAG_BACKGROUND: u16 = 8;
+
+const CEL_TYPE_RAW: u16 = 0;
+const CEL_TYPE_LINKED: u16 = 1;
+const CEL_TYPE_COMPRESSED: u16 = 2;
+
+const PALETTE_ENTRY_FLAG_HAS_NAME: u16 = 1;
+
+const SLICE_FLAG_NINE_PATCH: u32 = 1;
+const SLICE_FLAG_PIVOT: u32 = 2;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AsepriteColorDepth {
+	Rgba,
+	Grayscale,
+	Indexed,
+}
+
+impl AsepriteColorDepth {
+	fn from(value: u16) -> Result<Self, AsepriteError> {
+		use AsepriteColorDepth::*;
+		match value {
+			32 => Ok(Rgba),
+			16 => Ok(Grayscale),
+			8 => Ok(Indexed),
+			_ => Err(AsepriteError::BadFile(format!("Unsupported color depth: {}", value))),
+		}
+	}
+
+	fn bytes_per_pixel(&self) -> usize {
+		use AsepriteColorDepth::*;
+		match self {
+			Rgba => 4,
+			Grayscale => 2,
+			Indexed => 1,
+		}
+	}
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AsepriteLayerType {
+	Normal,
+	Group,
+	Tilemap,
+}
+
+/// The blend modes that can be set on Aseprite layers, which determine how a layer's pixels are combined with the
+/// pixels of the layers below it when a frame is flattened.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AsepriteBlendMode {
+	Normal,
+	Multiply,
+	Screen,
+	Overlay,
+	Darken,
+	Lighten,
+	ColorDodge,
+	ColorBurn,
+	HardLight,
+	SoftLight,
+	Difference,
+	Exclusion,
+	Hue,
+	Saturation,
+	Color,
+	Luminosity,
+	Addition,
+	Subtract,
+	Divide,
+}
+
+impl AsepriteBlendMode {
+	fn from(value: u16) -> Result<Self, AsepriteError> {
+		use AsepriteBlendMode::*;
+		Ok(match value {
+			0 => Normal,
+			1 => Multiply,
+			2 => Screen,
+			3 => Overlay,
+			4 => Darken,
+			5 => Lighten,
+			6 => ColorDodge,
+			7 => ColorBurn,
+			8 => HardLight,
+			9 => SoftLight,
+			10 => Difference,
+			11 => Exclusion,
+			12 => Hue,
+			13 => Saturation,
+			14 => Color,
+			15 => Luminosity,
+			16 => Addition,
+			17 => Subtract,
+			18 => Divide,
+			_ => return Err(AsepriteError::BadFile(format!("Unsupported layer blend mode: {}", value))),
+		})
+	}
+
+	/// Blends the source color over the destination color using this blend mode, the same way that Aseprite does
+	/// when flattening layers.
+	///
+	/// # Arguments
+	///
+	/// * `src`: the source color to blend
+	/// * `dest`: the destination color to blend the source color over
+	/// * `opacity`: the opacity to apply to the source color (on top of its own alpha)
+	///
+	/// returns: the blended color
+	pub fn blend(&self, src: RGBA, dest: RGBA, opacity: u8) -> RGBA {
+		use AsepriteBlendMode::*;
+		if dest.a() == 0 || *self == Normal {
+			return blend_normal(src, dest, opacity);
+		}
+
+		let [sr, sg, sb, sa] = src.to_array();
+		let [dr, dg, db, _] = dest.to_array();
+		let separable = |f: fn(u8, u8) -> u8| [f(dr, sr), f(dg, sg), f(db, sb)];
+		let [r, g, b] = match self {
+			Normal => unreachable!(),
+			Multiply => separable(blend_multiply),
+			Screen => separable(blend_screen),
+			Overlay => separable(|b, s| blend_hard_light(s, b)),
+			Darken => separable(|b, s| b.min(s)),
+			Lighten => separable(|b, s| b.max(s)),
+			ColorDodge => separable(blend_color_dodge),
+			ColorBurn => separable(blend_color_burn),
+			HardLight => separable(blend_hard_light),
+			SoftLight => separable(blend_soft_light),
+			Difference => separable(|b, s| b.abs_diff(s)),
+			Exclusion => separable(|b, s| (b as i32 + s as i32 - 2 * mul_un8(b, s) as i32) as u8),
+			Addition => separable(|b, s| b.saturating_add(s)),
+			Subtract => separable(|b, s| b.saturating_sub(s)),
+			Divide => separable(blend_divide),
+			Hue | Saturation | Color | Luminosity => {
+				let backdrop = [dr as f64 / 255.0, dg as f64 / 255.0, db as f64 / 255.0];
+				let source = [sr as f64 / 255.0, sg as f64 / 255.0, sb as f64 / 255.0];
+				let result = match self {
+					Hue => set_lum(set_sat(source, sat(backdrop)), lum(backdrop)),
+					Saturation => set_lum(set_sat(backdrop, sat(source)), lum(backdrop)),
+					Color => set_lum(source, lum(backdrop)),
+					_ => set_lum(backdrop, lum(source)),
+				};
+				result.map(|c| (c * 255.0).round() as u8)
+			}
+		};
+		blend_normal(RGBA::from_rgba([r, g, b, sa]), dest, opacity)
+	}
+}
+
+#[inline]
+fn mul_un8(a: u8, b: u8) -> u8 {
+	let t = a as u32 * b as u32 + 0x80;
+	(((t >> 8) + t) >> 8) as u8
+}
+
+#[inline]
+fn div_un8(a: u8, b: u8) -> u8 {
+	((a as u32 * 0xff + (b as u32 / 2)) / b as u32) as u8
+}
+
+fn blend_normal(src: RGBA, dest: RGBA, opacity: u8) -> RGBA {
+	let [sr, sg, sb, sa] = src.to_array();
+	let sa = mul_un8(sa, opacity);
+	if dest.a() == 0 {
+		return RGBA::from_rgba([sr, sg, sb, sa]);
+	} else if sa == 0 {
+		return dest;
+	}
+	let [dr, dg, db, da] = dest.to_array();
+	let ra = sa as i32 + da as i32 - mul_un8(da, sa) as i32;
+	let channel = |d: u8, s: u8| (d as i32 + (s as i32 - d as i32) * sa as i32 / ra) as u8;
+	RGBA::from_rgba([channel(dr, sr), channel(dg, sg), channel(db, sb), ra as u8])
+}
+
+fn blend_multiply(b: u8, s: u8) -> u8 {
+	mul_un8(b, s)
+}
+
+fn blend_screen(b: u8, s: u8) -> u8 {
+	(b as i32 + s as i32 - mul_un8(b, s) as i32) as u8
+}
+
+fn blend_hard_light(b: u8, s: u8) -> u8 {
+	if s < 128 {
+		blend_multiply(b, s << 1)
+	} else {
+		blend_screen(b, ((s as i32) * 2 - 255) as u8)
+	}
+}
+
+fn blend_soft_light(b: u8, s: u8) -> u8 {
+	let b = b as f64 / 255.0;
+	let s = s as f64 / 255.0;
+	let r = if s <= 0.5 {
+		b - (1.0 - 2.0 * s) * b * (1.0 - b)
+	} else {
+		let d = if b <= 0.25 { ((16.0 * b - 12.0) * b + 4.0) * b } else { b.sqrt() };
+		b + (2.0 * s - 1.0) * (d - b)
+	};
+	(r * 255.0 + 0.5) as u8
+}
+
+fn blend_color_dodge(b: u8, s: u8) -> u8 {
+	if b == 0 {
+		return 0;
+	}
+	let s = 255 - s;
+	if b >= s {
+		255
+	} else {
+		div_un8(b, s)
+	}
+}
+
+fn blend_color_burn(b: u8, s: u8) -> u8 {
+	if b == 255 {
+		return 255;
+	}
+	let b = 255 - b;
+	if b >= s {
+		0
+	} else {
+		255 - div_un8(b, s)
+	}
+}
+
+fn blend_divide(b: u8, s: u8) -> u8 {
+	if b == 0 {
+		0
+	} else if b >= s {
+		255
+	} else {
+		div_un8(b, s)
+	}
+}
+
+// non-separable blend mode helpers, as per the W3C compositing spec
+
+fn lum(c: [f64; 3]) -> f64 {
+	0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+fn clip_color(c: [f64; 3]) -> [f64; 3] {
+	let l = lum(c);
+	let n = c[0].min(c[1]).min(c[2]);
+	let x = c[0].max(c[1]).max(c[2]);
+	c.map(|c| {
+		let mut c = c;
+		if n < 0.0 {
+			c = l + (((c - l) * l) / (l - n));
+		}
+		if x > 1.0 {
+			c = l + (((c - l) * (1.0 - l)) / (x - l));
+		}
+		c.clamp(0.0, 1.0)
+	})
+}
+
+fn set_lum(c: [f64; 3], l: f64) -> [f64; 3] {
+	let d = l - lum(c);
+	clip_color(c.map(|c| c + d))
+}
+
+fn sat(c: [f64; 3]) -> f64 {
+	c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn set_sat(c: [f64; 3], s: f64) -> [f64; 3] {
+	let max = c[0].max(c[1]).max(c[2]);
+	let min = c[0].min(c[1]).min(c[2]);
+	c.map(|c| if max > min { (c - min) * s / (max - min) } else { 0.0 })
+}
+
+#[derive(Debug, Clone)]
+pub struct AsepriteLayer {
+	pub name: String,
+	pub layer_type: AsepriteLayerType,
+	pub visible: bool,
+	pub background: bool,
+	pub opacity: u8,
+	pub blend_mode: AsepriteBlendMode,
+	/// How deeply nested within groups this layer is. Top-level layers are 0.
+	pub child_level: u16,
+	/// The index of the group layer that this layer is in, if any.
+	pub parent: Option<usize>,
+}
+
+/// The pixels of a single cel. Indexed color sprites have indexed cels, while RGBA and grayscale sprites have RGBA
+/// cels (grayscale pixels are converted when loaded).
+#[derive(Debug, Clone)]
+pub enum AsepriteCelImage {
+	Indexed(IndexedBitmap),
+	Rgba(RgbaBitmap),
+}
+
+impl AsepriteCelImage {
+	#[inline]
+	pub fn width(&self) -> u32 {
+		match self {
+			AsepriteCelImage::Indexed(bitmap) => bitmap.width(),
+			AsepriteCelImage::Rgba(bitmap) => bitmap.width(),
+		}
+	}
+
+	#[inline]
+	pub fn height(&self) -> u32 {
+		match self {
+			AsepriteCelImage::Indexed(bitmap) => bitmap.height(),
+			AsepriteCelImage::Rgba(bitmap) => bitmap.height(),
+		}
+	}
+}
+
+/// A layer's image for a single frame, positioned somewhere on the sprite's canvas.
+#[derive(Debug, Clone)]
+pub struct AsepriteCel {
+	pub layer: usize,
+	pub x: i32,
+	pub y: i32,
+	pub opacity: u8,
+	pub z_index: i16,
+	pub image: AsepriteCelImage,
+}
+
+#[derive(Debug, Clone)]
+pub struct AsepriteFrame {
+	/// How long this frame should be shown for, in seconds.
+	pub duration: f32,
+	pub cels: Vec<AsepriteCel>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AsepriteTagDirection {
+	Forward,
+	Reverse,
+	PingPong,
+	PingPongReverse,
+}
+
+/// A named range of frames, used to define separate animations within a single sprite.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AsepriteTag {
+	pub name: String,
+	/// The first frame in the range (inclusive).
+	pub from: usize,
+	/// The last frame in the range (inclusive).
+	pub to: usize,
+	pub direction: AsepriteTagDirection,
+	/// How many times the animation should be repeated. 0 means forever.
+	pub repeat: u16,
+}
+
+/// The position and size of a slice starting at a given frame.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct AsepriteSliceKey {
+	pub frame: usize,
+	pub bounds: Rect,
+	/// The center region for 9-patch slices, relative to the slice bounds.
+	pub center: Option<Rect>,
+	/// The pivot point, relative to the slice bounds.
+	pub pivot: Option<(i32, i32)>,
+}
+
+/// A named region of the sprite, which can change position and size from frame to frame.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AsepriteSlice {
+	pub name: String,
+	pub keys: Vec<AsepriteSliceKey>,
+}
+
+impl AsepriteSlice {
+	/// Returns the slice key in effect for the given frame, if any.
+	pub fn key_for_frame(&self, frame: usize) -> Option<&AsepriteSliceKey> {
+		self.keys.iter().filter(|key| key.frame <= frame).last()
+	}
+}
+
+fn read_string<T: ReadBytesExt>(reader: &mut T) -> Result<String, AsepriteError> {
+	let length = reader.read_u16::<LittleEndian>()?;
+	let mut bytes = vec![0u8; length as usize];
+	reader.read_exact(&mut bytes)?;
+	String::from_utf8(bytes).map_err(|_| AsepriteError::BadFile(String::from("Invalid UTF-8 string")))
+}
+
+fn skip<T: ReadBytesExt>(reader: &mut T, count: u64) -> Result<(), AsepriteError> {
+	std::io::copy(&mut reader.take(count), &mut std::io::sink())?;
+	Ok(())
+}
+
+fn read_cel_image(
+	data: &[u8],
+	width: u32,
+	height: u32,
+	color_depth: AsepriteColorDepth,
+	compressed: bool,
+) -> Result<AsepriteCelImage, AsepriteError> {
+	let num_pixels = (width * height) as usize;
+	let image_size = num_pixels * color_depth.bytes_per_pixel();
+	let mut pixels = Vec::new();
+	if compressed {
+		// read one byte more than needed, so that overly large pixel data can be detected without inflating all of it
+		let inflater = flate2::read::ZlibDecoder::new(data);
+		inflater.take(image_size as u64 + 1).read_to_end(&mut pixels)?;
+		if pixels.len() > image_size {
+			return Err(AsepriteError::BadFile(String::from("Cel pixel data is larger than the cel dimensions")));
+		}
+	} else {
+		pixels.extend_from_slice(data);
+	}
+
+	if pixels.len() < image_size {
+		return Err(AsepriteError::BadFile(String::from("Cel pixel data is truncated")));
+	}
+	let bad_dimensions = |_| AsepriteError::BadFile(String::from("Invalid cel dimensions"));
+
+	Ok(match color_depth {
+		AsepriteColorDepth::Indexed => {
+			let mut bitmap = IndexedBitmap::new(width, height).map_err(bad_dimensions)?;
+			bitmap.pixels_mut().copy_from_slice(&pixels[0..num_pixels]);
+			AsepriteCelImage::Indexed(bitmap)
+		}
+		AsepriteColorDepth::Rgba => {
+			let mut bitmap = RgbaBitmap::new(width, height).map_err(bad_dimensions)?;
+			for (dest, src) in bitmap.pixels_mut().iter_mut().zip(pixels.chunks_exact(4)) {
+				*dest = RGBA::from_rgba([src[0], src[1], src[2], src[3]]);
+			}
+			AsepriteCelImage::Rgba(bitmap)
+		}
+		AsepriteColorDepth::Grayscale => {
+			let mut bitmap = RgbaBitmap::new(width, height).map_err(bad_dimensions)?;
+			for (dest, src) in bitmap.pixels_mut().iter_mut().zip(pixels.chunks_exact(2)) {
+				*dest = RGBA::from_rgba([src[0], src[0], src[0], src[1]]);
+			}
+			AsepriteCelImage::Rgba(bitmap)
+		}
+	})
+}
+
+fn read_old_palette<T: ReadBytesExt>(
+	reader: &mut T,
+	palette: &mut Palette,
+	six_bit: bool,
+) -> Result<(), AsepriteError> {
+	let num_packets = reader.read_u16::<LittleEndian>()?;
+	let mut index = 0usize;
+	for _ in 0..num_packets {
+		index += reader.read_u8()? as usize;
+		let count = match reader.read_u8()? {
+			0 => 256,
+			count => count as usize,
+		};
+		for _ in 0..count {
+			let mut rgb = [reader.read_u8()?, reader.read_u8()?, reader.read_u8()?];
+			if six_bit {
+				rgb = rgb.map(|c| (c << 2) | (c >> 4));
+			}
+			if index < 256 {
+				palette[index as u8] = RGBA::from_rgb(rgb);
+			}
+			index += 1;
+		}
+	}
+	Ok(())
+}
+
+fn read_palette<T: ReadBytesExt>(reader: &mut T, palette: &mut Palette) -> Result<(), AsepriteError> {
+	let _size = reader.read_u32::<LittleEndian>()?;
+	let first = reader.read_u32::<LittleEndian>()? as usize;
+	let last = reader.read_u32::<LittleEndian>()? as usize;
+	skip(reader, 8)?;
+	for index in first..=last {
+		let flags = reader.read_u16::<LittleEndian>()?;
+		let rgba = [reader.read_u8()?, reader.read_u8()?, reader.read_u8()?, reader.read_u8()?];
+		if flags & PALETTE_ENTRY_FLAG_HAS_NAME != 0 {
+			read_string(reader)?;
+		}
+		if index < 256 {
+			palette[index as u8] = RGBA::from_rgba(rgba);
+		}
+	}
+	Ok(())
+}
+
+fn read_layer<T: ReadBytesExt>(
+	reader: &mut T,
+	header_flags: u32,
+	layers: &[AsepriteLayer],
+) -> Result<AsepriteLayer, AsepriteError> {
+	let flags = reader.read_u16::<LittleEndian>()?;
+	let layer_type = match reader.read_u16::<LittleEndian>()? {
+		0 => AsepriteLayerType::Normal,
+		1 => AsepriteLayerType::Group,
+		2 => AsepriteLayerType::Tilemap,
+		other => return Err(AsepriteError::BadFile(format!("Unsupported layer type: {}", other))),
+	};
+	let child_level = reader.read_u16::<LittleEndian>()?;
+	let _default_width = reader.read_u16::<LittleEndian>()?;
+	let _default_height = reader.read_u16::<LittleEndian>()?;
+	let blend_mode = AsepriteBlendMode::from(reader.read_u16::<LittleEndian>()?)?;
+	let opacity = reader.read_u8()?;
+	skip(reader, 3)?;
+	let name = read_string(reader)?;
+
+	// layers are listed in order, with the layers within a group following the group itself, and one child level
+	// deeper than it. so the closest preceding layer that is one level up must be the group containing this one
+	let parent =
+		if child_level > 0 { layers.iter().rposition(|layer| layer.child_level == child_level - 1) } else { None };
+
+	Ok(AsepriteLayer {
+		name,
+		layer_type,
+		visible: flags & LAYER_FLAG_VISIBLE != 0,
+		background: flags & LAYER_FLAG_BACKGROUND != 0,
+		opacity: if header_flags & HEADER_FLAG_LAYER_OPACITY_VALID != 0 { opacity } else { 255 },
+		blend_mode,
+		child_level,
+		parent,
+	})
+}
+
+fn read_tags<T: ReadBytesExt>(reader: &mut T) -> Result<Vec<AsepriteTag>, AsepriteError> {
+	let num_tags = reader.read_u16::<LittleEndian>()?;
+	skip(reader, 8)?;
+	let mut tags = Vec::with_capacity(num_tags as usize);
+	for _ in 0..num_tags {
+		let from = reader.read_u16::<LittleEndian>()? as usize;
+		let to = reader.read_u16::<LittleEndian>()? as usize;
+		let direction = match reader.read_u8()? {
+			0 => AsepriteTagDirection::Forward,
+			1 => AsepriteTagDirection::Reverse,
+			2 => AsepriteTagDirection::PingPong,
+			3 => AsepriteTagDirection::PingPongReverse,
+			other => return Err(AsepriteError::BadFile(format!("Unsupported tag direction: {}", other))),
+		};
+		let repeat = reader.read_u16::<LittleEndian>()?;
+		// 6 reserved bytes, followed by a deprecated tag color and an extra byte
+		skip(reader, 10)?;
+		let name = read_string(reader)?;
+		tags.push(AsepriteTag { name, from, to, direction, repeat });
+	}
+	Ok(tags)
+}
+
+fn read_slice<T: ReadBytesExt>(reader: &mut T) -> Result<AsepriteSlice, AsepriteError> {
+	let num_keys = reader.read_u32::<LittleEndian>()?;
+	let flags = reader.read_u32::<LittleEndian>()?;
+	let _reserved = reader.read_u32::<LittleEndian>()?;
+	let name = read_string(reader)?;
+	// the number of keys is not trusted to size the vec up front, as it may be far larger than the chunk itself
+	let mut keys = Vec::new();
+	for _ in 0..num_keys {
+		let frame = reader.read_u32::<LittleEndian>()? as usize;
+		let mut read_rect = || -> Result<Rect, AsepriteError> {
+			let x = reader.read_i32::<LittleEndian>()?;
+			let y = reader.read_i32::<LittleEndian>()?;
+			let width = reader.read_u32::<LittleEndian>()?;
+			let height = reader.read_u32::<LittleEndian>()?;
+			Ok(Rect::new(x, y, width, height))
+		};
+		let bounds = read_rect()?;
+		let center = if flags & SLICE_FLAG_NINE_PATCH != 0 { Some(read_rect()?) } else { None };
+		let pivot = if flags & SLICE_FLAG_PIVOT != 0 {
+			Some((reader.read_i32::<LittleEndian>()?, reader.read_i32::<LittleEndian>()?))
+		} else {
+			None
+		};
+		keys.push(AsepriteSliceKey { frame, bounds, center, pivot });
+	}
+	Ok(AsepriteSlice { name, keys })
+}
+
+/// A sprite loaded from an Aseprite (.ase / .aseprite) file, including all of its layers, frames, tags, slices and
+/// its palette.
+///
+/// Frames can be flattened (with [`Aseprite::frame_rgba`] or, for indexed color sprites,
+/// [`Aseprite::frame_indexed`]) into a single bitmap, or each layer can be pulled out separately.
+/// Tilemap layers are not supported and are skipped when flattening.
+#[derive(Debug, Clone)]
+pub struct Aseprite {
+	width: u32,
+	height: u32,
+	color_depth: AsepriteColorDepth,
+	transparent_index: u8,
+	palette: Palette,
+	layers: Vec<AsepriteLayer>,
+	frames: Vec<AsepriteFrame>,
+	tags: Vec<AsepriteTag>,
+	slices: Vec<AsepriteSlice>,
+}
+
+impl Aseprite {
+	pub fn load_bytes<T: ReadBytesExt>(reader: &mut T) -> Result<Aseprite, AsepriteError> {
+		let file_size = reader.read_u32::<LittleEndian>()? as usize;
+		if reader.read_u16::<LittleEndian>()? != HEADER_MAGIC {
+			return Err(AsepriteError::BadFile(String::from(
+				"Unexpected header magic number, probably not an Aseprite file",
+			)));
+		}
+		let num_frames = reader.read_u16::<LittleEndian>()?;
+		let width = reader.read_u16::<LittleEndian>()? as u32;
+		let height = reader.read_u16::<LittleEndian>()? as u32;
+		let color_depth = AsepriteColorDepth::from(reader.read_u16::<LittleEndian>()?)?;
+		let header_flags = reader.read_u32::<LittleEndian>()?;
+		let _speed = reader.read_u16::<LittleEndian>()?;
+		skip(reader, 8)?;
+		let transparent_index = reader.read_u8()?;
+		// skip the rest of the header, which is only of interest to the Aseprite editor itself
+		skip(reader, (HEADER_SIZE - 29) as u64)?;
+
+		if width == 0 || height == 0 {
+			return Err(AsepriteError::BadFile(String::from("Invalid sprite dimensions")));
+		}
+		let mut remaining_size = file_size
+			.checked_sub(HEADER_SIZE)
+			.ok_or_else(|| AsepriteError::BadFile(String::from("Invalid file size")))?;
+
+		let mut sprite = Aseprite {
+			width,
+			height,
+			color_depth,
+			transparent_index,
+			palette: Palette::new(),
+			layers: Vec::new(),
+			frames: Vec::with_capacity(num_frames as usize),
+			tags: Vec::new(),
+			slices: Vec::new(),
+		};
+		let mut has_new_palette = false;
+
+		for _ in 0..num_frames {
+			let frame_size = reader.read_u32::<LittleEndian>()? as usize;
+			if reader.read_u16::<LittleEndian>()? != FRAME_MAGIC || frame_size < FRAME_HEADER_SIZE {
+				return Err(AsepriteError::BadFile(String::from("Unexpected frame header")));
+			}
+			if frame_size > remaining_size {
+				return Err(AsepriteError::BadFile(String::from("Frame size extends past the end of the file")));
+			}
+			remaining_size -= frame_size;
+			let old_num_chunks = reader.read_u16::<LittleEndian>()?;
+			let duration = reader.read_u16::<LittleEndian>()?;
+			skip(reader, 2)?;
+			let num_chunks = match reader.read_u32::<LittleEndian>()? {
+				0 => old_num_chunks as u32,
+				num_chunks => num_chunks,
+			};
+
+			let frame_data_size = frame_size - FRAME_HEADER_SIZE;
+			let mut frame_data = Vec::new();
+			reader.take(frame_data_size as u64).read_to_end(&mut frame_data)?;
+			if frame_data.len() < frame_data_size {
+				return Err(AsepriteError::BadFile(String::from("Frame data is truncated")));
+			}
+
+			let mut frame = AsepriteFrame { duration: duration as f32 / 1000.0, cels: Vec::new() };
+			let mut position = 0;
+			for _ in 0..num_chunks {
+				let mut header = Cursor::new(
+					frame_data
+						.get(position..position + CHUNK_HEADER_SIZE)
+						.ok_or_else(|| AsepriteError::BadFile(String::from("Chunk header is truncated")))?,
+				);
+				let chunk_size = header.read_u32::<LittleEndian>()? as usize;
+				let chunk_type = header.read_u16::<LittleEndian>()?;
+				let chunk_data = chunk_size
+					.checked_sub(CHUNK_HEADER_SIZE)
+					.and_then(|size| frame_data.get(position + CHUNK_HEADER_SIZE..position + CHUNK_HEADER_SIZE + size))
+					.ok_or_else(|| AsepriteError::BadFile(String::from("Chunk data is truncated")))?;
+				position += chunk_size;
+
+				let mut reader = Cursor::new(chunk_data);
+				match chunk_type {
+					CHUNK_OLD_PALETTE | CHUNK_OLD_PALETTE_6BIT if !has_new_palette => {
+						read_old_palette(&mut reader, &mut sprite.palette, chunk_type == CHUNK_OLD_PALETTE_6BIT)?;
+					}
+					CHUNK_PALETTE => {
+						read_palette(&mut reader, &mut sprite.palette)?;
+						has_new_palette = true;
+					}
+					CHUNK_LAYER => {
+						let layer = read_layer(&mut reader, header_flags, &sprite.layers)?;
+						sprite.layers.push(layer);
+					}
+					CHUNK_CEL => {
+						if let Some(cel) = sprite.read_cel(&mut reader, chunk_data)? {
+							frame.cels.push(cel);
+						}
+					}
+					CHUNK_TAGS => sprite.tags = read_tags(&mut reader)?,
+					CHUNK_SLICE => sprite.slices.push(read_slice(&mut reader)?),
+					_ => {}
+				}
+			}
+
+			sprite.frames.push(frame);
+		}
+
+		Ok(sprite)
+	}
+
+	fn read_cel(&self, reader: &mut Cursor<&[u8]>, chunk_data: &[u8]) -> Result<Option<AsepriteCel>, AsepriteError> {
+		let layer = reader.read_u16::<LittleEndian>()? as usize;
+		let x = reader.read_i16::<LittleEndian>()? as i32;
+		let y = reader.read_i16::<LittleEndian>()? as i32;
+		let opacity = reader.read_u8()?;
+		let cel_type = reader.read_u16::<LittleEndian>()?;
+		let z_index = reader.read_i16::<LittleEndian>()?;
+		skip(reader, 5)?;
+
+		if layer >= self.layers.len() {
+			return Err(AsepriteError::BadFile(format!("Cel refers to an unknown layer: {}", layer)));
+		}
+
+		let image = match cel_type {
+			CEL_TYPE_RAW | CEL_TYPE_COMPRESSED => {
+				let width = reader.read_u16::<LittleEndian>()? as u32;
+				let height = reader.read_u16::<LittleEndian>()? as u32;
+				let data = &chunk_data[reader.position() as usize..];
+				read_cel_image(data, width, height, self.color_depth, cel_type == CEL_TYPE_COMPRESSED)?
+			}
+			CEL_TYPE_LINKED => {
+				// linked cels share their image with the same layer's cel in an earlier frame
+				let linked_frame = reader.read_u16::<LittleEndian>()? as usize;
+				match self.frames.get(linked_frame).and_then(|frame| frame.cels.iter().find(|cel| cel.layer == layer)) {
+					Some(linked_cel) => linked_cel.image.clone(),
+					None => return Err(AsepriteError::BadFile(String::from("Linked cel refers to a missing cel"))),
+				}
+			}
+			// tilemap cels are not supported
+			_ => return Ok(None),
+		};
+
+		Ok(Some(AsepriteCel { layer, x, y, opacity, z_index, image }))
+	}
+
+	pub fn load_file(path: &Path) -> Result<Aseprite, AsepriteError> {
+		let f = File::open(path)?;
+		let mut reader = BufReader::new(f);
+		Self::load_bytes(&mut reader)
+	}
+
+	#[inline]
+	pub fn width(&self) -> u32 {
+		self.width
+	}
+
+	#[inline]
+	pub fn height(&self) -> u32 {
+		self.height
+	}
+
+	#[inline]
+	pub fn color_depth(&self) -> AsepriteColorDepth {
+		self.color_depth
+	}
+
+	/// Returns the palette color index that is treated as transparent in indexed color sprites.
+	#[inline]
+	pub fn transparent_index(&self) -> u8 {
+		self.transparent_index
+	}
+
+	#[inline]
+	pub fn palette(&self) -> &Palette {
+		&self.palette
+	}
+
+	#[inline]
+	pub fn layers(&self) -> &[AsepriteLayer] {
+		&self.layers
+	}
+
+	#[inline]
+	pub fn frames(&self) -> &[AsepriteFrame] {
+		&self.frames
+	}
+
+	#[inline]
+	pub fn num_frames(&self) -> usize {
+		self.frames.len()
+	}
+
+	#[inline]
+	pub fn tags(&self) -> &[AsepriteTag] {
+		&self.tags
+	}
+
+	/// Returns the tag with the given name, if there is one.
+	pub fn tag(&self, name: &str) -> Option<&AsepriteTag> {
+		self.tags.iter().find(|tag| tag.name == name)
+	}
+
+	#[inline]
+	pub fn slices(&self) -> &[AsepriteSlice] {
+		&self.slices
+	}
+
+	/// Returns the slice with the given name, if there is one.
+	pub fn slice(&self, name: &str) -> Option<&AsepriteSlice> {
+		self.slices.iter().find(|slice| slice.name == name)
+	}
+
+	/// Returns true if the given layer is visible, and so are all of the groups it is in.
+	pub fn is_layer_visible(&self, index: usize) -> bool {
+		let mut layer = self.layers.get(index);
+		while let Some(current) = layer {
+			if !current.visible {
+				return false;
+			}
+			layer = current.parent.and_then(|parent| self.layers.get(parent));
+		}
+		index < self.layers.len()
+	}
+
+	/// Returns the cels of the given frame in the order that they should be drawn in, bottom-most first.
+	fn cels_in_draw_order(&self, frame: usize) -> Result<Vec<&AsepriteCel>, AsepriteError> {
+		let frame = self.frames.get(frame).ok_or(AsepriteError::InvalidFrame(frame))?;
+		let mut cels: Vec<&AsepriteCel> = frame.cels.iter().collect();
+		// as per Aseprite, a cel's z-index moves it up or down relative to the other layers, with the z-index itself
+		// breaking ties
+		cels.sort_by_key(|cel| (cel.layer as i32 + cel.z_index as i32, cel.z_index));
+		Ok(cels)
+	}
+
+	fn draw_cel_rgba(&self, cel: &AsepriteCel, dest: &mut RgbaBitmap, blend_mode: AsepriteBlendMode, opacity: u8) {
+		let layer = &self.layers[cel.layer];
+		let transparent = RGBA::from_rgba([0, 0, 0, 0]);
+		let width = cel.image.width() as i32;
+		for cy in 0..cel.image.height() as i32 {
+			for cx in 0..width {
+				let (x, y) = (cel.x + cx, cel.y + cy);
+				if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+					continue;
+				}
+				let index = (cy * width + cx) as usize;
+				let src = match &cel.image {
+					AsepriteCelImage::Rgba(bitmap) => bitmap.pixels()[index],
+					AsepriteCelImage::Indexed(bitmap) => {
+						let color = bitmap.pixels()[index];
+						if color == self.transparent_index && !layer.background {
+							transparent
+						} else {
+							self.palette[color]
+						}
+					}
+				};
+				let dest = &mut dest.pixels_mut()[(y as u32 * self.width + x as u32) as usize];
+				*dest = blend_mode.blend(src, *dest, opacity);
+			}
+		}
+	}
+
+	fn draw_cel_indexed(&self, cel: &AsepriteCel, dest: &mut IndexedBitmap) -> Result<(), AsepriteError> {
+		let AsepriteCelImage::Indexed(bitmap) = &cel.image else {
+			return Err(AsepriteError::NotIndexed);
+		};
+		let background = self.layers[cel.layer].background;
+		let width = bitmap.width() as i32;
+		for cy in 0..bitmap.height() as i32 {
+			for cx in 0..width {
+				let (x, y) = (cel.x + cx, cel.y + cy);
+				if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+					continue;
+				}
+				let color = bitmap.pixels()[(cy * width + cx) as usize];
+				if color != self.transparent_index || background {
+					dest.pixels_mut()[(y as u32 * self.width + x as u32) as usize] = color;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Flattens all visible layers of the given frame into a single RGBA bitmap, using each layer's opacity and
+	/// blend mode.
+	pub fn frame_rgba(&self, frame: usize) -> Result<RgbaBitmap, AsepriteError> {
+		let mut bitmap = RgbaBitmap::new(self.width, self.height).unwrap();
+		bitmap.clear(RGBA::from_rgba([0, 0, 0, 0]));
+		for cel in self.cels_in_draw_order(frame)? {
+			let layer = &self.layers[cel.layer];
+			if layer.layer_type == AsepriteLayerType::Normal && self.is_layer_visible(cel.layer) {
+				self.draw_cel_rgba(cel, &mut bitmap, layer.blend_mode, mul_un8(cel.opacity, layer.opacity));
+			}
+		}
+		Ok(bitmap)
+	}
+
+	/// Returns the given layer's image for the given frame as an RGBA bitmap the size of the whole sprite,
+	/// regardless of whether the layer is visible or not. The layer and cel opacity are applied.
+	pub fn layer_frame_rgba(&self, frame: usize, layer: usize) -> Result<RgbaBitmap, AsepriteError> {
+		let layer_info = self.layers.get(layer).ok_or(AsepriteError::InvalidLayer(layer))?;
+		let mut bitmap = RgbaBitmap::new(self.width, self.height).unwrap();
+		bitmap.clear(RGBA::from_rgba([0, 0, 0, 0]));
+		for cel in self.cels_in_draw_order(frame)?.into_iter().filter(|cel| cel.layer == layer) {
+			let opacity = mul_un8(cel.opacity, layer_info.opacity);
+			self.draw_cel_rgba(cel, &mut bitmap, AsepriteBlendMode::Normal, opacity);
+		}
+		Ok(bitmap)
+	}
+
+	/// Flattens all visible layers of the given frame into a single indexed bitmap. This is only possible for
+	/// indexed color sprites. Since there is no way to blend indexed pixels together, layer opacity and blend modes
+	/// are ignored (other than fully transparent layers being skipped). Pixels not covered by any layer are set to
+	/// the sprite's transparent color index.
+	pub fn frame_indexed(&self, frame: usize) -> Result<IndexedBitmap, AsepriteError> {
+		if self.color_depth != AsepriteColorDepth::Indexed {
+			return Err(AsepriteError::NotIndexed);
+		}
+		let mut bitmap = IndexedBitmap::new(self.width, self.height).unwrap();
+		bitmap.clear(self.transparent_index);
+		for cel in self.cels_in_draw_order(frame)? {
+			let layer = &self.layers[cel.layer];
+			if layer.layer_type == AsepriteLayerType::Normal
+				&& self.is_layer_visible(cel.layer)
+				&& mul_un8(cel.opacity, layer.opacity) > 0
+			{
+				self.draw_cel_indexed(cel, &mut bitmap)?;
+			}
+		}
+		Ok(bitmap)
+	}
+
+	/// Returns the given layer's image for the given frame as an indexed bitmap the size of the whole sprite,
+	/// regardless of whether the layer is visible or not. This is only possible for indexed color sprites.
+	pub fn layer_frame_indexed(&self, frame: usize, layer: usize) -> Result<IndexedBitmap, AsepriteError> {
+		if self.color_depth != AsepriteColorDepth::Indexed {
+			return Err(AsepriteError::NotIndexed);
+		}
+		if layer >= self.layers.len() {
+			return Err(AsepriteError::InvalidLayer(layer));
+		}
+		let mut bitmap = IndexedBitmap::new(self.width, self.height).unwrap();
+		bitmap.clear(self.transparent_index);
+		for cel in self.cels_in_draw_order(frame)?.into_iter().filter(|cel| cel.layer == layer) {
+			self.draw_cel_indexed(cel, &mut bitmap)?;
+		}
+		Ok(bitmap)
+	}
+
+	fn build_atlas<BitmapType: GeneralBitmap>(
+		&self,
+		frames: Vec<BitmapType>,
+		background: BitmapType::PixelType,
+	) -> Result<BitmapAtlas<BitmapType>, AsepriteError> {
+		// lay the frames out in a roughly square grid
+		let num_frames = frames.len() as u32;
+		let columns = (num_frames as f32).sqrt().ceil().max(1.0) as u32;
+		let rows = ((num_frames + columns - 1) / columns).max(1);
+
+		let mut bitmap = BitmapType::new(columns * self.width, rows * self.height).unwrap();
+		bitmap.clear(background);
+		let mut rects = Vec::with_capacity(frames.len());
+		for (index, frame) in frames.iter().enumerate() {
+			let x = (index as u32 % columns) * self.width;
+			let y = (index as u32 / columns) * self.height;
+			bitmap.blit(GeneralBlitMethod::Solid, frame, x as i32, y as i32);
+			rects.push(Rect::new(x as i32, y as i32, self.width, self.height));
+		}
+
+		let mut atlas = BitmapAtlas::new(bitmap);
+		for rect in rects {
+			atlas.add(rect)?;
+		}
+		Ok(atlas)
+	}
+
+	/// Flattens every frame (see [`Aseprite::frame_rgba`]) and packs them all into a single bitmap atlas, where
+	/// the atlas tile index of each frame is the same as its frame index. This means that the frame ranges of
+	/// [`AsepriteTag`]s can be used as-is with the returned atlas.
+	pub fn to_atlas_rgba(&self) -> Result<BitmapAtlas<RgbaBitmap>, AsepriteError> {
+		let frames = (0..self.num_frames()).map(|index| self.frame_rgba(index)).collect::<Result<Vec<_>, _>>()?;
+		self.build_atlas(frames, RGBA::from_rgba([0, 0, 0, 0]))
+	}
+
+	/// Flattens every frame (see [`Aseprite::frame_indexed`]) and packs them all into a single bitmap atlas, where
+	/// the atlas tile index of each frame is the same as its frame index. This means that the frame ranges of
+	/// [`AsepriteTag`]s can be used as-is with the returned atlas. This is only possible for indexed color sprites.
+	pub fn to_atlas_indexed(&self) -> Result<BitmapAtlas<IndexedBitmap>, AsepriteError> {
+		let frames = (0..self.num_frames()).map(|index| self.frame_indexed(index)).collect::<Result<Vec<_>, _>>()?;
+		self.build_atlas(frames, self.transparent_index)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Write;
+	use std::path::PathBuf;
+
+	use claim::*;
+
+	use crate::tests::{load_raw_indexed, load_raw_rgba};
+
+	use super::*;
+
+	const BASE_PATH: &str = "./test-assets/aseprite/";
+
+	fn test_file(file: &Path) -> PathBuf {
+		PathBuf::from(BASE_PATH).join(file)
+	}
+
+	#[test]
+	pub fn load_rgba_sprite() -> Result<(), AsepriteError> {
+		let ref_pixels = load_raw_rgba(test_file(Path::new("rgba_frames.bin")).as_path())?;
+		let frame_size = 16 * 12;
+
+		let sprite = Aseprite::load_file(test_file(Path::new("rgba.aseprite")).as_path())?;
+		assert_eq!(16, sprite.width());
+		assert_eq!(12, sprite.height());
+		assert_eq!(AsepriteColorDepth::Rgba, sprite.color_depth());
+		assert_eq!(3, sprite.num_frames());
+		assert_eq!(0.1, sprite.frames()[0].duration);
+		assert_eq!(0.15, sprite.frames()[1].duration);
+		assert_eq!(0.2, sprite.frames()[2].duration);
+
+		assert_eq!(RGBA::from_rgba([10, 20, 30, 255]), sprite.palette()[0]);
+		assert_eq!(RGBA::from_rgba([40, 50, 60, 128]), sprite.palette()[1]);
+		assert_eq!(RGBA::from_rgba([255, 255, 255, 255]), sprite.palette()[3]);
+
+		let layers = sprite.layers();
+		assert_eq!(7, layers.len());
+		assert_eq!("background", layers[0].name);
+		assert!(layers[0].background);
+		assert_eq!(AsepriteLayerType::Group, layers[1].layer_type);
+		assert_eq!(AsepriteBlendMode::Multiply, layers[2].blend_mode);
+		assert_eq!(Some(1), layers[2].parent);
+		assert_eq!(Some(1), layers[3].parent);
+		assert_eq!(None, layers[4].parent);
+		assert_eq!(128, layers[4].opacity);
+		assert_eq!(Some(5), layers[6].parent);
+		assert!(sprite.is_layer_visible(2));
+		assert!(!sprite.is_layer_visible(3));
+		assert!(!sprite.is_layer_visible(6)); // visible itself, but in a hidden group
+
+		assert_eq!(
+			Some(&AsepriteTag {
+				name: String::from("walk"),
+				from: 0,
+				to: 1,
+				direction: AsepriteTagDirection::Forward,
+				repeat: 0
+			}),
+			sprite.tag("walk")
+		);
+		assert_eq!(
+			Some(&AsepriteTag {
+				name: String::from("idle"),
+				from: 2,
+				to: 2,
+				direction: AsepriteTagDirection::PingPong,
+				repeat: 3
+			}),
+			sprite.tag("idle")
+		);
+		assert!(sprite.tag("run").is_none());
+
+		let slice = sprite.slice("button").unwrap();
+		assert_eq!(2, slice.keys.len());
+		assert_eq!(
+			AsepriteSliceKey {
+				frame: 0,
+				bounds: Rect::new(1, 2, 10, 8),
+				center: Some(Rect::new(3, 3, 4, 2)),
+				pivot: Some((5, 4))
+			},
+			slice.keys[0]
+		);
+		assert_eq!(Rect::new(1, 2, 10, 8), slice.key_for_frame(1).unwrap().bounds);
+		assert_eq!(Rect::new(2, 3, 12, 9), slice.key_for_frame(2).unwrap().bounds);
+		let slice = sprite.slice("plain").unwrap();
+		assert_eq!(None, slice.keys[0].center);
+		assert_eq!(None, slice.keys[0].pivot);
+
+		for frame in 0..sprite.num_frames() {
+			let bitmap = sprite.frame_rgba(frame)?;
+			assert_eq!(&ref_pixels[frame * frame_size..(frame + 1) * frame_size], bitmap.pixels(), "frame {}", frame);
+		}
+
+		// the linked cel in frame 1 uses the background cel image from frame 0
+		let background = sprite.layer_frame_rgba(1, 0)?;
+		assert_eq!(sprite.layer_frame_rgba(0, 0)?, background);
+		// hidden layers can still be pulled out on their own
+		let hidden = sprite.layer_frame_rgba(0, 3)?;
+		assert_eq!(255, hidden.get_pixel(0, 0).unwrap().a());
+		assert_eq!(RGBA::from_rgba([0, 0, 0, 0]), sprite.layer_frame_rgba(1, 3)?.get_pixel(0, 0).unwrap());
+
+		assert!(matches!(sprite.frame_rgba(3), Err(AsepriteError::InvalidFrame(3))));
+		assert!(matches!(sprite.layer_frame_rgba(0, 7), Err(AsepriteError::InvalidLayer(7))));
+		assert!(matches!(sprite.frame_indexed(0), Err(AsepriteError::NotIndexed)));
+
+		let atlas = sprite.to_atlas_rgba()?;
+		assert_eq!(3, atlas.len());
+		assert_eq!(32, atlas.bitmap().width());
+		assert_eq!(24, atlas.bitmap().height());
+		for frame in 0..sprite.num_frames() {
+			assert_eq!(sprite.frame_rgba(frame)?, atlas.clone_tile(frame)?);
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn load_indexed_sprite() -> Result<(), AsepriteError> {
+		let ref_pixels = load_raw_indexed(test_file(Path::new("indexed_frames.bin")).as_path())?;
+		let frame_size = 8 * 8;
+
+		let sprite = Aseprite::load_file(test_file(Path::new("indexed.aseprite")).as_path())?;
+		assert_eq!(AsepriteColorDepth::Indexed, sprite.color_depth());
+		assert_eq!(0, sprite.transparent_index());
+		assert_eq!(2, sprite.num_frames());
+		assert_eq!(RGBA::from_rgb([16, 239, 8]), sprite.palette()[1]);
+
+		for frame in 0..sprite.num_frames() {
+			let bitmap = sprite.frame_indexed(frame)?;
+			assert_eq!(&ref_pixels[frame * frame_size..(frame + 1) * frame_size], bitmap.pixels(), "frame {}", frame);
+
+			// flattening to rgba should be the same thing, just with the palette applied
+			let rgba = sprite.frame_rgba(frame)?;
+			for (index, color) in bitmap.pixels().iter().zip(rgba.pixels().iter()) {
+				assert_eq!(sprite.palette()[*index], *color);
+			}
+		}
+
+		let foreground = sprite.layer_frame_indexed(1, 1)?;
+		assert_eq!(Some(5), foreground.get_pixel(7, 6));
+		assert_eq!(Some(0), foreground.get_pixel(0, 0));
+
+		let atlas = sprite.to_atlas_indexed()?;
+		assert_eq!(2, atlas.len());
+		assert_eq!(sprite.frame_indexed(1)?, atlas.clone_tile(1)?);
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn load_grayscale_sprite() -> Result<(), AsepriteError> {
+		let ref_pixels = load_raw_rgba(test_file(Path::new("grayscale.bin")).as_path())?;
+		let sprite = Aseprite::load_file(test_file(Path::new("grayscale.aseprite")).as_path())?;
+		assert_eq!(AsepriteColorDepth::Grayscale, sprite.color_depth());
+		assert_eq!(ref_pixels.as_ref(), sprite.frame_rgba(0)?.pixels());
+		Ok(())
+	}
+
+	#[test]
+	pub fn rejects_oversized_data() -> Result<(), AsepriteError> {
+		let bytes = std::fs::read(test_file(Path::new("indexed.aseprite")))?;
+
+		// the first frame's size is the first field after the file header
+		let mut corrupt = bytes.clone();
+		corrupt[HEADER_SIZE..HEADER_SIZE + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+		assert_matches!(Aseprite::load_bytes(&mut corrupt.as_slice()), Err(AsepriteError::BadFile(..)));
+
+		let mut corrupt = bytes.clone();
+		corrupt[0..4].copy_from_slice(&(HEADER_SIZE as u32 + 4).to_le_bytes());
+		assert_matches!(Aseprite::load_bytes(&mut corrupt.as_slice()), Err(AsepriteError::BadFile(..)));
+
+		let compress = |pixels: &[u8]| {
+			let mut deflater = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+			deflater.write_all(pixels).unwrap();
+			deflater.finish().unwrap()
+		};
+		assert_matches!(
+			read_cel_image(&compress(&[1, 2, 3, 4]), 2, 2, AsepriteColorDepth::Indexed, true),
+			Ok(AsepriteCelImage::Indexed(..))
+		);
+		assert_matches!(
+			read_cel_image(&compress(&[0; 4096]), 2, 2, AsepriteColorDepth::Indexed, true),
+			Err(AsepriteError::BadFile(..))
+		);
+		assert_matches!(
+			read_cel_image(&compress(&[1, 2, 3]), 2, 2, AsepriteColorDepth::Indexed, true),
+			Err(AsepriteError::BadFile(..))
+		);
+
+		// a slice claiming far more keys than the chunk holds fails once the chunk runs out
+		let mut slice = Vec::new();
+		slice.extend_from_slice(&u32::MAX.to_le_bytes());
+		slice.extend_from_slice(&[0; 8]);
+		slice.extend_from_slice(&0u16.to_le_bytes());
+		assert_matches!(read_slice(&mut slice.as_slice()), Err(AsepriteError::IOError(..)));
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn blend_modes() {
+		let dest = RGBA::from_rgba([200, 100, 50, 255]);
+		let src = RGBA::from_rgba([100, 200, 255, 255]);
+		assert_eq!(src, AsepriteBlendMode::Normal.blend(src, dest, 255));
+		assert_eq!(dest, AsepriteBlendMode::Normal.blend(src, dest, 0));
+		assert_eq!(RGBA::from_rgba([78, 78, 50, 255]), AsepriteBlendMode::Multiply.blend(src, dest, 255));
+		assert_eq!(RGBA::from_rgba([222, 222, 255, 255]), AsepriteBlendMode::Screen.blend(src, dest, 255));
+		assert_eq!(RGBA::from_rgba([100, 100, 50, 255]), AsepriteBlendMode::Darken.blend(src, dest, 255));
+		assert_eq!(RGBA::from_rgba([200, 200, 255, 255]), AsepriteBlendMode::Lighten.blend(src, dest, 255));
+		assert_eq!(RGBA::from_rgba([100, 100, 205, 255]), AsepriteBlendMode::Difference.blend(src, dest, 255));
+		assert_eq!(RGBA::from_rgba([255, 255, 255, 255]), AsepriteBlendMode::Addition.blend(src, dest, 255));
+		assert_eq!(RGBA::from_rgba([100, 0, 0, 255]), AsepriteBlendMode::Subtract.blend(src, dest, 255));
+		// luminosity of the source, with the hue and saturation of the destination
+		let result = AsepriteBlendMode::Luminosity.blend(src, dest, 255);
+		let [r, g, b, _] = result.to_array();
+		let to_f64 = |c: u8| c as f64 / 255.0;
+		assert!((lum([0.392, 0.784, 1.0]) - lum([to_f64(r), to_f64(g), to_f64(b)])).abs() < 0.01);
+		assert!(r > g && g > b);
+		// blending onto a fully transparent destination is always the same as a normal blend
+		let transparent = RGBA::from_rgba([0, 0, 0, 0]);
+		assert_eq!(src, AsepriteBlendMode::Multiply.blend(src, transparent, 255));
+	}
+}
@@ -45,13 +45,14 @@ pub fn clip_blit(
 	}
 
 	// off the right edge?
-	if *dest_x > dest_clip_region.width as i32 - src_blit_region.width as i32 {
+	let clip_right_edge = dest_clip_region.x + dest_clip_region.width as i32;
+	if *dest_x > clip_right_edge - src_blit_region.width as i32 {
 		// completely off the right edge?
 		if *dest_x > dest_clip_region.right() {
 			return false;
 		}
 
-		let offset = *dest_x + src_blit_region.width as i32 - dest_clip_region.width as i32;
+		let offset = *dest_x + src_blit_region.width as i32 - clip_right_edge;
 		if horizontal_flip {
 			src_blit_region.x += offset;
 		}
@@ -74,13 +75,14 @@ pub fn clip_blit(
 	}
 
 	// off the bottom edge?
-	if *dest_y > dest_clip_region.height as i32 - src_blit_region.height as i32 {
+	let clip_bottom_edge = dest_clip_region.y + dest_clip_region.height as i32;
+	if *dest_y > clip_bottom_edge - src_blit_region.height as i32 {
 		// completely off the bottom edge?
 		if *dest_y > dest_clip_region.bottom() {
 			return false;
 		}
 
-		let offset = *dest_y + src_blit_region.height as i32 - dest_clip_region.height as i32;
+		let offset = *dest_y + src_blit_region.height as i32 - clip_bottom_edge;
 		if vertical_flip {
 			src_blit_region.y += offset;
 		}
@@ -183,6 +185,138 @@ pub unsafe fn per_pixel_flipped_blit<PixelType: Pixel>(
 	}
 }
 
+// number of fractional bits used for the fixed-point source coordinates stepped through during scaled blits
+const SCALED_BLIT_FRACTION_BITS: u32 = 16;
+
+/// Calculates the region on the destination bitmap that a scaled blit of the given source region
+/// at the given location will be drawn to.
+#[inline]
+pub fn scaled_blit_region(src_region: &Rect, dest_x: i32, dest_y: i32, scale_x: f32, scale_y: f32) -> Rect {
+	Rect::new(
+		dest_x,
+		dest_y,
+		(src_region.width as f32 * scale_x).round() as u32,
+		(src_region.height as f32 * scale_y).round() as u32,
+	)
+}
+
+/// Clips the destination region for a scaled blit against the clipping region given for the
+/// destination bitmap. This is the scaled blit equivalent of [`clip_blit`]. As the visible portion
+/// of a scaled source region will usually not line up exactly with whole source pixels, the source
+/// region is not clipped. Instead, the number of destination pixels clipped off of the left and top
+/// edges are returned so that drawing can begin part way through the source region.
+///
+/// # Arguments
+///
+/// * `dest_clip_region`: the clipping region for the destination bitmap
+/// * `dest_region`: the region on the destination bitmap that the scaled source will be drawn to,
+///   which will be clipped if necessary to fit into the destination clipping region given
+/// * `skipped_x`: set to the number of destination pixels clipped off of the left edge
+/// * `skipped_y`: set to the number of destination pixels clipped off of the top edge
+///
+/// returns: true if the results of the clip is partially or entirely visible on the destination
+/// bitmap, or false if the blit is entirely outside of the destination bitmap (and so no blit
+/// needs to occur)
+pub fn clip_scaled_blit(
+	dest_clip_region: &Rect,
+	dest_region: &mut Rect,
+	skipped_x: &mut u32,
+	skipped_y: &mut u32,
+) -> bool {
+	if dest_region.width == 0 || dest_region.height == 0 {
+		return false;
+	}
+
+	let original_x = dest_region.x;
+	let original_y = dest_region.y;
+	if !dest_region.clamp_to(dest_clip_region) {
+		return false;
+	}
+
+	*skipped_x = (dest_region.x - original_x) as u32;
+	*skipped_y = (dest_region.y - original_y) as u32;
+	true
+}
+
+/// Draws the source region scaled to fit the given destination region using nearest-neighbour
+/// sampling, calling `pixel_fn` for each destination pixel drawn. Unlike the other per-pixel blit
+/// functions, the destination region is clipped here (using [`clip_scaled_blit`]) against the
+/// destination bitmap's clipping region.
+///
+/// # Safety
+///
+/// The source region must be entirely within the bounds of the source bitmap.
+#[inline]
+pub unsafe fn per_pixel_scaled_blit<PixelType: Pixel>(
+	dest: &mut Bitmap<PixelType>,
+	src: &Bitmap<PixelType>,
+	src_region: &Rect,
+	dest_region: &Rect,
+	pixel_fn: impl Fn(*const PixelType, *mut PixelType),
+) {
+	per_pixel_scaled_flipped_blit(dest, src, src_region, dest_region, false, false, pixel_fn);
+}
+
+/// Same as [`per_pixel_scaled_blit`], but the drawn image can also be flipped horizontally and/or
+/// vertically.
+///
+/// # Safety
+///
+/// The source region must be entirely within the bounds of the source bitmap.
+#[inline]
+pub unsafe fn per_pixel_scaled_flipped_blit<PixelType: Pixel>(
+	dest: &mut Bitmap<PixelType>,
+	src: &Bitmap<PixelType>,
+	src_region: &Rect,
+	dest_region: &Rect,
+	horizontal_flip: bool,
+	vertical_flip: bool,
+	pixel_fn: impl Fn(*const PixelType, *mut PixelType),
+) {
+	if src_region.width == 0 || src_region.height == 0 {
+		return;
+	}
+
+	let mut clipped_region = *dest_region;
+	let mut skipped_x = 0;
+	let mut skipped_y = 0;
+	if !clip_scaled_blit(&dest.clip_region, &mut clipped_region, &mut skipped_x, &mut skipped_y) {
+		return;
+	}
+
+	let step_x = ((src_region.width as u64) << SCALED_BLIT_FRACTION_BITS) / dest_region.width as u64;
+	let step_y = ((src_region.height as u64) << SCALED_BLIT_FRACTION_BITS) / dest_region.height as u64;
+	let last_src_x = src_region.width as usize - 1;
+	let last_src_y = src_region.height as i32 - 1;
+
+	// each destination pixel samples the source at the position corresponding to its center, starting from
+	// wherever the left/top of the destination region was clipped to
+	let start_x = skipped_x as u64 * step_x + step_x / 2;
+	let mut src_y = skipped_y as u64 * step_y + step_y / 2;
+
+	for y in clipped_region.y..=clipped_region.bottom() {
+		let mut row = (src_y >> SCALED_BLIT_FRACTION_BITS) as i32;
+		if vertical_flip {
+			row = last_src_y - row;
+		}
+		let src_pixels = src.pixels_at_ptr_unchecked(src_region.x, src_region.y + row);
+		let mut dest_pixels = dest.pixels_at_mut_ptr_unchecked(clipped_region.x, y);
+		let mut src_x = start_x;
+
+		for _ in 0..clipped_region.width {
+			let mut column = (src_x >> SCALED_BLIT_FRACTION_BITS) as usize;
+			if horizontal_flip {
+				column = last_src_x - column;
+			}
+			pixel_fn(src_pixels.add(column), dest_pixels);
+			dest_pixels = dest_pixels.add(1);
+			src_x += step_x;
+		}
+
+		src_y += step_y;
+	}
+}
+
 #[inline]
 pub unsafe fn per_pixel_rotozoom_blit<PixelType: Pixel>(
 	dest: &mut Bitmap<PixelType>,
@@ -750,5 +884,80 @@ mod tests {
 		assert_eq!(src, Rect::new(20, 0, 64, 32));
 		assert_eq!(0, x);
 		assert_eq!(10, y);
+
+		// clipping regions not located at the top-left of the destination
+
+		let dest = Rect::new(100, 50, 60, 40);
+
+		src = Rect::new(0, 0, 16, 16);
+		x = 92;
+		y = 80;
+		assert!(clip_blit(&dest, &mut src, &mut x, &mut y, false, false));
+		assert_eq!(src, Rect::new(8, 0, 8, 10));
+		assert_eq!(100, x);
+		assert_eq!(80, y);
+
+		src = Rect::new(0, 0, 16, 16);
+		x = 150;
+		y = 40;
+		assert!(clip_blit(&dest, &mut src, &mut x, &mut y, false, false));
+		assert_eq!(src, Rect::new(0, 10, 10, 6));
+		assert_eq!(150, x);
+		assert_eq!(50, y);
+
+		src = Rect::new(0, 0, 16, 16);
+		x = 150;
+		y = 40;
+		assert!(clip_blit(&dest, &mut src, &mut x, &mut y, true, true));
+		assert_eq!(src, Rect::new(6, 0, 10, 6));
+		assert_eq!(150, x);
+		assert_eq!(50, y);
+
+		src = Rect::new(0, 0, 16, 16);
+		x = 160;
+		y = 60;
+		assert!(!clip_blit(&dest, &mut src, &mut x, &mut y, false, false));
+	}
+
+	#[test]
+	pub fn clip_scaled_blit_regions() {
+		let dest = Rect::new(0, 0, 320, 240);
+
+		let mut region: Rect;
+		let mut skipped_x = 0;
+		let mut skipped_y = 0;
+
+		region = Rect::new(10, 10, 40, 24);
+		assert!(clip_scaled_blit(&dest, &mut region, &mut skipped_x, &mut skipped_y));
+		assert_eq!(region, Rect::new(10, 10, 40, 24));
+		assert_eq!(0, skipped_x);
+		assert_eq!(0, skipped_y);
+
+		// top-left edges
+
+		region = Rect::new(-15, -4, 40, 24);
+		assert!(clip_scaled_blit(&dest, &mut region, &mut skipped_x, &mut skipped_y));
+		assert_eq!(region, Rect::new(0, 0, 25, 20));
+		assert_eq!(15, skipped_x);
+		assert_eq!(4, skipped_y);
+
+		region = Rect::new(-40, 10, 40, 24);
+		assert!(!clip_scaled_blit(&dest, &mut region, &mut skipped_x, &mut skipped_y));
+
+		// bottom-right edges
+
+		region = Rect::new(300, 230, 40, 24);
+		assert!(clip_scaled_blit(&dest, &mut region, &mut skipped_x, &mut skipped_y));
+		assert_eq!(region, Rect::new(300, 230, 20, 10));
+		assert_eq!(0, skipped_x);
+		assert_eq!(0, skipped_y);
+
+		region = Rect::new(10, 240, 40, 24);
+		assert!(!clip_scaled_blit(&dest, &mut region, &mut skipped_x, &mut skipped_y));
+
+		// scaled down to nothing
+
+		region = Rect::new(10, 10, 0, 24);
+		assert!(!clip_scaled_blit(&dest, &mut region, &mut skipped_x, &mut skipped_y));
 	}
 }
@@ -0,0 +1,738 @@
+use std::fs::File;
+use std::io;
+use std::io::{BufReader, BufWriter, Read};
+use std::path::Path;
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use thiserror::Error;
+
+use crate::graphics::{IndexedBitmap, Palette, PaletteError, RgbaBitmap, RGBA};
+use crate::utils::ReadFixedLengthByteArray;
+
+const BMP_MAGIC: [u8; 2] = *b"BM";
+
+const BMP_FILE_HEADER_SIZE: u32 = 14;
+const BMP_CORE_HEADER_SIZE: u32 = 12;
+const BMP_INFO_HEADER_SIZE: u32 = 40;
+const BMP_V4_HEADER_SIZE: u32 = 108;
+
+const BI_RGB: u32 = 0;
+const BI_RLE8: u32 = 1;
+const BI_RLE4: u32 = 2;
+const BI_BITFIELDS: u32 = 3;
+const BI_ALPHABITFIELDS: u32 = 6;
+
+// "sRGB", as the color space type written out in V4 headers
+const LCS_SRGB: u32 = 0x73524742;
+
+#[derive(Error, Debug)]
+pub enum BmpError {
+	#[error("Bad or unsupported BMP file: {0}")]
+	BadFile(String),
+
+	#[error("BMP palette data error")]
+	BadPalette(#[from] PaletteError),
+
+	#[error("BMP I/O error")]
+	IOError(#[from] std::io::Error),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BmpFormat {
+	/// 24-bit color, with no alpha channel.
+	RGB,
+	/// 32-bit color, with an alpha channel.
+	RGBA,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct BmpFileHeader {
+	magic: [u8; 2],
+	file_size: u32,
+	reserved1: u16,
+	reserved2: u16,
+	pixel_data_offset: u32,
+}
+
+impl BmpFileHeader {
+	pub fn read<T: ReadBytesExt>(reader: &mut T) -> Result<Self, BmpError> {
+		Ok(BmpFileHeader {
+			magic: reader.read_bytes()?,
+			file_size: reader.read_u32::<LittleEndian>()?,
+			reserved1: reader.read_u16::<LittleEndian>()?,
+			reserved2: reader.read_u16::<LittleEndian>()?,
+			pixel_data_offset: reader.read_u32::<LittleEndian>()?,
+		})
+	}
+
+	pub fn write<T: WriteBytesExt>(&self, writer: &mut T) -> Result<(), BmpError> {
+		writer.write_all(&self.magic)?;
+		writer.write_u32::<LittleEndian>(self.file_size)?;
+		writer.write_u16::<LittleEndian>(self.reserved1)?;
+		writer.write_u16::<LittleEndian>(self.reserved2)?;
+		writer.write_u32::<LittleEndian>(self.pixel_data_offset)?;
+		Ok(())
+	}
+}
+
+/// The "DIB header" that follows the file header. There are several different versions of this header, each one
+/// extending the previous. This covers the fields of all the versions we care about, with fields not present in the
+/// version that was read being left at their default values.
+#[derive(Debug, Copy, Clone)]
+struct BmpInfoHeader {
+	header_size: u32,
+	width: i32,
+	height: i32,
+	planes: u16,
+	bpp: u16,
+	compression: u32,
+	image_size: u32,
+	x_pixels_per_meter: i32,
+	y_pixels_per_meter: i32,
+	colors_used: u32,
+	colors_important: u32,
+	// red, green, blue, alpha
+	masks: [u32; 4],
+}
+
+impl BmpInfoHeader {
+	pub fn read<T: ReadBytesExt>(reader: &mut T) -> Result<Self, BmpError> {
+		let header_size = reader.read_u32::<LittleEndian>()?;
+		if header_size == BMP_CORE_HEADER_SIZE {
+			// the old OS/2 "BITMAPCOREHEADER"
+			return Ok(BmpInfoHeader {
+				header_size,
+				width: reader.read_u16::<LittleEndian>()? as i32,
+				height: reader.read_u16::<LittleEndian>()? as i32,
+				planes: reader.read_u16::<LittleEndian>()?,
+				bpp: reader.read_u16::<LittleEndian>()?,
+				compression: BI_RGB,
+				image_size: 0,
+				x_pixels_per_meter: 0,
+				y_pixels_per_meter: 0,
+				colors_used: 0,
+				colors_important: 0,
+				masks: [0; 4],
+			});
+		} else if header_size < BMP_INFO_HEADER_SIZE {
+			return Err(BmpError::BadFile(format!("Unsupported header size: {}", header_size)));
+		}
+
+		let mut header = BmpInfoHeader {
+			header_size,
+			width: reader.read_i32::<LittleEndian>()?,
+			height: reader.read_i32::<LittleEndian>()?,
+			planes: reader.read_u16::<LittleEndian>()?,
+			bpp: reader.read_u16::<LittleEndian>()?,
+			compression: reader.read_u32::<LittleEndian>()?,
+			image_size: reader.read_u32::<LittleEndian>()?,
+			x_pixels_per_meter: reader.read_i32::<LittleEndian>()?,
+			y_pixels_per_meter: reader.read_i32::<LittleEndian>()?,
+			colors_used: reader.read_u32::<LittleEndian>()?,
+			colors_important: reader.read_u32::<LittleEndian>()?,
+			masks: [0; 4],
+		};
+
+		// newer versions of the header include the color masks (and then other stuff we don't care about)
+		let mut remaining = header_size - BMP_INFO_HEADER_SIZE;
+		for mask in header.masks.iter_mut() {
+			if remaining < 4 {
+				break;
+			}
+			*mask = reader.read_u32::<LittleEndian>()?;
+			remaining -= 4;
+		}
+		skip_bytes(reader, remaining as u64)?;
+
+		Ok(header)
+	}
+
+	pub fn write<T: WriteBytesExt>(&self, writer: &mut T) -> Result<(), BmpError> {
+		writer.write_u32::<LittleEndian>(self.header_size)?;
+		writer.write_i32::<LittleEndian>(self.width)?;
+		writer.write_i32::<LittleEndian>(self.height)?;
+		writer.write_u16::<LittleEndian>(self.planes)?;
+		writer.write_u16::<LittleEndian>(self.bpp)?;
+		writer.write_u32::<LittleEndian>(self.compression)?;
+		writer.write_u32::<LittleEndian>(self.image_size)?;
+		writer.write_i32::<LittleEndian>(self.x_pixels_per_meter)?;
+		writer.write_i32::<LittleEndian>(self.y_pixels_per_meter)?;
+		writer.write_u32::<LittleEndian>(self.colors_used)?;
+		writer.write_u32::<LittleEndian>(self.colors_important)?;
+		if self.header_size == BMP_V4_HEADER_SIZE {
+			for mask in self.masks.iter() {
+				writer.write_u32::<LittleEndian>(*mask)?;
+			}
+			writer.write_u32::<LittleEndian>(LCS_SRGB)?;
+			// color space endpoints and gamma values, which are unused for srgb
+			writer.write_all(&[0u8; 48])?;
+		}
+		Ok(())
+	}
+}
+
+fn skip_bytes<T: Read>(reader: &mut T, count: u64) -> Result<(), BmpError> {
+	let skipped = io::copy(&mut reader.take(count), &mut io::sink())?;
+	if skipped != count {
+		return Err(BmpError::IOError(io::Error::from(io::ErrorKind::UnexpectedEof)));
+	}
+	Ok(())
+}
+
+/// Returns the number of bytes in each row of pixel data. Rows are always padded to a multiple of 4 bytes.
+fn row_stride(width: u32, bpp: u16) -> usize {
+	((width as usize * bpp as usize).div_ceil(32)) * 4
+}
+
+/// Extracts a color component from a pixel value using the given bit mask, scaling it up to 8 bits.
+fn extract_component(value: u32, mask: u32) -> u8 {
+	if mask == 0 {
+		return 0;
+	}
+	let shift = mask.trailing_zeros();
+	let max = (mask >> shift) as u64;
+	let component = ((value & mask) >> shift) as u64;
+	((component * 255 + max / 2) / max) as u8
+}
+
+enum DecodedBmp {
+	Indexed(IndexedBitmap, Box<Palette>),
+	Rgba(RgbaBitmap),
+}
+
+fn read_rle_pixels<T: ReadBytesExt>(reader: &mut T, bmp: &mut IndexedBitmap, compression: u32) -> Result<(), BmpError> {
+	let width = bmp.width() as i32;
+	let height = bmp.height() as i32;
+	// rle-compressed pixel data is always stored bottom-up
+	let mut x = 0;
+	let mut y = height - 1;
+	let mut put_pixel = |x: i32, y: i32, color: u8| {
+		if x < width && y >= 0 {
+			unsafe { bmp.set_pixel_unchecked(x, y, color) };
+		}
+	};
+
+	loop {
+		let count = reader.read_u8()? as i32;
+		let value = reader.read_u8()?;
+		if count > 0 {
+			// encoded mode. a run of 'count' pixels. for rle4, the two nibbles give two alternating colors
+			for i in 0..count {
+				let color = match compression {
+					BI_RLE4 if i % 2 == 0 => value >> 4,
+					BI_RLE4 => value & 0x0f,
+					_ => value,
+				};
+				put_pixel(x, y, color);
+				x += 1;
+			}
+		} else {
+			match value {
+				// end of line
+				0 => {
+					x = 0;
+					y -= 1;
+				}
+				// end of bitmap
+				1 => break,
+				// delta. skipped over pixels are left as color 0
+				2 => {
+					x += reader.read_u8()? as i32;
+					y -= reader.read_u8()? as i32;
+				}
+				// absolute mode. a series of 'value' uncompressed pixels, padded to a 16-bit boundary
+				_ => {
+					let num_pixels = value as i32;
+					let num_bytes = match compression {
+						BI_RLE4 => (value as usize).div_ceil(2),
+						_ => value as usize,
+					};
+					let mut bytes = vec![0u8; num_bytes.next_multiple_of(2)];
+					reader.read_exact(&mut bytes)?;
+					for i in 0..num_pixels {
+						let color = match compression {
+							BI_RLE4 if i % 2 == 0 => bytes[i as usize / 2] >> 4,
+							BI_RLE4 => bytes[i as usize / 2] & 0x0f,
+							_ => bytes[i as usize],
+						};
+						put_pixel(x, y, color);
+						x += 1;
+					}
+				}
+			}
+		}
+	}
+
+	Ok(())
+}
+
+fn load_bmp_bytes<T: ReadBytesExt>(reader: &mut T) -> Result<DecodedBmp, BmpError> {
+	let file_header = BmpFileHeader::read(reader)?;
+	if file_header.magic != BMP_MAGIC {
+		return Err(BmpError::BadFile(String::from("Unexpected 2-byte header, probably not a BMP file")));
+	}
+
+	let mut header = BmpInfoHeader::read(reader)?;
+	let mut bytes_read = BMP_FILE_HEADER_SIZE + header.header_size;
+
+	let top_down = header.height < 0;
+	let width = header.width;
+	let height = header.height.unsigned_abs();
+	let num_pixels = (width.max(0) as usize).checked_mul(height as usize);
+	if width <= 0 || height == 0 || !matches!(num_pixels, Some(n) if n <= super::MAX_LOADED_PIXELS) {
+		return Err(BmpError::BadFile(format!("Invalid BMP image dimensions: {}x{}", width, height)));
+	}
+	let width = width as u32;
+
+	match (header.compression, header.bpp) {
+		(BI_RGB, 1 | 4 | 8 | 16 | 24 | 32) => {}
+		(BI_RLE8, 8) | (BI_RLE4, 4) => {
+			if top_down {
+				return Err(BmpError::BadFile(String::from("RLE-compressed BMP files cannot be top-down")));
+			}
+		}
+		(BI_BITFIELDS | BI_ALPHABITFIELDS, 16 | 32) => {
+			// with the original info header, the masks follow the header instead of being a part of it
+			if header.header_size == BMP_INFO_HEADER_SIZE {
+				let num_masks = if header.compression == BI_ALPHABITFIELDS { 4 } else { 3 };
+				for mask in header.masks.iter_mut().take(num_masks) {
+					*mask = reader.read_u32::<LittleEndian>()?;
+				}
+				bytes_read += num_masks as u32 * 4;
+			}
+		}
+		(compression, bpp) => {
+			return Err(BmpError::BadFile(format!(
+				"Unsupported combination of compression method {} and bit depth {}",
+				compression, bpp
+			)))
+		}
+	}
+
+	// the masks given in headers for uncompressed images are meant to be ignored. instead, these defaults are used
+	if header.compression == BI_RGB {
+		header.masks = match header.bpp {
+			16 => [0x7c00, 0x03e0, 0x001f, 0],
+			_ => [0x00ff0000, 0x0000ff00, 0x000000ff, 0],
+		};
+	}
+
+	// indexed-color images have a palette before the pixel data
+
+	let mut palette = None;
+	if header.bpp <= 8 {
+		let max_colors = 1u32 << header.bpp;
+		let num_colors = match header.colors_used {
+			0 => max_colors,
+			n => n.min(max_colors),
+		};
+		let entry_size = if header.header_size == BMP_CORE_HEADER_SIZE { 3 } else { 4 };
+		let mut bmp_palette = Palette::new();
+		for index in 0..num_colors {
+			let b = reader.read_u8()?;
+			let g = reader.read_u8()?;
+			let r = reader.read_u8()?;
+			if entry_size == 4 {
+				reader.read_u8()?;
+			}
+			bmp_palette[index as u8] = RGBA::from_rgb([r, g, b]);
+		}
+		bytes_read += num_colors * entry_size;
+		palette = Some(bmp_palette);
+	}
+
+	// skip over anything else between the headers and the pixel data
+
+	if file_header.pixel_data_offset < bytes_read {
+		return Err(BmpError::BadFile(String::from("Pixel data offset overlaps with the headers")));
+	}
+	skip_bytes(reader, (file_header.pixel_data_offset - bytes_read) as u64)?;
+
+	if let Some(palette) = palette {
+		let mut bmp = IndexedBitmap::new(width, height)
+			.map_err(|_| BmpError::BadFile(String::from("Invalid BMP image dimensions")))?;
+		if header.compression == BI_RLE8 || header.compression == BI_RLE4 {
+			read_rle_pixels(reader, &mut bmp, header.compression)?;
+		} else {
+			let bpp = header.bpp as usize;
+			let pixels_per_byte = 8 / bpp;
+			let pixel_mask = ((1u16 << bpp) - 1) as u8;
+			let mut row = vec![0u8; row_stride(width, header.bpp)];
+			for row_index in 0..height {
+				reader.read_exact(&mut row)?;
+				let y = if top_down { row_index } else { height - 1 - row_index };
+				for x in 0..width as usize {
+					// pixels are packed from the most significant bits first
+					let byte = row[x / pixels_per_byte];
+					let shift = 8 - bpp * (x % pixels_per_byte + 1);
+					let color = (byte >> shift) & pixel_mask;
+					unsafe { bmp.set_pixel_unchecked(x as i32, y as i32, color) };
+				}
+			}
+		}
+		Ok(DecodedBmp::Indexed(bmp, Box::new(palette)))
+	} else {
+		let mut bmp = RgbaBitmap::new(width, height)
+			.map_err(|_| BmpError::BadFile(String::from("Invalid BMP image dimensions")))?;
+		let bytes_per_pixel = header.bpp as usize / 8;
+		let [r_mask, g_mask, b_mask, a_mask] = header.masks;
+		let mut row = vec![0u8; row_stride(width, header.bpp)];
+		for row_index in 0..height {
+			reader.read_exact(&mut row)?;
+			let y = if top_down { row_index } else { height - 1 - row_index };
+			for (x, bytes) in row.chunks_exact(bytes_per_pixel).take(width as usize).enumerate() {
+				let color = match bytes {
+					[b, g, r] => RGBA::from_rgb([*r, *g, *b]),
+					_ => {
+						let value = match bytes {
+							[lo, hi] => u16::from_le_bytes([*lo, *hi]) as u32,
+							_ => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+						};
+						RGBA::from_rgba([
+							extract_component(value, r_mask),
+							extract_component(value, g_mask),
+							extract_component(value, b_mask),
+							if a_mask == 0 { 255 } else { extract_component(value, a_mask) },
+						])
+					}
+				};
+				unsafe { bmp.set_pixel_unchecked(x as i32, y as i32, color) };
+			}
+		}
+		Ok(DecodedBmp::Rgba(bmp))
+	}
+}
+
+fn write_bmp_bytes<T: WriteBytesExt>(
+	writer: &mut T,
+	width: u32,
+	height: u32,
+	header: BmpInfoHeader,
+	palette: Option<&Palette>,
+	mut write_row: impl FnMut(&mut Vec<u8>, u32),
+) -> Result<(), BmpError> {
+	let palette_size = if palette.is_some() { 256 * 4 } else { 0 };
+	let stride = row_stride(width, header.bpp);
+	let image_size = (stride * height as usize) as u32;
+	let pixel_data_offset = BMP_FILE_HEADER_SIZE + header.header_size + palette_size;
+
+	let file_header = BmpFileHeader {
+		magic: BMP_MAGIC,
+		file_size: pixel_data_offset + image_size,
+		reserved1: 0,
+		reserved2: 0,
+		pixel_data_offset,
+	};
+	file_header.write(writer)?;
+	BmpInfoHeader { image_size, ..header }.write(writer)?;
+
+	if let Some(palette) = palette {
+		for i in 0..=255 {
+			let color = palette[i];
+			writer.write_all(&[color.b(), color.g(), color.r(), 0])?;
+		}
+	}
+
+	// always written out bottom-up, as that is what the most software expects
+	let mut row = Vec::with_capacity(stride);
+	for y in (0..height).rev() {
+		row.clear();
+		write_row(&mut row, y);
+		row.resize(stride, 0);
+		writer.write_all(&row)?;
+	}
+
+	Ok(())
+}
+
+impl IndexedBitmap {
+	pub fn load_bmp_bytes<T: ReadBytesExt>(reader: &mut T) -> Result<(IndexedBitmap, Palette), BmpError> {
+		match load_bmp_bytes(reader)? {
+			DecodedBmp::Indexed(bmp, palette) => Ok((bmp, *palette)),
+			DecodedBmp::Rgba(_) => Err(BmpError::BadFile(String::from(
+				"Only indexed-color (8-bit or less) BMP files can be loaded into an IndexedBitmap",
+			))),
+		}
+	}
+
+	pub fn load_bmp_file(path: &Path) -> Result<(IndexedBitmap, Palette), BmpError> {
+		let f = File::open(path)?;
+		let mut reader = BufReader::new(f);
+		Self::load_bmp_bytes(&mut reader)
+	}
+
+	pub fn to_bmp_bytes<T: WriteBytesExt>(&self, writer: &mut T, palette: &Palette) -> Result<(), BmpError> {
+		let header = BmpInfoHeader {
+			header_size: BMP_INFO_HEADER_SIZE,
+			width: self.width() as i32,
+			height: self.height() as i32,
+			planes: 1,
+			bpp: 8,
+			compression: BI_RGB,
+			image_size: 0,
+			x_pixels_per_meter: 0,
+			y_pixels_per_meter: 0,
+			colors_used: 256,
+			colors_important: 0,
+			masks: [0; 4],
+		};
+		let width = self.width() as usize;
+		write_bmp_bytes(writer, self.width(), self.height(), header, Some(palette), |row, y| {
+			let start = y as usize * width;
+			row.extend_from_slice(&self.pixels()[start..start + width]);
+		})
+	}
+
+	pub fn to_bmp_file(&self, path: &Path, palette: &Palette) -> Result<(), BmpError> {
+		let f = File::create(path)?;
+		let mut writer = BufWriter::new(f);
+		self.to_bmp_bytes(&mut writer, palette)
+	}
+}
+
+impl RgbaBitmap {
+	pub fn load_bmp_bytes<T: ReadBytesExt>(reader: &mut T) -> Result<(RgbaBitmap, Option<Palette>), BmpError> {
+		match load_bmp_bytes(reader)? {
+			DecodedBmp::Indexed(bmp, palette) => Ok((bmp.to_rgba(&palette), Some(*palette))),
+			DecodedBmp::Rgba(bmp) => Ok((bmp, None)),
+		}
+	}
+
+	pub fn load_bmp_file(path: &Path) -> Result<(RgbaBitmap, Option<Palette>), BmpError> {
+		let f = File::open(path)?;
+		let mut reader = BufReader::new(f);
+		Self::load_bmp_bytes(&mut reader)
+	}
+
+	pub fn to_bmp_bytes<T: WriteBytesExt>(&self, writer: &mut T, format: BmpFormat) -> Result<(), BmpError> {
+		let header = BmpInfoHeader {
+			header_size: BMP_INFO_HEADER_SIZE,
+			width: self.width() as i32,
+			height: self.height() as i32,
+			planes: 1,
+			bpp: 24,
+			compression: BI_RGB,
+			image_size: 0,
+			x_pixels_per_meter: 0,
+			y_pixels_per_meter: 0,
+			colors_used: 0,
+			colors_important: 0,
+			masks: [0; 4],
+		};
+		// the alpha channel is only properly supported with bitfields and a v4 (or later) header
+		let header = match format {
+			BmpFormat::RGB => header,
+			BmpFormat::RGBA => BmpInfoHeader {
+				header_size: BMP_V4_HEADER_SIZE,
+				bpp: 32,
+				compression: BI_BITFIELDS,
+				masks: [0x00ff0000, 0x0000ff00, 0x000000ff, 0xff000000],
+				..header
+			},
+		};
+		let width = self.width() as usize;
+		write_bmp_bytes(writer, self.width(), self.height(), header, None, |row, y| {
+			let start = y as usize * width;
+			for pixel in self.pixels()[start..start + width].iter() {
+				match format {
+					BmpFormat::RGB => row.extend_from_slice(&[pixel.b(), pixel.g(), pixel.r()]),
+					BmpFormat::RGBA => row.extend_from_slice(&[pixel.b(), pixel.g(), pixel.r(), pixel.a()]),
+				}
+			}
+		})
+	}
+
+	pub fn to_bmp_file(&self, path: &Path, format: BmpFormat) -> Result<(), BmpError> {
+		let f = File::create(path)?;
+		let mut writer = BufWriter::new(f);
+		self.to_bmp_bytes(&mut writer, format)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Cursor;
+	use std::path::PathBuf;
+
+	use claim::*;
+	use tempfile::TempDir;
+
+	use crate::graphics::PaletteFormat;
+	use crate::tests::{load_raw_indexed, load_raw_rgba, test_assets_file};
+
+	use super::*;
+
+	const BASE_PATH: &str = "./test-assets/bmp/";
+
+	fn test_file(file: &Path) -> PathBuf {
+		PathBuf::from(BASE_PATH).join(file)
+	}
+
+	#[test]
+	pub fn load_and_save() -> Result<(), BmpError> {
+		let tmp_dir = TempDir::new()?;
+
+		let ref_pixels = load_raw_indexed(test_file(Path::new("small.bin")).as_path())?;
+		let dp2_palette = Palette::load_from_file(
+			test_assets_file(Path::new("dp2.pal")).as_path(), //
+			PaletteFormat::Normal,
+		)
+		.unwrap();
+
+		let (bmp, palette) = IndexedBitmap::load_bmp_file(test_file(Path::new("small.bmp")).as_path())?;
+		assert_eq!(16, bmp.width());
+		assert_eq!(16, bmp.height());
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+		assert_eq!(palette, dp2_palette);
+
+		let save_path = tmp_dir.path().join("test_save.bmp");
+		bmp.to_bmp_file(&save_path, &palette)?;
+		let (reloaded_bmp, reloaded_palette) = IndexedBitmap::load_bmp_file(&save_path)?;
+		assert_eq!(16, reloaded_bmp.width());
+		assert_eq!(16, reloaded_bmp.height());
+		assert_eq!(reloaded_bmp.pixels(), ref_pixels.as_ref());
+		assert_eq!(reloaded_palette, dp2_palette);
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn load_and_save_larger_image() -> Result<(), BmpError> {
+		let tmp_dir = TempDir::new()?;
+
+		// first image
+
+		let ref_pixels = load_raw_indexed(test_file(Path::new("large_1.bin")).as_path())?;
+
+		let (bmp, palette) = IndexedBitmap::load_bmp_file(test_file(Path::new("large_1.bmp")).as_path())?;
+		assert_eq!(320, bmp.width());
+		assert_eq!(200, bmp.height());
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+
+		let save_path = tmp_dir.path().join("test_save.bmp");
+		bmp.to_bmp_file(&save_path, &palette)?;
+		let (reloaded_bmp, _) = IndexedBitmap::load_bmp_file(&save_path)?;
+		assert_eq!(320, reloaded_bmp.width());
+		assert_eq!(200, reloaded_bmp.height());
+		assert_eq!(reloaded_bmp.pixels(), ref_pixels.as_ref());
+
+		// second image
+
+		let ref_pixels = load_raw_indexed(test_file(Path::new("large_2.bin")).as_path())?;
+
+		let (bmp, palette) = IndexedBitmap::load_bmp_file(test_file(Path::new("large_2.bmp")).as_path())?;
+		assert_eq!(320, bmp.width());
+		assert_eq!(200, bmp.height());
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+
+		let save_path = tmp_dir.path().join("test_save_2.bmp");
+		bmp.to_bmp_file(&save_path, &palette)?;
+		let (reloaded_bmp, _) = IndexedBitmap::load_bmp_file(&save_path)?;
+		assert_eq!(320, reloaded_bmp.width());
+		assert_eq!(200, reloaded_bmp.height());
+		assert_eq!(reloaded_bmp.pixels(), ref_pixels.as_ref());
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn loads_indexed_variants() -> Result<(), BmpError> {
+		let ref_pixels = load_raw_indexed(test_file(Path::new("large_1.bin")).as_path())?;
+		let (_, ref_palette) = IndexedBitmap::load_bmp_file(test_file(Path::new("large_1.bmp")).as_path())?;
+		for file in ["large_1_rle8.bmp", "large_1_topdown.bmp", "large_1_os2.bmp"] {
+			let (bmp, palette) = IndexedBitmap::load_bmp_file(test_file(Path::new(file)).as_path())?;
+			assert_eq!(bmp.pixels(), ref_pixels.as_ref(), "{}", file);
+			assert_eq!(palette, ref_palette, "{}", file);
+		}
+		Ok(())
+	}
+
+	#[test]
+	pub fn loads_low_bit_depths() -> Result<(), BmpError> {
+		for (file, bin_file) in [
+			("indexed_1bit.bmp", "indexed_1bit"),
+			("indexed_4bit.bmp", "indexed_4bit"),
+			("indexed_4bit_rle4.bmp", "indexed_4bit"),
+		] {
+			let ref_pixels = load_raw_indexed(test_file(Path::new(&format!("{}.bin", bin_file))).as_path())?;
+			let (bmp, _) = IndexedBitmap::load_bmp_file(test_file(Path::new(file)).as_path())?;
+			assert_eq!(37, bmp.width());
+			assert_eq!(23, bmp.height());
+			assert_eq!(bmp.pixels(), ref_pixels.as_ref(), "{}", file);
+
+			let ref_pixels = load_raw_rgba(test_file(Path::new(&format!("{}_rgba.bin", bin_file))).as_path())?;
+			let (bmp, palette) = RgbaBitmap::load_bmp_file(test_file(Path::new(file)).as_path())?;
+			assert!(palette.is_some());
+			assert_eq!(bmp.pixels(), ref_pixels.as_ref(), "{}", file);
+		}
+		Ok(())
+	}
+
+	#[test]
+	pub fn loads_rgb_and_rgba() -> Result<(), BmpError> {
+		for (file, bin_file) in [
+			("rgb_24bit.bmp", "rgb_rgba.bin"),
+			("rgb_24bit_topdown.bmp", "rgb_rgba.bin"),
+			("rgb_32bit.bmp", "rgb_rgba.bin"),
+			("rgba_32bit.bmp", "rgba_rgba.bin"),
+			("rgb_16bit.bmp", "rgb_16bit_rgba.bin"),
+			("rgb_16bit_565.bmp", "rgb_16bit_565_rgba.bin"),
+		] {
+			let ref_pixels = load_raw_rgba(test_file(Path::new(bin_file)).as_path())?;
+			let (bmp, palette) = RgbaBitmap::load_bmp_file(test_file(Path::new(file)).as_path())?;
+			assert_eq!(37, bmp.width());
+			assert_eq!(23, bmp.height());
+			assert!(palette.is_none());
+			assert_eq!(bmp.pixels(), ref_pixels.as_ref(), "{}", file);
+
+			assert_matches!(
+				IndexedBitmap::load_bmp_file(test_file(Path::new(file)).as_path()),
+				Err(BmpError::BadFile(..))
+			);
+		}
+		Ok(())
+	}
+
+	#[test]
+	pub fn rejects_oversized_dimensions() {
+		let mut bytes = Vec::new();
+		bytes.extend_from_slice(&BMP_MAGIC);
+		bytes.extend_from_slice(&[0u8; 8]);
+		bytes.extend_from_slice(&(BMP_FILE_HEADER_SIZE + BMP_INFO_HEADER_SIZE).to_le_bytes());
+		bytes.extend_from_slice(&BMP_INFO_HEADER_SIZE.to_le_bytes());
+		bytes.extend_from_slice(&65536i32.to_le_bytes());
+		bytes.extend_from_slice(&65536i32.to_le_bytes());
+		bytes.extend_from_slice(&1u16.to_le_bytes());
+		bytes.extend_from_slice(&24u16.to_le_bytes());
+		bytes.extend_from_slice(&[0u8; 24]);
+
+		assert_matches!(RgbaBitmap::load_bmp_bytes(&mut Cursor::new(&bytes)), Err(BmpError::BadFile(..)));
+	}
+
+	#[test]
+	pub fn load_and_save_rgb_and_rgba() -> Result<(), BmpError> {
+		let tmp_dir = TempDir::new()?;
+
+		let ref_pixels = load_raw_rgba(test_file(Path::new("rgb_rgba.bin")).as_path())?;
+		let (bmp, _) = RgbaBitmap::load_bmp_file(test_file(Path::new("rgb_24bit.bmp")).as_path())?;
+		let save_path = tmp_dir.path().join("test_save_rgb.bmp");
+		bmp.to_bmp_file(&save_path, BmpFormat::RGB)?;
+		let (reloaded_bmp, reloaded_palette) = RgbaBitmap::load_bmp_file(&save_path)?;
+		assert_eq!(37, reloaded_bmp.width());
+		assert_eq!(23, reloaded_bmp.height());
+		assert!(reloaded_palette.is_none());
+		assert_eq!(reloaded_bmp.pixels(), ref_pixels.as_ref());
+
+		let ref_pixels = load_raw_rgba(test_file(Path::new("rgba_rgba.bin")).as_path())?;
+		let (bmp, _) = RgbaBitmap::load_bmp_file(test_file(Path::new("rgba_32bit.bmp")).as_path())?;
+		let save_path = tmp_dir.path().join("test_save_rgba.bmp");
+		bmp.to_bmp_file(&save_path, BmpFormat::RGBA)?;
+		let (reloaded_bmp, reloaded_palette) = RgbaBitmap::load_bmp_file(&save_path)?;
+		assert_eq!(37, reloaded_bmp.width());
+		assert_eq!(23, reloaded_bmp.height());
+		assert!(reloaded_palette.is_none());
+		assert_eq!(reloaded_bmp.pixels(), ref_pixels.as_ref());
+
+		Ok(())
+	}
+}
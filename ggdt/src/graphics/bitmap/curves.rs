@@ -0,0 +1,268 @@
+use crate::graphics::bitmap::shapes::points_extents;
+use crate::graphics::{Bitmap, BitmapViewMut, LineCap, Pixel};
+use crate::math::{Curve, Rect, Vector2};
+
+// the maximum distance (in pixels) that the lines used to draw a curve can deviate from the actual curve
+const CURVE_TOLERANCE: f32 = 0.25;
+
+// wraps a curve, moving all of its points by a fixed offset. used to draw curves given in view-local coordinates
+struct OffsetCurve<'a, C: Curve + ?Sized> {
+	curve: &'a C,
+	offset: Vector2,
+}
+
+impl<C: Curve + ?Sized> Curve for OffsetCurve<'_, C> {
+	#[inline]
+	fn point_at(&self, t: f32) -> Vector2 {
+		self.curve.point_at(t) + self.offset
+	}
+
+	#[inline]
+	fn tangent_at(&self, t: f32) -> Vector2 {
+		self.curve.tangent_at(t)
+	}
+
+	#[inline]
+	fn segment_count(&self) -> usize {
+		self.curve.segment_count()
+	}
+}
+
+impl<PixelType: Pixel> Bitmap<PixelType> {
+	/// Draws the given curve (such as a [`crate::math::QuadraticBezier`], [`crate::math::CubicBezier`] or
+	/// [`crate::math::CatmullRomSpline`]). The curve is drawn as a series of connected lines, using as few lines as
+	/// needed to closely follow the curve. As with [`Bitmap::polyline`], pixels where the lines join are not drawn
+	/// twice.
+	pub fn curve<C: Curve + ?Sized>(&mut self, curve: &C, color: PixelType) {
+		self.curve_custom(curve, |_| color);
+	}
+
+	pub fn curve_custom<C: Curve + ?Sized>(&mut self, curve: &C, pixel_fn: impl Fn(PixelType) -> PixelType) {
+		let mut points = curve.flatten(CURVE_TOLERANCE);
+		if points.len() > 2 && points[0] == points[points.len() - 1] {
+			// a closed curve, such as a closed spline, so draw it as a polygon to avoid drawing the start point twice
+			points.pop();
+			self.polygon_custom(&points, pixel_fn);
+		} else {
+			self.polyline_custom(&points, pixel_fn);
+		}
+	}
+
+	/// Draws the given curve with the given thickness, using the given cap style for the ends of the curve. The
+	/// lines that the curve is drawn with are joined together smoothly, and no pixel is drawn more than once.
+	pub fn thick_curve<C: Curve + ?Sized>(&mut self, curve: &C, thickness: u32, cap: LineCap, color: PixelType) {
+		self.thick_curve_custom(curve, thickness, cap, |_| color);
+	}
+
+	pub fn thick_curve_custom<C: Curve + ?Sized>(
+		&mut self,
+		curve: &C,
+		thickness: u32,
+		cap: LineCap,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		if thickness == 0 {
+			return;
+		}
+
+		let points = curve.flatten(CURVE_TOLERANCE);
+		let half_thickness = thickness as f32 / 2.0;
+		let extent = thickness as f32;
+
+		let (min, max) = points_extents(&points);
+		let to_bounds = |min: Vector2, max: Vector2| {
+			Rect::from_coords(
+				(min.x - extent).floor() as i32,
+				(min.y - extent).floor() as i32,
+				(max.x + extent).ceil() as i32,
+				(max.y + extent).ceil() as i32,
+			)
+		};
+		let mut bounds = to_bounds(min, max);
+		if !bounds.clamp_to(&self.clip_region) {
+			return;
+		}
+
+		// each of the lines making up the curve overlap where they join, so the pixels to be drawn are collected
+		// first to ensure that each one is only drawn once
+		let mut mask = vec![false; (bounds.width * bounds.height) as usize];
+		let mut mark = |line_bounds: Rect, is_inside: &dyn Fn(Vector2) -> bool| {
+			let mut line_bounds = line_bounds;
+			if !line_bounds.clamp_to(&bounds) {
+				return;
+			}
+			for y in line_bounds.y..=line_bounds.bottom() {
+				for x in line_bounds.x..=line_bounds.right() {
+					let index = ((y - bounds.y) * bounds.width as i32 + (x - bounds.x)) as usize;
+					if !mask[index] && is_inside(Vector2::new(x as f32, y as f32)) {
+						mask[index] = true;
+					}
+				}
+			}
+		};
+
+		let radius_squared = half_thickness * half_thickness;
+		let last_segment = points.len() - 2;
+		for (i, pair) in points.windows(2).enumerate() {
+			let (start, end) = (pair[0], pair[1]);
+			let delta = end - start;
+			let length = delta.length();
+			if length > 0.0 {
+				let direction = delta / length;
+				let along_min = if i == 0 && cap == LineCap::Square { -half_thickness } else { 0.0 };
+				let along_max =
+					if i == last_segment && cap == LineCap::Square { length + half_thickness } else { length };
+				let line_bounds = to_bounds(
+					Vector2::new(start.x.min(end.x), start.y.min(end.y)),
+					Vector2::new(start.x.max(end.x), start.y.max(end.y)),
+				);
+				mark(line_bounds, &|point| {
+					// distance along the line from the start point, and distance from the line itself
+					let offset = point - start;
+					let along = offset.dot(&direction);
+					let across = offset.x * -direction.y + offset.y * direction.x;
+					along >= along_min && along <= along_max && across >= -half_thickness && across < half_thickness
+				});
+			}
+
+			// round joins between lines, and round caps on the ends of the curve
+			let is_end = i == last_segment;
+			let joins = [(start, i > 0 || cap == LineCap::Round), (end, is_end && cap == LineCap::Round)];
+			for (joint, is_round) in joins {
+				if is_round {
+					mark(to_bounds(joint, joint), &|point| point.distance_squared(&joint) < radius_squared);
+				}
+			}
+		}
+
+		for y in bounds.y..=bounds.bottom() {
+			for x in bounds.x..=bounds.right() {
+				if mask[((y - bounds.y) * bounds.width as i32 + (x - bounds.x)) as usize] {
+					// safety: the bounds were clamped to the clipping region above
+					unsafe { self.set_custom_pixel_unchecked(x, y, &pixel_fn) };
+				}
+			}
+		}
+	}
+}
+
+impl<PixelType: Pixel> BitmapViewMut<'_, PixelType> {
+	#[inline]
+	fn offset_curve<'c, C: Curve + ?Sized>(&self, curve: &'c C) -> OffsetCurve<'c, C> {
+		OffsetCurve { curve, offset: Vector2::new(self.x() as f32, self.y() as f32) }
+	}
+
+	/// Draws the given curve, the same as [`Bitmap::curve`].
+	pub fn curve<C: Curve + ?Sized>(&mut self, curve: &C, color: PixelType) {
+		self.curve_custom(curve, |_| color);
+	}
+
+	pub fn curve_custom<C: Curve + ?Sized>(&mut self, curve: &C, pixel_fn: impl Fn(PixelType) -> PixelType) {
+		let curve = self.offset_curve(curve);
+		self.with_bitmap(|bitmap| bitmap.curve_custom(&curve, pixel_fn));
+	}
+
+	/// Draws the given curve with the given thickness, the same as [`Bitmap::thick_curve`].
+	pub fn thick_curve<C: Curve + ?Sized>(&mut self, curve: &C, thickness: u32, cap: LineCap, color: PixelType) {
+		self.thick_curve_custom(curve, thickness, cap, |_| color);
+	}
+
+	pub fn thick_curve_custom<C: Curve + ?Sized>(
+		&mut self,
+		curve: &C,
+		thickness: u32,
+		cap: LineCap,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		let curve = self.offset_curve(curve);
+		self.with_bitmap(|bitmap| bitmap.thick_curve_custom(&curve, thickness, cap, pixel_fn));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::graphics::IndexedBitmap;
+	use crate::math::{CatmullRomSpline, CubicBezier, QuadraticBezier};
+
+	use crate::tests::assert_no_overdraw;
+
+	use super::*;
+
+	#[test]
+	pub fn straight_curves() {
+		// a curve with all of its points along a straight line is drawn exactly the same as a line
+		let curve = CubicBezier::new(
+			Vector2::new(2.0, 3.0),
+			Vector2::new(5.0, 3.0),
+			Vector2::new(9.0, 3.0),
+			Vector2::new(12.0, 3.0),
+		);
+		let mut bmp = IndexedBitmap::new(16, 8).unwrap();
+		bmp.curve(&curve, 1);
+		let mut expected = IndexedBitmap::new(16, 8).unwrap();
+		expected.line(2, 3, 12, 3, 1);
+		assert_eq!(expected.pixels(), bmp.pixels());
+
+		for cap in [LineCap::Butt, LineCap::Square, LineCap::Round] {
+			let mut bmp = IndexedBitmap::new(16, 8).unwrap();
+			bmp.thick_curve(&curve, 3, cap, 1);
+			let mut expected = IndexedBitmap::new(16, 8).unwrap();
+			expected.thick_line(2, 3, 12, 3, 3, cap, 1);
+			assert_eq!(expected.pixels(), bmp.pixels(), "{:?}", cap);
+		}
+	}
+
+	#[test]
+	pub fn curves() {
+		let curve = QuadraticBezier::new(Vector2::new(2.0, 2.0), Vector2::new(16.0, 40.0), Vector2::new(30.0, 2.0));
+		let mut bmp = IndexedBitmap::new(32, 32).unwrap();
+		bmp.curve(&curve, 1);
+		// starts and ends at the curve's end points, and passes through its middle
+		assert_eq!(Some(1), bmp.get_pixel(2, 2));
+		assert_eq!(Some(1), bmp.get_pixel(30, 2));
+		assert_eq!(Some(1), bmp.get_pixel(16, 21));
+		// and is drawn as one continuous line with no gaps
+		assert_eq!(Some(0), bmp.get_pixel(0, 0));
+		for y in 2..=20 {
+			let row = &bmp.pixels()[(y * 32) as usize..((y + 1) * 32) as usize];
+			assert!(row.iter().filter(|&&pixel| pixel == 1).count() >= 2, "row {}", y);
+		}
+
+		let mut bmp = IndexedBitmap::new(32, 32).unwrap();
+		bmp.thick_curve(&curve, 5, LineCap::Round, 1);
+		assert_eq!(Some(1), bmp.get_pixel(16, 21));
+		assert_eq!(Some(1), bmp.get_pixel(16, 19));
+		assert_eq!(Some(1), bmp.get_pixel(16, 22));
+		assert_eq!(Some(0), bmp.get_pixel(16, 17));
+		assert_eq!(Some(0), bmp.get_pixel(16, 25));
+		assert_eq!(Some(1), bmp.get_pixel(2, 0));
+
+		let mut bmp = IndexedBitmap::new(32, 32).unwrap();
+		bmp.thick_curve(&curve, 5, LineCap::Butt, 1);
+		assert_eq!(Some(0), bmp.get_pixel(2, 0));
+	}
+
+	#[test]
+	pub fn curves_are_drawn_without_overdraw() {
+		let quadratic = QuadraticBezier::new(Vector2::new(2.0, 2.0), Vector2::new(16.0, 40.0), Vector2::new(30.0, 2.0));
+		let cubic = CubicBezier::new(
+			Vector2::new(2.0, 28.0),
+			Vector2::new(30.0, 28.0),
+			Vector2::new(2.0, 2.0),
+			Vector2::new(28.0, 2.0),
+		);
+		let spline = CatmullRomSpline::new(
+			vec![Vector2::new(4.0, 4.0), Vector2::new(28.0, 6.0), Vector2::new(26.0, 28.0), Vector2::new(6.0, 20.0)],
+			true,
+		);
+
+		assert_no_overdraw(|bmp| bmp.curve_custom(&quadratic, |pixel| pixel + 1));
+		assert_no_overdraw(|bmp| bmp.curve_custom(&cubic, |pixel| pixel + 1));
+		assert_no_overdraw(|bmp| bmp.curve_custom(&spline, |pixel| pixel + 1));
+		for cap in [LineCap::Butt, LineCap::Square, LineCap::Round] {
+			assert_no_overdraw(|bmp| bmp.thick_curve_custom(&quadratic, 4, cap, |pixel| pixel + 1));
+			assert_no_overdraw(|bmp| bmp.thick_curve_custom(&cubic, 3, cap, |pixel| pixel + 1));
+			assert_no_overdraw(|bmp| bmp.thick_curve_custom(&spline, 2, cap, |pixel| pixel + 1));
+		}
+	}
+}
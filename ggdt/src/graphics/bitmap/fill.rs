@@ -0,0 +1,427 @@
+use crate::graphics::{Bitmap, BitmapViewMut, Pixel};
+
+/// Determines which neighbouring pixels are considered to be connected to each other when performing a flood fill.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FillConnectivity {
+	/// Only pixels sharing an edge (up, down, left, right) are connected. Fills will not "leak" through diagonal
+	/// gaps in a boundary.
+	Four,
+	/// Pixels sharing an edge or a corner are connected.
+	Eight,
+}
+
+impl<PixelType: Pixel> Bitmap<PixelType> {
+	/// Core scanline fill implementation shared by all of the fill variants. Starting at the given coordinates, all
+	/// connected pixels within the clipping region for which `is_fillable` returns true (given the pixel's original
+	/// color) are replaced by the color returned by `fill_fn` (given the pixel's coordinates and original color).
+	/// Each pixel is visited at most once, so `fill_fn` may return colors that are themselves fillable.
+	pub(super) fn fill_region(
+		&mut self,
+		x: i32,
+		y: i32,
+		connectivity: FillConnectivity,
+		is_fillable: impl Fn(PixelType) -> bool,
+		fill_fn: impl Fn(i32, i32, PixelType) -> PixelType,
+	) {
+		if !self.is_xy_visible(x, y) {
+			return;
+		}
+
+		let clip = self.clip_region;
+		let clip_right = clip.right();
+		let clip_bottom = clip.bottom();
+		let mut visited = vec![false; (clip.width * clip.height) as usize];
+		let visited_index = |x: i32, y: i32| ((y - clip.y) * clip.width as i32 + (x - clip.x)) as usize;
+
+		let mut stack = vec![(x, y)];
+		while let Some((x, y)) = stack.pop() {
+			// safety: everything pushed onto the stack is within the clipping region
+			if visited[visited_index(x, y)] || !is_fillable(unsafe { self.get_pixel_unchecked(x, y) }) {
+				continue;
+			}
+
+			// find the extent of the fillable span on this row that includes the current pixel
+			let mut left = x;
+			while left > clip.x
+				&& !visited[visited_index(left - 1, y)]
+				&& is_fillable(unsafe { self.get_pixel_unchecked(left - 1, y) })
+			{
+				left -= 1;
+			}
+			let mut right = x;
+			while right < clip_right
+				&& !visited[visited_index(right + 1, y)]
+				&& is_fillable(unsafe { self.get_pixel_unchecked(right + 1, y) })
+			{
+				right += 1;
+			}
+
+			unsafe {
+				let row = self.pixels_at_mut_unchecked(left, y);
+				for (offset, pixel) in row[0..(right - left + 1) as usize].iter_mut().enumerate() {
+					*pixel = fill_fn(left + offset as i32, y, *pixel);
+				}
+			}
+			visited[visited_index(left, y)..=visited_index(right, y)].fill(true);
+
+			// queue up the start of each fillable span on the rows directly above and below this one that is
+			// connected to this span
+			let (scan_left, scan_right) = match connectivity {
+				FillConnectivity::Four => (left, right),
+				FillConnectivity::Eight => ((left - 1).max(clip.x), (right + 1).min(clip_right)),
+			};
+			for scan_y in [y - 1, y + 1] {
+				if scan_y < clip.y || scan_y > clip_bottom {
+					continue;
+				}
+				let mut in_span = false;
+				for scan_x in scan_left..=scan_right {
+					if !visited[visited_index(scan_x, scan_y)]
+						&& is_fillable(unsafe { self.get_pixel_unchecked(scan_x, scan_y) })
+					{
+						if !in_span {
+							stack.push((scan_x, scan_y));
+							in_span = true;
+						}
+					} else {
+						in_span = false;
+					}
+				}
+			}
+		}
+	}
+
+	/// Flood fills ("paint bucket") the area containing the given coordinates. All pixels connected to the starting
+	/// pixel that have the same color as it are replaced with the given color. The fill will not extend outside of
+	/// the bitmap's clipping region.
+	pub fn flood_fill(&mut self, x: i32, y: i32, color: PixelType, connectivity: FillConnectivity) {
+		if let Some(target) = self.get_pixel(x, y) {
+			if target != color {
+				self.fill_region(x, y, connectivity, |pixel| pixel == target, |_, _, _| color);
+			}
+		}
+	}
+
+	/// Flood fills the area containing the given coordinates, the same as [`Bitmap::flood_fill`], except that each
+	/// filled pixel is set to the color returned by the given function. The given function is one that accepts a
+	/// color value that corresponds to the current pixel being filled.
+	pub fn flood_fill_custom(
+		&mut self,
+		x: i32,
+		y: i32,
+		connectivity: FillConnectivity,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		if let Some(target) = self.get_pixel(x, y) {
+			self.fill_region(x, y, connectivity, |pixel| pixel == target, |_, _, pixel| pixel_fn(pixel));
+		}
+	}
+
+	/// Fills the area containing the given coordinates that is enclosed by pixels of the boundary color. Unlike
+	/// [`Bitmap::flood_fill`], the pixels being filled need not all be the same color, the fill simply continues
+	/// until it reaches pixels of the boundary color (or the edges of the bitmap's clipping region).
+	pub fn boundary_fill(
+		&mut self,
+		x: i32,
+		y: i32,
+		boundary_color: PixelType,
+		color: PixelType,
+		connectivity: FillConnectivity,
+	) {
+		self.fill_region(x, y, connectivity, |pixel| pixel != boundary_color, |_, _, _| color);
+	}
+
+	/// Flood fills the area containing the given coordinates, the same as [`Bitmap::flood_fill`], except that the
+	/// filled pixels are taken from the given pattern bitmap instead of being a single solid color. The pattern is
+	/// tiled across the bitmap, aligned to the top-left corner of the bitmap (so that adjacent fills will line up
+	/// seamlessly).
+	pub fn pattern_flood_fill(&mut self, x: i32, y: i32, pattern: &Self, connectivity: FillConnectivity) {
+		if let Some(target) = self.get_pixel(x, y) {
+			let pattern_width = pattern.width() as i32;
+			let pattern_height = pattern.height() as i32;
+			self.fill_region(
+				x,
+				y,
+				connectivity,
+				|pixel| pixel == target,
+				// safety: the coordinates are always wrapped to lie within the pattern's bounds
+				|x, y, _| unsafe {
+					pattern.get_pixel_unchecked(x.rem_euclid(pattern_width), y.rem_euclid(pattern_height))
+				},
+			);
+		}
+	}
+}
+
+impl<PixelType: Pixel> BitmapViewMut<'_, PixelType> {
+	/// Flood fills the area containing the given coordinates, the same as [`Bitmap::flood_fill`]. The fill will not
+	/// extend outside of the view's clipping region.
+	pub fn flood_fill(&mut self, x: i32, y: i32, color: PixelType, connectivity: FillConnectivity) {
+		let (offset_x, offset_y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.flood_fill(x + offset_x, y + offset_y, color, connectivity));
+	}
+
+	pub fn flood_fill_custom(
+		&mut self,
+		x: i32,
+		y: i32,
+		connectivity: FillConnectivity,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		let (offset_x, offset_y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.flood_fill_custom(x + offset_x, y + offset_y, connectivity, pixel_fn));
+	}
+
+	/// Fills the area containing the given coordinates that is enclosed by pixels of the boundary color, the same as
+	/// [`Bitmap::boundary_fill`].
+	pub fn boundary_fill(
+		&mut self,
+		x: i32,
+		y: i32,
+		boundary_color: PixelType,
+		color: PixelType,
+		connectivity: FillConnectivity,
+	) {
+		let (offset_x, offset_y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| {
+			bitmap.boundary_fill(x + offset_x, y + offset_y, boundary_color, color, connectivity)
+		});
+	}
+
+	/// Flood fills the area containing the given coordinates with the given pattern bitmap, the same as
+	/// [`Bitmap::pattern_flood_fill`]. The pattern is aligned to the top-left corner of the bitmap this view is of,
+	/// not the view itself.
+	pub fn pattern_flood_fill(&mut self, x: i32, y: i32, pattern: &Bitmap<PixelType>, connectivity: FillConnectivity) {
+		let (offset_x, offset_y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.pattern_flood_fill(x + offset_x, y + offset_y, pattern, connectivity));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::graphics::{IndexedBitmap, RgbaBitmap, RGBA};
+	use crate::math::Rect;
+
+	use super::*;
+
+	#[rustfmt::skip]
+	const SHAPES: &[u8] = &[
+		0, 0, 0, 0, 0, 0, 0, 0,
+		0, 1, 1, 1, 0, 0, 0, 0,
+		0, 1, 0, 1, 0, 2, 2, 0,
+		0, 1, 1, 1, 0, 2, 3, 2,
+		0, 0, 0, 0, 2, 3, 3, 2,
+		0, 0, 0, 0, 2, 2, 3, 0,
+		0, 0, 0, 0, 0, 0, 0, 0,
+		5, 5, 5, 5, 5, 5, 5, 0,
+	];
+
+	fn shapes_bitmap() -> IndexedBitmap {
+		let mut bmp = IndexedBitmap::new(8, 8).unwrap();
+		bmp.pixels_mut().copy_from_slice(SHAPES);
+		bmp
+	}
+
+	#[rustfmt::skip]
+	#[test]
+	pub fn flood_fill() {
+		let mut bmp = shapes_bitmap();
+		bmp.flood_fill(0, 0, 9, FillConnectivity::Four);
+		assert_eq!(
+			bmp.pixels(),
+			&[
+				9, 9, 9, 9, 9, 9, 9, 9,
+				9, 1, 1, 1, 9, 9, 9, 9,
+				9, 1, 0, 1, 9, 2, 2, 9,
+				9, 1, 1, 1, 9, 2, 3, 2,
+				9, 9, 9, 9, 2, 3, 3, 2,
+				9, 9, 9, 9, 2, 2, 3, 9,
+				9, 9, 9, 9, 9, 9, 9, 9,
+				5, 5, 5, 5, 5, 5, 5, 9,
+			]
+		);
+
+		let mut bmp = shapes_bitmap();
+		bmp.flood_fill(6, 3, 8, FillConnectivity::Four);
+		assert_eq!(&bmp.pixels()[24..48], &[
+			0, 1, 1, 1, 0, 2, 8, 2,
+			0, 0, 0, 0, 2, 8, 8, 2,
+			0, 0, 0, 0, 2, 2, 8, 0,
+		]);
+
+		// the 2's form a ring that is partly only connected diagonally
+		let mut bmp = shapes_bitmap();
+		bmp.flood_fill(5, 2, 7, FillConnectivity::Four);
+		assert_eq!(&bmp.pixels()[16..48], &[
+			0, 1, 0, 1, 0, 7, 7, 0,
+			0, 1, 1, 1, 0, 7, 3, 2,
+			0, 0, 0, 0, 2, 3, 3, 2,
+			0, 0, 0, 0, 2, 2, 3, 0,
+		]);
+		let mut bmp = shapes_bitmap();
+		bmp.flood_fill(5, 2, 7, FillConnectivity::Eight);
+		assert_eq!(&bmp.pixels()[16..48], &[
+			0, 1, 0, 1, 0, 7, 7, 0,
+			0, 1, 1, 1, 0, 7, 3, 7,
+			0, 0, 0, 0, 7, 3, 3, 7,
+			0, 0, 0, 0, 7, 7, 3, 0,
+		]);
+
+		// filling with the same color does nothing, and out of bounds coordinates are ignored
+		let mut bmp = shapes_bitmap();
+		bmp.flood_fill(0, 0, 0, FillConnectivity::Eight);
+		bmp.flood_fill(-1, 3, 4, FillConnectivity::Eight);
+		bmp.flood_fill(3, 8, 4, FillConnectivity::Eight);
+		assert_eq!(bmp.pixels(), SHAPES);
+	}
+
+	#[rustfmt::skip]
+	#[test]
+	pub fn flood_fill_respects_clip_region() {
+		let mut bmp = shapes_bitmap();
+		bmp.set_clip_region(&Rect::new(0, 0, 4, 8));
+		bmp.flood_fill(0, 6, 9, FillConnectivity::Four);
+		bmp.flood_fill(6, 6, 4, FillConnectivity::Four);
+		assert_eq!(
+			bmp.pixels(),
+			&[
+				9, 9, 9, 9, 0, 0, 0, 0,
+				9, 1, 1, 1, 0, 0, 0, 0,
+				9, 1, 0, 1, 0, 2, 2, 0,
+				9, 1, 1, 1, 0, 2, 3, 2,
+				9, 9, 9, 9, 2, 3, 3, 2,
+				9, 9, 9, 9, 2, 2, 3, 0,
+				9, 9, 9, 9, 0, 0, 0, 0,
+				5, 5, 5, 5, 5, 5, 5, 0,
+			]
+		);
+	}
+
+	#[rustfmt::skip]
+	#[test]
+	pub fn flood_fill_custom() {
+		let mut bmp = shapes_bitmap();
+		bmp.flood_fill_custom(1, 1, FillConnectivity::Four, |pixel| pixel + 10);
+		assert_eq!(&bmp.pixels()[8..32], &[
+			0, 11, 11, 11, 0, 0, 0, 0,
+			0, 11, 0, 11, 0, 2, 2, 0,
+			0, 11, 11, 11, 0, 2, 3, 2,
+		]);
+	}
+
+	#[rustfmt::skip]
+	#[test]
+	pub fn boundary_fill() {
+		// the pixels inside the ring of 2's are not all the same color, and the ring is not fully closed
+		let mut bmp = shapes_bitmap();
+		bmp.boundary_fill(6, 4, 2, 6, FillConnectivity::Four);
+		assert_eq!(
+			bmp.pixels(),
+			&[
+				6, 6, 6, 6, 6, 6, 6, 6,
+				6, 6, 6, 6, 6, 6, 6, 6,
+				6, 6, 6, 6, 6, 2, 2, 6,
+				6, 6, 6, 6, 6, 2, 6, 2,
+				6, 6, 6, 6, 2, 6, 6, 2,
+				6, 6, 6, 6, 2, 2, 6, 6,
+				6, 6, 6, 6, 6, 6, 6, 6,
+				6, 6, 6, 6, 6, 6, 6, 6,
+			]
+		);
+
+		let mut bmp = shapes_bitmap();
+		bmp.boundary_fill(2, 2, 1, 6, FillConnectivity::Eight);
+		assert_eq!(&bmp.pixels()[0..32], &[
+			0, 0, 0, 0, 0, 0, 0, 0,
+			0, 1, 1, 1, 0, 0, 0, 0,
+			0, 1, 6, 1, 0, 2, 2, 0,
+			0, 1, 1, 1, 0, 2, 3, 2,
+		]);
+	}
+
+	#[rustfmt::skip]
+	#[test]
+	pub fn pattern_flood_fill() {
+		let mut pattern = IndexedBitmap::new(2, 2).unwrap();
+		pattern.pixels_mut().copy_from_slice(&[7, 8, 9, 10]);
+
+		let mut bmp = shapes_bitmap();
+		bmp.pattern_flood_fill(0, 0, &pattern, FillConnectivity::Four);
+		assert_eq!(
+			bmp.pixels(),
+			&[
+				7, 8, 7, 8, 7, 8, 7, 8,
+				9, 1, 1, 1, 9, 10, 9, 10,
+				7, 1, 0, 1, 7, 2, 2, 8,
+				9, 1, 1, 1, 9, 2, 3, 2,
+				7, 8, 7, 8, 2, 3, 3, 2,
+				9, 10, 9, 10, 2, 2, 3, 10,
+				7, 8, 7, 8, 7, 8, 7, 8,
+				5, 5, 5, 5, 5, 5, 5, 10,
+			]
+		);
+
+		// fill colors matching the original color must not cause the fill to run forever
+		pattern.pixels_mut().copy_from_slice(&[0, 4, 4, 0]);
+		let mut bmp = shapes_bitmap();
+		bmp.pattern_flood_fill(0, 0, &pattern, FillConnectivity::Eight);
+		assert_eq!(&bmp.pixels()[0..8], &[0, 4, 0, 4, 0, 4, 0, 4]);
+	}
+
+	#[test]
+	pub fn rgba_flood_fill() {
+		let background = RGBA::from_rgb([10, 20, 30]);
+		let border = RGBA::from_rgb([255, 255, 255]);
+		let fill = RGBA::from_rgb([255, 0, 0]);
+
+		let mut bmp = RgbaBitmap::new(10, 10).unwrap();
+		bmp.clear(background);
+		bmp.rect(2, 2, 7, 7, border);
+		bmp.flood_fill(4, 4, fill, FillConnectivity::Four);
+		bmp.boundary_fill(0, 0, border, RGBA::from_rgb([0, 0, 255]), FillConnectivity::Four);
+
+		for y in 0..10 {
+			for x in 0..10 {
+				let expected = if x > 2 && x < 7 && y > 2 && y < 7 {
+					fill
+				} else if (x == 2 || x == 7 || y == 2 || y == 7) && (2..=7).contains(&x) && (2..=7).contains(&y) {
+					border
+				} else {
+					RGBA::from_rgb([0, 0, 255])
+				};
+				assert_eq!(Some(expected), bmp.get_pixel(x, y), "{}, {}", x, y);
+			}
+		}
+	}
+
+	#[test]
+	pub fn tolerance_flood_fill() {
+		let fill = RGBA::from_rgb([255, 0, 0]);
+
+		// a horizontal gradient, with a gap in the middle of the top row
+		let mut bmp = RgbaBitmap::new(8, 2).unwrap();
+		for x in 0..8 {
+			bmp.set_pixel(x, 0, RGBA::from_rgb([x as u8 * 10, 100, 100]));
+			bmp.set_pixel(x, 1, RGBA::from_rgb([x as u8 * 10, 100, 100]));
+		}
+		bmp.set_pixel(3, 0, RGBA::from_rgba([30, 100, 100, 200]));
+
+		let mut filled = bmp.clone();
+		filled.tolerance_flood_fill(0, 0, fill, 50, FillConnectivity::Four);
+		for x in 0..8 {
+			let expected = if x <= 5 && x != 3 { fill } else { bmp.get_pixel(x, 0).unwrap() };
+			assert_eq!(Some(expected), filled.get_pixel(x, 0), "{}", x);
+			let expected = if x <= 5 { fill } else { bmp.get_pixel(x, 1).unwrap() };
+			assert_eq!(Some(expected), filled.get_pixel(x, 1), "{}", x);
+		}
+
+		let mut filled = bmp.clone();
+		filled.tolerance_flood_fill(0, 0, fill, 0, FillConnectivity::Four);
+		let mut expected = bmp.clone();
+		expected.flood_fill(0, 0, fill, FillConnectivity::Four);
+		assert_eq!(expected.pixels(), filled.pixels());
+		assert_eq!(Some(fill), filled.get_pixel(0, 0));
+		assert_eq!(Some(fill), filled.get_pixel(0, 1));
+		assert_eq!(Some(RGBA::from_rgb([10, 100, 100])), filled.get_pixel(1, 0));
+	}
+}
@@ -0,0 +1,265 @@
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+// enough to cover the largest header we need to inspect (TGA)
+const DETECTION_HEADER_LENGTH: usize = 18;
+
+/// The image file types that bitmaps can be loaded from by [`crate::graphics::IndexedBitmap::load_bytes`] and
+/// [`crate::graphics::RgbaBitmap::load_bytes`] (and their `load_file` counterparts).
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BitmapFileType {
+	Png,
+	Pcx,
+	Gif,
+	Iff,
+	Bmp,
+	Tga,
+	Netpbm,
+	Qoi,
+}
+
+impl BitmapFileType {
+	/// Determines the type of image file that the given reader contains by inspecting the magic number / header found
+	/// at the reader's current position. The reader's position is left unchanged. TGA files have no magic number and
+	/// so can only be detected by a sanity check of their header, which is why this check is performed last. Their
+	/// (optional) version 2 footer is not used, as it is found at the end of the file which may not be the end of the
+	/// stream, such as when the image is stored within a larger file.
+	///
+	/// # Arguments
+	///
+	/// * `reader`: the reader to inspect
+	///
+	/// returns: the detected file type, or `None` if the file type could not be determined
+	pub fn detect<T: Read + Seek>(reader: &mut T) -> Result<Option<Self>, std::io::Error> {
+		let start = reader.stream_position()?;
+
+		let mut header = [0u8; DETECTION_HEADER_LENGTH];
+		let mut length = 0;
+		while length < header.len() {
+			match reader.read(&mut header[length..])? {
+				0 => break,
+				n => length += n,
+			}
+		}
+		let header = &header[0..length];
+
+		let file_type = if let Some(file_type) = Self::detect_from_magic(header) {
+			Some(file_type)
+		} else if Self::is_plausible_tga_header(header) {
+			Some(BitmapFileType::Tga)
+		} else {
+			None
+		};
+
+		reader.seek(SeekFrom::Start(start))?;
+		Ok(file_type)
+	}
+
+	/// Determines the type of image file from the extension of the given path alone.
+	///
+	/// # Arguments
+	///
+	/// * `path`: the path of the file
+	///
+	/// returns: the file type that the extension is normally used for, or `None` if the extension is not recognized
+	pub fn from_extension(path: &Path) -> Option<Self> {
+		let extension = path.extension()?.to_ascii_lowercase();
+		match extension.to_str()? {
+			"png" => Some(BitmapFileType::Png),
+			"pcx" => Some(BitmapFileType::Pcx),
+			"gif" => Some(BitmapFileType::Gif),
+			// note that .pbm is ambiguous as it is also used by Netpbm, but magic number detection handles that
+			"iff" | "lbm" | "pbm" | "bbm" => Some(BitmapFileType::Iff),
+			"bmp" => Some(BitmapFileType::Bmp),
+			"tga" => Some(BitmapFileType::Tga),
+			"pgm" | "ppm" | "pam" | "pnm" => Some(BitmapFileType::Netpbm),
+			"qoi" => Some(BitmapFileType::Qoi),
+			_ => None,
+		}
+	}
+
+	fn detect_from_magic(header: &[u8]) -> Option<Self> {
+		match header {
+			[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, ..] => Some(BitmapFileType::Png),
+			[b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => Some(BitmapFileType::Gif),
+			[b'F', b'O', b'R', b'M', _, _, _, _, b'I', b'L', b'B', b'M', ..]
+			| [b'F', b'O', b'R', b'M', _, _, _, _, b'P', b'B', b'M', b' ', ..] => Some(BitmapFileType::Iff),
+			[b'q', b'o', b'i', b'f', ..] => Some(BitmapFileType::Qoi),
+			[b'P', b'1'..=b'7', b' ' | b'\t' | b'\r' | b'\n' | b'#', ..] => Some(BitmapFileType::Netpbm),
+			// the info header size immediately follows the 14 byte file header, and is 12 bytes or larger
+			[b'B', b'M', _, _, _, _, _, _, _, _, _, _, _, _, size, 0, 0, 0, ..] if *size >= 12 => {
+				Some(BitmapFileType::Bmp)
+			}
+			// manufacturer, version and encoding
+			[0x0a, 0 | 2 | 3 | 4 | 5, 1, ..] => Some(BitmapFileType::Pcx),
+			_ => None,
+		}
+	}
+
+	fn is_plausible_tga_header(header: &[u8]) -> bool {
+		if header.len() < DETECTION_HEADER_LENGTH {
+			return false;
+		}
+		let color_map_type = header[1];
+		let image_type = header[2];
+		let color_map_entry_size = header[7];
+		let width = u16::from_le_bytes([header[12], header[13]]);
+		let height = u16::from_le_bytes([header[14], header[15]]);
+		let bits_per_pixel = header[16];
+		let color_map_valid = match color_map_type {
+			0 => true,
+			1 => matches!(color_map_entry_size, 15 | 16 | 24 | 32),
+			_ => false,
+		};
+		color_map_valid
+			&& matches!(image_type, 1 | 2 | 3 | 9 | 10 | 11)
+			&& matches!(bits_per_pixel, 8 | 15 | 16 | 24 | 32)
+			&& width > 0 && height > 0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Cursor;
+	use std::path::PathBuf;
+
+	use claim::*;
+	use tempfile::TempDir;
+
+	use crate::graphics::{BitmapError, IndexedBitmap, RgbaBitmap};
+	use crate::tests::{load_raw_indexed, load_raw_rgba};
+
+	use super::*;
+
+	const BASE_PATH: &str = "./test-assets/";
+
+	fn test_file(file: &Path) -> PathBuf {
+		PathBuf::from(BASE_PATH).join(file)
+	}
+
+	const TEST_FILES: &[(&str, BitmapFileType)] = &[
+		("png/indexed_16col.png", BitmapFileType::Png),
+		("png/filter_0_rgb.png", BitmapFileType::Png),
+		("pcx/small.pcx", BitmapFileType::Pcx),
+		("gif/small.gif", BitmapFileType::Gif),
+		("iff/small.lbm", BitmapFileType::Iff),
+		("iff/small.pbm", BitmapFileType::Iff),
+		("bmp/small.bmp", BitmapFileType::Bmp),
+		("bmp/large_1_os2.bmp", BitmapFileType::Bmp),
+		("tga/small.tga", BitmapFileType::Tga),
+		("netpbm/p1.pbm", BitmapFileType::Netpbm),
+		("netpbm/p4.pbm", BitmapFileType::Netpbm),
+		("netpbm/p6.ppm", BitmapFileType::Netpbm),
+		("netpbm/p7_rgba.pam", BitmapFileType::Netpbm),
+		("qoi/rgba.qoi", BitmapFileType::Qoi),
+	];
+
+	#[test]
+	pub fn detect_file_types() -> Result<(), BitmapError> {
+		for (file, expected) in TEST_FILES.iter() {
+			let bytes = std::fs::read(test_file(Path::new(file)))?;
+
+			let mut reader = Cursor::new(&bytes);
+			assert_eq!(Some(*expected), BitmapFileType::detect(&mut reader)?, "{}", file);
+			assert_eq!(0, reader.position(), "{}", file);
+
+			// detection should work from wherever the reader is currently positioned, such as when the image is
+			// stored at some offset within a larger file
+			let mut packed = vec![0xffu8; 100];
+			packed.extend_from_slice(&bytes);
+			packed.extend_from_slice(&[0u8; 100]);
+			let mut reader = Cursor::new(&packed);
+			reader.set_position(100);
+			assert_eq!(Some(*expected), BitmapFileType::detect(&mut reader)?, "{}", file);
+			assert_eq!(100, reader.position(), "{}", file);
+		}
+
+		// a tga file without the optional version 2 footer
+		let bytes = std::fs::read(test_file(Path::new("tga/rgb_24bit.tga")))?;
+		let mut reader = Cursor::new(&bytes[0..bytes.len() - 26]);
+		assert_eq!(Some(BitmapFileType::Tga), BitmapFileType::detect(&mut reader)?);
+
+		assert_eq!(None, BitmapFileType::detect(&mut Cursor::new(b"this is not an image file"))?);
+		assert_eq!(None, BitmapFileType::detect(&mut Cursor::new(b""))?);
+		// the tga footer at the end of the stream belongs to a different file than the one at the reader's position
+		let mut packed = b"this is not an image file".to_vec();
+		packed.extend_from_slice(&std::fs::read(test_file(Path::new("tga/small.tga")))?);
+		assert_eq!(None, BitmapFileType::detect(&mut Cursor::new(&packed))?);
+		// an iff file that does not contain an image
+		let bytes = std::fs::read(test_file(Path::new("iff/anim_op5.anim")))?;
+		assert_eq!(None, BitmapFileType::detect(&mut Cursor::new(&bytes))?);
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn file_type_from_extension() {
+		assert_eq!(Some(BitmapFileType::Png), BitmapFileType::from_extension(Path::new("foo.PNG")));
+		assert_eq!(Some(BitmapFileType::Iff), BitmapFileType::from_extension(Path::new("foo.lbm")));
+		assert_eq!(Some(BitmapFileType::Netpbm), BitmapFileType::from_extension(Path::new("foo.ppm")));
+		assert_eq!(None, BitmapFileType::from_extension(Path::new("foo.txt")));
+		assert_eq!(None, BitmapFileType::from_extension(Path::new("foo")));
+	}
+
+	#[test]
+	pub fn load_bytes_and_files_with_detection() -> Result<(), BitmapError> {
+		let tmp_dir = TempDir::new()?;
+
+		let ref_pixels = load_raw_indexed(test_file(Path::new("pcx/small.bin")).as_path())?;
+		let bytes = std::fs::read(test_file(Path::new("pcx/small.pcx")))?;
+
+		let (bmp, ref_palette) = IndexedBitmap::load_bytes(&mut Cursor::new(&bytes))?;
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+
+		// stored at some offset within a larger file, with other data following it
+		let mut packed = vec![0xffu8; 100];
+		packed.extend_from_slice(&bytes);
+		packed.extend_from_slice(&[0x0cu8; 1000]);
+		let mut reader = Cursor::new(&packed);
+		reader.set_position(100);
+		let (bmp, palette) = IndexedBitmap::load_bytes(&mut reader)?;
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+		assert_eq!(palette, ref_palette);
+		assert_eq!(100 + bytes.len() as u64, reader.position());
+
+		// wrong file extension
+		let path = tmp_dir.path().join("small.png");
+		std::fs::write(&path, &bytes)?;
+		let (bmp, _) = IndexedBitmap::load_file(&path)?;
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+
+		// no file extension
+		let path = tmp_dir.path().join("small");
+		std::fs::write(&path, &bytes)?;
+		let (bmp, _) = IndexedBitmap::load_file(&path)?;
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+
+		// .pbm can be either iff or netpbm
+		let ref_pixels = load_raw_indexed(test_file(Path::new("iff/small.bin")).as_path())?;
+		let (bmp, _) = IndexedBitmap::load_file(test_file(Path::new("iff/small.pbm")).as_path())?;
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+		let ref_pixels = load_raw_rgba(test_file(Path::new("netpbm/bw_rgba.bin")).as_path())?;
+		let (bmp, palette) = RgbaBitmap::load_file(test_file(Path::new("netpbm/p4.pbm")).as_path())?;
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+		assert_none!(palette);
+
+		let ref_pixels = load_raw_rgba(test_file(Path::new("qoi/rgba.bin")).as_path())?;
+		let bytes = std::fs::read(test_file(Path::new("qoi/rgba.qoi")))?;
+		let (bmp, _) = RgbaBitmap::load_bytes(&mut Cursor::new(&bytes))?;
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+		assert_matches!(
+			IndexedBitmap::load_bytes(&mut Cursor::new(&bytes)),
+			Err(BitmapError::UnsupportedFileType(BitmapFileType::Qoi))
+		);
+
+		assert_matches!(
+			RgbaBitmap::load_bytes(&mut Cursor::new(b"this is not an image file")),
+			Err(BitmapError::UnknownFileType(..))
+		);
+		let path = tmp_dir.path().join("garbage.txt");
+		std::fs::write(&path, b"this is not an image file")?;
+		assert_matches!(RgbaBitmap::load_file(&path), Err(BitmapError::UnknownFileType(..)));
+
+		Ok(())
+	}
+}
@@ -0,0 +1,148 @@
+use crate::graphics::{clip_blit, Bitmap, BitmapAtlas, BitmapView, HicolorPixel};
+use crate::math::Rect;
+
+#[derive(Clone, PartialEq)]
+pub enum HicolorBlitMethod<PixelType: HicolorPixel> {
+	/// Solid blit, no transparency or other per-pixel adjustments.
+	Solid,
+	/// Same as [HicolorBlitMethod::Solid] but the drawn image can also be flipped horizontally
+	/// and/or vertically.
+	SolidFlipped { horizontal_flip: bool, vertical_flip: bool },
+	/// Transparent blit, the specified source color pixels are skipped.
+	Transparent(PixelType),
+	/// Same as [HicolorBlitMethod::Transparent] but the drawn image can also be flipped horizontally
+	/// and/or vertically.
+	TransparentFlipped { transparent_color: PixelType, horizontal_flip: bool, vertical_flip: bool },
+}
+
+impl<PixelType: HicolorPixel> Bitmap<PixelType> {
+	pub fn blit_region(
+		&mut self,
+		method: HicolorBlitMethod<PixelType>,
+		src: &Self,
+		src_region: &Rect,
+		mut dest_x: i32,
+		mut dest_y: i32,
+	) {
+		// make sure the source region is clipped or even valid at all for the source bitmap given
+		let mut src_region = *src_region;
+		if !src_region.clamp_to(&src.clip_region) {
+			return;
+		}
+
+		use HicolorBlitMethod::*;
+		let (horizontal_flip, vertical_flip) = match method {
+			SolidFlipped { horizontal_flip, vertical_flip }
+			| TransparentFlipped { horizontal_flip, vertical_flip, .. } => (horizontal_flip, vertical_flip),
+			_ => (false, false),
+		};
+		if !clip_blit(
+			self.clip_region(), //
+			&mut src_region,
+			&mut dest_x,
+			&mut dest_y,
+			horizontal_flip,
+			vertical_flip,
+		) {
+			return;
+		}
+
+		unsafe {
+			self.blit_region_unchecked(method, src, &src_region, dest_x, dest_y);
+		};
+	}
+
+	/// Blits the given region of the source bitmap without any clipping.
+	///
+	/// # Safety
+	///
+	/// The source region must lie within the bounds of the source bitmap, and the destination region
+	/// it is drawn to must lie within the bounds of this bitmap.
+	#[inline]
+	#[rustfmt::skip]
+	pub unsafe fn blit_region_unchecked(
+		&mut self,
+		method: HicolorBlitMethod<PixelType>,
+		src: &Self,
+		src_region: &Rect,
+		dest_x: i32,
+		dest_y: i32,
+	) {
+		use HicolorBlitMethod::*;
+		match method {
+			Solid => self.solid_blit(src, src_region, dest_x, dest_y),
+			SolidFlipped { horizontal_flip, vertical_flip } => {
+				self.solid_flipped_blit(src, src_region, dest_x, dest_y, horizontal_flip, vertical_flip)
+			}
+			Transparent(transparent_color) => {
+				self.transparent_blit(src, src_region, dest_x, dest_y, transparent_color)
+			}
+			TransparentFlipped { transparent_color, horizontal_flip, vertical_flip } => {
+				self.transparent_flipped_blit(src, src_region, dest_x, dest_y, transparent_color, horizontal_flip, vertical_flip)
+			}
+		}
+	}
+
+	#[inline]
+	pub fn blit(&mut self, method: HicolorBlitMethod<PixelType>, src: &Self, x: i32, y: i32) {
+		let src_region = Rect::new(0, 0, src.width, src.height);
+		self.blit_region(method, src, &src_region, x, y);
+	}
+
+	#[inline]
+	pub fn blit_atlas(
+		&mut self,
+		method: HicolorBlitMethod<PixelType>,
+		src: &BitmapAtlas<Self>,
+		index: usize,
+		x: i32,
+		y: i32,
+	) {
+		if let Some(src_region) = src.get(index) {
+			self.blit_region(method, src.bitmap(), src_region, x, y);
+		}
+	}
+
+	#[inline]
+	pub fn blit_view(&mut self, method: HicolorBlitMethod<PixelType>, src: &BitmapView<PixelType>, x: i32, y: i32) {
+		self.blit_region(method, src.bitmap(), src.region(), x, y);
+	}
+
+	/// Blits the entire source bitmap without any clipping.
+	///
+	/// # Safety
+	///
+	/// The source bitmap drawn at the given coordinates must lie within the bounds of this bitmap.
+	#[inline]
+	pub unsafe fn blit_unchecked(&mut self, method: HicolorBlitMethod<PixelType>, src: &Self, x: i32, y: i32) {
+		let src_region = Rect::new(0, 0, src.width, src.height);
+		self.blit_region_unchecked(method, src, &src_region, x, y);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::graphics::{Rgb565Bitmap, RGB565};
+
+	use super::*;
+
+	#[test]
+	pub fn flipped_and_clipped_blits() {
+		let mut src = Rgb565Bitmap::new(2, 2).unwrap();
+		src.pixels_mut().copy_from_slice(&[RGB565(1), RGB565(2), RGB565(3), RGB565(0)]);
+
+		let mut dest = Rgb565Bitmap::new(3, 3).unwrap();
+		dest.clear(RGB565(9));
+		dest.blit(HicolorBlitMethod::Solid, &src, -1, -1);
+		dest.blit(HicolorBlitMethod::Transparent(RGB565(0)), &src, 1, 1);
+		dest.blit(HicolorBlitMethod::SolidFlipped { horizontal_flip: true, vertical_flip: false }, &src, 2, -1);
+
+		#[rustfmt::skip]
+		let expected = [
+			RGB565(0), RGB565(9), RGB565(0),
+			RGB565(9), RGB565(1), RGB565(2),
+			RGB565(9), RGB565(3), RGB565(9),
+		];
+		assert_eq!(&expected, dest.pixels());
+	}
+}
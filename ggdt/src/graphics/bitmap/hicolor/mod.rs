@@ -0,0 +1,119 @@
+use crate::graphics::{Bitmap, BitmapError, IndexedBitmap, Palette, Pixel, RgbaBitmap, ARGB1555, RGB565, RGBA};
+
+mod blit;
+
+pub use blit::*;
+
+pub type Rgb565Bitmap = Bitmap<RGB565>;
+pub type Argb1555Bitmap = Bitmap<ARGB1555>;
+
+/// Common trait for the packed 16-bit pixel formats, allowing bitmaps using any of them to share the same
+/// conversion and blitting functionality.
+pub trait HicolorPixel: Pixel + From<RGBA> + Into<RGBA> {}
+
+impl HicolorPixel for RGB565 {}
+impl HicolorPixel for ARGB1555 {}
+
+impl<PixelType: HicolorPixel> Bitmap<PixelType> {
+	/// Creates a new Bitmap with the specified dimensions.
+	///
+	/// # Arguments
+	///
+	/// * `width`: the width of the bitmap in pixels
+	/// * `height`: the height of the bitmap in pixels
+	///
+	/// returns: `Result<Bitmap, BitmapError>`
+	pub fn new(width: u32, height: u32) -> Result<Self, BitmapError> {
+		Self::internal_new(width, height, RGBA::from_rgb([0, 0, 0]).into())
+	}
+
+	/// Makes a 16-bit copy of the given [`RgbaBitmap`], converting each pixel to the nearest color that can be
+	/// represented by this bitmap's pixel format.
+	///
+	/// # Arguments
+	///
+	/// * `src`: the bitmap to convert
+	///
+	/// returns: `Bitmap`
+	pub fn from_rgba(src: &RgbaBitmap) -> Self {
+		let mut output = Self::new(src.width(), src.height()).unwrap();
+		for (src, dest) in src.pixels().iter().zip(output.pixels_mut().iter_mut()) {
+			*dest = (*src).into();
+		}
+		output
+	}
+
+	/// Makes a 16-bit copy of the given [`IndexedBitmap`], using the specified 256 colour palette during the
+	/// pixel format conversion.
+	///
+	/// # Arguments
+	///
+	/// * `src`: the bitmap to convert
+	/// * `palette`: the 256 colour palette to use during pixel conversion
+	///
+	/// returns: `Bitmap`
+	pub fn from_indexed(src: &IndexedBitmap, palette: &Palette) -> Self {
+		let mut output = Self::new(src.width(), src.height()).unwrap();
+		for (src, dest) in src.pixels().iter().zip(output.pixels_mut().iter_mut()) {
+			*dest = palette[*src].into();
+		}
+		output
+	}
+
+	/// Copies and converts the entire pixel data from this bitmap to a destination expecting
+	/// 32-bit RGBA-format pixel data.
+	///
+	/// # Arguments
+	///
+	/// * `dest`: destination 32-bit RGBA pixel buffer to copy converted pixels to
+	pub fn copy_as_rgba_to(&self, dest: &mut [RGBA]) {
+		for (src, dest) in self.pixels().iter().zip(dest.iter_mut()) {
+			*dest = (*src).into();
+		}
+	}
+
+	/// Makes a [`RgbaBitmap`] copy of this bitmap.
+	///
+	/// returns: `RgbaBitmap`
+	pub fn to_rgba(&self) -> RgbaBitmap {
+		let mut output = RgbaBitmap::new(self.width, self.height).unwrap();
+		self.copy_as_rgba_to(output.pixels_mut());
+		output
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	pub fn rgba_conversion() {
+		let mut rgba = RgbaBitmap::new(2, 2).unwrap();
+		rgba.set_pixel(0, 0, RGBA::from_rgba([255, 0, 0, 255]));
+		rgba.set_pixel(1, 0, RGBA::from_rgba([0, 255, 0, 255]));
+		rgba.set_pixel(0, 1, RGBA::from_rgba([0, 0, 255, 255]));
+		rgba.set_pixel(1, 1, RGBA::from_rgba([255, 255, 255, 0]));
+
+		let bmp = Rgb565Bitmap::from_rgba(&rgba);
+		assert_eq!(&[RGB565(0xf800), RGB565(0x07e0), RGB565(0x001f), RGB565(0xffff)], bmp.pixels());
+		let converted = bmp.to_rgba();
+		assert_eq!(RGBA::from_rgb([255, 0, 0]), converted.get_pixel(0, 0).unwrap());
+		assert_eq!(RGBA::from_rgb([255, 255, 255]), converted.get_pixel(1, 1).unwrap());
+
+		let bmp = Argb1555Bitmap::from_rgba(&rgba);
+		assert_eq!(&[ARGB1555(0xfc00), ARGB1555(0x83e0), ARGB1555(0x801f), ARGB1555(0x7fff)], bmp.pixels());
+		assert_eq!(rgba, bmp.to_rgba());
+	}
+
+	#[test]
+	pub fn indexed_conversion() {
+		let palette = Palette::new_vga_palette().unwrap();
+		let mut indexed = IndexedBitmap::new(2, 1).unwrap();
+		indexed.set_pixel(0, 0, 4);
+		indexed.set_pixel(1, 0, 15);
+
+		let bmp = Rgb565Bitmap::from_indexed(&indexed, &palette);
+		assert_eq!(RGB565::from(palette[4]), bmp.get_pixel(0, 0).unwrap());
+		assert_eq!(RGB565(0xffff), bmp.get_pixel(1, 0).unwrap());
+	}
+}
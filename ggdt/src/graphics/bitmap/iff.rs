@@ -6,7 +6,7 @@ use std::path::Path;
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use thiserror::Error;
 
-use crate::graphics::{IndexedBitmap, Palette, PaletteError, PaletteFormat, RgbaBitmap};
+use crate::graphics::{ColorCycle, IndexedBitmap, Palette, PaletteError, PaletteFormat, RgbaBitmap};
 use crate::utils::{pack_bits, unpack_bits, PackBitsError};
 
 #[derive(Error, Debug)]
@@ -59,8 +59,8 @@ impl IffFormat {
 
 #[derive(Debug, Copy, Clone)]
 #[repr(packed)]
-struct IffId {
-	id: [u8; 4],
+pub(super) struct IffId {
+	pub(super) id: [u8; 4],
 }
 
 impl IffId {
@@ -78,10 +78,10 @@ impl IffId {
 
 #[derive(Debug, Copy, Clone)]
 #[repr(packed)]
-struct FormChunkHeader {
-	chunk_id: IffId,
-	size: u32,
-	type_id: IffId,
+pub(super) struct FormChunkHeader {
+	pub(super) chunk_id: IffId,
+	pub(super) size: u32,
+	pub(super) type_id: IffId,
 }
 
 impl FormChunkHeader {
@@ -102,9 +102,9 @@ impl FormChunkHeader {
 
 #[derive(Debug, Copy, Clone)]
 #[repr(packed)]
-struct SubChunkHeader {
-	chunk_id: IffId,
-	size: u32,
+pub(super) struct SubChunkHeader {
+	pub(super) chunk_id: IffId,
+	pub(super) size: u32,
 }
 
 impl SubChunkHeader {
@@ -126,14 +126,14 @@ impl SubChunkHeader {
 
 #[derive(Debug, Copy, Clone)]
 #[repr(packed)]
-struct BMHDChunk {
-	width: u16,
-	height: u16,
+pub(super) struct BMHDChunk {
+	pub(super) width: u16,
+	pub(super) height: u16,
 	left: u16,
 	top: u16,
-	bitplanes: u8,
-	masking: u8,
-	compress: u8,
+	pub(super) bitplanes: u8,
+	pub(super) masking: u8,
+	pub(super) compress: u8,
 	padding: u8,
 	transparency: u16,
 	x_aspect_ratio: u8,
@@ -179,7 +179,109 @@ impl BMHDChunk {
 	}
 }
 
-fn merge_bitplane(plane: u32, src: &[u8], dest: &mut [u8], row_size: usize) {
+// CRNG rates are specified such that a rate of 16384 is equal to 60 steps per second
+const CRNG_RATE_60_STEPS: f32 = 16384.0;
+const CRNG_FLAG_ACTIVE: u16 = 1;
+const CRNG_FLAG_REVERSE: u16 = 2;
+
+/// Deluxe Paint color cycling range.
+#[derive(Debug, Copy, Clone)]
+#[repr(packed)]
+struct CRNGChunk {
+	padding: u16,
+	rate: u16,
+	flags: u16,
+	low: u8,
+	high: u8,
+}
+
+impl CRNGChunk {
+	pub fn read<T: ReadBytesExt>(reader: &mut T) -> Result<Self, IffError> {
+		Ok(CRNGChunk {
+			padding: reader.read_u16::<BigEndian>()?,
+			rate: reader.read_u16::<BigEndian>()?,
+			flags: reader.read_u16::<BigEndian>()?,
+			low: reader.read_u8()?,
+			high: reader.read_u8()?,
+		})
+	}
+
+	pub fn write<T: WriteBytesExt>(&self, writer: &mut T) -> Result<(), IffError> {
+		writer.write_u16::<BigEndian>(self.padding)?;
+		writer.write_u16::<BigEndian>(self.rate)?;
+		writer.write_u16::<BigEndian>(self.flags)?;
+		writer.write_u8(self.low)?;
+		writer.write_u8(self.high)?;
+		Ok(())
+	}
+
+	pub fn from_color_cycle(cycle: &ColorCycle) -> Self {
+		let mut flags = 0;
+		if cycle.active {
+			flags |= CRNG_FLAG_ACTIVE;
+		}
+		if cycle.reverse {
+			flags |= CRNG_FLAG_REVERSE;
+		}
+		CRNGChunk {
+			padding: 0,
+			rate: (cycle.rate * CRNG_RATE_60_STEPS / 60.0).round().clamp(0.0, u16::MAX as f32) as u16,
+			flags,
+			low: cycle.start,
+			high: cycle.end,
+		}
+	}
+
+	pub fn to_color_cycle(self) -> ColorCycle {
+		ColorCycle {
+			start: self.low,
+			end: self.high,
+			rate: self.rate as f32 * 60.0 / CRNG_RATE_60_STEPS,
+			reverse: self.flags & CRNG_FLAG_REVERSE != 0,
+			active: self.flags & CRNG_FLAG_ACTIVE != 0,
+		}
+	}
+}
+
+/// Graphicraft color cycling range. Functionally the same as [`CRNGChunk`], but with the rate specified as the
+/// time between each step.
+#[derive(Debug, Copy, Clone)]
+#[repr(packed)]
+struct CCRTChunk {
+	direction: i16,
+	start: u8,
+	end: u8,
+	seconds: u32,
+	microseconds: u32,
+}
+
+impl CCRTChunk {
+	pub fn read<T: ReadBytesExt>(reader: &mut T) -> Result<Self, IffError> {
+		let chunk = CCRTChunk {
+			direction: reader.read_i16::<BigEndian>()?,
+			start: reader.read_u8()?,
+			end: reader.read_u8()?,
+			seconds: reader.read_u32::<BigEndian>()?,
+			microseconds: reader.read_u32::<BigEndian>()?,
+		};
+		reader.read_u16::<BigEndian>()?; // padding
+		Ok(chunk)
+	}
+
+	pub fn to_color_cycle(self) -> ColorCycle {
+		let interval = self.seconds as f32 + (self.microseconds as f32 / 1_000_000.0);
+		ColorCycle {
+			start: self.start,
+			end: self.end,
+			rate: if interval > 0.0 { 1.0 / interval } else { 0.0 },
+			// "forward" here means towards higher color numbers, same as a non-reversed CRNG range
+			reverse: self.direction < 0,
+			active: self.direction != 0,
+		}
+	}
+}
+
+pub(super) fn merge_bitplane(plane: u32, src: &[u8], dest: &mut [u8], row_size: usize) {
 	let bitmask = 1 << plane;
 	for x in 0..row_size {
 		let data = src[x];
@@ -364,6 +466,15 @@ fn write_chunky_body<T: WriteBytesExt>(
 
 impl IndexedBitmap {
 	pub fn load_iff_bytes<T: ReadBytesExt + Seek>(reader: &mut T) -> Result<(IndexedBitmap, Palette), IffError> {
+		let (bitmap, palette, _) = Self::load_iff_bytes_with_color_cycles(reader)?;
+		Ok((bitmap, palette))
+	}
+
+	/// Loads an IFF bitmap and palette, along with any color cycling ranges (from CRNG or CCRT chunks) that are
+	/// present in the file. Empty ranges, which Deluxe Paint writes out for unused range slots, are skipped.
+	pub fn load_iff_bytes_with_color_cycles<T: ReadBytesExt + Seek>(
+		reader: &mut T,
+	) -> Result<(IndexedBitmap, Palette, Vec<ColorCycle>), IffError> {
 		let form_chunk = FormChunkHeader::read(reader)?;
 		if form_chunk.chunk_id.id != *b"FORM" {
 			return Err(IffError::BadFile(String::from("Unexpected form chunk ID, probably not an IFF file")));
@@ -375,6 +486,7 @@ impl IndexedBitmap {
 		let mut bmhd: Option<BMHDChunk> = None;
 		let mut palette: Option<Palette> = None;
 		let mut bitmap: Option<IndexedBitmap> = None;
+		let mut color_cycles = Vec::new();
 
 		loop {
 			let header = match SubChunkHeader::read(reader) {
@@ -400,6 +512,16 @@ impl IndexedBitmap {
 					return Err(IffError::BadFile(String::from("Only 256 color files are supported")));
 				}
 				palette = Some(Palette::load_from_bytes(reader, PaletteFormat::Normal)?)
+			} else if header.chunk_id.id == *b"CRNG" {
+				let cycle = CRNGChunk::read(reader)?.to_color_cycle();
+				if cycle.start < cycle.end {
+					color_cycles.push(cycle);
+				}
+			} else if header.chunk_id.id == *b"CCRT" {
+				let cycle = CCRTChunk::read(reader)?.to_color_cycle();
+				if cycle.start < cycle.end {
+					color_cycles.push(cycle);
+				}
 			} else if header.chunk_id.id == *b"BODY" {
 				if let Some(bmhd) = &bmhd {
 					if form_chunk.type_id.id == *b"PBM " {
@@ -427,7 +549,7 @@ impl IndexedBitmap {
 			return Err(IffError::BadFile(String::from("No CMAP chunk was found")));
 		}
 
-		Ok((bitmap.unwrap(), palette.unwrap()))
+		Ok((bitmap.unwrap(), palette.unwrap(), color_cycles))
 	}
 
 	pub fn load_iff_file(path: &Path) -> Result<(IndexedBitmap, Palette), IffError> {
@@ -436,11 +558,28 @@ impl IndexedBitmap {
 		Self::load_iff_bytes(&mut reader)
 	}
 
+	pub fn load_iff_file_with_color_cycles(path: &Path) -> Result<(IndexedBitmap, Palette, Vec<ColorCycle>), IffError> {
+		let f = File::open(path)?;
+		let mut reader = BufReader::new(f);
+		Self::load_iff_bytes_with_color_cycles(&mut reader)
+	}
+
 	pub fn to_iff_bytes<T: WriteBytesExt + Seek>(
 		&self,
 		writer: &mut T,
 		palette: &Palette,
 		format: IffFormat,
+	) -> Result<(), IffError> {
+		self.to_iff_bytes_with_color_cycles(writer, palette, format, &[])
+	}
+
+	/// Writes this bitmap and palette out in IFF format, along with the given color cycling ranges as CRNG chunks.
+	pub fn to_iff_bytes_with_color_cycles<T: WriteBytesExt + Seek>(
+		&self,
+		writer: &mut T,
+		palette: &Palette,
+		format: IffFormat,
+		color_cycles: &[ColorCycle],
 	) -> Result<(), IffError> {
 		let form_chunk_position = writer.stream_position()?;
 
@@ -482,6 +621,13 @@ impl IndexedBitmap {
 		cmap_chunk_header.write(writer)?;
 		palette.to_bytes(writer, PaletteFormat::Normal)?;
 
+		for cycle in color_cycles.iter() {
+			let crng_chunk_header =
+				SubChunkHeader { chunk_id: IffId { id: *b"CRNG" }, size: std::mem::size_of::<CRNGChunk>() as u32 };
+			crng_chunk_header.write(writer)?;
+			CRNGChunk::from_color_cycle(cycle).write(writer)?;
+		}
+
 		let body_position = writer.stream_position()?;
 
 		let mut body_chunk_header = SubChunkHeader {
@@ -527,6 +673,18 @@ impl IndexedBitmap {
 		let mut writer = BufWriter::new(f);
 		self.to_iff_bytes(&mut writer, palette, format)
 	}
+
+	pub fn to_iff_file_with_color_cycles(
+		&self,
+		path: &Path,
+		palette: &Palette,
+		format: IffFormat,
+		color_cycles: &[ColorCycle],
+	) -> Result<(), IffError> {
+		let f = File::create(path)?;
+		let mut writer = BufWriter::new(f);
+		self.to_iff_bytes_with_color_cycles(&mut writer, palette, format, color_cycles)
+	}
 }
 
 // wasteful temporary measures until i feel like re-working the above loading process with some kind of
@@ -674,4 +832,50 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	pub fn load_and_save_color_cycles() -> Result<(), IffError> {
+		let tmp_dir = TempDir::new()?;
+
+		let (bmp, palette, color_cycles) =
+			IndexedBitmap::load_iff_file_with_color_cycles(test_assets_file(Path::new("test-tiles.lbm")).as_path())?;
+		assert_eq!(6, color_cycles.len());
+		assert_eq!(
+			ColorCycle { start: 0x10, end: 0x1f, rate: 0.0, reverse: false, active: false }, //
+			color_cycles[0]
+		);
+		assert_eq!(
+			ColorCycle { start: 0xa7, end: 0xad, rate: 0.0, reverse: false, active: false }, //
+			color_cycles[5]
+		);
+
+		let (_, _, color_cycles) =
+			IndexedBitmap::load_iff_file_with_color_cycles(test_file(Path::new("small.lbm")).as_path())?;
+		assert_eq!(4, color_cycles.len());
+		assert_eq!(0x78, color_cycles[0].start);
+		assert_eq!(0x7f, color_cycles[0].end);
+		assert!(!color_cycles[0].active);
+
+		// graphicraft-style ranges, reverse direction with 50000 microseconds between steps
+		let ccrt_bytes = [0xff, 0xff, 0x10, 0x1f, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc3, 0x50, 0x00, 0x00];
+		let cycle = CCRTChunk::read(&mut ccrt_bytes.as_slice())?.to_color_cycle();
+		assert_eq!(ColorCycle { start: 0x10, end: 0x1f, rate: 20.0, reverse: true, active: true }, cycle);
+
+		let color_cycles = vec![
+			ColorCycle::new(0x20, 0x2f, 30.0, false), //
+			ColorCycle::new(0x40, 0x47, 7.5, true),
+			ColorCycle { start: 0x80, end: 0x8f, rate: 60.0, reverse: false, active: false },
+		];
+		for (format, file) in [(IffFormat::Ilbm, "test_save.lbm"), (IffFormat::Pbm, "test_save.pbm")] {
+			let save_path = tmp_dir.path().join(file);
+			bmp.to_iff_file_with_color_cycles(&save_path, &palette, format, &color_cycles)?;
+			let (reloaded_bmp, reloaded_palette, reloaded_color_cycles) =
+				IndexedBitmap::load_iff_file_with_color_cycles(&save_path)?;
+			assert_eq!(reloaded_bmp.pixels(), bmp.pixels());
+			assert_eq!(reloaded_palette, palette);
+			assert_eq!(reloaded_color_cycles, color_cycles);
+		}
+
+		Ok(())
+	}
 }
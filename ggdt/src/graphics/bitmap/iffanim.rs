@@ -0,0 +1,785 @@
+use std::fs::File;
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::graphics::{IffError, IndexedBitmap, Palette, PaletteFormat};
+use crate::utils::unpack_bits;
+
+use super::iff::{merge_bitplane, BMHDChunk, FormChunkHeader, IffId, SubChunkHeader};
+
+/// ANIM frame timing is specified in "jiffies", 1/60th of a second.
+const JIFFIES_PER_SECOND: f32 = 60.0;
+
+const ANHD_BITS_LONG_DATA: u32 = 1;
+const ANHD_BITS_XOR: u32 = 2;
+
+const ANIM_OP_BODY: u8 = 0;
+const ANIM_OP_BYTE_VERTICAL: u8 = 5;
+const ANIM_OP_VERTICAL_SEPARATE_DATA: u8 = 7;
+const ANIM_OP_VERTICAL: u8 = 8;
+
+/// The number of plane pointers found at the start of an op-5/7/8 DLTA chunk.
+const NUM_DELTA_POINTERS: usize = 16;
+
+#[derive(Debug, Copy, Clone)]
+struct ANHDChunk {
+	operation: u8,
+	rel_time: u32,
+	interleave: u8,
+	bits: u32,
+}
+
+impl ANHDChunk {
+	pub fn read<T: ReadBytesExt>(reader: &mut T) -> Result<Self, IffError> {
+		let operation = reader.read_u8()?;
+		let _mask = reader.read_u8()?;
+		let _width = reader.read_u16::<BigEndian>()?;
+		let _height = reader.read_u16::<BigEndian>()?;
+		let _left = reader.read_i16::<BigEndian>()?;
+		let _top = reader.read_i16::<BigEndian>()?;
+		let _abs_time = reader.read_u32::<BigEndian>()?;
+		let rel_time = reader.read_u32::<BigEndian>()?;
+		let interleave = reader.read_u8()?;
+		let _padding = reader.read_u8()?;
+		let bits = reader.read_u32::<BigEndian>()?;
+		Ok(ANHDChunk { operation, rel_time, interleave, bits })
+	}
+}
+
+/// Describes how the planar pixel data for an animation frame is laid out in memory. Each bitplane is stored
+/// separately, one after the other, with each row of a bitplane padded to a multiple of 16 pixels as in ILBM files.
+#[derive(Debug, Copy, Clone)]
+struct PlanarLayout {
+	width: usize,
+	height: usize,
+	bitplanes: usize,
+	row_bytes: usize,
+}
+
+impl PlanarLayout {
+	fn new(width: usize, height: usize, bitplanes: usize) -> Self {
+		let row_bytes = ((width + 15) >> 4) << 1;
+		PlanarLayout { width, height, bitplanes, row_bytes }
+	}
+
+	#[inline]
+	fn size(&self) -> usize {
+		self.row_bytes * self.height * self.bitplanes
+	}
+
+	#[inline]
+	fn offset(&self, plane: usize, y: usize) -> usize {
+		((plane * self.height) + y) * self.row_bytes
+	}
+
+	fn read_body<T: ReadBytesExt>(&self, reader: &mut T, compressed: bool, planes: &mut [u8]) -> Result<(), IffError> {
+		let mut buffer = Vec::with_capacity(self.row_bytes);
+		for y in 0..self.height {
+			// same as with ILBM bodies, each scanline contains all the bitplane rows for that line in sequence, and
+			// compression never crosses bitplane row boundaries
+			for plane in 0..self.bitplanes {
+				let offset = self.offset(plane, y);
+				let dest = &mut planes[offset..offset + self.row_bytes];
+				if compressed {
+					buffer.clear();
+					unpack_bits(reader, &mut buffer, self.row_bytes)?;
+					dest.copy_from_slice(&buffer[0..self.row_bytes]);
+				} else {
+					reader.read_exact(dest)?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	fn copy_to_bitmap(&self, planes: &[u8], bitmap: &mut IndexedBitmap) {
+		let mut row = vec![0u8; self.row_bytes * 8];
+		for y in 0..self.height {
+			row.fill(0);
+			for plane in 0..self.bitplanes {
+				let offset = self.offset(plane, y);
+				merge_bitplane(plane as u32, &planes[offset..offset + self.row_bytes], &mut row, self.row_bytes);
+			}
+			bitmap.pixels_at_mut(0, y as i32).unwrap()[0..self.width].copy_from_slice(&row[0..self.width]);
+		}
+	}
+}
+
+/// Bounds-checked big-endian reads out of a DLTA chunk, starting at one of the pointers found at the start of it.
+struct DeltaReader<'a> {
+	data: &'a [u8],
+	position: usize,
+}
+
+impl<'a> DeltaReader<'a> {
+	fn new(data: &'a [u8], position: usize) -> Self {
+		DeltaReader { data, position }
+	}
+
+	fn read(&mut self, size: usize) -> Result<u32, IffError> {
+		let bytes = self
+			.data
+			.get(self.position..self.position + size)
+			.ok_or_else(|| IffError::BadFile(String::from("ANIM delta data is truncated or corrupt")))?;
+		self.position += size;
+		Ok(bytes.iter().fold(0, |value, &byte| (value << 8) | byte as u32))
+	}
+}
+
+fn read_delta_pointers(data: &[u8]) -> Result<[usize; NUM_DELTA_POINTERS], IffError> {
+	let mut reader = DeltaReader::new(data, 0);
+	let mut pointers = [0; NUM_DELTA_POINTERS];
+	for pointer in pointers.iter_mut() {
+		*pointer = reader.read(4)? as usize;
+	}
+	Ok(pointers)
+}
+
+/// Decodes one column of a vertical delta compressed bitplane. All three of the vertical delta methods (5, 7 and 8)
+/// encode each column as a count of ops followed by the ops themselves, which are one of:
+///
+/// * zero: a "same" op, followed by a count and a single value which is written that many times down the column
+/// * high bit set: a "unique" op, where the remaining bits are a count of values which are written down the column
+/// * anything else: a "skip" op, where the value is the number of rows to move down the column without changing them
+///
+/// The only real differences between the methods are the size of the ops and values, and whether the values are
+/// found inline with the ops or in a separate list.
+#[allow(clippy::too_many_arguments)]
+fn decode_vertical_column(
+	planes: &mut [u8],
+	layout: &PlanarLayout,
+	plane: usize,
+	column: usize,
+	op_size: usize,
+	data_size: usize,
+	xor: bool,
+	ops: &mut DeltaReader,
+	mut data: Option<&mut DeltaReader>,
+) -> Result<(), IffError> {
+	let unique_flag = 1u32 << (op_size * 8 - 1);
+	let mut put = |y: usize, value: u32| {
+		let offset = layout.offset(plane, y) + column;
+		for (i, dest) in planes[offset..offset + data_size].iter_mut().enumerate() {
+			let byte = (value >> ((data_size - 1 - i) * 8)) as u8;
+			if xor {
+				*dest ^= byte;
+			} else {
+				*dest = byte;
+			}
+		}
+	};
+	// every op moves down the column by some number of rows, which must not run past the bottom of the frame
+	let advance = |y: usize, count: u32| -> Result<usize, IffError> {
+		match y.checked_add(count as usize) {
+			Some(end) if end <= layout.height => Ok(end),
+			_ => Err(IffError::BadFile(String::from("ANIM delta op runs past the bottom of the frame"))),
+		}
+	};
+
+	let num_ops = ops.read(op_size)?;
+	let mut y = 0;
+	for _ in 0..num_ops {
+		let op = ops.read(op_size)?;
+		if op == 0 {
+			let count = ops.read(op_size)?;
+			let value = match data.as_deref_mut() {
+				Some(data) => data.read(data_size)?,
+				None => ops.read(data_size)?,
+			};
+			let end = advance(y, count)?;
+			for row in y..end {
+				put(row, value);
+			}
+			y = end;
+		} else if (op & unique_flag) != 0 {
+			let end = advance(y, op & !unique_flag)?;
+			for row in y..end {
+				let value = match data.as_deref_mut() {
+					Some(data) => data.read(data_size)?,
+					None => ops.read(data_size)?,
+				};
+				put(row, value);
+			}
+			y = end;
+		} else {
+			y = advance(y, op)?;
+		}
+	}
+	Ok(())
+}
+
+/// Returns the byte offset and size of each column of a vertically delta compressed bitplane using values of the
+/// given size. When using 32-bit values with a width that isn't a multiple of 32 pixels, the last column is 16 bits.
+fn delta_columns(layout: &PlanarLayout, data_size: usize) -> impl Iterator<Item = (usize, usize)> {
+	let row_bytes = layout.row_bytes;
+	(0..row_bytes).step_by(data_size).map(move |column| (column, data_size.min(row_bytes - column)))
+}
+
+fn apply_delta(planes: &mut [u8], layout: &PlanarLayout, delta: &AnimDelta) -> Result<(), IffError> {
+	let data = delta.data.as_slice();
+	let long_data = (delta.bits & ANHD_BITS_LONG_DATA) != 0;
+	let data_size = if long_data { 4 } else { 2 };
+
+	match delta.operation {
+		ANIM_OP_BODY => layout.read_body(&mut Cursor::new(data), delta.compressed, planes),
+		ANIM_OP_BYTE_VERTICAL => {
+			let xor = (delta.bits & ANHD_BITS_XOR) != 0;
+			let pointers = read_delta_pointers(data)?;
+			for (plane, &pointer) in pointers.iter().enumerate().take(layout.bitplanes) {
+				if pointer == 0 {
+					continue; // plane is unchanged
+				}
+				let mut ops = DeltaReader::new(data, pointer);
+				for column in 0..layout.row_bytes {
+					decode_vertical_column(planes, layout, plane, column, 1, 1, xor, &mut ops, None)?;
+				}
+			}
+			Ok(())
+		}
+		ANIM_OP_VERTICAL_SEPARATE_DATA => {
+			// the first 8 pointers are to the op lists for each plane, and the next 8 to the data lists
+			let pointers = read_delta_pointers(data)?;
+			for plane in 0..layout.bitplanes {
+				if pointers[plane] == 0 {
+					continue;
+				}
+				let mut ops = DeltaReader::new(data, pointers[plane]);
+				let mut values = DeltaReader::new(data, pointers[plane + 8]);
+				for (column, size) in delta_columns(layout, data_size) {
+					decode_vertical_column(planes, layout, plane, column, 1, size, false, &mut ops, Some(&mut values))?;
+				}
+			}
+			Ok(())
+		}
+		ANIM_OP_VERTICAL => {
+			let pointers = read_delta_pointers(data)?;
+			for (plane, &pointer) in pointers.iter().enumerate().take(layout.bitplanes) {
+				if pointer == 0 {
+					continue;
+				}
+				let mut ops = DeltaReader::new(data, pointer);
+				for (column, size) in delta_columns(layout, data_size) {
+					decode_vertical_column(planes, layout, plane, column, size, size, false, &mut ops, None)?;
+				}
+			}
+			Ok(())
+		}
+		operation => Err(IffError::BadFile(format!("Unsupported ANIM compression method: {}", operation))),
+	}
+}
+
+/// A single, still compressed, frame of an animation following the initial frame.
+#[derive(Debug, Clone)]
+struct AnimDelta {
+	operation: u8,
+	interleave: u8,
+	bits: u32,
+	compressed: bool,
+	rel_time: u32,
+	palette: Option<Palette>,
+	data: Vec<u8>,
+}
+
+/// The chunks of interest found in one ILBM form within an ANIM form.
+#[derive(Default)]
+struct FrameChunks {
+	bmhd: Option<BMHDChunk>,
+	anhd: Option<ANHDChunk>,
+	palette: Option<Palette>,
+	body: Option<Vec<u8>>,
+	delta: Option<Vec<u8>>,
+}
+
+fn read_chunk_data<T: ReadBytesExt>(reader: &mut T, size: u32) -> Result<Vec<u8>, IffError> {
+	let mut data = Vec::with_capacity(size as usize);
+	reader.take(size as u64).read_to_end(&mut data)?;
+	Ok(data)
+}
+
+fn read_frame_chunks<T: ReadBytesExt + Seek>(reader: &mut T, end: u64) -> Result<FrameChunks, IffError> {
+	let mut chunks = FrameChunks::default();
+	while reader.stream_position()? + 8 <= end {
+		let header = SubChunkHeader::read(reader)?;
+		let chunk_data_position = reader.stream_position()?;
+		let size = header.size;
+
+		match &header.chunk_id.id {
+			b"BMHD" => {
+				let bmhd = BMHDChunk::read(reader)?;
+				if bmhd.width == 0 || bmhd.height == 0 {
+					return Err(IffError::BadFile(String::from("Invalid image dimensions")));
+				}
+				if bmhd.bitplanes == 0 || bmhd.bitplanes > 8 {
+					return Err(IffError::BadFile(String::from("Only 1 to 8 bitplane animations are supported")));
+				}
+				if bmhd.masking == 1 {
+					return Err(IffError::BadFile(String::from("Masking is not supported")));
+				}
+				chunks.bmhd = Some(bmhd);
+			}
+			b"CMAP" => {
+				let num_colors = (size as usize / 3).min(256);
+				chunks.palette = Some(Palette::load_num_colors_from_bytes(reader, PaletteFormat::Normal, num_colors)?);
+			}
+			b"ANHD" => chunks.anhd = Some(ANHDChunk::read(reader)?),
+			b"BODY" => chunks.body = Some(read_chunk_data(reader, size)?),
+			b"DLTA" => chunks.delta = Some(read_chunk_data(reader, size)?),
+			_ => {}
+		}
+
+		reader.seek(SeekFrom::Start(chunk_data_position + size as u64))?;
+	}
+	Ok(chunks)
+}
+
+/// A single fully decoded frame of an [`IffAnimation`].
+#[derive(Debug, Clone)]
+pub struct IffAnimationFrame {
+	pub bitmap: IndexedBitmap,
+	pub palette: Palette,
+	/// How long this frame should be shown for, in seconds.
+	pub duration: f32,
+}
+
+/// An Amiga/Deluxe Paint style IFF ANIM animation. Only the first frame is decoded up-front. All subsequent frames
+/// are kept in their delta compressed form and are decoded one at a time as they are played back (see
+/// [`IffAnimationPlayer`]), so even long animations need very little memory.
+///
+/// Frames can use delta compression methods 0 (a complete ILBM body), 5 (byte vertical delta), 7 (short/long
+/// vertical delta with separate op and data lists) and 8 (short/long vertical delta). Palette changes (a CMAP chunk
+/// in any frame) are supported.
+#[derive(Debug, Clone)]
+pub struct IffAnimation {
+	layout: PlanarLayout,
+	palette: Palette,
+	first_frame: Vec<u8>,
+	first_rel_time: u32,
+	deltas: Vec<AnimDelta>,
+}
+
+impl IffAnimation {
+	pub fn load_bytes<T: ReadBytesExt + Seek>(reader: &mut T) -> Result<IffAnimation, IffError> {
+		let form_chunk = FormChunkHeader::read(reader)?;
+		if form_chunk.chunk_id.id != *b"FORM" {
+			return Err(IffError::BadFile(String::from("Unexpected form chunk ID, probably not an IFF file")));
+		}
+		if form_chunk.type_id.id != *b"ANIM" {
+			return Err(IffError::BadFile(String::from("Only ANIM formats are supported")));
+		}
+		// the form chunk size includes the type id we just read
+		let end = reader.stream_position()? + form_chunk.size as u64 - 4;
+
+		let mut first: Option<(BMHDChunk, FrameChunks)> = None;
+		let mut deltas = Vec::new();
+
+		while reader.stream_position()? + 8 <= end {
+			let header = SubChunkHeader::read(reader)?;
+			let chunk_data_position = reader.stream_position()?;
+			let chunk_end = chunk_data_position + header.size as u64;
+
+			if header.chunk_id.id == *b"FORM" && IffId::read(reader)?.id == *b"ILBM" {
+				let chunks = read_frame_chunks(reader, chunk_end)?;
+				if let Some((bmhd, _)) = &first {
+					let anhd = chunks
+						.anhd
+						.ok_or_else(|| IffError::BadFile(String::from("Animation frame has no ANHD chunk")))?;
+					match anhd.operation {
+						ANIM_OP_BODY | ANIM_OP_BYTE_VERTICAL | ANIM_OP_VERTICAL_SEPARATE_DATA | ANIM_OP_VERTICAL => {}
+						operation => {
+							return Err(IffError::BadFile(format!(
+								"Unsupported ANIM compression method: {}",
+								operation
+							)))
+						}
+					}
+					if anhd.interleave > 2 {
+						return Err(IffError::BadFile(format!("Unsupported ANIM interleave: {}", anhd.interleave)));
+					}
+					let data = if anhd.operation == ANIM_OP_BODY { chunks.body } else { chunks.delta };
+					let data = data.ok_or_else(|| IffError::BadFile(String::from("Animation frame has no data")))?;
+					deltas.push(AnimDelta {
+						operation: anhd.operation,
+						interleave: anhd.interleave,
+						bits: anhd.bits,
+						compressed: chunks.bmhd.unwrap_or(*bmhd).compress == 1,
+						rel_time: anhd.rel_time,
+						palette: chunks.palette,
+						data,
+					});
+				} else {
+					let bmhd = chunks
+						.bmhd
+						.ok_or_else(|| IffError::BadFile(String::from("First animation frame has no BMHD chunk")))?;
+					first = Some((bmhd, chunks));
+				}
+			}
+
+			reader.seek(SeekFrom::Start(chunk_end))?;
+		}
+
+		let (bmhd, chunks) = first.ok_or_else(|| IffError::BadFile(String::from("No ILBM frames were found")))?;
+		let layout = PlanarLayout::new(bmhd.width as usize, bmhd.height as usize, bmhd.bitplanes as usize);
+		let body = chunks.body.ok_or_else(|| IffError::BadFile(String::from("No BODY chunk was found")))?;
+		let palette = chunks.palette.ok_or_else(|| IffError::BadFile(String::from("No CMAP chunk was found")))?;
+		let mut first_frame = vec![0u8; layout.size()];
+		layout.read_body(&mut Cursor::new(body), bmhd.compress == 1, &mut first_frame)?;
+
+		Ok(IffAnimation {
+			layout,
+			palette,
+			first_frame,
+			first_rel_time: chunks.anhd.map_or(0, |anhd| anhd.rel_time),
+			deltas,
+		})
+	}
+
+	pub fn load_file(path: &Path) -> Result<IffAnimation, IffError> {
+		let f = File::open(path)?;
+		let mut reader = BufReader::new(f);
+		Self::load_bytes(&mut reader)
+	}
+
+	#[inline]
+	pub fn width(&self) -> u32 {
+		self.layout.width as u32
+	}
+
+	#[inline]
+	pub fn height(&self) -> u32 {
+		self.layout.height as u32
+	}
+
+	/// Returns the total number of frames in the animation, including the first frame.
+	#[inline]
+	pub fn num_frames(&self) -> usize {
+		self.deltas.len() + 1
+	}
+
+	/// Returns the palette used by the first frame of the animation.
+	#[inline]
+	pub fn palette(&self) -> &Palette {
+		&self.palette
+	}
+
+	/// Returns how long the given frame should be shown for, in seconds. Each ANIM frame records how long to wait
+	/// after the previous frame before it is shown, so this is taken from the following frame, except for the last
+	/// frame which uses its own timing.
+	pub fn frame_duration(&self, index: usize) -> f32 {
+		let rel_time = match self.deltas.get(index) {
+			Some(next) => next.rel_time,
+			None if index == 0 => self.first_rel_time,
+			None => self.deltas.last().map_or(0, |delta| delta.rel_time),
+		};
+		rel_time as f32 / JIFFIES_PER_SECOND
+	}
+
+	/// Decodes every frame of the animation up-front. For long animations, consider using an
+	/// [`IffAnimationPlayer`] instead, which only ever keeps the current frame decoded.
+	pub fn frames(&self) -> Result<Vec<IffAnimationFrame>, IffError> {
+		let mut decoder = FrameDecoder::new(self);
+		let mut frames = Vec::with_capacity(self.num_frames());
+		for index in 0..self.num_frames() {
+			if index > 0 {
+				decoder.next_frame(self)?;
+			}
+			let mut bitmap = IndexedBitmap::new(self.width(), self.height()).unwrap();
+			decoder.copy_to_bitmap(self, &mut bitmap);
+			frames.push(IffAnimationFrame {
+				bitmap,
+				palette: decoder.palette.clone(),
+				duration: self.frame_duration(index),
+			});
+		}
+		Ok(frames)
+	}
+}
+
+/// Holds the planar frame buffers needed to decode animation frames one after the other. Two buffers are needed
+/// as most animations are delta compressed against the frame before the previous one (for double-buffered
+/// playback on the Amiga).
+#[derive(Debug, Clone)]
+struct FrameDecoder {
+	buffers: [Vec<u8>; 2],
+	index: usize,
+	palette: Palette,
+}
+
+impl FrameDecoder {
+	fn new(animation: &IffAnimation) -> Self {
+		FrameDecoder {
+			buffers: [animation.first_frame.clone(), animation.first_frame.clone()],
+			index: 0,
+			palette: animation.palette.clone(),
+		}
+	}
+
+	fn next_frame(&mut self, animation: &IffAnimation) -> Result<(), IffError> {
+		let delta = &animation.deltas[self.index];
+		let current = self.index % 2;
+		let target = (self.index + 1) % 2;
+		if delta.interleave == 1 {
+			// delta is against the previous frame instead of the one before that
+			let [a, b] = &mut self.buffers;
+			if current == 0 {
+				b.copy_from_slice(a);
+			} else {
+				a.copy_from_slice(b);
+			}
+		}
+		apply_delta(&mut self.buffers[target], &animation.layout, delta)?;
+		if let Some(palette) = &delta.palette {
+			self.palette = palette.clone();
+		}
+		self.index += 1;
+		Ok(())
+	}
+
+	fn copy_to_bitmap(&self, animation: &IffAnimation, bitmap: &mut IndexedBitmap) {
+		animation.layout.copy_to_bitmap(&self.buffers[self.index % 2], bitmap);
+	}
+}
+
+/// Plays back an [`IffAnimation`], decoding each frame only as it is needed.
+#[derive(Debug, Clone)]
+pub struct IffAnimationPlayer {
+	animation: IffAnimation,
+	decoder: FrameDecoder,
+	bitmap: IndexedBitmap,
+	elapsed: f32,
+}
+
+impl IffAnimationPlayer {
+	/// Creates a new player for the given animation, with the first frame of the animation ready to be shown.
+	pub fn new(animation: IffAnimation) -> Self {
+		let decoder = FrameDecoder::new(&animation);
+		let mut bitmap = IndexedBitmap::new(animation.width(), animation.height()).unwrap();
+		decoder.copy_to_bitmap(&animation, &mut bitmap);
+		IffAnimationPlayer { animation, decoder, bitmap, elapsed: 0.0 }
+	}
+
+	#[inline]
+	pub fn animation(&self) -> &IffAnimation {
+		&self.animation
+	}
+
+	/// Returns the index of the current frame.
+	#[inline]
+	pub fn frame_index(&self) -> usize {
+		self.decoder.index
+	}
+
+	/// Returns the bitmap for the current frame.
+	#[inline]
+	pub fn frame(&self) -> &IndexedBitmap {
+		&self.bitmap
+	}
+
+	/// Returns the palette for the current frame.
+	#[inline]
+	pub fn palette(&self) -> &Palette {
+		&self.decoder.palette
+	}
+
+	/// Returns true if the current frame is the last frame of the animation.
+	#[inline]
+	pub fn is_last_frame(&self) -> bool {
+		self.decoder.index + 1 >= self.animation.num_frames()
+	}
+
+	/// Goes back to the first frame of the animation.
+	pub fn rewind(&mut self) {
+		self.restart();
+		self.elapsed = 0.0;
+	}
+
+	fn restart(&mut self) {
+		self.decoder = FrameDecoder::new(&self.animation);
+		self.decoder.copy_to_bitmap(&self.animation, &mut self.bitmap);
+	}
+
+	/// Decodes the next frame of the animation, regardless of timing.
+	///
+	/// returns: false if the current frame is already the last frame, in which case nothing is changed
+	pub fn next_frame(&mut self) -> Result<bool, IffError> {
+		if self.is_last_frame() {
+			return Ok(false);
+		}
+		self.decoder.next_frame(&self.animation)?;
+		self.decoder.copy_to_bitmap(&self.animation, &mut self.bitmap);
+		Ok(true)
+	}
+
+	/// Advances playback by the given amount of time, decoding as many frames as needed to catch up.
+	///
+	/// # Arguments
+	///
+	/// * `delta`: the time elapsed since the last update, in seconds
+	/// * `looping`: whether to go back to the first frame after the last frame has been shown for its duration
+	///
+	/// returns: true if the current frame (and possibly the palette) changed
+	pub fn update(&mut self, delta: f32, looping: bool) -> Result<bool, IffError> {
+		self.elapsed += delta;
+		let mut changed = false;
+		loop {
+			let duration = self.animation.frame_duration(self.decoder.index);
+			if self.elapsed < duration {
+				break;
+			}
+			if self.is_last_frame() {
+				if !looping || self.animation.num_frames() == 1 {
+					self.elapsed = duration;
+					break;
+				}
+				self.restart();
+			} else {
+				self.next_frame()?;
+			}
+			self.elapsed -= duration;
+			changed = true;
+			if duration <= 0.0 {
+				// frames with no timing information are shown for one update each
+				break;
+			}
+		}
+		Ok(changed)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::PathBuf;
+
+	use claim::*;
+
+	use crate::tests::load_raw_indexed;
+
+	use super::*;
+
+	const BASE_PATH: &str = "./test-assets/iff/";
+
+	const NUM_FRAMES: usize = 8;
+	const PALETTE_CHANGE_FRAME: usize = 4;
+	const FRAME_JIFFIES: [u32; NUM_FRAMES] = [5, 5, 10, 5, 5, 8, 5, 5];
+
+	fn test_file(file: &Path) -> PathBuf {
+		PathBuf::from(BASE_PATH).join(file)
+	}
+
+	fn load_palette(file: &str) -> Palette {
+		Palette::load_from_file(test_file(Path::new(file)).as_path(), PaletteFormat::Normal).unwrap()
+	}
+
+	#[test]
+	pub fn load_and_decode_frames() -> Result<(), IffError> {
+		let ref_pixels = load_raw_indexed(test_file(Path::new("anim_frames.bin")).as_path())?;
+		let ref_frame_size = 40 * 30;
+		let palette_1 = load_palette("anim_1.pal");
+		let palette_2 = load_palette("anim_2.pal");
+
+		for file in [
+			"anim_op5.anim",
+			"anim_op5_xor.anim",
+			"anim_op7_short.anim",
+			"anim_op7_long.anim",
+			"anim_op8_short.anim",
+			"anim_op8_long.anim",
+		] {
+			let animation = IffAnimation::load_file(test_file(Path::new(file)).as_path())?;
+			assert_eq!(40, animation.width(), "{}", file);
+			assert_eq!(30, animation.height(), "{}", file);
+			assert_eq!(NUM_FRAMES, animation.num_frames(), "{}", file);
+			assert_eq!(palette_1, *animation.palette(), "{}", file);
+
+			let frames = animation.frames()?;
+			assert_eq!(NUM_FRAMES, frames.len(), "{}", file);
+			for (index, frame) in frames.iter().enumerate() {
+				let ref_frame = &ref_pixels[index * ref_frame_size..(index + 1) * ref_frame_size];
+				assert_eq!(ref_frame, frame.bitmap.pixels(), "{} frame {}", file, index);
+				let ref_palette = if index < PALETTE_CHANGE_FRAME { &palette_1 } else { &palette_2 };
+				assert_eq!(*ref_palette, frame.palette, "{} frame {}", file, index);
+				assert_eq!(FRAME_JIFFIES[index] as f32 / 60.0, frame.duration, "{} frame {}", file, index);
+			}
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn rejects_zero_dimensions() -> Result<(), IffError> {
+		let bytes = std::fs::read(test_file(Path::new("anim_op5.anim")))?;
+		let bmhd = bytes.windows(4).position(|id| id == b"BMHD").unwrap() + 8;
+		for field in [bmhd, bmhd + 2] {
+			let mut corrupt = bytes.clone();
+			corrupt[field..field + 2].fill(0);
+			assert_matches!(IffAnimation::load_bytes(&mut Cursor::new(corrupt)), Err(IffError::BadFile(..)));
+		}
+		Ok(())
+	}
+
+	#[test]
+	pub fn delta_ops_cannot_overrun_frame() {
+		let layout = PlanarLayout::new(16, 4, 1);
+		let mut planes = vec![0u8; layout.size()];
+		let mut decode = |ops: &[u8]| {
+			let mut ops = DeltaReader::new(ops, 0);
+			decode_vertical_column(&mut planes, &layout, 0, 1, 1, 1, false, &mut ops, None)
+		};
+
+		// skip 1 row, write 2 unique values, then repeat a value for the last row
+		assert!(decode(&[3, 1, 0x82, 0xaa, 0xbb, 0, 1, 0xcc]).is_ok());
+		assert!(decode(&[1, 5]).is_err());
+		assert!(decode(&[2, 3, 0, 2, 0xff]).is_err());
+		assert!(decode(&[1, 0x85, 1, 2, 3, 4, 5]).is_err());
+		assert!(decode(&[1, 0, 0xff, 0xff]).is_err());
+
+		assert_eq!(&[0, 0, 0, 0xaa, 0, 0xbb, 0, 0xcc], planes.as_slice());
+	}
+
+	#[test]
+	pub fn player_decodes_frames_over_time() -> Result<(), IffError> {
+		let ref_pixels = load_raw_indexed(test_file(Path::new("anim_frames.bin")).as_path())?;
+		let ref_frame_size = 40 * 30;
+		let ref_frame = |index: usize| &ref_pixels[index * ref_frame_size..(index + 1) * ref_frame_size];
+
+		let animation = IffAnimation::load_file(test_file(Path::new("anim_op8_long.anim")).as_path())?;
+		let mut player = IffAnimationPlayer::new(animation);
+		assert_eq!(0, player.frame_index());
+		assert_eq!(ref_frame(0), player.frame().pixels());
+
+		assert!(!player.update(4.0 / 60.0, false)?);
+		assert_eq!(0, player.frame_index());
+		assert!(player.update(1.0 / 60.0, false)?);
+		assert_eq!(1, player.frame_index());
+		assert_eq!(ref_frame(1), player.frame().pixels());
+
+		// catching up on several frames at once
+		assert!(player.update(21.0 / 60.0, false)?);
+		assert_eq!(4, player.frame_index());
+		assert_eq!(ref_frame(4), player.frame().pixels());
+		assert_eq!(load_palette("anim_2.pal"), *player.palette());
+
+		// without looping, playback stops on the last frame
+		assert!(player.update(10.0, false)?);
+		assert_eq!(NUM_FRAMES - 1, player.frame_index());
+		assert!(player.is_last_frame());
+		assert!(!player.next_frame()?);
+		assert!(!player.update(10.0, false)?);
+
+		// with looping, playback goes back to the start
+		assert!(player.update(1.0 / 60.0, true)?);
+		assert_eq!(0, player.frame_index());
+		assert_eq!(ref_frame(0), player.frame().pixels());
+		assert_eq!(load_palette("anim_1.pal"), *player.palette());
+
+		assert!(player.next_frame()?);
+		assert!(player.next_frame()?);
+		assert_eq!(ref_frame(2), player.frame().pixels());
+		player.rewind();
+		assert_eq!(0, player.frame_index());
+		assert_eq!(ref_frame(0), player.frame().pixels());
+
+		Ok(())
+	}
+}
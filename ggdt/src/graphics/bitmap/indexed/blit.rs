@@ -1,7 +1,9 @@
 use std::rc::Rc;
 
 use crate::graphics::{
-	clip_blit, per_pixel_blit, per_pixel_flipped_blit, per_pixel_rotozoom_blit, BitmapAtlas, BlendMap, IndexedBitmap,
+	clip_blit, clip_scaled_blit, for_each_nine_slice_part, for_each_tile, per_pixel_blit, per_pixel_flipped_blit,
+	per_pixel_rotozoom_blit, per_pixel_scaled_flipped_blit, scaled_blit_region, BitmapAtlas, BitmapView, BitmapViewMut,
+	BlendMap, IndexedBitmap, NineSlice,
 };
 use crate::math::Rect;
 
@@ -123,6 +125,96 @@ pub enum IndexedBlitMethod {
 		transparent_color: u8,
 		offset: u8,
 	},
+	/// Scaled blit, works the same as [IndexedBlitMethod::Solid] except that the drawn image is scaled
+	/// independently along the x and y axis. Much faster than [IndexedBlitMethod::RotoZoom] for
+	/// scaling without any rotation.
+	Scaled {
+		scale_x: f32,
+		scale_y: f32,
+	},
+	ScaledBlended {
+		scale_x: f32,
+		scale_y: f32,
+		blend_map: Rc<BlendMap>,
+	},
+	/// Same as [IndexedBlitMethod::Scaled] but the drawn image can also be flipped horizontally
+	/// and/or vertically.
+	ScaledFlipped {
+		scale_x: f32,
+		scale_y: f32,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+	},
+	ScaledFlippedBlended {
+		scale_x: f32,
+		scale_y: f32,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+		blend_map: Rc<BlendMap>,
+	},
+	/// Same as [IndexedBlitMethod::Scaled] except that the drawn pixels have their color indices offset
+	/// by the amount given.
+	ScaledOffset {
+		scale_x: f32,
+		scale_y: f32,
+		offset: u8,
+	},
+	/// Combination of [IndexedBlitMethod::ScaledFlipped] and [IndexedBlitMethod::ScaledOffset].
+	ScaledFlippedOffset {
+		scale_x: f32,
+		scale_y: f32,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+		offset: u8,
+	},
+	/// Same as [IndexedBlitMethod::Scaled] except that the specified source color pixels are skipped.
+	ScaledTransparent {
+		scale_x: f32,
+		scale_y: f32,
+		transparent_color: u8,
+	},
+	ScaledTransparentBlended {
+		scale_x: f32,
+		scale_y: f32,
+		transparent_color: u8,
+		blend_map: Rc<BlendMap>,
+	},
+	/// Same as [IndexedBlitMethod::ScaledTransparent] but the drawn image can also be flipped
+	/// horizontally and/or vertically.
+	ScaledTransparentFlipped {
+		scale_x: f32,
+		scale_y: f32,
+		transparent_color: u8,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+	},
+	ScaledTransparentFlippedBlended {
+		scale_x: f32,
+		scale_y: f32,
+		transparent_color: u8,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+		blend_map: Rc<BlendMap>,
+	},
+	/// Same as [IndexedBlitMethod::ScaledTransparent] except that the drawn pixels have their color
+	/// indices offset by the amount given. The transparent color check is not affected by the
+	/// offset and is always treated as an absolute palette color index.
+	ScaledTransparentOffset {
+		scale_x: f32,
+		scale_y: f32,
+		transparent_color: u8,
+		offset: u8,
+	},
+	/// Combination of [IndexedBlitMethod::ScaledTransparentFlipped] and
+	/// [IndexedBlitMethod::ScaledTransparentOffset].
+	ScaledTransparentFlippedOffset {
+		scale_x: f32,
+		scale_y: f32,
+		transparent_color: u8,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+		offset: u8,
+	},
 }
 
 impl IndexedBitmap {
@@ -482,6 +574,28 @@ impl IndexedBitmap {
 			RotoZoomTransparentBlended { .. } => {}
 			RotoZoomTransparentOffset { .. } => {}
 
+			// scaled blits are clipped as they are drawn (see clip_scaled_blit), but can still be skipped
+			// entirely here if they would be drawn completely outside of the clipping region
+			Scaled { scale_x, scale_y }
+			| ScaledBlended { scale_x, scale_y, .. }
+			| ScaledFlipped { scale_x, scale_y, .. }
+			| ScaledFlippedBlended { scale_x, scale_y, .. }
+			| ScaledOffset { scale_x, scale_y, .. }
+			| ScaledFlippedOffset { scale_x, scale_y, .. }
+			| ScaledTransparent { scale_x, scale_y, .. }
+			| ScaledTransparentBlended { scale_x, scale_y, .. }
+			| ScaledTransparentFlipped { scale_x, scale_y, .. }
+			| ScaledTransparentFlippedBlended { scale_x, scale_y, .. }
+			| ScaledTransparentOffset { scale_x, scale_y, .. }
+			| ScaledTransparentFlippedOffset { scale_x, scale_y, .. } => {
+				let mut dest_region = scaled_blit_region(&src_region, dest_x, dest_y, scale_x, scale_y);
+				let mut skipped_x = 0;
+				let mut skipped_y = 0;
+				if !clip_scaled_blit(self.clip_region(), &mut dest_region, &mut skipped_x, &mut skipped_y) {
+					return;
+				}
+			}
+
 			// set axis flip arguments
 			SolidFlipped { horizontal_flip, vertical_flip, .. }
 			| SolidFlippedBlended { horizontal_flip, vertical_flip, .. }
@@ -534,6 +648,24 @@ impl IndexedBitmap {
 	) {
 		use IndexedBlitMethod::*;
 		match method {
+			Scaled { scale_x, scale_y }
+			| ScaledBlended { scale_x, scale_y, .. }
+			| ScaledOffset { scale_x, scale_y, .. }
+			| ScaledTransparent { scale_x, scale_y, .. }
+			| ScaledTransparentBlended { scale_x, scale_y, .. }
+			| ScaledTransparentOffset { scale_x, scale_y, .. } => {
+				let dest_region = scaled_blit_region(src_region, dest_x, dest_y, scale_x, scale_y);
+				self.scaled_blit_region_unchecked(&method, src, src_region, &dest_region, false, false)
+			}
+			ScaledFlipped { scale_x, scale_y, horizontal_flip, vertical_flip }
+			| ScaledFlippedBlended { scale_x, scale_y, horizontal_flip, vertical_flip, .. }
+			| ScaledFlippedOffset { scale_x, scale_y, horizontal_flip, vertical_flip, .. }
+			| ScaledTransparentFlipped { scale_x, scale_y, horizontal_flip, vertical_flip, .. }
+			| ScaledTransparentFlippedBlended { scale_x, scale_y, horizontal_flip, vertical_flip, .. }
+			| ScaledTransparentFlippedOffset { scale_x, scale_y, horizontal_flip, vertical_flip, .. } => {
+				let dest_region = scaled_blit_region(src_region, dest_x, dest_y, scale_x, scale_y);
+				self.scaled_blit_region_unchecked(&method, src, src_region, &dest_region, horizontal_flip, vertical_flip)
+			}
 			Solid => self.solid_blit(src, src_region, dest_x, dest_y),
 			SolidFlipped { horizontal_flip, vertical_flip } => {
 				self.solid_flipped_blit(src, src_region, dest_x, dest_y, horizontal_flip, vertical_flip)
@@ -606,6 +738,11 @@ impl IndexedBitmap {
 		}
 	}
 
+	#[inline]
+	pub fn blit_view(&mut self, method: IndexedBlitMethod, src: &BitmapView<u8>, x: i32, y: i32) {
+		self.blit_region(method, src.bitmap(), src.region(), x, y);
+	}
+
 	#[inline]
 	pub unsafe fn blit_unchecked(&mut self, method: IndexedBlitMethod, src: &Self, x: i32, y: i32) {
 		let src_region = Rect::new(0, 0, src.width, src.height);
@@ -625,4 +762,272 @@ impl IndexedBitmap {
 			self.blit_region_unchecked(method, src.bitmap(), src_region, x, y);
 		}
 	}
+
+	// draws the source region scaled to fit the destination region, applying the per-pixel behaviour of the given
+	// blit method. any flipping, rotation or scaling that the blit method itself specifies is ignored in favour of
+	// the destination region and flip arguments given.
+	unsafe fn scaled_blit_region_unchecked(
+		&mut self,
+		method: &IndexedBlitMethod,
+		src: &Self,
+		src_region: &Rect,
+		dest_region: &Rect,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+	) {
+		use IndexedBlitMethod::*;
+		match method {
+			Solid | SolidFlipped { .. } | RotoZoom { .. } | Scaled { .. } | ScaledFlipped { .. } => {
+				per_pixel_scaled_flipped_blit(
+					self,
+					src,
+					src_region,
+					dest_region,
+					horizontal_flip,
+					vertical_flip,
+					|src_pixels, dest_pixels| {
+						*dest_pixels = *src_pixels;
+					},
+				)
+			}
+			SolidBlended { blend_map }
+			| SolidFlippedBlended { blend_map, .. }
+			| RotoZoomBlended { blend_map, .. }
+			| ScaledBlended { blend_map, .. }
+			| ScaledFlippedBlended { blend_map, .. } => per_pixel_scaled_flipped_blit(
+				self,
+				src,
+				src_region,
+				dest_region,
+				horizontal_flip,
+				vertical_flip,
+				|src_pixels, dest_pixels| {
+					if let Some(blended_pixel) = blend_map.blend(*src_pixels, *dest_pixels) {
+						*dest_pixels = blended_pixel;
+					} else {
+						*dest_pixels = *src_pixels;
+					}
+				},
+			),
+			SolidOffset(offset)
+			| SolidFlippedOffset { offset, .. }
+			| RotoZoomOffset { offset, .. }
+			| ScaledOffset { offset, .. }
+			| ScaledFlippedOffset { offset, .. } => per_pixel_scaled_flipped_blit(
+				self,
+				src,
+				src_region,
+				dest_region,
+				horizontal_flip,
+				vertical_flip,
+				|src_pixels, dest_pixels| {
+					*dest_pixels = (*src_pixels).wrapping_add(*offset);
+				},
+			),
+			Transparent(transparent_color)
+			| TransparentFlipped { transparent_color, .. }
+			| RotoZoomTransparent { transparent_color, .. }
+			| ScaledTransparent { transparent_color, .. }
+			| ScaledTransparentFlipped { transparent_color, .. } => per_pixel_scaled_flipped_blit(
+				self,
+				src,
+				src_region,
+				dest_region,
+				horizontal_flip,
+				vertical_flip,
+				|src_pixels, dest_pixels| {
+					if *src_pixels != *transparent_color {
+						*dest_pixels = *src_pixels;
+					}
+				},
+			),
+			TransparentBlended { transparent_color, blend_map }
+			| TransparentFlippedBlended { transparent_color, blend_map, .. }
+			| RotoZoomTransparentBlended { transparent_color, blend_map, .. }
+			| ScaledTransparentBlended { transparent_color, blend_map, .. }
+			| ScaledTransparentFlippedBlended { transparent_color, blend_map, .. } => per_pixel_scaled_flipped_blit(
+				self,
+				src,
+				src_region,
+				dest_region,
+				horizontal_flip,
+				vertical_flip,
+				|src_pixels, dest_pixels| {
+					if *src_pixels != *transparent_color {
+						if let Some(blended_pixel) = blend_map.blend(*src_pixels, *dest_pixels) {
+							*dest_pixels = blended_pixel;
+						} else {
+							*dest_pixels = *src_pixels;
+						}
+					}
+				},
+			),
+			TransparentSingle { transparent_color, draw_color }
+			| TransparentFlippedSingle { transparent_color, draw_color, .. } => per_pixel_scaled_flipped_blit(
+				self,
+				src,
+				src_region,
+				dest_region,
+				horizontal_flip,
+				vertical_flip,
+				|src_pixels, dest_pixels| {
+					if *src_pixels != *transparent_color {
+						*dest_pixels = *draw_color;
+					}
+				},
+			),
+			TransparentOffset { transparent_color, offset }
+			| TransparentFlippedOffset { transparent_color, offset, .. }
+			| RotoZoomTransparentOffset { transparent_color, offset, .. }
+			| ScaledTransparentOffset { transparent_color, offset, .. }
+			| ScaledTransparentFlippedOffset { transparent_color, offset, .. } => per_pixel_scaled_flipped_blit(
+				self,
+				src,
+				src_region,
+				dest_region,
+				horizontal_flip,
+				vertical_flip,
+				|src_pixels, dest_pixels| {
+					if *src_pixels != *transparent_color {
+						*dest_pixels = (*src_pixels).wrapping_add(*offset);
+					}
+				},
+			),
+		}
+	}
+
+	/// Draws the source region as a nine-slice panel filling the given destination region. The corners of
+	/// the source region are drawn as-is, while the edges and center are stretched or tiled as specified by
+	/// the [`NineSlice`]. The per-pixel behaviour of the blit method (transparency, blending, offsets, etc) is
+	/// applied, but any flipping, rotation or scaling it would normally perform is ignored.
+	pub fn blit_nine_slice_region(
+		&mut self,
+		method: IndexedBlitMethod,
+		src: &Self,
+		src_region: &Rect,
+		slice: &NineSlice,
+		dest_region: &Rect,
+	) {
+		let mut src_region = *src_region;
+		if !src_region.clamp_to(&src.clip_region) {
+			return;
+		}
+
+		let clip_region = *self.clip_region();
+		for_each_nine_slice_part(slice, &src_region, dest_region, &clip_region, |src_part, dest_part| unsafe {
+			self.scaled_blit_region_unchecked(&method, src, src_part, dest_part, false, false);
+		});
+	}
+
+	#[inline]
+	pub fn blit_nine_slice(&mut self, method: IndexedBlitMethod, src: &Self, slice: &NineSlice, dest_region: &Rect) {
+		let src_region = Rect::new(0, 0, src.width, src.height);
+		self.blit_nine_slice_region(method, src, &src_region, slice, dest_region);
+	}
+
+	#[inline]
+	pub fn blit_atlas_nine_slice(
+		&mut self,
+		method: IndexedBlitMethod,
+		src: &BitmapAtlas<Self>,
+		index: usize,
+		slice: &NineSlice,
+		dest_region: &Rect,
+	) {
+		if let Some(src_region) = src.get(index) {
+			self.blit_nine_slice_region(method, src.bitmap(), src_region, slice, dest_region);
+		}
+	}
+
+	/// Fills the given destination region by repeatedly drawing the source region across it. The offsets
+	/// scroll the repeated pattern, such that the source pixel at (`offset_x`, `offset_y`) is drawn at the
+	/// top-left of the destination region. The per-pixel behaviour of the blit method (transparency,
+	/// blending, offsets, etc) is applied, but any flipping, rotation or scaling it would normally perform
+	/// is ignored.
+	pub fn blit_tiled_region(
+		&mut self,
+		method: IndexedBlitMethod,
+		src: &Self,
+		src_region: &Rect,
+		dest_region: &Rect,
+		offset_x: i32,
+		offset_y: i32,
+	) {
+		let mut src_region = *src_region;
+		if !src_region.clamp_to(&src.clip_region) {
+			return;
+		}
+
+		let clip_region = *self.clip_region();
+		for_each_tile(&src_region, dest_region, &clip_region, offset_x, offset_y, |src_part, dest_part| unsafe {
+			self.scaled_blit_region_unchecked(&method, src, src_part, dest_part, false, false);
+		});
+	}
+
+	#[inline]
+	pub fn blit_tiled(
+		&mut self,
+		method: IndexedBlitMethod,
+		src: &Self,
+		dest_region: &Rect,
+		offset_x: i32,
+		offset_y: i32,
+	) {
+		let src_region = Rect::new(0, 0, src.width, src.height);
+		self.blit_tiled_region(method, src, &src_region, dest_region, offset_x, offset_y);
+	}
+
+	#[inline]
+	pub fn blit_atlas_tiled(
+		&mut self,
+		method: IndexedBlitMethod,
+		src: &BitmapAtlas<Self>,
+		index: usize,
+		dest_region: &Rect,
+		offset_x: i32,
+		offset_y: i32,
+	) {
+		if let Some(src_region) = src.get(index) {
+			self.blit_tiled_region(method, src.bitmap(), src_region, dest_region, offset_x, offset_y);
+		}
+	}
+}
+
+impl BitmapViewMut<'_, u8> {
+	pub fn blit_region(
+		&mut self,
+		method: IndexedBlitMethod,
+		src: &IndexedBitmap,
+		src_region: &Rect,
+		dest_x: i32,
+		dest_y: i32,
+	) {
+		let (offset_x, offset_y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.blit_region(method, src, src_region, dest_x + offset_x, dest_y + offset_y));
+	}
+
+	#[inline]
+	pub fn blit(&mut self, method: IndexedBlitMethod, src: &IndexedBitmap, x: i32, y: i32) {
+		let src_region = Rect::new(0, 0, src.width(), src.height());
+		self.blit_region(method, src, &src_region, x, y);
+	}
+
+	#[inline]
+	pub fn blit_atlas(
+		&mut self,
+		method: IndexedBlitMethod,
+		src: &BitmapAtlas<IndexedBitmap>,
+		index: usize,
+		x: i32,
+		y: i32,
+	) {
+		if let Some(src_region) = src.get(index) {
+			self.blit_region(method, src.bitmap(), src_region, x, y);
+		}
+	}
+
+	#[inline]
+	pub fn blit_view(&mut self, method: IndexedBlitMethod, src: &BitmapView<u8>, x: i32, y: i32) {
+		self.blit_region(method, src.bitmap(), src.region(), x, y);
+	}
 }
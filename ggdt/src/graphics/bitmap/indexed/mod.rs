@@ -1,13 +1,20 @@
+use std::fs::File;
+use std::io::{BufReader, Seek};
 use std::path::Path;
 
-use crate::graphics::{Bitmap, BitmapError, Palette, RgbaBitmap, RGBA};
+use byteorder::ReadBytesExt;
+
+use crate::graphics::{Bitmap, BitmapError, BitmapFileType, Palette, RgbaBitmap, RGBA};
 
 mod blit;
 mod primitives;
+mod remap;
+mod scale;
 mod triangles;
 
 pub use blit::*;
 pub use primitives::*;
+pub use scale::*;
 pub use triangles::*;
 
 pub type IndexedBitmap = Bitmap<u8>;
@@ -25,21 +32,55 @@ impl IndexedBitmap {
 		Self::internal_new(width, height, 0)
 	}
 
-	pub fn load_file(path: &Path) -> Result<(Self, Palette), BitmapError> {
-		if let Some(extension) = path.extension() {
-			let extension = extension.to_ascii_lowercase();
-			match extension.to_str() {
-				Some("png") => {
-					let (bmp, palette) = Self::load_png_file(path)?;
-					Ok((bmp, palette.expect("Indexed color PNG loaded and should have returned a Palette")))
-				}
-				Some("pcx") => Ok(Self::load_pcx_file(path)?),
-				Some("gif") => Ok(Self::load_gif_file(path)?),
-				Some("iff") | Some("lbm") | Some("pbm") | Some("bbm") => Ok(Self::load_iff_file(path)?),
-				_ => Err(BitmapError::UnknownFileType(String::from("Unrecognized file extension"))),
+	/// Loads an image from the given reader, automatically detecting the file type from its contents. Only images
+	/// using 256 colors or less (or grayscale, in the case of Netpbm files) can be loaded.
+	///
+	/// # Arguments
+	///
+	/// * `reader`: the reader to load the image from
+	///
+	/// returns: `Result<(IndexedBitmap, Palette), BitmapError>`
+	pub fn load_bytes<T: ReadBytesExt + Seek>(reader: &mut T) -> Result<(Self, Palette), BitmapError> {
+		match BitmapFileType::detect(reader)? {
+			Some(file_type) => Self::load_bytes_as(reader, file_type),
+			None => Err(BitmapError::UnknownFileType(String::from("Unrecognized file contents"))),
+		}
+	}
+
+	fn load_bytes_as<T: ReadBytesExt + Seek>(
+		reader: &mut T,
+		file_type: BitmapFileType,
+	) -> Result<(Self, Palette), BitmapError> {
+		use BitmapFileType::*;
+		match file_type {
+			Png => {
+				let (bmp, palette) = Self::load_png_bytes(reader)?;
+				Ok((bmp, palette.expect("Indexed color PNG loaded and should have returned a Palette")))
 			}
-		} else {
-			Err(BitmapError::UnknownFileType(String::from("No file extension")))
+			Pcx => Ok(Self::load_pcx_bytes(reader)?),
+			Gif => Ok(Self::load_gif_bytes(reader)?),
+			Iff => Ok(Self::load_iff_bytes(reader)?),
+			Bmp => Ok(Self::load_bmp_bytes(reader)?),
+			Tga => Ok(Self::load_tga_bytes(reader)?),
+			Netpbm => Ok(Self::load_netpbm_bytes(reader)?),
+			Qoi => Err(BitmapError::UnsupportedFileType(file_type)),
+		}
+	}
+
+	/// Loads an image from the given file. The file type is detected from the file's contents, falling back to
+	/// the file extension only if the contents could not be identified.
+	///
+	/// # Arguments
+	///
+	/// * `path`: the path of the file to load
+	///
+	/// returns: `Result<(IndexedBitmap, Palette), BitmapError>`
+	pub fn load_file(path: &Path) -> Result<(Self, Palette), BitmapError> {
+		let f = File::open(path)?;
+		let mut reader = BufReader::new(f);
+		match BitmapFileType::detect(&mut reader)?.or_else(|| BitmapFileType::from_extension(path)) {
+			Some(file_type) => Self::load_bytes_as(&mut reader, file_type),
+			None => Err(BitmapError::UnknownFileType(String::from("Unrecognized file contents and file extension"))),
 		}
 	}
 
@@ -1,4 +1,5 @@
-use crate::graphics::{BlendMap, IndexedBitmap};
+use crate::graphics::{BlendMap, IndexedBitmap, LineCap, PolygonFillRule};
+use crate::math::{Curve, Vector2};
 
 impl IndexedBitmap {
 	/// Sets the pixel at the given coordinates using a blended color via the specified blend map,
@@ -124,4 +125,253 @@ impl IndexedBitmap {
 			self.filled_rect(x1, y1, x2, y2, color);
 		}
 	}
+
+	/// Draws the outline of an ellipse formed by the center point and radii given by blending the drawn pixels using
+	/// the given blend map, or the color specified if the blend map does not include this color.
+	#[inline]
+	pub fn blended_ellipse(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		color: u8,
+		blend_map: &BlendMap,
+	) {
+		if let Some(blend_mapping) = blend_map.get_mapping(color) {
+			self.ellipse_custom(center_x, center_y, radius_x, radius_y, |dest_color| {
+				blend_mapping[dest_color as usize]
+			});
+		} else {
+			self.ellipse(center_x, center_y, radius_x, radius_y, color);
+		}
+	}
+
+	/// Draws a filled ellipse formed by the center point and radii given by blending the drawn pixels using the given
+	/// blend map, or the color specified if the blend map does not include this color.
+	#[inline]
+	pub fn blended_filled_ellipse(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		color: u8,
+		blend_map: &BlendMap,
+	) {
+		if let Some(blend_mapping) = blend_map.get_mapping(color) {
+			self.filled_ellipse_custom(center_x, center_y, radius_x, radius_y, |dest_color| {
+				blend_mapping[dest_color as usize]
+			});
+		} else {
+			self.filled_ellipse(center_x, center_y, radius_x, radius_y, color);
+		}
+	}
+
+	/// Draws an arc of an ellipse between the start and end angles (see [`IndexedBitmap::arc`]) by blending the drawn pixels
+	/// using the given blend map, or the color specified if the blend map does not include this color.
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_arc(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: u8,
+		blend_map: &BlendMap,
+	) {
+		if let Some(blend_mapping) = blend_map.get_mapping(color) {
+			self.arc_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |dest_color| {
+				blend_mapping[dest_color as usize]
+			});
+		} else {
+			self.arc(center_x, center_y, radius_x, radius_y, start_angle, end_angle, color);
+		}
+	}
+
+	/// Draws the outline of a pie slice of an ellipse between the start and end angles (see [`IndexedBitmap::pie`]) by
+	/// blending the drawn pixels using the given blend map, or the color specified if the blend map does not include
+	/// this color.
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_pie(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: u8,
+		blend_map: &BlendMap,
+	) {
+		if let Some(blend_mapping) = blend_map.get_mapping(color) {
+			self.pie_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |dest_color| {
+				blend_mapping[dest_color as usize]
+			});
+		} else {
+			self.pie(center_x, center_y, radius_x, radius_y, start_angle, end_angle, color);
+		}
+	}
+
+	/// Draws a filled pie slice of an ellipse between the start and end angles (see [`IndexedBitmap::filled_pie`]) by blending
+	/// the drawn pixels using the given blend map, or the color specified if the blend map does not include this color.
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_filled_pie(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: u8,
+		blend_map: &BlendMap,
+	) {
+		if let Some(blend_mapping) = blend_map.get_mapping(color) {
+			self.filled_pie_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |dest_color| {
+				blend_mapping[dest_color as usize]
+			});
+		} else {
+			self.filled_pie(center_x, center_y, radius_x, radius_y, start_angle, end_angle, color);
+		}
+	}
+
+	/// Draws an empty box (rectangle) with rounded corners using the points x1,y1 and x2,y2 to form the box to be drawn
+	/// by blending the drawn pixels using the given blend map, or the color specified if the blend map does not include
+	/// this color.
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_rounded_rect(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		radius: u32,
+		color: u8,
+		blend_map: &BlendMap,
+	) {
+		if let Some(blend_mapping) = blend_map.get_mapping(color) {
+			self.rounded_rect_custom(x1, y1, x2, y2, radius, |dest_color| blend_mapping[dest_color as usize]);
+		} else {
+			self.rounded_rect(x1, y1, x2, y2, radius, color);
+		}
+	}
+
+	/// Draws a filled box (rectangle) with rounded corners using the points x1,y1 and x2,y2 to form the box to be drawn
+	/// by blending the drawn pixels using the given blend map, or the color specified if the blend map does not include
+	/// this color.
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_filled_rounded_rect(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		radius: u32,
+		color: u8,
+		blend_map: &BlendMap,
+	) {
+		if let Some(blend_mapping) = blend_map.get_mapping(color) {
+			self.filled_rounded_rect_custom(x1, y1, x2, y2, radius, |dest_color| blend_mapping[dest_color as usize]);
+		} else {
+			self.filled_rounded_rect(x1, y1, x2, y2, radius, color);
+		}
+	}
+
+	/// Draws a series of connected lines between each of the given points in turn by blending the drawn pixels using
+	/// the given blend map, or the color specified if the blend map does not include this color.
+	#[inline]
+	pub fn blended_polyline(&mut self, points: &[Vector2], color: u8, blend_map: &BlendMap) {
+		if let Some(blend_mapping) = blend_map.get_mapping(color) {
+			self.polyline_custom(points, |dest_color| blend_mapping[dest_color as usize]);
+		} else {
+			self.polyline(points, color);
+		}
+	}
+
+	/// Draws the outline of a polygon formed by the given points by blending the drawn pixels using the given blend
+	/// map, or the color specified if the blend map does not include this color.
+	#[inline]
+	pub fn blended_polygon(&mut self, points: &[Vector2], color: u8, blend_map: &BlendMap) {
+		if let Some(blend_mapping) = blend_map.get_mapping(color) {
+			self.polygon_custom(points, |dest_color| blend_mapping[dest_color as usize]);
+		} else {
+			self.polygon(points, color);
+		}
+	}
+
+	/// Draws a filled polygon formed by the given points, using the given fill rule by blending the drawn pixels using
+	/// the given blend map, or the color specified if the blend map does not include this color.
+	#[inline]
+	pub fn blended_filled_polygon(
+		&mut self,
+		points: &[Vector2],
+		fill_rule: PolygonFillRule,
+		color: u8,
+		blend_map: &BlendMap,
+	) {
+		if let Some(blend_mapping) = blend_map.get_mapping(color) {
+			self.filled_polygon_custom(points, fill_rule, |dest_color| blend_mapping[dest_color as usize]);
+		} else {
+			self.filled_polygon(points, fill_rule, color);
+		}
+	}
+
+	/// Draws a line from x1,y1 to x2,y2 with the given thickness and cap style by blending the drawn pixels using the
+	/// given blend map, or the color specified if the blend map does not include this color.
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_thick_line(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		thickness: u32,
+		cap: LineCap,
+		color: u8,
+		blend_map: &BlendMap,
+	) {
+		if let Some(blend_mapping) = blend_map.get_mapping(color) {
+			self.thick_line_custom(x1, y1, x2, y2, thickness, cap, |dest_color| blend_mapping[dest_color as usize]);
+		} else {
+			self.thick_line(x1, y1, x2, y2, thickness, cap, color);
+		}
+	}
+
+	/// Draws the given curve by blending the drawn pixels using the given blend map, or the color specified if the
+	/// blend map does not include this color.
+	#[inline]
+	pub fn blended_curve<C: Curve + ?Sized>(&mut self, curve: &C, color: u8, blend_map: &BlendMap) {
+		if let Some(blend_mapping) = blend_map.get_mapping(color) {
+			self.curve_custom(curve, |dest_color| blend_mapping[dest_color as usize]);
+		} else {
+			self.curve(curve, color);
+		}
+	}
+
+	/// Draws the given curve with the given thickness and cap style by blending the drawn pixels using the given
+	/// blend map, or the color specified if the blend map does not include this color.
+	#[inline]
+	pub fn blended_thick_curve<C: Curve + ?Sized>(
+		&mut self,
+		curve: &C,
+		thickness: u32,
+		cap: LineCap,
+		color: u8,
+		blend_map: &BlendMap,
+	) {
+		if let Some(blend_mapping) = blend_map.get_mapping(color) {
+			self.thick_curve_custom(curve, thickness, cap, |dest_color| blend_mapping[dest_color as usize]);
+		} else {
+			self.thick_curve(curve, thickness, cap, color);
+		}
+	}
 }
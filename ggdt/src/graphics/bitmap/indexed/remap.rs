@@ -0,0 +1,107 @@
+use crate::graphics::{IndexedBitmap, Palette, PaletteError, RemapTable};
+
+impl IndexedBitmap {
+	/// Returns the number of pixels in this bitmap using each of the 256 colors. The current clipping region is
+	/// ignored, the entire bitmap is always counted.
+	pub fn color_histogram(&self) -> [u32; 256] {
+		let mut histogram = [0u32; 256];
+		for pixel in self.pixels().iter() {
+			histogram[*pixel as usize] += 1;
+		}
+		histogram
+	}
+
+	/// Remaps all of the pixels in this bitmap from the `from` palette to the closest matching colors in the `to`
+	/// palette. The current clipping region is ignored, the entire bitmap is always remapped.
+	///
+	/// # Arguments
+	///
+	/// * `from`: the palette this bitmap is currently using
+	/// * `to`: the palette this bitmap is to be remapped to
+	pub fn remap(&mut self, from: &Palette, to: &Palette) {
+		self.remap_with_table(&RemapTable::from_palettes(from, to));
+	}
+
+	/// Remaps all of the pixels in this bitmap using the given remap table. The current clipping region is ignored,
+	/// the entire bitmap is always remapped.
+	///
+	/// # Arguments
+	///
+	/// * `table`: the remap table to use
+	pub fn remap_with_table(&mut self, table: &RemapTable) {
+		for pixel in self.pixels_mut().iter_mut() {
+			*pixel = table[*pixel];
+		}
+	}
+
+	/// Reduces the number of colors used by this bitmap to at most `num_colors`, using median cut color
+	/// quantization, remapping this bitmap's pixels to use the returned palette. If this bitmap already uses
+	/// `num_colors` colors or less, this instead just removes unused colors from the palette. See
+	/// [`Palette::merge`] for more details.
+	///
+	/// # Arguments
+	///
+	/// * `palette`: the palette this bitmap is currently using
+	/// * `num_colors`: the maximum number of colors to use (<= 256)
+	///
+	/// returns: the palette this bitmap has been remapped to
+	pub fn reduce_colors(&mut self, palette: &Palette, num_colors: usize) -> Result<Palette, PaletteError> {
+		let (reduced, tables) = Palette::merge(&[(self, palette)], num_colors)?;
+		self.remap_with_table(&tables[0]);
+		Ok(reduced)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use claim::*;
+
+	use crate::graphics::{COLOR_BLACK, COLOR_BRIGHT_BLUE, COLOR_BRIGHT_RED, COLOR_RED, RGBA};
+
+	use super::*;
+
+	#[test]
+	pub fn remap_between_palettes() {
+		let mut from = Palette::new();
+		from[1] = COLOR_RED;
+		from[2] = COLOR_BRIGHT_BLUE;
+		from[3] = RGBA::from_rgb([250, 80, 80]);
+		let mut to = Palette::new();
+		to[10] = COLOR_BRIGHT_BLUE;
+		to[20] = COLOR_BRIGHT_RED;
+		to[30] = COLOR_RED;
+
+		let mut bmp = IndexedBitmap::new(4, 1).unwrap();
+		bmp.pixels_mut().copy_from_slice(&[0, 1, 2, 3]);
+		bmp.remap(&from, &to);
+		assert_eq!(&[0, 30, 10, 20], bmp.pixels());
+
+		let mut table = RemapTable::from_palettes(&from, &to);
+		table[0] = 5;
+		bmp.pixels_mut().copy_from_slice(&[0, 1, 2, 3]);
+		bmp.remap_with_table(&table);
+		assert_eq!(&[5, 30, 10, 20], bmp.pixels());
+	}
+
+	#[test]
+	pub fn reduce_colors() {
+		let mut palette = Palette::new();
+		for index in 0..8 {
+			palette[index] = RGBA::from_rgb([200 + index * 2, 10, 10]);
+			palette[index + 8] = RGBA::from_rgb([10, 10, 200 + index * 2]);
+		}
+
+		let mut bmp = IndexedBitmap::new(16, 1).unwrap();
+		for (index, pixel) in bmp.pixels_mut().iter_mut().enumerate() {
+			*pixel = index as u8;
+		}
+
+		let reduced = bmp.reduce_colors(&palette, 2).unwrap();
+		assert_eq!(RGBA::from_rgb([207, 10, 10]), reduced[0]);
+		assert_eq!(RGBA::from_rgb([10, 10, 207]), reduced[1]);
+		assert_eq!(COLOR_BLACK, reduced[2]);
+		assert_eq!(&[0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1], bmp.pixels());
+
+		assert_matches!(bmp.reduce_colors(&reduced, 257), Err(PaletteError::OutOfRange(257)));
+	}
+}
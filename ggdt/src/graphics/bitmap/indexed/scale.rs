@@ -0,0 +1,52 @@
+use crate::graphics::{BitmapError, IndexedBitmap};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum IndexedScaleMethod {
+	/// Nearest-neighbour sampling. Works for any scale factor, both up and down.
+	Nearest,
+	/// Upscales using the Scale2x (a.k.a. EPX) pixel-art scaler, followed by nearest-neighbour sampling if the
+	/// final size is not exactly 2x the original size.
+	Scale2x,
+	/// Upscales using the Scale3x pixel-art scaler, followed by nearest-neighbour sampling if the final size is not
+	/// exactly 3x the original size.
+	Scale3x,
+	/// Upscales using the "hqx-style" pixel-art scaler, followed by nearest-neighbour sampling if the final size is
+	/// not exactly 2x the original size. Since palette indices cannot be blended together, this will give the
+	/// same results as [`IndexedScaleMethod::Scale2x`].
+	Hq2x,
+}
+
+impl IndexedBitmap {
+	/// Returns a copy of this bitmap scaled to the given size using the specified scaling method. The current
+	/// clipping region is ignored, the entire bitmap is always scaled.
+	///
+	/// # Arguments
+	///
+	/// * `width`: the width of the scaled bitmap
+	/// * `height`: the height of the scaled bitmap
+	/// * `method`: the scaling method to use
+	///
+	/// returns: `Result<IndexedBitmap, BitmapError>`
+	pub fn scaled(&self, width: u32, height: u32, method: IndexedScaleMethod) -> Result<Self, BitmapError> {
+		let mut dest = Self::new(width, height)?;
+		self.scale_into(&mut dest, method);
+		Ok(dest)
+	}
+
+	/// Scales this bitmap using the specified scaling method such that it fills the entire destination bitmap
+	/// given. The current clipping regions of both bitmaps are ignored.
+	///
+	/// # Arguments
+	///
+	/// * `dest`: the destination bitmap to scale this bitmap's pixels into
+	/// * `method`: the scaling method to use
+	pub fn scale_into(&self, dest: &mut Self, method: IndexedScaleMethod) {
+		use IndexedScaleMethod::*;
+		match method {
+			Nearest => self.nearest_scale_into(dest),
+			Scale2x => self.scale2x().nearest_scale_into(dest),
+			Scale3x => self.scale3x().nearest_scale_into(dest),
+			Hq2x => self.hq2x().nearest_scale_into(dest),
+		}
+	}
+}
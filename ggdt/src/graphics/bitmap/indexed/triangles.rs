@@ -1,4 +1,4 @@
-use crate::graphics::{edge_function, per_pixel_triangle_2d, BlendMap, IndexedBitmap};
+use crate::graphics::{edge_function, per_pixel_triangle_2d, BitmapViewMut, BlendMap, IndexedBitmap};
 use crate::math::Vector2;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -25,6 +25,25 @@ pub enum IndexedTriangle2d<'a> {
 	},
 }
 
+impl IndexedTriangle2d<'_> {
+	// returns a copy of this triangle with all of its vertex positions moved by the given offset
+	fn translated(&self, offset: Vector2) -> Self {
+		use IndexedTriangle2d::*;
+		let mut triangle = *self;
+		match &mut triangle {
+			Solid { position, .. }
+			| SolidBlended { position, .. }
+			| SolidTextured { position, .. }
+			| SolidTexturedBlended { position, .. } => {
+				for vertex in position.iter_mut() {
+					*vertex += offset;
+				}
+			}
+		}
+		triangle
+	}
+}
+
 impl IndexedBitmap {
 	pub fn solid_triangle_2d(&mut self, positions: &[Vector2; 3], color: u8) {
 		per_pixel_triangle_2d(
@@ -103,3 +122,16 @@ impl IndexedBitmap {
 		}
 	}
 }
+
+impl BitmapViewMut<'_, u8> {
+	pub fn triangle_2d(&mut self, triangle: &IndexedTriangle2d) {
+		let triangle = triangle.translated(Vector2::new(self.x() as f32, self.y() as f32));
+		self.with_bitmap(|bitmap| bitmap.triangle_2d(&triangle));
+	}
+
+	pub fn triangle_list_2d(&mut self, triangles: &[IndexedTriangle2d]) {
+		for triangle in triangles.iter() {
+			self.triangle_2d(triangle);
+		}
+	}
+}
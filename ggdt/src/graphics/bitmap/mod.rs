@@ -4,26 +4,57 @@ use crate::graphics::Pixel;
 use crate::math::Rect;
 
 mod blit;
+mod bmp;
+mod curves;
+mod fill;
+mod format;
 mod general;
 mod gif;
+mod hicolor;
 mod iff;
+mod iffanim;
 mod indexed;
+mod mono;
+mod netpbm;
+mod nineslice;
 mod pcx;
 mod png;
 mod primitives;
+mod qoi;
 mod rgb;
+mod scale;
+mod shapes;
+mod tga;
 mod triangles;
+mod view;
 
 pub use blit::*;
+pub use bmp::*;
+pub use fill::*;
+pub use format::*;
 pub use general::*;
 pub use gif::*;
+pub use hicolor::*;
 pub use iff::*;
+pub use iffanim::*;
 pub use indexed::*;
+pub use mono::*;
+pub use netpbm::*;
+pub use nineslice::*;
 pub use pcx::*;
 pub use png::*;
 pub use primitives::*;
+pub use qoi::*;
 pub use rgb::*;
+pub use scale::*;
+pub use shapes::*;
+pub use tga::*;
 pub use triangles::*;
+pub use view::*;
+
+/// The largest number of pixels that the file loaders will accept from an image header, so that a malformed or
+/// malicious file can't cause a huge allocation up front.
+const MAX_LOADED_PIXELS: usize = 400_000_000;
 
 #[derive(Error, Debug)]
 pub enum BitmapError {
@@ -36,6 +67,9 @@ pub enum BitmapError {
 	#[error("Unknown bitmap file type: {0}")]
 	UnknownFileType(String),
 
+	#[error("Bitmap file type {0:?} cannot be loaded into this type of bitmap")]
+	UnsupportedFileType(BitmapFileType),
+
 	#[error("Bitmap I/O error")]
 	IOError(#[from] std::io::Error),
 
@@ -50,6 +84,18 @@ pub enum BitmapError {
 
 	#[error("Bitmap PNG file error")]
 	PngError(#[from] png::PngError),
+
+	#[error("Bitmap BMP file error")]
+	BmpError(#[from] bmp::BmpError),
+
+	#[error("Bitmap TGA file error")]
+	TgaError(#[from] tga::TgaError),
+
+	#[error("Bitmap Netpbm file error")]
+	NetpbmError(#[from] netpbm::NetpbmError),
+
+	#[error("Bitmap QOI file error")]
+	QoiError(#[from] qoi::QoiError),
 }
 
 /// Container for 256 color 2D pixel/image data that can be rendered to the screen. Pixel data
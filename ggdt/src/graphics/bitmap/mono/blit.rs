@@ -0,0 +1,183 @@
+use crate::graphics::{clip_blit, BitmapAtlas, MonoBitmap};
+use crate::math::Rect;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MonoBlitMethod {
+	/// Solid blit, no transparency or other per-pixel adjustments.
+	Solid,
+	/// Same as [MonoBlitMethod::Solid] but the drawn image can also be flipped horizontally
+	/// and/or vertically.
+	SolidFlipped { horizontal_flip: bool, vertical_flip: bool },
+	/// Transparent blit, the specified source color pixels are skipped. `Transparent(false)` will
+	/// only ever set pixels on the destination, while `Transparent(true)` will only ever clear them.
+	Transparent(bool),
+	/// Same as [MonoBlitMethod::Transparent] but the drawn image can also be flipped horizontally
+	/// and/or vertically.
+	TransparentFlipped { transparent_color: bool, horizontal_flip: bool, vertical_flip: bool },
+}
+
+impl MonoBitmap {
+	pub fn blit_region(
+		&mut self,
+		method: MonoBlitMethod,
+		src: &Self,
+		src_region: &Rect,
+		mut dest_x: i32,
+		mut dest_y: i32,
+	) {
+		// make sure the source region is clipped or even valid at all for the source bitmap given
+		let mut src_region = *src_region;
+		if !src_region.clamp_to(&src.clip_region) {
+			return;
+		}
+
+		let (horizontal_flip, vertical_flip) = method.flips();
+		if !clip_blit(
+			self.clip_region(), //
+			&mut src_region,
+			&mut dest_x,
+			&mut dest_y,
+			horizontal_flip,
+			vertical_flip,
+		) {
+			return;
+		}
+
+		unsafe {
+			self.blit_region_unchecked(method, src, &src_region, dest_x, dest_y);
+		};
+	}
+
+	/// Blits the given region of the source bitmap without any clipping.
+	///
+	/// # Safety
+	///
+	/// The source region must lie within the bounds of the source bitmap, and the destination region
+	/// it is drawn to must lie within the bounds of this bitmap.
+	pub unsafe fn blit_region_unchecked(
+		&mut self,
+		method: MonoBlitMethod,
+		src: &Self,
+		src_region: &Rect,
+		dest_x: i32,
+		dest_y: i32,
+	) {
+		let (horizontal_flip, vertical_flip) = method.flips();
+		let transparent_color = match method {
+			MonoBlitMethod::Transparent(transparent_color)
+			| MonoBlitMethod::TransparentFlipped { transparent_color, .. } => Some(transparent_color),
+			_ => None,
+		};
+
+		let width = src_region.width as i32;
+		let height = src_region.height as i32;
+		for y in 0..height {
+			let src_y = if vertical_flip { src_region.y + height - 1 - y } else { src_region.y + y };
+			for x in 0..width {
+				let src_x = if horizontal_flip { src_region.x + width - 1 - x } else { src_region.x + x };
+				let pixel = src.get_pixel_unchecked(src_x, src_y);
+				if Some(pixel) != transparent_color {
+					self.set_pixel_unchecked(dest_x + x, dest_y + y, pixel);
+				}
+			}
+		}
+	}
+
+	#[inline]
+	pub fn blit(&mut self, method: MonoBlitMethod, src: &Self, x: i32, y: i32) {
+		let src_region = Rect::new(0, 0, src.width, src.height);
+		self.blit_region(method, src, &src_region, x, y);
+	}
+
+	#[inline]
+	pub fn blit_atlas(&mut self, method: MonoBlitMethod, src: &BitmapAtlas<Self>, index: usize, x: i32, y: i32) {
+		if let Some(src_region) = src.get(index) {
+			self.blit_region(method, src.bitmap(), src_region, x, y);
+		}
+	}
+
+	/// Blits the entire source bitmap without any clipping.
+	///
+	/// # Safety
+	///
+	/// The source bitmap drawn at the given coordinates must lie within the bounds of this bitmap.
+	#[inline]
+	pub unsafe fn blit_unchecked(&mut self, method: MonoBlitMethod, src: &Self, x: i32, y: i32) {
+		let src_region = Rect::new(0, 0, src.width, src.height);
+		self.blit_region_unchecked(method, src, &src_region, x, y);
+	}
+}
+
+impl MonoBlitMethod {
+	#[inline]
+	fn flips(&self) -> (bool, bool) {
+		use MonoBlitMethod::*;
+		match *self {
+			SolidFlipped { horizontal_flip, vertical_flip }
+			| TransparentFlipped { horizontal_flip, vertical_flip, .. } => (horizontal_flip, vertical_flip),
+			_ => (false, false),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn source_bitmap() -> MonoBitmap {
+		let mut src = MonoBitmap::new(3, 2).unwrap();
+		src.set_pixel(0, 0, true);
+		src.set_pixel(1, 0, true);
+		src.set_pixel(2, 1, true);
+		src
+	}
+
+	#[test]
+	pub fn solid_and_transparent_blits() {
+		let src = source_bitmap();
+
+		let mut dest = MonoBitmap::new(10, 3).unwrap();
+		dest.clear(true);
+		dest.blit(MonoBlitMethod::Solid, &src, 0, 0);
+		dest.blit(MonoBlitMethod::Transparent(true), &src, 7, 1);
+		dest.blit(MonoBlitMethod::Solid, &src, 9, -1);
+		#[rustfmt::skip]
+		assert_eq!(
+			&[
+				0b11011111, 0b10000000,
+				0b00111111, 0b10000000,
+				0b11111110, 0b01000000,
+			],
+			dest.bytes()
+		);
+
+		dest.clear(false);
+		dest.blit(MonoBlitMethod::Transparent(false), &src, 6, 0);
+		dest.blit(MonoBlitMethod::Transparent(false), &src, 7, 1);
+		#[rustfmt::skip]
+		assert_eq!(
+			&[
+				0b00000011, 0b00000000,
+				0b00000001, 0b10000000,
+				0b00000000, 0b01000000,
+			],
+			dest.bytes()
+		);
+	}
+
+	#[test]
+	pub fn flipped_blits() {
+		let src = source_bitmap();
+
+		let mut dest = MonoBitmap::new(8, 2).unwrap();
+		dest.blit(MonoBlitMethod::SolidFlipped { horizontal_flip: true, vertical_flip: false }, &src, 0, 0);
+		dest.blit(MonoBlitMethod::SolidFlipped { horizontal_flip: false, vertical_flip: true }, &src, 3, 0);
+		dest.blit(
+			MonoBlitMethod::TransparentFlipped { transparent_color: false, horizontal_flip: true, vertical_flip: true },
+			&src,
+			6,
+			0,
+		);
+		assert_eq!(&[0b01100110, 0b10011001], dest.bytes());
+	}
+}
@@ -0,0 +1,312 @@
+use crate::graphics::{BitmapError, IndexedBitmap, RgbaBitmap, RGBA};
+use crate::math::Rect;
+
+mod blit;
+mod primitives;
+
+pub use blit::*;
+
+/// Container for 1-bit (monochrome) 2D pixel data, useful for masks, stencils and fonts. Pixels are
+/// stored packed 8 to a byte, with the left-most pixel of each byte in its most significant bit. Each
+/// row is padded out to a whole number of bytes, so the stride from one row to the next is the width
+/// rounded up to the next multiple of 8, divided by 8. Pixels are represented as `bool` values, where
+/// `true` is a set pixel. Rendering operations provided here are done with respect to the bitmaps
+/// clipping region, where rendering outside of the clipping region is simply not performed / stops
+/// at the clipping boundary.
+#[derive(Clone, Eq, PartialEq)]
+pub struct MonoBitmap {
+	width: u32,
+	height: u32,
+	stride: usize,
+	pixels: Box<[u8]>,
+	clip_region: Rect,
+}
+
+impl std::fmt::Debug for MonoBitmap {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("MonoBitmap") //
+			.field("width", &self.width)
+			.field("height", &self.height)
+			.field("clip_region", &self.clip_region)
+			.finish_non_exhaustive()
+	}
+}
+
+impl MonoBitmap {
+	/// Creates a new Bitmap with the specified dimensions, with all pixels cleared.
+	///
+	/// # Arguments
+	///
+	/// * `width`: the width of the bitmap in pixels
+	/// * `height`: the height of the bitmap in pixels
+	///
+	/// returns: `Result<MonoBitmap, BitmapError>`
+	pub fn new(width: u32, height: u32) -> Result<Self, BitmapError> {
+		if width == 0 || height == 0 {
+			return Err(BitmapError::InvalidDimensions);
+		}
+
+		let stride = (width as usize + 7) / 8;
+		Ok(MonoBitmap {
+			width, //
+			height,
+			stride,
+			pixels: vec![0u8; stride * height as usize].into_boxed_slice(),
+			clip_region: Rect { x: 0, y: 0, width, height },
+		})
+	}
+
+	/// Creates a new Bitmap, copying the pixel data from a sub-region of another source Bitmap.
+	/// The resulting bitmap will have dimensions equal to that of the region specified.
+	///
+	/// # Arguments
+	///
+	/// * `source`: the source bitmap to copy from
+	/// * `region`: the region on the source bitmap to copy from
+	///
+	/// returns: `Result<MonoBitmap, BitmapError>`
+	pub fn from(source: &Self, region: &Rect) -> Result<Self, BitmapError> {
+		if !source.full_bounds().contains_rect(region) {
+			return Err(BitmapError::OutOfBounds);
+		}
+
+		let mut bmp = Self::new(region.width, region.height)?;
+		unsafe { bmp.blit_region_unchecked(MonoBlitMethod::Solid, source, region, 0, 0) };
+		Ok(bmp)
+	}
+
+	/// Creates a new Bitmap from an [`IndexedBitmap`], where every pixel that is not the given
+	/// transparent color is set.
+	///
+	/// # Arguments
+	///
+	/// * `src`: the bitmap to convert
+	/// * `transparent_color`: the color of the pixels in the source bitmap that should be left cleared
+	///
+	/// returns: `MonoBitmap`
+	pub fn from_indexed(src: &IndexedBitmap, transparent_color: u8) -> Self {
+		let mut output = Self::new(src.width(), src.height()).unwrap();
+		for (y, row) in src.pixels().chunks_exact(src.width() as usize).enumerate() {
+			for (x, pixel) in row.iter().enumerate() {
+				if *pixel != transparent_color {
+					unsafe { output.set_pixel_unchecked(x as i32, y as i32, true) };
+				}
+			}
+		}
+		output
+	}
+
+	/// Creates a new Bitmap from an [`RgbaBitmap`], where every pixel with an alpha component greater
+	/// than or equal to the given alpha threshold is set.
+	///
+	/// # Arguments
+	///
+	/// * `src`: the bitmap to convert
+	/// * `alpha_threshold`: the minimum alpha component value of the source pixels that should be set
+	///
+	/// returns: `MonoBitmap`
+	pub fn from_rgba(src: &RgbaBitmap, alpha_threshold: u8) -> Self {
+		let mut output = Self::new(src.width(), src.height()).unwrap();
+		for (y, row) in src.pixels().chunks_exact(src.width() as usize).enumerate() {
+			for (x, pixel) in row.iter().enumerate() {
+				if pixel.a() >= alpha_threshold {
+					unsafe { output.set_pixel_unchecked(x as i32, y as i32, true) };
+				}
+			}
+		}
+		output
+	}
+
+	/// Makes an [`IndexedBitmap`] copy of this bitmap, using the given colors for set and cleared pixels.
+	///
+	/// # Arguments
+	///
+	/// * `set_color`: the color to use for set pixels
+	/// * `clear_color`: the color to use for cleared pixels
+	///
+	/// returns: `IndexedBitmap`
+	pub fn to_indexed(&self, set_color: u8, clear_color: u8) -> IndexedBitmap {
+		let mut output = IndexedBitmap::new(self.width, self.height).unwrap();
+		for (y, row) in output.pixels_mut().chunks_exact_mut(self.width as usize).enumerate() {
+			for (x, pixel) in row.iter_mut().enumerate() {
+				let set = unsafe { self.get_pixel_unchecked(x as i32, y as i32) };
+				*pixel = if set { set_color } else { clear_color };
+			}
+		}
+		output
+	}
+
+	/// Makes an [`RgbaBitmap`] copy of this bitmap, using the given colors for set and cleared pixels.
+	///
+	/// # Arguments
+	///
+	/// * `set_color`: the color to use for set pixels
+	/// * `clear_color`: the color to use for cleared pixels
+	///
+	/// returns: `RgbaBitmap`
+	pub fn to_rgba(&self, set_color: RGBA, clear_color: RGBA) -> RgbaBitmap {
+		let mut output = RgbaBitmap::new(self.width, self.height).unwrap();
+		for (y, row) in output.pixels_mut().chunks_exact_mut(self.width as usize).enumerate() {
+			for (x, pixel) in row.iter_mut().enumerate() {
+				let set = unsafe { self.get_pixel_unchecked(x as i32, y as i32) };
+				*pixel = if set { set_color } else { clear_color };
+			}
+		}
+		output
+	}
+
+	/// Returns the width of the bitmap in pixels.
+	#[inline]
+	pub fn width(&self) -> u32 {
+		self.width
+	}
+
+	/// Returns the height of the bitmap in pixels.
+	#[inline]
+	pub fn height(&self) -> u32 {
+		self.height
+	}
+
+	/// Returns the right x coordinate of the bitmap.
+	#[inline]
+	pub fn right(&self) -> u32 {
+		self.width - 1
+	}
+
+	/// Returns the bottom x coordinate of the bitmap.
+	#[inline]
+	pub fn bottom(&self) -> u32 {
+		self.height - 1
+	}
+
+	/// Returns the number of bytes used by each row of pixels.
+	#[inline]
+	pub fn stride(&self) -> usize {
+		self.stride
+	}
+
+	/// Returns the current clipping region set on this bitmap.
+	#[inline]
+	pub fn clip_region(&self) -> &Rect {
+		&self.clip_region
+	}
+
+	/// Returns a rect representing the full bitmap boundaries, ignoring the current clipping
+	/// region set on this bitmap.
+	#[inline]
+	pub fn full_bounds(&self) -> Rect {
+		Rect {
+			x: 0, //
+			y: 0,
+			width: self.width,
+			height: self.height,
+		}
+	}
+
+	/// Sets a new clipping region on this bitmap. The region will be automatically clamped to
+	/// the maximum bitmap boundaries if the supplied region extends beyond it.
+	///
+	/// # Arguments
+	///
+	/// * `region`: the new clipping region
+	#[inline]
+	pub fn set_clip_region(&mut self, region: &Rect) {
+		self.clip_region = *region;
+		self.clip_region.clamp_to(&self.full_bounds());
+	}
+
+	/// Resets the bitmaps clipping region back to the default (full boundaries of the bitmap).
+	#[inline]
+	pub fn reset_clip_region(&mut self) {
+		self.clip_region = self.full_bounds();
+	}
+
+	/// Returns a reference to the raw packed pixel bytes in this bitmap.
+	#[inline]
+	pub fn bytes(&self) -> &[u8] {
+		&self.pixels
+	}
+
+	/// Returns a mutable reference to the raw packed pixel bytes in this bitmap.
+	#[inline]
+	pub fn bytes_mut(&mut self) -> &mut [u8] {
+		&mut self.pixels
+	}
+
+	/// Returns true if the given coordinates are within the bitmap's current clipping region.
+	#[inline]
+	pub fn is_xy_visible(&self, x: i32, y: i32) -> bool {
+		(x >= self.clip_region.x)
+			&& (y >= self.clip_region.y)
+			&& (x <= self.clip_region.right())
+			&& (y <= self.clip_region.bottom())
+	}
+
+	/// Returns the offset of the byte containing the pixel at the given coordinates, along with the bit
+	/// mask for that pixel within that byte.
+	#[inline]
+	fn get_offset_and_mask_to_xy(&self, x: i32, y: i32) -> (usize, u8) {
+		let offset = (y as usize * self.stride) + (x as usize / 8);
+		let mask = 0x80 >> (x as usize % 8);
+		(offset, mask)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use claim::*;
+
+	use super::*;
+
+	#[test]
+	pub fn creation_and_sizing() {
+		assert_matches!(MonoBitmap::new(0, 0), Err(BitmapError::InvalidDimensions));
+
+		let bmp = MonoBitmap::new(12, 3).unwrap();
+		assert_eq!(12, bmp.width());
+		assert_eq!(3, bmp.height());
+		assert_eq!(2, bmp.stride());
+		assert_eq!(6, bmp.bytes().len());
+		assert!(bmp.bytes().iter().all(|byte| *byte == 0));
+		assert_eq!(Rect::new(0, 0, 12, 3), *bmp.clip_region());
+	}
+
+	#[test]
+	pub fn conversions() {
+		let mut indexed = IndexedBitmap::new(10, 2).unwrap();
+		indexed.set_pixel(0, 0, 3);
+		indexed.set_pixel(9, 0, 3);
+		indexed.set_pixel(8, 1, 7);
+
+		let bmp = MonoBitmap::from_indexed(&indexed, 0);
+		assert_eq!(&[0b10000000, 0b01000000, 0b00000000, 0b10000000], bmp.bytes());
+		assert_eq!(Some(true), bmp.get_pixel(9, 0));
+		assert_eq!(Some(false), bmp.get_pixel(1, 0));
+
+		let converted = bmp.to_indexed(15, 1);
+		assert_eq!(Some(15), converted.get_pixel(0, 0));
+		assert_eq!(Some(1), converted.get_pixel(1, 0));
+		assert_eq!(Some(15), converted.get_pixel(8, 1));
+
+		let rgba = bmp.to_rgba(RGBA::from_rgba([255, 255, 255, 255]), RGBA::from_rgba([0, 0, 0, 0]));
+		assert_eq!(Some(RGBA::from_rgba([255, 255, 255, 255])), rgba.get_pixel(9, 0));
+		assert_eq!(Some(RGBA::from_rgba([0, 0, 0, 0])), rgba.get_pixel(9, 1));
+		assert_eq!(bmp, MonoBitmap::from_rgba(&rgba, 128));
+	}
+
+	#[test]
+	pub fn copy_from() {
+		let mut bmp = MonoBitmap::new(16, 4).unwrap();
+		bmp.set_pixel(5, 1, true);
+		bmp.set_pixel(12, 2, true);
+
+		assert_matches!(MonoBitmap::from(&bmp, &Rect::new(10, 0, 8, 4)), Err(BitmapError::OutOfBounds));
+
+		let copy = MonoBitmap::from(&bmp, &Rect::new(4, 1, 9, 2)).unwrap();
+		assert_eq!(9, copy.width());
+		assert_eq!(2, copy.height());
+		assert_eq!(Some(true), copy.get_pixel(1, 0));
+		assert_eq!(Some(true), copy.get_pixel(8, 1));
+		assert_eq!(Some(false), copy.get_pixel(0, 0));
+	}
+}
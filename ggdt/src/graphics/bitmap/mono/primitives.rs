@@ -0,0 +1,291 @@
+use crate::graphics::bitmap::shapes::{for_each_circle_point, for_each_filled_circle_span, for_each_line_point};
+use crate::graphics::{Bitmap, Character, Font, FontRenderOpts, MonoBitmap};
+use crate::math::Rect;
+
+impl MonoBitmap {
+	/// Fills the entire bitmap with the given color.
+	pub fn clear(&mut self, color: bool) {
+		for y in 0..self.height as i32 {
+			self.fill_span(0, y, self.width, color);
+		}
+	}
+
+	/// Sets the pixel at the given coordinates to the color specified. If the coordinates lie
+	/// outside of the bitmaps clipping region, no pixels will be changed.
+	#[inline]
+	pub fn set_pixel(&mut self, x: i32, y: i32, color: bool) {
+		if self.is_xy_visible(x, y) {
+			unsafe { self.set_pixel_unchecked(x, y, color) };
+		}
+	}
+
+	/// Sets the pixel at the given coordinates to the color specified. The coordinates are not
+	/// checked for validity, so it is up to you to ensure they lie within the bounds of the
+	/// bitmap.
+	///
+	/// # Safety
+	///
+	/// The coordinates must lie within the bounds of the bitmap.
+	#[inline]
+	pub unsafe fn set_pixel_unchecked(&mut self, x: i32, y: i32, color: bool) {
+		let (offset, mask) = self.get_offset_and_mask_to_xy(x, y);
+		let byte = self.pixels.get_unchecked_mut(offset);
+		if color {
+			*byte |= mask;
+		} else {
+			*byte &= !mask;
+		}
+	}
+
+	/// Gets the pixel at the given coordinates. If the coordinates lie outside of the bitmaps
+	/// clipping region, None is returned.
+	#[inline]
+	pub fn get_pixel(&self, x: i32, y: i32) -> Option<bool> {
+		if self.is_xy_visible(x, y) {
+			Some(unsafe { self.get_pixel_unchecked(x, y) })
+		} else {
+			None
+		}
+	}
+
+	/// Gets the pixel at the given coordinates. The coordinates are not checked for validity, so
+	/// it is up to you to ensure they lie within the bounds of the bitmap.
+	///
+	/// # Safety
+	///
+	/// The coordinates must lie within the bounds of the bitmap.
+	#[inline]
+	pub unsafe fn get_pixel_unchecked(&self, x: i32, y: i32) -> bool {
+		let (offset, mask) = self.get_offset_and_mask_to_xy(x, y);
+		self.pixels.get_unchecked(offset) & mask != 0
+	}
+
+	/// Renders a single character using the font given.
+	pub fn print_char<T: Font>(&mut self, ch: char, x: i32, y: i32, opts: FontRenderOpts<bool>, font: &T) {
+		// fonts only know how to draw themselves onto a Bitmap, so the character's shape is drawn onto a temporary
+		// unpacked bitmap first, and then used as a mask to draw the character in the requested color
+		let (mask_opts, color) = match opts {
+			FontRenderOpts::Color(color) => (FontRenderOpts::Color(true), color),
+			FontRenderOpts::None => (FontRenderOpts::None, true),
+		};
+		let character = font.character(ch);
+		let bounds = character.bounds();
+		if let Ok(mut mask) = Bitmap::internal_new(bounds.width, bounds.height, false) {
+			character.draw(&mut mask, 0, 0, mask_opts);
+			for char_y in 0..bounds.height as i32 {
+				for char_x in 0..bounds.width as i32 {
+					if let Some(true) = mask.get_pixel(char_x, char_y) {
+						self.set_pixel(x + char_x, y + char_y, color);
+					}
+				}
+			}
+		}
+	}
+
+	/// Renders the string of text using the font given.
+	pub fn print_string<T: Font>(&mut self, text: &str, x: i32, y: i32, opts: FontRenderOpts<bool>, font: &T) {
+		let mut current_x = x;
+		let mut current_y = y;
+		for ch in text.chars() {
+			match ch {
+				' ' => current_x += font.space_width() as i32,
+				'\n' => {
+					current_x = x;
+					current_y += font.line_height() as i32
+				}
+				'\r' => (),
+				otherwise => {
+					self.print_char(otherwise, current_x, current_y, opts, font);
+					current_x += font.character(otherwise).bounds().width as i32;
+				}
+			}
+		}
+	}
+
+	/// Draws a line from x1,y1 to x2,y2.
+	pub fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: bool) {
+		for_each_line_point(x1, y1, x2, y2, |x, y| self.set_pixel(x, y, color));
+	}
+
+	/// Draws a horizontal line from x1,y to x2,y.
+	pub fn horiz_line(&mut self, x1: i32, x2: i32, y: i32, color: bool) {
+		let mut region = Rect::from_coords(x1, y, x2, y);
+		if region.clamp_to(&self.clip_region) {
+			self.fill_span(region.x, region.y, region.width, color);
+		}
+	}
+
+	/// Draws a vertical line from x,y1 to x,y2.
+	pub fn vert_line(&mut self, x: i32, y1: i32, y2: i32, color: bool) {
+		let mut region = Rect::from_coords(x, y1, x, y2);
+		if region.clamp_to(&self.clip_region) {
+			for y in region.y..region.y + region.height as i32 {
+				unsafe { self.set_pixel_unchecked(region.x, y, color) };
+			}
+		}
+	}
+
+	/// Draws an empty box (rectangle) using the points x1,y1 and x2,y2 to form the box to be
+	/// drawn, assuming they are specifying the top-left and bottom-right corners respectively.
+	pub fn rect(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: bool) {
+		let rect = Rect::from_coords(x1, y1, x2, y2);
+		self.horiz_line(rect.x, rect.right(), rect.y, color);
+		self.horiz_line(rect.x, rect.right(), rect.bottom(), color);
+		if rect.height > 2 {
+			self.vert_line(rect.x, rect.y + 1, rect.bottom() - 1, color);
+			self.vert_line(rect.right(), rect.y + 1, rect.bottom() - 1, color);
+		}
+	}
+
+	/// Draws a filled box (rectangle) using the points x1,y1 and x2,y2 to form the box to be
+	/// drawn, assuming they are specifying the top-left and bottom-right corners respectively.
+	pub fn filled_rect(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: bool) {
+		let mut region = Rect::from_coords(x1, y1, x2, y2);
+		if region.clamp_to(&self.clip_region) {
+			for y in region.y..region.y + region.height as i32 {
+				self.fill_span(region.x, y, region.width, color);
+			}
+		}
+	}
+
+	/// Draws the outline of a circle formed by the center point and radius given.
+	pub fn circle(&mut self, center_x: i32, center_y: i32, radius: u32, color: bool) {
+		for_each_circle_point(center_x, center_y, radius, |x, y| self.set_pixel(x, y, color));
+	}
+
+	/// Draws a filled circle formed by the center point and radius given.
+	pub fn filled_circle(&mut self, center_x: i32, center_y: i32, radius: u32, color: bool) {
+		for_each_filled_circle_span(center_x, center_y, radius, |left, right, y| {
+			self.horiz_line(left, right, y, color)
+		});
+	}
+
+	/// Sets or clears a horizontal run of pixels a whole byte at a time where possible. The span
+	/// must already lie entirely within the bitmap's bounds.
+	fn fill_span(&mut self, x: i32, y: i32, width: u32, color: bool) {
+		let row_offset = y as usize * self.stride;
+		let mut x = x as usize;
+		let end = x + width as usize;
+		while x < end {
+			let bit = x % 8;
+			let count = (8 - bit).min(end - x);
+			let mask = (0xffu8 >> bit) & !0xffu8.checked_shr((bit + count) as u32).unwrap_or(0);
+			let byte = &mut self.pixels[row_offset + x / 8];
+			if color {
+				*byte |= mask;
+			} else {
+				*byte &= !mask;
+			}
+			x += count;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::graphics::BitmaskFont;
+
+	use super::*;
+
+	#[test]
+	pub fn set_and_get_pixel() {
+		let mut bmp = MonoBitmap::new(10, 2).unwrap();
+
+		assert_eq!(None, bmp.get_pixel(-1, 0));
+		assert_eq!(None, bmp.get_pixel(10, 0));
+
+		bmp.set_pixel(0, 0, true);
+		bmp.set_pixel(7, 0, true);
+		bmp.set_pixel(8, 1, true);
+		bmp.set_pixel(10, 1, true);
+		assert_eq!(&[0b10000001, 0b00000000, 0b00000000, 0b10000000], bmp.bytes());
+		assert_eq!(Some(true), bmp.get_pixel(7, 0));
+
+		bmp.set_pixel(7, 0, false);
+		assert_eq!(&[0b10000000, 0b00000000, 0b00000000, 0b10000000], bmp.bytes());
+
+		bmp.set_clip_region(&Rect::new(1, 0, 8, 2));
+		bmp.set_pixel(0, 1, true);
+		assert_eq!(None, bmp.get_pixel(0, 0));
+		assert_eq!(&[0b10000000, 0b00000000, 0b00000000, 0b10000000], bmp.bytes());
+	}
+
+	#[test]
+	pub fn clear_and_spans() {
+		let mut bmp = MonoBitmap::new(20, 3).unwrap();
+		bmp.clear(true);
+		#[rustfmt::skip]
+		assert_eq!(
+			&[
+				0b11111111, 0b11111111, 0b11110000,
+				0b11111111, 0b11111111, 0b11110000,
+				0b11111111, 0b11111111, 0b11110000,
+			],
+			bmp.bytes()
+		);
+
+		bmp.clear(false);
+		bmp.horiz_line(3, 17, 0, true);
+		bmp.horiz_line(-5, 2, 1, true);
+		bmp.horiz_line(9, 12, 2, true);
+		#[rustfmt::skip]
+		assert_eq!(
+			&[
+				0b00011111, 0b11111111, 0b11000000,
+				0b11100000, 0b00000000, 0b00000000,
+				0b00000000, 0b01111000, 0b00000000,
+			],
+			bmp.bytes()
+		);
+
+		bmp.filled_rect(2, 0, 12, 2, false);
+		bmp.vert_line(19, -1, 1, true);
+		#[rustfmt::skip]
+		assert_eq!(
+			&[
+				0b00000000, 0b00000111, 0b11010000,
+				0b11000000, 0b00000000, 0b00010000,
+				0b00000000, 0b00000000, 0b00000000,
+			],
+			bmp.bytes()
+		);
+	}
+
+	#[test]
+	pub fn shapes_match_indexed_bitmap() {
+		let mut bmp = MonoBitmap::new(40, 30).unwrap();
+		let mut indexed = crate::graphics::IndexedBitmap::new(40, 30).unwrap();
+
+		bmp.line(-3, 2, 35, 28, true);
+		indexed.line(-3, 2, 35, 28, 1);
+		bmp.rect(5, 5, 45, 20, true);
+		indexed.rect(5, 5, 45, 20, 1);
+		bmp.circle(20, 15, 9, true);
+		indexed.circle(20, 15, 9, 1);
+		bmp.filled_circle(10, 22, 5, true);
+		indexed.filled_circle(10, 22, 5, 1);
+		bmp.filled_rect(30, 1, 33, 3, true);
+		indexed.filled_rect(30, 1, 33, 3, 1);
+
+		let font = BitmaskFont::new_vga_font().unwrap();
+		bmp.print_string("Hi!", 1, 1, FontRenderOpts::Color(true), &font);
+		indexed.print_string("Hi!", 1, 1, FontRenderOpts::Color(1), &font);
+
+		assert_eq!(indexed, bmp.to_indexed(1, 0));
+	}
+
+	#[test]
+	pub fn print_char_uses_color() {
+		let font = BitmaskFont::new_vga_font().unwrap();
+		let mut expected = MonoBitmap::new(8, 8).unwrap();
+		expected.print_char('A', 0, 0, FontRenderOpts::Color(true), &font);
+		assert!(expected.bytes().iter().any(|byte| *byte != 0));
+
+		// drawing with a color of false clears only the pixels of the glyph
+		let mut bmp = MonoBitmap::new(8, 8).unwrap();
+		bmp.clear(true);
+		bmp.print_char('A', 0, 0, FontRenderOpts::Color(false), &font);
+		let inverted = expected.bytes().iter().map(|byte| !byte).collect::<Vec<u8>>();
+		assert_eq!(&inverted[..], bmp.bytes());
+	}
+}
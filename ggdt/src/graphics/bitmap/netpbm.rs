@@ -0,0 +1,630 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use byteorder::{ReadBytesExt, WriteBytesExt};
+use thiserror::Error;
+
+use crate::graphics::{IndexedBitmap, Palette, RgbaBitmap, RGBA};
+
+// the netpbm spec recommends that lines in the ascii formats be no longer than this
+const NETPBM_MAX_ASCII_LINE_LENGTH: usize = 70;
+
+#[derive(Error, Debug)]
+pub enum NetpbmError {
+	#[error("Bad or unsupported Netpbm file: {0}")]
+	BadFile(String),
+
+	#[error("Netpbm I/O error")]
+	IOError(#[from] std::io::Error),
+}
+
+/// The Netpbm formats that bitmaps can be saved as. PBM, PGM and PPM can each be written in either their binary or
+/// ASCII ("plain") variants.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum NetpbmFormat {
+	/// P4: 1-bit black and white. Pixels are thresholded on their luminance.
+	Pbm,
+	/// P1: 1-bit black and white, written as ASCII.
+	PbmAscii,
+	/// P5: 8-bit grayscale.
+	Pgm,
+	/// P2: 8-bit grayscale, written as ASCII.
+	PgmAscii,
+	/// P6: 24-bit color, with no alpha channel.
+	Ppm,
+	/// P3: 24-bit color, with no alpha channel, written as ASCII.
+	PpmAscii,
+	/// P7: 32-bit color, with an alpha channel.
+	Pam,
+}
+
+impl NetpbmFormat {
+	fn magic(&self) -> &'static [u8; 2] {
+		use NetpbmFormat::*;
+		match self {
+			PbmAscii => b"P1",
+			PgmAscii => b"P2",
+			PpmAscii => b"P3",
+			Pbm => b"P4",
+			Pgm => b"P5",
+			Ppm => b"P6",
+			Pam => b"P7",
+		}
+	}
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum TupleType {
+	/// 0 is black, 1 is white. Note that this is the opposite of how PBM files store their pixels.
+	BlackAndWhite,
+	Grayscale,
+	Rgb,
+}
+
+/// Raw decoded samples of any of the Netpbm formats, which are normalized here into the same form as PAM files.
+struct DecodedNetpbm {
+	width: u32,
+	height: u32,
+	tuple_type: TupleType,
+	has_alpha: bool,
+	max_value: u32,
+	samples: Vec<u16>,
+}
+
+impl DecodedNetpbm {
+	fn depth(&self) -> usize {
+		let depth = match self.tuple_type {
+			TupleType::BlackAndWhite | TupleType::Grayscale => 1,
+			TupleType::Rgb => 3,
+		};
+		if self.has_alpha {
+			depth + 1
+		} else {
+			depth
+		}
+	}
+
+	#[inline]
+	fn scale(&self, sample: u16) -> u8 {
+		let max_value = self.max_value;
+		(((sample as u32).min(max_value) * 255 + max_value / 2) / max_value) as u8
+	}
+
+	fn to_rgba(&self) -> RgbaBitmap {
+		let mut bitmap = RgbaBitmap::new(self.width, self.height).unwrap();
+		let depth = self.depth();
+		for (pixel, samples) in bitmap.pixels_mut().iter_mut().zip(self.samples.chunks_exact(depth)) {
+			let alpha = if self.has_alpha { self.scale(samples[depth - 1]) } else { 255 };
+			*pixel = match self.tuple_type {
+				TupleType::BlackAndWhite | TupleType::Grayscale => {
+					let value = self.scale(samples[0]);
+					RGBA::from_rgba([value, value, value, alpha])
+				}
+				TupleType::Rgb => {
+					RGBA::from_rgba([self.scale(samples[0]), self.scale(samples[1]), self.scale(samples[2]), alpha])
+				}
+			};
+		}
+		bitmap
+	}
+
+	fn to_indexed(&self) -> Result<(IndexedBitmap, Palette), NetpbmError> {
+		if self.tuple_type == TupleType::Rgb || self.has_alpha {
+			return Err(NetpbmError::BadFile(String::from(
+				"Only grayscale or black and white files with no alpha channel can be loaded into an IndexedBitmap",
+			)));
+		}
+		let mut bitmap = IndexedBitmap::new(self.width, self.height).unwrap();
+		for (pixel, &sample) in bitmap.pixels_mut().iter_mut().zip(self.samples.iter()) {
+			*pixel = self.scale(sample);
+		}
+		let mut palette = Palette::new();
+		for i in 0..=255 {
+			palette[i] = RGBA::from_rgb([i, i, i]);
+		}
+		Ok((bitmap, palette))
+	}
+}
+
+/// Returns the number of pixels in an image with the given dimensions, if they are within the supported limits.
+fn num_pixels(width: u32, height: u32) -> Result<usize, NetpbmError> {
+	match (width as usize).checked_mul(height as usize) {
+		Some(num_pixels) if num_pixels > 0 && num_pixels <= super::MAX_LOADED_PIXELS => Ok(num_pixels),
+		_ => Err(NetpbmError::BadFile(format!("Invalid image dimensions: {}x{}", width, height))),
+	}
+}
+
+fn num_samples(num_pixels: usize, depth: usize) -> Result<usize, NetpbmError> {
+	num_pixels.checked_mul(depth).ok_or_else(|| NetpbmError::BadFile(String::from("Invalid image dimensions")))
+}
+
+struct NetpbmParser {
+	data: Vec<u8>,
+	position: usize,
+}
+
+impl NetpbmParser {
+	fn eof() -> NetpbmError {
+		NetpbmError::BadFile(String::from("Unexpected end of file"))
+	}
+
+	fn skip_whitespace_and_comments(&mut self) {
+		while let Some(&byte) = self.data.get(self.position) {
+			if byte == b'#' {
+				while self.data.get(self.position).map_or(false, |&byte| byte != b'\n' && byte != b'\r') {
+					self.position += 1;
+				}
+			} else if byte.is_ascii_whitespace() {
+				self.position += 1;
+			} else {
+				break;
+			}
+		}
+	}
+
+	fn read_token(&mut self) -> Result<&[u8], NetpbmError> {
+		self.skip_whitespace_and_comments();
+		let start = self.position;
+		while self.data.get(self.position).map_or(false, |byte| !byte.is_ascii_whitespace() && *byte != b'#') {
+			self.position += 1;
+		}
+		if start == self.position {
+			return Err(Self::eof());
+		}
+		Ok(&self.data[start..self.position])
+	}
+
+	fn read_number(&mut self) -> Result<u32, NetpbmError> {
+		let token = self.read_token()?;
+		std::str::from_utf8(token).ok().and_then(|token| token.parse().ok()).ok_or_else(|| {
+			NetpbmError::BadFile(format!("Expected a number, found: {}", String::from_utf8_lossy(token)))
+		})
+	}
+
+	fn read_byte(&mut self) -> Result<u8, NetpbmError> {
+		let byte = *self.data.get(self.position).ok_or_else(Self::eof)?;
+		self.position += 1;
+		Ok(byte)
+	}
+
+	/// Skips the single whitespace character that separates the header from the raster in the binary formats.
+	fn skip_raster_separator(&mut self) -> Result<(), NetpbmError> {
+		if !self.read_byte()?.is_ascii_whitespace() {
+			return Err(NetpbmError::BadFile(String::from("Expected whitespace after the header")));
+		}
+		Ok(())
+	}
+
+	/// Returns a vector with room for the given number of samples, or as many as could be left in the file.
+	fn sample_buffer(&self, count: usize) -> Vec<u16> {
+		Vec::with_capacity(count.min(self.data.len().saturating_sub(self.position)))
+	}
+
+	fn read_binary_samples(&mut self, count: usize, max_value: u32) -> Result<Vec<u16>, NetpbmError> {
+		let mut samples = self.sample_buffer(count);
+		for _ in 0..count {
+			samples.push(if max_value > 255 {
+				u16::from_be_bytes([self.read_byte()?, self.read_byte()?])
+			} else {
+				self.read_byte()? as u16
+			});
+		}
+		Ok(samples)
+	}
+
+	fn read_ascii_samples(&mut self, count: usize, max_value: u32) -> Result<Vec<u16>, NetpbmError> {
+		let mut samples = self.sample_buffer(count);
+		for _ in 0..count {
+			let sample = self.read_number()?;
+			if sample > max_value {
+				return Err(NetpbmError::BadFile(format!("Sample value {} is larger than the maximum value", sample)));
+			}
+			samples.push(sample as u16);
+		}
+		Ok(samples)
+	}
+
+	fn read_dimensions(&mut self) -> Result<(u32, u32, usize), NetpbmError> {
+		let width = self.read_number()?;
+		let height = self.read_number()?;
+		let num_pixels = num_pixels(width, height)?;
+		Ok((width, height, num_pixels))
+	}
+
+	fn read_max_value(&mut self) -> Result<u32, NetpbmError> {
+		let max_value = self.read_number()?;
+		if max_value == 0 || max_value > u16::MAX as u32 {
+			return Err(NetpbmError::BadFile(format!("Unsupported maximum value: {}", max_value)));
+		}
+		Ok(max_value)
+	}
+
+	fn read_pbm(&mut self, binary: bool) -> Result<DecodedNetpbm, NetpbmError> {
+		let (width, height, num_pixels) = self.read_dimensions()?;
+		let mut samples = self.sample_buffer(num_pixels);
+		if binary {
+			self.skip_raster_separator()?;
+			// each row is padded out to a whole number of bytes
+			for _ in 0..height {
+				let mut byte = 0;
+				for x in 0..width {
+					if x % 8 == 0 {
+						byte = self.read_byte()?;
+					}
+					samples.push(((byte >> (7 - (x % 8))) & 1) as u16);
+				}
+			}
+		} else {
+			// pixels in plain pbm files don't need to be separated by whitespace
+			for _ in 0..num_pixels {
+				self.skip_whitespace_and_comments();
+				samples.push(match self.read_byte()? {
+					b'0' => 0,
+					b'1' => 1,
+					other => return Err(NetpbmError::BadFile(format!("Unexpected PBM pixel: {}", other as char))),
+				});
+			}
+		}
+		// pbm files use 1 for black, unlike everything else
+		samples.iter_mut().for_each(|sample| *sample = 1 - *sample);
+		Ok(DecodedNetpbm {
+			width,
+			height,
+			tuple_type: TupleType::BlackAndWhite,
+			has_alpha: false,
+			max_value: 1,
+			samples,
+		})
+	}
+
+	fn read_pgm_or_ppm(&mut self, tuple_type: TupleType, binary: bool) -> Result<DecodedNetpbm, NetpbmError> {
+		let (width, height, num_pixels) = self.read_dimensions()?;
+		let max_value = self.read_max_value()?;
+		let count = num_samples(num_pixels, if tuple_type == TupleType::Rgb { 3 } else { 1 })?;
+		let samples = if binary {
+			self.skip_raster_separator()?;
+			self.read_binary_samples(count, max_value)?
+		} else {
+			self.read_ascii_samples(count, max_value)?
+		};
+		Ok(DecodedNetpbm { width, height, tuple_type, has_alpha: false, max_value, samples })
+	}
+
+	fn read_pam(&mut self) -> Result<DecodedNetpbm, NetpbmError> {
+		let mut width = None;
+		let mut height = None;
+		let mut depth = None;
+		let mut max_value = None;
+		let mut tuple_type = None;
+
+		loop {
+			let token = self.read_token()?.to_vec();
+			match token.as_slice() {
+				b"ENDHDR" => break,
+				b"WIDTH" => width = Some(self.read_number()?),
+				b"HEIGHT" => height = Some(self.read_number()?),
+				b"DEPTH" => depth = Some(self.read_number()?),
+				b"MAXVAL" => max_value = Some(self.read_max_value()?),
+				b"TUPLTYPE" => tuple_type = Some(self.read_token()?.to_vec()),
+				_ => {
+					return Err(NetpbmError::BadFile(format!(
+						"Unknown PAM header: {}",
+						String::from_utf8_lossy(&token)
+					)))
+				}
+			}
+		}
+		self.skip_raster_separator()?;
+
+		let missing = |name: &str| NetpbmError::BadFile(format!("PAM header is missing {}", name));
+		let width = width.ok_or_else(|| missing("WIDTH"))?;
+		let height = height.ok_or_else(|| missing("HEIGHT"))?;
+		let depth = depth.ok_or_else(|| missing("DEPTH"))?;
+		let max_value = max_value.ok_or_else(|| missing("MAXVAL"))?;
+		let num_pixels = num_pixels(width, height)?;
+
+		// the tuple type is optional, in which case we go by the depth alone
+		let (tuple_type, has_alpha) = match tuple_type.as_deref() {
+			Some(b"BLACKANDWHITE") => (TupleType::BlackAndWhite, false),
+			Some(b"BLACKANDWHITE_ALPHA") => (TupleType::BlackAndWhite, true),
+			Some(b"GRAYSCALE") => (TupleType::Grayscale, false),
+			Some(b"GRAYSCALE_ALPHA") => (TupleType::Grayscale, true),
+			Some(b"RGB") => (TupleType::Rgb, false),
+			Some(b"RGB_ALPHA") => (TupleType::Rgb, true),
+			None => match depth {
+				1 => (TupleType::Grayscale, false),
+				2 => (TupleType::Grayscale, true),
+				3 => (TupleType::Rgb, false),
+				4 => (TupleType::Rgb, true),
+				_ => return Err(NetpbmError::BadFile(format!("Unsupported PAM depth: {}", depth))),
+			},
+			Some(other) => {
+				return Err(NetpbmError::BadFile(format!(
+					"Unsupported PAM tuple type: {}",
+					String::from_utf8_lossy(other)
+				)))
+			}
+		};
+
+		let mut decoded = DecodedNetpbm { width, height, tuple_type, has_alpha, max_value, samples: Vec::new() };
+		if decoded.depth() != depth as usize {
+			return Err(NetpbmError::BadFile(String::from("PAM depth does not match the tuple type")));
+		}
+		decoded.samples = self.read_binary_samples(num_samples(num_pixels, depth as usize)?, max_value)?;
+		Ok(decoded)
+	}
+}
+
+fn load_netpbm_bytes<T: ReadBytesExt>(reader: &mut T) -> Result<DecodedNetpbm, NetpbmError> {
+	let mut data = Vec::new();
+	reader.read_to_end(&mut data)?;
+	let mut parser = NetpbmParser { data, position: 0 };
+
+	if parser.read_byte()? != b'P' {
+		return Err(NetpbmError::BadFile(String::from("Unexpected magic number, probably not a Netpbm file")));
+	}
+	match parser.read_byte()? {
+		b'1' => parser.read_pbm(false),
+		b'2' => parser.read_pgm_or_ppm(TupleType::Grayscale, false),
+		b'3' => parser.read_pgm_or_ppm(TupleType::Rgb, false),
+		b'4' => parser.read_pbm(true),
+		b'5' => parser.read_pgm_or_ppm(TupleType::Grayscale, true),
+		b'6' => parser.read_pgm_or_ppm(TupleType::Rgb, true),
+		b'7' => parser.read_pam(),
+		other => Err(NetpbmError::BadFile(format!("Unsupported Netpbm format: P{}", other as char))),
+	}
+}
+
+/// Writes out ASCII samples, separated by spaces and wrapped so that lines stay within the recommended length.
+fn write_ascii_samples<T: WriteBytesExt>(writer: &mut T, samples: &[u8], separator: &str) -> Result<(), NetpbmError> {
+	let mut line = String::new();
+	for sample in samples.iter() {
+		let value = sample.to_string();
+		if !line.is_empty() && line.len() + separator.len() + value.len() > NETPBM_MAX_ASCII_LINE_LENGTH {
+			writeln!(writer, "{}", line)?;
+			line.clear();
+		}
+		if !line.is_empty() {
+			line.push_str(separator);
+		}
+		line.push_str(&value);
+	}
+	writeln!(writer, "{}", line)?;
+	Ok(())
+}
+
+impl IndexedBitmap {
+	/// Loads a grayscale or black and white Netpbm file (PBM, PGM, or a PAM with a grayscale or black and white
+	/// tuple type). Gray levels are scaled to 0-255 and used as the color indices directly. The returned palette is
+	/// a matching grayscale ramp.
+	pub fn load_netpbm_bytes<T: ReadBytesExt>(reader: &mut T) -> Result<(IndexedBitmap, Palette), NetpbmError> {
+		load_netpbm_bytes(reader)?.to_indexed()
+	}
+
+	pub fn load_netpbm_file(path: &Path) -> Result<(IndexedBitmap, Palette), NetpbmError> {
+		let f = File::open(path)?;
+		let mut reader = BufReader::new(f);
+		Self::load_netpbm_bytes(&mut reader)
+	}
+}
+
+impl RgbaBitmap {
+	/// Loads any Netpbm file (PBM, PGM, PPM or PAM, in either their binary or ASCII variants). Samples with a
+	/// maximum value other than 255 are scaled to 0-255.
+	pub fn load_netpbm_bytes<T: ReadBytesExt>(reader: &mut T) -> Result<RgbaBitmap, NetpbmError> {
+		Ok(load_netpbm_bytes(reader)?.to_rgba())
+	}
+
+	pub fn load_netpbm_file(path: &Path) -> Result<RgbaBitmap, NetpbmError> {
+		let f = File::open(path)?;
+		let mut reader = BufReader::new(f);
+		Self::load_netpbm_bytes(&mut reader)
+	}
+
+	pub fn to_netpbm_bytes<T: WriteBytesExt>(&self, writer: &mut T, format: NetpbmFormat) -> Result<(), NetpbmError> {
+		use NetpbmFormat::*;
+
+		let width = self.width() as usize;
+		let height = self.height() as usize;
+		writer.write_all(format.magic())?;
+		match format {
+			Pbm | PbmAscii => writeln!(writer, "\n{} {}", width, height)?,
+			Pgm | PgmAscii | Ppm | PpmAscii => writeln!(writer, "\n{} {}\n255", width, height)?,
+			Pam => writeln!(
+				writer,
+				"\nWIDTH {}\nHEIGHT {}\nDEPTH 4\nMAXVAL 255\nTUPLTYPE RGB_ALPHA\nENDHDR",
+				width, height
+			)?,
+		}
+
+		let pixels = self.pixels();
+		match format {
+			Pbm | PbmAscii => {
+				// 1 is black
+				let bits: Vec<u8> = pixels.iter().map(|pixel| (pixel.greyscale() < 128) as u8).collect();
+				if format == Pbm {
+					for row in bits.chunks_exact(width) {
+						for byte_bits in row.chunks(8) {
+							let byte = byte_bits.iter().enumerate().fold(0, |byte, (i, bit)| byte | (bit << (7 - i)));
+							writer.write_u8(byte)?;
+						}
+					}
+				} else {
+					for row in bits.chunks_exact(width) {
+						write_ascii_samples(writer, row, "")?;
+					}
+				}
+			}
+			Pgm | PgmAscii => {
+				let values: Vec<u8> = pixels.iter().map(|pixel| pixel.greyscale()).collect();
+				if format == Pgm {
+					writer.write_all(&values)?;
+				} else {
+					write_ascii_samples(writer, &values, " ")?;
+				}
+			}
+			Ppm | PpmAscii => {
+				let values: Vec<u8> = pixels.iter().flat_map(|pixel| [pixel.r(), pixel.g(), pixel.b()]).collect();
+				if format == Ppm {
+					writer.write_all(&values)?;
+				} else {
+					write_ascii_samples(writer, &values, " ")?;
+				}
+			}
+			Pam => {
+				for pixel in pixels.iter() {
+					writer.write_all(&pixel.to_array())?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	pub fn to_netpbm_file(&self, path: &Path, format: NetpbmFormat) -> Result<(), NetpbmError> {
+		let f = File::create(path)?;
+		let mut writer = BufWriter::new(f);
+		self.to_netpbm_bytes(&mut writer, format)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::PathBuf;
+
+	use claim::*;
+	use tempfile::TempDir;
+
+	use crate::tests::{load_raw_indexed, load_raw_rgba};
+
+	use super::*;
+
+	const BASE_PATH: &str = "./test-assets/netpbm/";
+
+	fn test_file(file: &Path) -> PathBuf {
+		PathBuf::from(BASE_PATH).join(file)
+	}
+
+	#[test]
+	pub fn load_rgba() -> Result<(), NetpbmError> {
+		for (file, ref_file) in [
+			("p1.pbm", "bw_rgba.bin"),
+			("p4.pbm", "bw_rgba.bin"),
+			("p2.pgm", "gray_rgba.bin"),
+			("p5.pgm", "gray_rgba.bin"),
+			("p5_16bit.pgm", "gray16_rgba.bin"),
+			("p3.ppm", "rgb_rgba.bin"),
+			("p6.ppm", "rgb_rgba.bin"),
+			("p6_maxval31.ppm", "rgb31_rgba.bin"),
+			("p7_bw.pam", "bw_rgba.bin"),
+			("p7_gray.pam", "gray_rgba.bin"),
+			("p7_gray_alpha.pam", "gray_alpha_rgba.bin"),
+			("p7_rgb.pam", "rgb_rgba.bin"),
+			("p7_rgba.pam", "rgba.bin"),
+		] {
+			let ref_pixels = load_raw_rgba(test_file(Path::new(ref_file)).as_path())?;
+			let bmp = RgbaBitmap::load_netpbm_file(test_file(Path::new(file)).as_path())?;
+			assert_eq!(37, bmp.width(), "{}", file);
+			assert_eq!(23, bmp.height(), "{}", file);
+			assert_eq!(bmp.pixels(), ref_pixels.as_ref(), "{}", file);
+		}
+		Ok(())
+	}
+
+	#[test]
+	pub fn load_indexed() -> Result<(), NetpbmError> {
+		for (file, ref_file) in [
+			("p1.pbm", "bw.bin"),
+			("p4.pbm", "bw.bin"),
+			("p2.pgm", "gray.bin"),
+			("p5.pgm", "gray.bin"),
+			("p5_16bit.pgm", "gray16.bin"),
+			("p7_bw.pam", "bw.bin"),
+			("p7_gray.pam", "gray.bin"),
+		] {
+			let ref_pixels = load_raw_indexed(test_file(Path::new(ref_file)).as_path())?;
+			let (bmp, palette) = IndexedBitmap::load_netpbm_file(test_file(Path::new(file)).as_path())?;
+			assert_eq!(37, bmp.width(), "{}", file);
+			assert_eq!(23, bmp.height(), "{}", file);
+			assert_eq!(bmp.pixels(), ref_pixels.as_ref(), "{}", file);
+			assert_eq!(RGBA::from_rgb([0, 0, 0]), palette[0]);
+			assert_eq!(RGBA::from_rgb([128, 128, 128]), palette[128]);
+			assert_eq!(RGBA::from_rgb([255, 255, 255]), palette[255]);
+		}
+
+		for file in ["p6.ppm", "p7_rgb.pam", "p7_gray_alpha.pam"] {
+			assert_matches!(
+				IndexedBitmap::load_netpbm_file(test_file(Path::new(file)).as_path()),
+				Err(NetpbmError::BadFile(..))
+			);
+		}
+		Ok(())
+	}
+
+	#[test]
+	pub fn load_bad_files() {
+		for bytes in [
+			&b"GIF89a"[..],
+			b"P9\n1 1\n255\n\0",
+			b"P6\n4 4\n255\n\x01\x02",
+			b"P2\n2 1\n255\n10 abc\n",
+			b"P2\n2 1\n15\n10 16\n",
+			b"P1\n0 4\n",
+			b"P5\n4294967295 4294967295\n255\n\0",
+			b"P6\n100000 100000\n255\n\0",
+			b"P4\n4294967295 2\n\0",
+			b"P7\nWIDTH 4294967295\nHEIGHT 65536\nDEPTH 4\nMAXVAL 255\nENDHDR\n\0",
+		] {
+			assert_matches!(RgbaBitmap::load_netpbm_bytes(&mut &bytes[..]), Err(NetpbmError::BadFile(..)));
+		}
+	}
+
+	#[test]
+	pub fn save_and_reload() -> Result<(), NetpbmError> {
+		let tmp_dir = TempDir::new()?;
+
+		let ref_pixels = load_raw_rgba(test_file(Path::new("rgba.bin")).as_path())?;
+		let bmp = RgbaBitmap::load_netpbm_file(test_file(Path::new("p7_rgba.pam")).as_path())?;
+
+		let opaque_pixels: Vec<RGBA> =
+			ref_pixels.iter().map(|pixel| RGBA::from_rgb([pixel.r(), pixel.g(), pixel.b()])).collect();
+		let grey_pixels: Vec<RGBA> = ref_pixels
+			.iter()
+			.map(|pixel| {
+				let value = pixel.greyscale();
+				RGBA::from_rgb([value, value, value])
+			})
+			.collect();
+		let bw_pixels: Vec<RGBA> = ref_pixels
+			.iter()
+			.map(
+				|pixel| {
+					if pixel.greyscale() < 128 {
+						RGBA::from_rgb([0, 0, 0])
+					} else {
+						RGBA::from_rgb([255, 255, 255])
+					}
+				},
+			)
+			.collect();
+
+		for (format, expected) in [
+			(NetpbmFormat::Pbm, &bw_pixels),
+			(NetpbmFormat::PbmAscii, &bw_pixels),
+			(NetpbmFormat::Pgm, &grey_pixels),
+			(NetpbmFormat::PgmAscii, &grey_pixels),
+			(NetpbmFormat::Ppm, &opaque_pixels),
+			(NetpbmFormat::PpmAscii, &opaque_pixels),
+			(NetpbmFormat::Pam, &ref_pixels.to_vec()),
+		] {
+			let save_path = tmp_dir.path().join("test_save.pnm");
+			bmp.to_netpbm_file(&save_path, format)?;
+			let reloaded_bmp = RgbaBitmap::load_netpbm_file(&save_path)?;
+			assert_eq!(37, reloaded_bmp.width(), "{:?}", format);
+			assert_eq!(23, reloaded_bmp.height(), "{:?}", format);
+			assert_eq!(reloaded_bmp.pixels(), expected.as_slice(), "{:?}", format);
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,281 @@
+use crate::math::Rect;
+
+/// How the parts of a [`NineSlice`] that lie between its corners are made to fill the space they are
+/// drawn to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum NineSliceFill {
+	/// The part is stretched (or shrunk) to exactly fill the space it is drawn to.
+	Stretch,
+	/// The part is repeated as many times as needed to fill the space it is drawn to. Repeats along the right
+	/// and bottom that do not fully fit are cut off.
+	Tile,
+}
+
+/// Describes how a source image is split into nine parts so that it can be drawn to fill a destination
+/// rect of any size. The four corners are always drawn at their original size (unless the destination
+/// is too small to fit them), while the four edges and center are stretched or tiled to fill the rest of
+/// the destination.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct NineSlice {
+	/// Width of the left column of the source image.
+	pub left: u32,
+	/// Height of the top row of the source image.
+	pub top: u32,
+	/// Width of the right column of the source image.
+	pub right: u32,
+	/// Height of the bottom row of the source image.
+	pub bottom: u32,
+	/// How the top, bottom, left and right edges are drawn.
+	pub edges: NineSliceFill,
+	/// How the center is drawn.
+	pub center: NineSliceFill,
+}
+
+impl NineSlice {
+	/// Creates a new nine-slice with the given border sizes, with the edges and center being stretched.
+	pub fn new(left: u32, top: u32, right: u32, bottom: u32) -> Self {
+		NineSlice { left, top, right, bottom, edges: NineSliceFill::Stretch, center: NineSliceFill::Stretch }
+	}
+
+	/// Creates a new nine-slice using the same border size on all four sides, with the edges and center
+	/// being stretched.
+	pub fn uniform(border: u32) -> Self {
+		Self::new(border, border, border, border)
+	}
+
+	/// Returns a copy of this nine-slice using the given methods of filling the edges and center.
+	pub fn with_fill(self, edges: NineSliceFill, center: NineSliceFill) -> Self {
+		NineSlice { edges, center, ..self }
+	}
+
+	/// Returns the source and destination rects for each of the nine parts, along with how each part should be
+	/// drawn. Parts that end up with no area on either the source or destination are not returned.
+	pub(crate) fn parts(&self, src_region: &Rect, dest_region: &Rect) -> Vec<(Rect, Rect, NineSliceFill)> {
+		let columns =
+			split_axis(src_region.x, src_region.width, dest_region.x, dest_region.width, self.left, self.right);
+		let rows =
+			split_axis(src_region.y, src_region.height, dest_region.y, dest_region.height, self.top, self.bottom);
+
+		let mut parts = Vec::with_capacity(9);
+		for (row_index, &(src_y, src_height, dest_y, dest_height)) in rows.iter().enumerate() {
+			for (column_index, &(src_x, src_width, dest_x, dest_width)) in columns.iter().enumerate() {
+				if src_width == 0 || src_height == 0 || dest_width == 0 || dest_height == 0 {
+					continue;
+				}
+				let fill = match (column_index, row_index) {
+					(1, 1) => self.center,
+					(1, _) | (_, 1) => self.edges,
+					// corners are only ever stretched when the destination is too small to fit them
+					_ => NineSliceFill::Stretch,
+				};
+				parts.push((
+					Rect::new(src_x, src_y, src_width, src_height),
+					Rect::new(dest_x, dest_y, dest_width, dest_height),
+					fill,
+				));
+			}
+		}
+		parts
+	}
+}
+
+// splits one axis of the source and destination into the leading border, middle and trailing border, returned
+// as (src_start, src_length, dest_start, dest_length) tuples. if the destination is too small to fit both
+// borders, they are shrunk proportionally and the middle is dropped.
+fn split_axis(
+	src_start: i32,
+	src_length: u32,
+	dest_start: i32,
+	dest_length: u32,
+	leading: u32,
+	trailing: u32,
+) -> [(i32, u32, i32, u32); 3] {
+	let leading = leading.min(src_length);
+	let trailing = trailing.min(src_length - leading);
+	let src_middle = src_length - leading - trailing;
+
+	let (dest_leading, dest_trailing) = if leading + trailing > dest_length {
+		let dest_leading = (leading as u64 * dest_length as u64 / (leading + trailing) as u64) as u32;
+		(dest_leading, dest_length - dest_leading)
+	} else {
+		(leading, trailing)
+	};
+	let dest_middle = dest_length - dest_leading - dest_trailing;
+
+	[
+		(src_start, leading, dest_start, dest_leading),
+		(src_start + leading as i32, src_middle, dest_start + dest_leading as i32, dest_middle),
+		(
+			src_start + (leading + src_middle) as i32,
+			trailing,
+			dest_start + (dest_leading + dest_middle) as i32,
+			dest_trailing,
+		),
+	]
+}
+
+/// Calls `tile_fn` with the source and destination rects for each repeat of the source region needed to fill
+/// the destination region, skipping any that lie entirely outside of `clip_region`. Repeats that are only
+/// partially within the destination and/or clipping region are cut down to fit, so the source and destination
+/// rects given are always the same size. The offsets scroll the repeated pattern, such that the source pixel
+/// at (`offset_x`, `offset_y`) (wrapped to the size of the source region) is drawn at the top-left of the
+/// destination region.
+pub(crate) fn for_each_tile(
+	src_region: &Rect,
+	dest_region: &Rect,
+	clip_region: &Rect,
+	offset_x: i32,
+	offset_y: i32,
+	mut tile_fn: impl FnMut(&Rect, &Rect),
+) {
+	if src_region.width == 0 || src_region.height == 0 || dest_region.width == 0 || dest_region.height == 0 {
+		return;
+	}
+
+	let mut visible_region = *dest_region;
+	if !visible_region.clamp_to(clip_region) {
+		return;
+	}
+
+	let tile_width = src_region.width as i32;
+	let tile_height = src_region.height as i32;
+	let start_x = dest_region.x - offset_x.rem_euclid(tile_width);
+	let start_y = dest_region.y - offset_y.rem_euclid(tile_height);
+
+	// skip straight to the first tiles that are actually visible
+	let first_x = start_x + (visible_region.x - start_x) / tile_width * tile_width;
+	let first_y = start_y + (visible_region.y - start_y) / tile_height * tile_height;
+
+	let mut tile_y = first_y;
+	while tile_y <= visible_region.bottom() {
+		let mut tile_x = first_x;
+		while tile_x <= visible_region.right() {
+			let mut part = Rect::new(tile_x, tile_y, src_region.width, src_region.height);
+			if part.clamp_to(&visible_region) {
+				let src_part =
+					Rect::new(src_region.x + part.x - tile_x, src_region.y + part.y - tile_y, part.width, part.height);
+				tile_fn(&src_part, &part);
+			}
+			tile_x += tile_width;
+		}
+		tile_y += tile_height;
+	}
+}
+
+/// Calls `part_fn` with the source and destination rects for each of the pieces that make up the given
+/// nine-slice when drawn to the destination region. Tiled parts are broken down into their individual repeats
+/// (see [`for_each_tile`]) so the rects given only differ in size for parts that are to be stretched.
+pub(crate) fn for_each_nine_slice_part(
+	slice: &NineSlice,
+	src_region: &Rect,
+	dest_region: &Rect,
+	clip_region: &Rect,
+	mut part_fn: impl FnMut(&Rect, &Rect),
+) {
+	for (src_part, dest_part, fill) in slice.parts(src_region, dest_region) {
+		match fill {
+			NineSliceFill::Stretch => {
+				if dest_part.overlaps(clip_region) {
+					part_fn(&src_part, &dest_part);
+				}
+			}
+			NineSliceFill::Tile => for_each_tile(&src_part, &dest_part, clip_region, 0, 0, &mut part_fn),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn collect_tiles(
+		src_region: Rect,
+		dest_region: Rect,
+		clip_region: Rect,
+		offset_x: i32,
+		offset_y: i32,
+	) -> Vec<(Rect, Rect)> {
+		let mut tiles = Vec::new();
+		for_each_tile(&src_region, &dest_region, &clip_region, offset_x, offset_y, |src, dest| {
+			tiles.push((*src, *dest))
+		});
+		tiles
+	}
+
+	#[test]
+	pub fn nine_slice_parts() {
+		let slice = NineSlice::new(2, 3, 4, 1).with_fill(NineSliceFill::Tile, NineSliceFill::Stretch);
+		let parts = slice.parts(&Rect::new(10, 20, 10, 8), &Rect::new(0, 0, 30, 20));
+		assert_eq!(9, parts.len());
+		// corners
+		assert_eq!((Rect::new(10, 20, 2, 3), Rect::new(0, 0, 2, 3), NineSliceFill::Stretch), parts[0]);
+		assert_eq!((Rect::new(16, 20, 4, 3), Rect::new(26, 0, 4, 3), NineSliceFill::Stretch), parts[2]);
+		assert_eq!((Rect::new(10, 27, 2, 1), Rect::new(0, 19, 2, 1), NineSliceFill::Stretch), parts[6]);
+		assert_eq!((Rect::new(16, 27, 4, 1), Rect::new(26, 19, 4, 1), NineSliceFill::Stretch), parts[8]);
+		// edges
+		assert_eq!((Rect::new(12, 20, 4, 3), Rect::new(2, 0, 24, 3), NineSliceFill::Tile), parts[1]);
+		assert_eq!((Rect::new(10, 23, 2, 4), Rect::new(0, 3, 2, 16), NineSliceFill::Tile), parts[3]);
+		assert_eq!((Rect::new(16, 23, 4, 4), Rect::new(26, 3, 4, 16), NineSliceFill::Tile), parts[5]);
+		assert_eq!((Rect::new(12, 27, 4, 1), Rect::new(2, 19, 24, 1), NineSliceFill::Tile), parts[7]);
+		// center
+		assert_eq!((Rect::new(12, 23, 4, 4), Rect::new(2, 3, 24, 16), NineSliceFill::Stretch), parts[4]);
+	}
+
+	#[test]
+	pub fn nine_slice_parts_with_small_destination() {
+		// the corners are shrunk to fit, and the edges and center are dropped entirely
+		let slice = NineSlice::new(4, 2, 4, 2);
+		let parts = slice.parts(&Rect::new(0, 0, 12, 6), &Rect::new(0, 0, 6, 8));
+		assert_eq!(6, parts.len());
+		assert_eq!((Rect::new(0, 0, 4, 2), Rect::new(0, 0, 3, 2), NineSliceFill::Stretch), parts[0]);
+		assert_eq!((Rect::new(8, 0, 4, 2), Rect::new(3, 0, 3, 2), NineSliceFill::Stretch), parts[1]);
+		assert_eq!((Rect::new(0, 2, 4, 2), Rect::new(0, 2, 3, 4), NineSliceFill::Stretch), parts[2]);
+		assert_eq!((Rect::new(8, 4, 4, 2), Rect::new(3, 6, 3, 2), NineSliceFill::Stretch), parts[5]);
+
+		// borders larger than the source itself are limited to the size of the source
+		let parts = NineSlice::uniform(8).parts(&Rect::new(0, 0, 12, 6), &Rect::new(0, 0, 40, 40));
+		assert_eq!(
+			vec![
+				(Rect::new(0, 0, 8, 6), Rect::new(0, 0, 8, 6), NineSliceFill::Stretch),
+				(Rect::new(8, 0, 4, 6), Rect::new(36, 0, 4, 6), NineSliceFill::Stretch),
+			],
+			parts
+		);
+	}
+
+	#[test]
+	pub fn tiles() {
+		let clip_region = Rect::new(0, 0, 100, 100);
+		let tiles = collect_tiles(Rect::new(5, 5, 4, 3), Rect::new(10, 10, 10, 5), clip_region, 0, 0);
+		assert_eq!(
+			vec![
+				(Rect::new(5, 5, 4, 3), Rect::new(10, 10, 4, 3)),
+				(Rect::new(5, 5, 4, 3), Rect::new(14, 10, 4, 3)),
+				(Rect::new(5, 5, 2, 3), Rect::new(18, 10, 2, 3)),
+				(Rect::new(5, 5, 4, 2), Rect::new(10, 13, 4, 2)),
+				(Rect::new(5, 5, 4, 2), Rect::new(14, 13, 4, 2)),
+				(Rect::new(5, 5, 2, 2), Rect::new(18, 13, 2, 2)),
+			],
+			tiles
+		);
+
+		// scrolled, with negative offsets wrapping around the same as positive ones
+		let tiles = collect_tiles(Rect::new(5, 5, 4, 3), Rect::new(10, 10, 6, 3), clip_region, 1, -1);
+		assert_eq!(
+			vec![
+				(Rect::new(6, 7, 3, 1), Rect::new(10, 10, 3, 1)),
+				(Rect::new(5, 7, 3, 1), Rect::new(13, 10, 3, 1)),
+				(Rect::new(6, 5, 3, 2), Rect::new(10, 11, 3, 2)),
+				(Rect::new(5, 5, 3, 2), Rect::new(13, 11, 3, 2)),
+			],
+			tiles
+		);
+
+		// tiles outside of the clipping region are skipped, and partially visible ones are cut down
+		let tiles = collect_tiles(Rect::new(0, 0, 4, 4), Rect::new(-20, -20, 40, 40), Rect::new(0, 0, 6, 2), 0, 0);
+		assert_eq!(
+			vec![(Rect::new(0, 0, 4, 2), Rect::new(0, 0, 4, 2)), (Rect::new(0, 0, 2, 2), Rect::new(4, 0, 2, 2))],
+			tiles
+		);
+	}
+}
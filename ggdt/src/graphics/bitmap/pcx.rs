@@ -162,15 +162,15 @@ impl IndexedBitmap {
 			}
 		}
 
-		// now read the palette data located at the end of the PCX file
+		// now read the palette data located immediately after the pixel data, at the end of the PCX file. this is
+		// read from the current position instead of seeking to the end of the stream, which may not be the end of the
+		// PCX file when it is stored within a larger file
 		// palette data should be for 256 colors, 3 bytes per color = 768 bytes
 		// the palette is preceded by a single byte, 0x0c, which we will also validate
 
-		reader.seek(SeekFrom::End(-769))?;
-
 		let palette_marker = reader.read_u8()?;
 		if palette_marker != 0x0c {
-			return Err(PcxError::BadFile(String::from("Palette not found at end of file")));
+			return Err(PcxError::BadFile(String::from("Palette not found after the image data")));
 		}
 
 		let palette = Palette::load_from_bytes(reader, PaletteFormat::Normal)?;
@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::hash::Hasher;
 use std::io;
@@ -29,6 +30,12 @@ pub enum PngError {
 	#[error("Unsupported filter: {0}")]
 	UnsupportedFilter(u8),
 
+	#[error("Too many colors to write an indexed-color PNG: {0}")]
+	TooManyColors(usize),
+
+	#[error("Invalid PNG text chunk: {0}")]
+	InvalidText(String),
+
 	#[error("PNG I/O error")]
 	IOError(#[from] std::io::Error),
 }
@@ -37,6 +44,49 @@ pub enum PngError {
 pub enum PngFormat {
 	RGB,
 	RGBA,
+	/// Indexed-color, using a palette built from the colors used in the bitmap. This requires that the bitmap uses
+	/// no more than 256 unique colors (including alpha).
+	Indexed,
+}
+
+/// The filtering method to use for each scanline when writing PNGs. Filtering makes the image data more
+/// compressible and does not affect the image itself.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PngFilter {
+	None,
+	Sub,
+	Up,
+	Average,
+	Paeth,
+	/// Selects a filter separately for each scanline, using the "minimum sum of absolute differences" heuristic
+	/// recommended by the PNG specification.
+	Adaptive,
+}
+
+/// Options that control how PNG files are written.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PngSettings {
+	/// Deflate compression level, from 0 (no compression) to 9 (best compression).
+	pub compression: u32,
+	/// The filtering method to use for each scanline.
+	pub filter: PngFilter,
+	/// For indexed-color PNGs, an optional palette index to mark as fully transparent in a tRNS chunk. Palette
+	/// entries with an alpha component of less than 255 are always written to the tRNS chunk regardless.
+	pub transparent_color: Option<u8>,
+	/// Keyword and text pairs to write out as tEXt chunks. Keywords must be 1-79 characters long and both keywords
+	/// and text must only use Latin-1 characters.
+	pub text: Vec<(String, String)>,
+}
+
+impl Default for PngSettings {
+	fn default() -> Self {
+		PngSettings {
+			compression: 6, //
+			filter: PngFilter::None,
+			transparent_color: None,
+			text: Vec::new(),
+		}
+	}
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -60,8 +110,34 @@ impl ColorFormat {
 			_ => Err(PngError::UnsupportedColorType(value)),
 		}
 	}
+
+	/// Returns the number of samples (color components) making up each pixel in this format.
+	pub fn channels(&self) -> usize {
+		use ColorFormat::*;
+		match self {
+			Grayscale => 1,
+			RGB => 3,
+			IndexedColor => 1,
+			GrayscaleAlpha => 2,
+			RGBA => 4,
+		}
+	}
+
+	/// Returns true if the given bit depth is allowed to be used with this format.
+	pub fn is_valid_bit_depth(&self, bit_depth: u8) -> bool {
+		use ColorFormat::*;
+		match self {
+			Grayscale => matches!(bit_depth, 1 | 2 | 4 | 8 | 16),
+			IndexedColor => matches!(bit_depth, 1 | 2 | 4 | 8),
+			RGB | GrayscaleAlpha | RGBA => matches!(bit_depth, 8 | 16),
+		}
+	}
 }
 
+// (x start, y start, x step, y step) of each of the 7 Adam7 interlacing passes
+const ADAM7_PASSES: [(usize, usize, usize, usize); 7] =
+	[(0, 0, 8, 8), (4, 0, 8, 8), (0, 4, 4, 8), (2, 0, 4, 4), (0, 2, 2, 4), (1, 0, 2, 2), (0, 1, 1, 2)];
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 struct ChunkHeader {
 	size: u32,
@@ -147,19 +223,6 @@ fn write_chunk<T: WriteBytesExt>(writer: &mut T, chunk_header: &ChunkHeader, dat
 	Ok(())
 }
 
-fn find_chunk<T: ReadBytesExt>(reader: &mut T, chunk_name: [u8; 4]) -> Result<ChunkHeader, PngError> {
-	loop {
-		let chunk_header = match ChunkHeader::read(reader) {
-			Ok(chunk_header) => chunk_header,
-			Err(err) => return Err(err),
-		};
-
-		if chunk_header.name == chunk_name {
-			return Ok(chunk_header);
-		}
-	}
-}
-
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Filter {
 	None = 0,
@@ -190,72 +253,152 @@ trait ScanlinePixelConverter<PixelType: Pixel> {
 
 struct ScanlineBuffer {
 	format: ColorFormat,
+	bit_depth: usize,
+	channels: usize,
 	stride: usize,
 	bpp: usize,
 	y: usize,
 	height: usize,
+	transparent_color: Option<[u16; 3]>,
 	current: Vec<u8>,
 	previous: Vec<u8>,
 }
 
 impl ScanlineBuffer {
 	pub fn new(ihdr: &ImageHeaderChunk) -> Result<Self, PngError> {
-		let bpp = match ihdr.format {
-			ColorFormat::IndexedColor => 1,
-			ColorFormat::RGB => 3,
-			ColorFormat::RGBA => 4,
-			_ => return Err(PngError::BadFile(format!("Unsupported color format: {:?}", ihdr.format))),
-		};
-		let stride = ihdr.width as usize * bpp;
+		Self::with_size(ihdr, ihdr.width as usize, ihdr.height as usize)
+	}
+
+	/// Creates a scanline buffer for an image of a different size than the one given in the image header. This is
+	/// used for each of the "sub-images" that make up an interlaced image.
+	pub fn with_size(ihdr: &ImageHeaderChunk, width: usize, height: usize) -> Result<Self, PngError> {
+		if !ihdr.format.is_valid_bit_depth(ihdr.bpp) {
+			return Err(PngError::BadFile(String::from("Unsupported color bit depth.")));
+		}
+		let bit_depth = ihdr.bpp as usize;
+		let channels = ihdr.format.channels();
+		let bits_per_pixel = bit_depth * channels;
+		// filtering works on whole bytes. for bit depths less than 8, pixels are treated as being 1 byte
+		let bpp = bits_per_pixel.div_ceil(8);
+		let stride = (width * bits_per_pixel).div_ceil(8);
 		Ok(ScanlineBuffer {
 			format: ihdr.format,
+			bit_depth,
+			channels,
 			stride,
 			bpp,
 			y: 0,
-			height: ihdr.height as usize,
+			height,
+			transparent_color: None,
 			current: vec![0u8; stride],
 			previous: vec![0u8; stride],
 		})
 	}
 
-	fn decode_byte(&mut self, filter: Filter, byte: u8, x: usize, y: usize) -> u8 {
+	/// Returns the raw value of a single sample (color component) of the pixel at the given x coordinate in the
+	/// current scanline, at the image's bit depth.
+	fn sample(&self, x: usize, channel: usize) -> u16 {
+		let index = x * self.channels + channel;
+		match self.bit_depth {
+			8 => self.current[index] as u16,
+			16 => u16::from_be_bytes([self.current[index * 2], self.current[index * 2 + 1]]),
+			bit_depth => {
+				// samples less than 8 bits are packed into bytes, leftmost pixel in the high-order bits
+				let bit_offset = index * bit_depth;
+				let shift = 8 - bit_depth - (bit_offset % 8);
+				((self.current[bit_offset / 8] >> shift) & ((1 << bit_depth) - 1) as u8) as u16
+			}
+		}
+	}
+
+	/// Scales a raw sample value at the image's bit depth to the full 8-bit range.
+	fn sample_to_8bit(&self, sample: u16) -> u8 {
+		match self.bit_depth {
+			16 => (sample >> 8) as u8,
+			8 => sample as u8,
+			bit_depth => (sample as u32 * 255 / ((1 << bit_depth) - 1)) as u8,
+		}
+	}
+
+	/// Returns the alpha value to use for the pixel with the given raw sample values, based on the color-key
+	/// transparency (if any) specified in the image's tRNS chunk.
+	fn color_key_alpha(&self, samples: [u16; 3]) -> u8 {
+		match self.transparent_color {
+			Some(transparent_color) if transparent_color == samples => 0,
+			_ => 255,
+		}
+	}
+
+	fn predict(&self, filter: Filter, x: usize, y: usize) -> u8 {
 		match filter {
-			Filter::None => byte,
-			Filter::Sub => byte.wrapping_add(if x < self.bpp { 0 } else { self.current[x - self.bpp] }), // unsigned arithmetic modulo 256
-			Filter::Up => byte.wrapping_add(if y < 1 { 0 } else { self.previous[x] }), // unsigned arithmetic modulo 256
+			Filter::None => 0,
+			Filter::Sub => {
+				if x < self.bpp {
+					0
+				} else {
+					self.current[x - self.bpp]
+				}
+			}
+			Filter::Up => {
+				if y < 1 {
+					0
+				} else {
+					self.previous[x]
+				}
+			}
 			Filter::Average => {
 				let a = if x < self.bpp { 0 } else { self.current[x - self.bpp] } as i16;
 				let b = if y < 1 { 0 } else { self.previous[x] } as i16;
-				// unsigned arithmetic modulo 256 *except* for the average calculation itself which must not overflow!
-				let average = (a + b) / 2;
-				byte.wrapping_add(average as u8)
+				// the average calculation itself must not overflow!
+				((a + b) / 2) as u8
 			}
 			Filter::Paeth => {
 				let a = if x < self.bpp { 0 } else { self.current[x - self.bpp] } as i16;
 				let b = if y < 1 { 0 } else { self.previous[x] } as i16;
 				let c = if x >= self.bpp && y >= 1 { self.previous[x - self.bpp] } else { 0 } as i16;
+				// all of this must not overflow
 				let p = a + b - c;
 				let pa = (p - a).abs();
 				let pb = (p - b).abs();
 				let pc = (p - c).abs();
-				let value = if pa <= pb && pa <= pc {
+				if pa <= pb && pa <= pc {
 					a as u8
 				} else if pb <= pc {
 					b as u8
 				} else {
 					c as u8
-				};
-				// all of the above must not overflow. however, this last part is unsigned arithmetic modulo 256
-				byte.wrapping_add(value)
+				}
 			}
 		}
 	}
 
-	fn encode_byte(&mut self, filter: Filter, byte: u8, _x: usize, _y: usize) -> u8 {
-		match filter {
-			Filter::None => byte,
-			_ => 0, // leaving out the rest for now. we hardcode usage of Filter::None when saving PNGs currently
+	fn decode_byte(&mut self, filter: Filter, byte: u8, x: usize, y: usize) -> u8 {
+		// unsigned arithmetic modulo 256
+		byte.wrapping_add(self.predict(filter, x, y))
+	}
+
+	fn encode_byte(&mut self, filter: Filter, byte: u8, x: usize, y: usize) -> u8 {
+		// unsigned arithmetic modulo 256
+		byte.wrapping_sub(self.predict(filter, x, y))
+	}
+
+	/// Picks the filter that results in the smallest sum of the encoded bytes of the current line, when each byte
+	/// is treated as a signed value.
+	fn select_filter(&mut self, y: usize) -> Filter {
+		let mut best_filter = Filter::None;
+		let mut best_sum = u64::MAX;
+		for filter in [Filter::None, Filter::Sub, Filter::Up, Filter::Average, Filter::Paeth] {
+			let mut sum = 0u64;
+			for x in 0..self.stride {
+				let encoded = self.encode_byte(filter, self.current[x], x, y);
+				sum += (encoded as i8).unsigned_abs() as u64;
+			}
+			if sum < best_sum {
+				best_sum = sum;
+				best_filter = filter;
+			}
 		}
+		best_filter
 	}
 
 	pub fn read_line<T: ReadBytesExt>(&mut self, reader: &mut T) -> Result<(), PngError> {
@@ -268,8 +411,10 @@ impl ScanlineBuffer {
 		self.y += 1;
 
 		let filter = Filter::from(reader.read_u8()?)?;
+		reader.read_exact(&mut self.current)?;
+		// decoding in-place is fine, as decoding only ever refers to bytes that come before the current one
 		for x in 0..self.stride {
-			let byte = reader.read_u8()?;
+			let byte = self.current[x];
 			let decoded = self.decode_byte(filter, byte, x, y);
 			self.current[x] = decoded;
 		}
@@ -277,22 +422,31 @@ impl ScanlineBuffer {
 		Ok(())
 	}
 
-	pub fn write_line<T: WriteBytesExt>(&mut self, filter: Filter, writer: &mut T) -> Result<(), PngError> {
+	pub fn write_line<T: WriteBytesExt>(&mut self, filter: PngFilter, writer: &mut T) -> Result<(), PngError> {
 		if self.y >= self.height {
 			return Err(PngError::IOError(io::Error::from(io::ErrorKind::UnexpectedEof)));
-		} else if self.y >= 1 {
-			self.previous.copy_from_slice(&self.current);
 		}
 		let y = self.y;
 		self.y += 1;
 
-		writer.write_u8(filter as u8)?;
+		let filter = match filter {
+			PngFilter::None => Filter::None,
+			PngFilter::Sub => Filter::Sub,
+			PngFilter::Up => Filter::Up,
+			PngFilter::Average => Filter::Average,
+			PngFilter::Paeth => Filter::Paeth,
+			PngFilter::Adaptive => self.select_filter(y),
+		};
+
+		let mut encoded = Vec::with_capacity(self.stride + 1);
+		encoded.push(filter as u8);
 		for x in 0..self.stride {
-			let byte = self.current[x];
-			let encoded = self.encode_byte(filter, byte, x, y);
-			writer.write_u8(encoded)?;
-			self.current[x] = encoded;
+			encoded.push(self.encode_byte(filter, self.current[x], x, y));
 		}
+		writer.write_all(&encoded)?;
+
+		// encoding refers to the un-encoded bytes of the previous line, so keep them around for the next line
+		self.previous.copy_from_slice(&self.current);
 
 		Ok(())
 	}
@@ -300,9 +454,8 @@ impl ScanlineBuffer {
 
 impl ScanlinePixelConverter<u8> for ScanlineBuffer {
 	fn read_pixel(&mut self, x: usize, _palette: &Option<Palette>) -> Result<u8, PngError> {
-		let offset = x * self.bpp;
 		match self.format {
-			ColorFormat::IndexedColor => Ok(self.current[offset]),
+			ColorFormat::IndexedColor => Ok(self.sample(x, 0) as u8),
 			_ => Err(PngError::BadFile(format!("Unsupported color format for this PixelReader: {:?}", self.format))),
 		}
 	}
@@ -321,10 +474,9 @@ impl ScanlinePixelConverter<u8> for ScanlineBuffer {
 
 impl ScanlinePixelConverter<RGBA> for ScanlineBuffer {
 	fn read_pixel(&mut self, x: usize, palette: &Option<Palette>) -> Result<RGBA, PngError> {
-		let offset = x * self.bpp;
 		match self.format {
 			ColorFormat::IndexedColor => {
-				let color = self.current[offset];
+				let color = self.sample(x, 0) as u8;
 				if let Some(palette) = palette {
 					Ok(palette[color])
 				} else {
@@ -333,20 +485,30 @@ impl ScanlinePixelConverter<RGBA> for ScanlineBuffer {
 					)))
 				}
 			}
+			ColorFormat::Grayscale => {
+				let sample = self.sample(x, 0);
+				let value = self.sample_to_8bit(sample);
+				Ok(RGBA::from_rgba([value, value, value, self.color_key_alpha([sample; 3])]))
+			}
+			ColorFormat::GrayscaleAlpha => {
+				let value = self.sample_to_8bit(self.sample(x, 0));
+				let a = self.sample_to_8bit(self.sample(x, 1));
+				Ok(RGBA::from_rgba([value, value, value, a]))
+			}
 			ColorFormat::RGB => {
-				let r = self.current[offset];
-				let g = self.current[offset + 1];
-				let b = self.current[offset + 2];
-				Ok(RGBA::from_rgb([r, g, b]))
+				let samples = [self.sample(x, 0), self.sample(x, 1), self.sample(x, 2)];
+				let r = self.sample_to_8bit(samples[0]);
+				let g = self.sample_to_8bit(samples[1]);
+				let b = self.sample_to_8bit(samples[2]);
+				Ok(RGBA::from_rgba([r, g, b, self.color_key_alpha(samples)]))
 			}
 			ColorFormat::RGBA => {
-				let r = self.current[offset];
-				let g = self.current[offset + 1];
-				let b = self.current[offset + 2];
-				let a = self.current[offset + 3];
+				let r = self.sample_to_8bit(self.sample(x, 0));
+				let g = self.sample_to_8bit(self.sample(x, 1));
+				let b = self.sample_to_8bit(self.sample(x, 2));
+				let a = self.sample_to_8bit(self.sample(x, 3));
 				Ok(RGBA::from_rgba([r, g, b, a]))
 			}
-			_ => Err(PngError::BadFile(format!("Unsupported color format for this PixelReader: {:?}", self.format))),
 		}
 	}
 
@@ -382,90 +544,138 @@ where
 		return Err(PngError::BadFile(String::from("Unexpected 8-byte header, probably not a PNG file")));
 	}
 
-	// get the IHDR chunk first
+	// read all of the chunks we care about up-front. ancillary chunks we don't support are skipped over.
+	// TODO: some way to read and decompress the IDAT data on the fly chunk-by-chunk, without first needing to
+	//       read in ALL of the chunks into a combined buffer? it looks like chunk boundaries just
+	//       arbitrarily cut off the deflate stream (that is, each chunk is NOT a separate deflate stream
+	//       with just more data). so we'd need some deflate decompressor that can stream its input
+	//       (compressed) byte stream too ...
 
-	let chunk_header = match find_chunk(reader, *b"IHDR") {
-		Ok(header) => header,
-		Err(PngError::IOError(io_error)) if io_error.kind() == io::ErrorKind::UnexpectedEof => {
-			return Err(PngError::BadFile(String::from("No IHDR chunk found, probably not a PNG file")));
+	let mut ihdr = None;
+	let mut palette = None;
+	let mut transparency = None;
+	let mut compressed_data = Vec::new();
+	loop {
+		let chunk_header = match ChunkHeader::read(reader) {
+			Ok(header) => header,
+			Err(PngError::IOError(io_error)) if io_error.kind() == io::ErrorKind::UnexpectedEof => break,
+			Err(err) => return Err(err),
+		};
+		let mut chunk_bytes = read_chunk_data(reader, &chunk_header)?;
+
+		match &chunk_header.name {
+			b"IHDR" => ihdr = Some(ImageHeaderChunk::read(&mut chunk_bytes.as_slice())?),
+			b"PLTE" => {
+				let num_colors = (chunk_header.size / 3) as usize;
+				palette = Some(Palette::load_num_colors_from_bytes(
+					&mut chunk_bytes.as_slice(), //
+					PaletteFormat::Normal,
+					num_colors,
+				)?);
+			}
+			b"tRNS" => transparency = Some(chunk_bytes),
+			b"IDAT" => compressed_data.append(&mut chunk_bytes),
+			b"IEND" => break,
+			_ => {}
 		}
-		Err(err) => return Err(err),
+	}
+
+	let ihdr = match ihdr {
+		Some(ihdr) => ihdr,
+		None => return Err(PngError::BadFile(String::from("No IHDR chunk found, probably not a PNG file"))),
 	};
-	let chunk_bytes = read_chunk_data(reader, &chunk_header)?;
-	let ihdr = ImageHeaderChunk::read(&mut chunk_bytes.as_slice())?;
 
-	// file format validations based on the limited subset of PNGs we will be supporting
+	// file format validations
 
-	if ihdr.bpp != 8 {
+	if !ihdr.format.is_valid_bit_depth(ihdr.bpp) {
 		return Err(PngError::BadFile(String::from("Unsupported color bit depth.")));
 	}
-	if ihdr.format != ColorFormat::IndexedColor // .
-		&& ihdr.format != ColorFormat::RGB
-		&& ihdr.format != ColorFormat::RGBA
-	{
-		return Err(PngError::BadFile(String::from("Unsupported pixel color format.")));
-	}
 	if ihdr.compression != 0 {
 		return Err(PngError::BadFile(String::from("Unsupported compression method.")));
 	}
 	if ihdr.filter != 0 {
 		return Err(PngError::BadFile(String::from("Unsupported filter method.")));
 	}
-	if ihdr.interlace != 0 {
-		return Err(PngError::BadFile(String::from("Interlaced images are not supported.")));
+	if ihdr.interlace > 1 {
+		return Err(PngError::BadFile(String::from("Unsupported interlace method.")));
 	}
 
-	// if this is an indexed-color PNG, we expect to find a PLTE chunk next (or at least before the IDAT chunks)
-
-	let palette = if ihdr.format == ColorFormat::IndexedColor {
-		let chunk_header = match find_chunk(reader, *b"PLTE") {
-			Ok(header) => header,
-			Err(PngError::IOError(io_error)) if io_error.kind() == io::ErrorKind::UnexpectedEof => {
-				return Err(PngError::BadFile(String::from("No PLTE chunk found in an indexed-color PNG")));
-			}
-			Err(err) => return Err(err),
-		};
+	// indexed-color PNGs require a palette. other formats may also have one (as a suggested palette to use), but
+	// we don't want to return it in those cases, as it is not needed to interpret the pixels
 
-		let chunk_bytes = read_chunk_data(reader, &chunk_header)?;
-		let num_colors = (chunk_header.size / 3) as usize;
-		Some(Palette::load_num_colors_from_bytes(
-			&mut chunk_bytes.as_slice(), //
-			PaletteFormat::Normal,
-			num_colors,
-		)?)
+	if ihdr.format == ColorFormat::IndexedColor {
+		if palette.is_none() {
+			return Err(PngError::BadFile(String::from("No PLTE chunk found in an indexed-color PNG")));
+		}
 	} else {
-		None
-	};
+		palette = None;
+	}
 
-	// now we're just looking for IDAT chunks. keep reading these chunks only, ignoring all others.
-	// TODO: some way to read and decompress this data on the fly chunk-by-chunk, without first needing to
-	//       read in ALL of the chunks into a combined buffer? it looks like chunk boundaries just
-	//       arbitrarily cut off the deflate stream (that is, each chunk is NOT a separate deflate stream
-	//       with just more data). so we'd need some deflate decompressor that can stream its input
-	//       (compressed) byte stream too ...
+	// tRNS chunks either provide alpha values for palette entries, or a single color (in the image's bit depth)
+	// that should be treated as fully transparent
 
-	let mut compressed_data = Vec::new();
-	loop {
-		let chunk_header = match find_chunk(reader, *b"IDAT") {
-			Ok(header) => header,
-			Err(PngError::IOError(io_error)) if io_error.kind() == io::ErrorKind::UnexpectedEof => break,
-			Err(err) => return Err(err),
+	let mut transparent_color = None;
+	if let Some(transparency) = transparency {
+		let read_sample = |index: usize| -> Result<u16, PngError> {
+			match transparency.get(index * 2..index * 2 + 2) {
+				Some(bytes) => Ok(u16::from_be_bytes([bytes[0], bytes[1]])),
+				None => Err(PngError::BadFile(String::from("tRNS chunk is too short"))),
+			}
 		};
-
-		compressed_data.append(&mut read_chunk_data(reader, &chunk_header)?);
+		match ihdr.format {
+			ColorFormat::IndexedColor => {
+				if let Some(palette) = &mut palette {
+					for (index, alpha) in transparency.iter().take(256).enumerate() {
+						palette[index as u8].set_a(*alpha);
+					}
+				}
+			}
+			ColorFormat::Grayscale => transparent_color = Some([read_sample(0)?; 3]),
+			ColorFormat::RGB => transparent_color = Some([read_sample(0)?, read_sample(1)?, read_sample(2)?]),
+			_ => return Err(PngError::BadFile(String::from("tRNS chunk is not allowed for images with alpha"))),
+		}
 	}
 
-	let mut scanline_buffer = ScanlineBuffer::new(&ihdr)?;
-
 	let mut output = Bitmap::internal_new(ihdr.width, ihdr.height, Default::default()).unwrap();
 	let mut deflater = flate2::read::ZlibDecoder::<&[u8]>::new(&compressed_data);
 
-	for y in 0..ihdr.height as usize {
-		scanline_buffer.read_line(&mut deflater)?;
-		for x in 0..ihdr.width as usize {
-			let pixel = scanline_buffer.read_pixel(x, &palette)?;
-			unsafe {
-				output.set_pixel_unchecked(x as i32, y as i32, pixel);
+	if ihdr.interlace == 0 {
+		let mut scanline_buffer = ScanlineBuffer::new(&ihdr)?;
+		scanline_buffer.transparent_color = transparent_color;
+
+		for y in 0..ihdr.height as usize {
+			scanline_buffer.read_line(&mut deflater)?;
+			for x in 0..ihdr.width as usize {
+				let pixel = scanline_buffer.read_pixel(x, &palette)?;
+				unsafe {
+					output.set_pixel_unchecked(x as i32, y as i32, pixel);
+				}
+			}
+		}
+	} else {
+		// adam7 interlacing. the image data is split into 7 passes, each of which is effectively a separate
+		// smaller image containing a subset of the final image's pixels. passes which end up with no pixels
+		// (possible with very small images) are omitted entirely
+		for (x_start, y_start, x_step, y_step) in ADAM7_PASSES.iter() {
+			let pass_width = (ihdr.width as usize).saturating_sub(*x_start).div_ceil(*x_step);
+			let pass_height = (ihdr.height as usize).saturating_sub(*y_start).div_ceil(*y_step);
+			if pass_width == 0 || pass_height == 0 {
+				continue;
+			}
+
+			let mut scanline_buffer = ScanlineBuffer::with_size(&ihdr, pass_width, pass_height)?;
+			scanline_buffer.transparent_color = transparent_color;
+
+			for pass_y in 0..pass_height {
+				scanline_buffer.read_line(&mut deflater)?;
+				let y = y_start + pass_y * y_step;
+				for pass_x in 0..pass_width {
+					let pixel = scanline_buffer.read_pixel(pass_x, &palette)?;
+					let x = x_start + pass_x * x_step;
+					unsafe {
+						output.set_pixel_unchecked(x as i32, y as i32, pixel);
+					}
+				}
 			}
 		}
 	}
@@ -473,11 +683,35 @@ where
 	Ok((output, palette))
 }
 
+fn write_text_chunk<T: WriteBytesExt>(writer: &mut T, keyword: &str, text: &str) -> Result<(), PngError> {
+	if keyword.is_empty() || keyword.chars().count() > 79 {
+		return Err(PngError::InvalidText(format!("Keyword must be 1-79 characters long: {:?}", keyword)));
+	}
+
+	// tEXt chunks are latin-1 encoded, with a null separator between the keyword and text
+	let mut chunk_bytes = Vec::with_capacity(keyword.len() + text.len() + 1);
+	for (index, string) in [keyword, text].iter().enumerate() {
+		if index > 0 {
+			chunk_bytes.push(0);
+		}
+		for c in string.chars() {
+			if c == '\0' || c as u32 > 0xff {
+				return Err(PngError::InvalidText(format!("Unsupported character {:?} in {:?}", c, string)));
+			}
+			chunk_bytes.push(c as u8);
+		}
+	}
+
+	let chunk_header = ChunkHeader { name: *b"tEXt", size: chunk_bytes.len() as u32 };
+	write_chunk(writer, &chunk_header, &chunk_bytes)
+}
+
 fn write_png_bytes<Writer, PixelType>(
 	writer: &mut Writer,
 	bitmap: &Bitmap<PixelType>,
 	format: ColorFormat,
-	palette: Option<&Palette>,
+	palette: Option<(&Palette, usize)>,
+	settings: &PngSettings,
 ) -> Result<(), PngError>
 where
 	Writer: WriteBytesExt,
@@ -504,13 +738,32 @@ where
 	let chunk_header = ChunkHeader { name: *b"IHDR", size: chunk_bytes.len() as u32 };
 	write_chunk(writer, &chunk_header, &chunk_bytes)?;
 
-	// if there is a palette, write it out in a PLTE chunk
+	// if there is a palette, write it out in a PLTE chunk, followed by a tRNS chunk if any of the palette's
+	// colors are not fully opaque
 
-	if let Some(palette) = palette {
+	if let Some((palette, num_colors)) = palette {
 		let mut chunk_bytes = Vec::new();
 		palette.to_bytes(&mut chunk_bytes, PaletteFormat::Normal)?;
-		let chunk_header = ChunkHeader { name: *b"PLTE", size: 768 };
+		chunk_bytes.truncate(num_colors * 3);
+		let chunk_header = ChunkHeader { name: *b"PLTE", size: chunk_bytes.len() as u32 };
 		write_chunk(writer, &chunk_header, &chunk_bytes)?;
+
+		let mut alphas = (0..num_colors).map(|index| palette[index as u8].a()).collect::<Vec<u8>>();
+		if let Some(alpha) = settings.transparent_color.and_then(|color| alphas.get_mut(color as usize)) {
+			*alpha = 0;
+		}
+		// the tRNS chunk can omit alpha values for entries at the end of the palette, which default to 255
+		while alphas.last() == Some(&255) {
+			alphas.pop();
+		}
+		if !alphas.is_empty() {
+			let chunk_header = ChunkHeader { name: *b"tRNS", size: alphas.len() as u32 };
+			write_chunk(writer, &chunk_header, &alphas)?;
+		}
+	}
+
+	for (keyword, text) in settings.text.iter() {
+		write_text_chunk(writer, keyword, text)?;
 	}
 
 	// now write out the raw pixel data as IDAT chunk(s)
@@ -518,14 +771,15 @@ where
 	// convert the bitmap pixels into png scanline format and compress via deflate
 
 	let mut scanline_buffer = ScanlineBuffer::new(&ihdr)?;
-	let mut inflater = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+	let compression = flate2::Compression::new(settings.compression.min(9));
+	let mut inflater = flate2::write::ZlibEncoder::new(Vec::new(), compression);
 
 	for y in 0..ihdr.height as usize {
 		for x in 0..ihdr.width as usize {
 			let pixel = unsafe { bitmap.get_pixel_unchecked(x as i32, y as i32) };
 			scanline_buffer.write_pixel(x, pixel)?;
 		}
-		scanline_buffer.write_line(Filter::None, &mut inflater)?;
+		scanline_buffer.write_line(settings.filter, &mut inflater)?;
 	}
 	let chunk_bytes = inflater.finish()?;
 
@@ -567,13 +821,31 @@ impl IndexedBitmap {
 	}
 
 	pub fn to_png_bytes<T: WriteBytesExt>(&self, writer: &mut T, palette: &Palette) -> Result<(), PngError> {
-		write_png_bytes(writer, self, ColorFormat::IndexedColor, Some(palette))
+		self.to_png_bytes_with_settings(writer, palette, &PngSettings::default())
+	}
+
+	pub fn to_png_bytes_with_settings<T: WriteBytesExt>(
+		&self,
+		writer: &mut T,
+		palette: &Palette,
+		settings: &PngSettings,
+	) -> Result<(), PngError> {
+		write_png_bytes(writer, self, ColorFormat::IndexedColor, Some((palette, 256)), settings)
 	}
 
 	pub fn to_png_file(&self, path: &Path, palette: &Palette) -> Result<(), PngError> {
+		self.to_png_file_with_settings(path, palette, &PngSettings::default())
+	}
+
+	pub fn to_png_file_with_settings(
+		&self,
+		path: &Path,
+		palette: &Palette,
+		settings: &PngSettings,
+	) -> Result<(), PngError> {
 		let f = File::create(path)?;
 		let mut writer = BufWriter::new(f);
-		self.to_png_bytes(&mut writer, palette)
+		self.to_png_bytes_with_settings(&mut writer, palette, settings)
 	}
 }
 
@@ -589,21 +861,57 @@ impl RgbaBitmap {
 	}
 
 	pub fn to_png_bytes<T: WriteBytesExt>(&self, writer: &mut T, format: PngFormat) -> Result<(), PngError> {
-		write_png_bytes(
-			writer,
-			self,
-			match format {
-				PngFormat::RGB => ColorFormat::RGB,
-				PngFormat::RGBA => ColorFormat::RGBA,
-			},
-			None,
-		)
+		self.to_png_bytes_with_settings(writer, format, &PngSettings::default())
+	}
+
+	pub fn to_png_bytes_with_settings<T: WriteBytesExt>(
+		&self,
+		writer: &mut T,
+		format: PngFormat,
+		settings: &PngSettings,
+	) -> Result<(), PngError> {
+		match format {
+			PngFormat::RGB => write_png_bytes(writer, self, ColorFormat::RGB, None, settings),
+			PngFormat::RGBA => write_png_bytes(writer, self, ColorFormat::RGBA, None, settings),
+			PngFormat::Indexed => {
+				let (indexed, palette, num_colors) = self.to_png_indexed()?;
+				write_png_bytes(writer, &indexed, ColorFormat::IndexedColor, Some((&palette, num_colors)), settings)
+			}
+		}
 	}
 
 	pub fn to_png_file(&self, path: &Path, format: PngFormat) -> Result<(), PngError> {
+		self.to_png_file_with_settings(path, format, &PngSettings::default())
+	}
+
+	pub fn to_png_file_with_settings(
+		&self,
+		path: &Path,
+		format: PngFormat,
+		settings: &PngSettings,
+	) -> Result<(), PngError> {
 		let f = File::create(path)?;
 		let mut writer = BufWriter::new(f);
-		self.to_png_bytes(&mut writer, format)
+		self.to_png_bytes_with_settings(&mut writer, format, settings)
+	}
+
+	/// Converts this bitmap to an indexed bitmap and a palette containing exactly the colors used by this bitmap,
+	/// in the order they are first encountered. Fails if more than 256 unique colors are used.
+	fn to_png_indexed(&self) -> Result<(IndexedBitmap, Palette, usize), PngError> {
+		let mut colors = HashMap::new();
+		let mut palette = Palette::new_with_default(0, 0, 0);
+		let mut indexed = IndexedBitmap::new(self.width, self.height).unwrap();
+		for (src, dest) in self.pixels.iter().zip(indexed.pixels_mut().iter_mut()) {
+			let num_colors = colors.len();
+			let index = *colors.entry(*src).or_insert(num_colors);
+			if index > 255 {
+				let num_colors = self.pixels.iter().collect::<HashSet<_>>().len();
+				return Err(PngError::TooManyColors(num_colors));
+			}
+			palette[index as u8] = *src;
+			*dest = index as u8;
+		}
+		Ok((indexed, palette, colors.len()))
 	}
 }
 
@@ -867,60 +1175,271 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	pub fn loads_greyscale() -> Result<(), PngError> {
+		for file in ["greyscale_1bit", "greyscale_2bit", "greyscale_4bit", "greyscale_8bit", "greyscale_16bit"] {
+			let ref_bytes = load_raw_rgba(test_file(Path::new(&format!("{}_rgba.bin", file))).as_path())?;
+			let (bmp, palette) = RgbaBitmap::load_png_file(test_file(Path::new(&format!("{}.png", file))).as_path())?;
+			assert!(palette.is_none());
+			assert_eq!(ref_bytes, bmp.pixels, "{}", file);
+		}
+		Ok(())
+	}
+
+	#[test]
+	pub fn loads_greyscale_with_alpha() -> Result<(), PngError> {
+		for file in ["alpha_8bit", "greyscale_alpha_16bit"] {
+			let ref_bytes = load_raw_rgba(test_file(Path::new(&format!("{}_rgba.bin", file))).as_path())?;
+			let (bmp, palette) = RgbaBitmap::load_png_file(test_file(Path::new(&format!("{}.png", file))).as_path())?;
+			assert!(palette.is_none());
+			assert_eq!(ref_bytes, bmp.pixels, "{}", file);
+		}
+		Ok(())
+	}
+
+	#[test]
+	pub fn loads_16bit_color() -> Result<(), PngError> {
+		for file in ["rgb_16bit", "rgba_16bit"] {
+			let ref_bytes = load_raw_rgba(test_file(Path::new(&format!("{}_rgba.bin", file))).as_path())?;
+			let (bmp, palette) = RgbaBitmap::load_png_file(test_file(Path::new(&format!("{}.png", file))).as_path())?;
+			assert!(palette.is_none());
+			assert_eq!(ref_bytes, bmp.pixels, "{}", file);
+		}
+		Ok(())
+	}
+
+	#[test]
+	pub fn loads_indexed_low_bit_depths() -> Result<(), PngError> {
+		for file in ["indexed_1bit", "indexed_2bit", "indexed_4bit"] {
+			let ref_bytes = load_raw_indexed(test_file(Path::new(&format!("{}.bin", file))).as_path())?;
+			let (bmp, palette) =
+				IndexedBitmap::load_png_file(test_file(Path::new(&format!("{}.png", file))).as_path())?;
+			assert!(palette.is_some());
+			assert_eq!(ref_bytes, bmp.pixels, "{}", file);
+
+			let ref_bytes = load_raw_rgba(test_file(Path::new(&format!("{}_rgba.bin", file))).as_path())?;
+			let (bmp, palette) = RgbaBitmap::load_png_file(test_file(Path::new(&format!("{}.png", file))).as_path())?;
+			assert!(palette.is_some());
+			assert_eq!(ref_bytes, bmp.pixels, "{}", file);
+		}
+
+		let ref_bytes = load_raw_rgba(test_file(Path::new("indexed_16col_rgba.bin")).as_path())?;
+		let (bmp, palette) = RgbaBitmap::load_png_file(test_file(Path::new("indexed_16col.png")).as_path())?;
+		assert!(palette.is_some());
+		assert_eq!(ref_bytes, bmp.pixels);
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn loads_with_transparency() -> Result<(), PngError> {
+		// palette alpha values
+		let ref_bytes = load_raw_indexed(test_file(Path::new("indexed_8bit_trns.bin")).as_path())?;
+		let (bmp, palette) = IndexedBitmap::load_png_file(test_file(Path::new("indexed_8bit_trns.png")).as_path())?;
+		assert_eq!(ref_bytes, bmp.pixels);
+		let palette = palette.unwrap();
+		assert_eq!(0, palette[0].a());
+		assert_eq!(64, palette[1].a());
+		assert_eq!(128, palette[2].a());
+		assert_eq!(200, palette[3].a());
+		assert_eq!(255, palette[4].a());
+
+		let ref_bytes = load_raw_rgba(test_file(Path::new("indexed_8bit_trns_rgba.bin")).as_path())?;
+		let (bmp, _) = RgbaBitmap::load_png_file(test_file(Path::new("indexed_8bit_trns.png")).as_path())?;
+		assert_eq!(ref_bytes, bmp.pixels);
+
+		// color-key transparency
+		for file in ["rgb_trns", "greyscale_trns_4bit"] {
+			let ref_bytes = load_raw_rgba(test_file(Path::new(&format!("{}_rgba.bin", file))).as_path())?;
+			let (bmp, _) = RgbaBitmap::load_png_file(test_file(Path::new(&format!("{}.png", file))).as_path())?;
+			assert!(bmp.pixels.iter().any(|pixel| pixel.a() == 0));
+			assert_eq!(ref_bytes, bmp.pixels, "{}", file);
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn loads_interlaced() -> Result<(), PngError> {
+		for file in ["rgba_8bit_interlaced", "rgb_16bit_interlaced", "greyscale_2bit_interlaced"] {
+			let ref_bytes = load_raw_rgba(test_file(Path::new(&format!("{}_rgba.bin", file))).as_path())?;
+			let (bmp, palette) = RgbaBitmap::load_png_file(test_file(Path::new(&format!("{}.png", file))).as_path())?;
+			assert!(palette.is_none());
+			assert_eq!(ref_bytes, bmp.pixels, "{}", file);
+		}
+
+		let ref_bytes = load_raw_indexed(test_file(Path::new("indexed_4bit_interlaced.bin")).as_path())?;
+		let (bmp, palette) =
+			IndexedBitmap::load_png_file(test_file(Path::new("indexed_4bit_interlaced.png")).as_path())?;
+		assert!(palette.is_some());
+		assert_eq!(ref_bytes, bmp.pixels);
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn loads_and_skips_ancillary_chunks() -> Result<(), PngError> {
+		for file in ["rgb_8bit_ancillary", "greyscale_8bit_ancillary"] {
+			let ref_bytes = load_raw_rgba(test_file(Path::new(&format!("{}_rgba.bin", file))).as_path())?;
+			let (bmp, _) = RgbaBitmap::load_png_file(test_file(Path::new(&format!("{}.png", file))).as_path())?;
+			assert_eq!(ref_bytes, bmp.pixels, "{}", file);
+		}
+		Ok(())
+	}
+
 	#[test]
 	pub fn load_fails_on_unsupported_formats() -> Result<(), PngError> {
+		// invalid color type and bit depth combination
 		assert_matches!(
-			RgbaBitmap::load_png_file(test_file(Path::new("unsupported_alpha_8bit.png")).as_path()),
-			Err(PngError::BadFile(..))
-		);
-		assert_matches!(
-			RgbaBitmap::load_png_file(test_file(Path::new("unsupported_greyscale_8bit.png")).as_path()),
-			Err(PngError::BadFile(..))
-		);
-		assert_matches!(
-			RgbaBitmap::load_png_file(test_file(Path::new("unsupported_indexed_16col.png")).as_path()),
+			RgbaBitmap::load_png_file(test_file(Path::new("unsupported_rgb_4bit.png")).as_path()),
 			Err(PngError::BadFile(..))
 		);
 		assert_matches!(
-			RgbaBitmap::load_png_file(test_file(Path::new("unsupported_rgb_16bit.png")).as_path()),
-			Err(PngError::BadFile(..))
-		);
-		assert_matches!(
-			RgbaBitmap::load_png_file(test_file(Path::new("unsupported_rgba_16bit.png")).as_path()),
+			IndexedBitmap::load_png_file(test_file(Path::new("unsupported_rgb_4bit.png")).as_path()),
 			Err(PngError::BadFile(..))
 		);
 
+		// also test the extra formats that IndexedBitmap does not support which RgbaBitmap does
+		// (anything not indexed basically ...)
 		assert_matches!(
-			IndexedBitmap::load_png_file(test_file(Path::new("unsupported_alpha_8bit.png")).as_path()),
+			IndexedBitmap::load_png_file(test_file(Path::new("rgb.png")).as_path()),
 			Err(PngError::BadFile(..))
 		);
 		assert_matches!(
-			IndexedBitmap::load_png_file(test_file(Path::new("unsupported_greyscale_8bit.png")).as_path()),
+			IndexedBitmap::load_png_file(test_file(Path::new("rgba.png")).as_path()),
 			Err(PngError::BadFile(..))
 		);
 		assert_matches!(
-			IndexedBitmap::load_png_file(test_file(Path::new("unsupported_indexed_16col.png")).as_path()),
+			IndexedBitmap::load_png_file(test_file(Path::new("greyscale_8bit.png")).as_path()),
 			Err(PngError::BadFile(..))
 		);
 		assert_matches!(
-			IndexedBitmap::load_png_file(test_file(Path::new("unsupported_rgb_16bit.png")).as_path()),
+			IndexedBitmap::load_png_file(test_file(Path::new("alpha_8bit.png")).as_path()),
 			Err(PngError::BadFile(..))
 		);
 		assert_matches!(
-			IndexedBitmap::load_png_file(test_file(Path::new("unsupported_rgba_16bit.png")).as_path()),
+			IndexedBitmap::load_png_file(test_file(Path::new("rgb_16bit.png")).as_path()),
 			Err(PngError::BadFile(..))
 		);
 
-		// also test the extra formats that IndexedBitmap does not support which RgbaBitmap does
-		// (anything not 256-color indexed basically ...)
-		assert_matches!(
-			IndexedBitmap::load_png_file(test_file(Path::new("rgb.png")).as_path()),
-			Err(PngError::BadFile(..))
-		);
-		assert_matches!(
-			IndexedBitmap::load_png_file(test_file(Path::new("rgba.png")).as_path()),
-			Err(PngError::BadFile(..))
-		);
+		Ok(())
+	}
+
+	#[test]
+	pub fn saves_with_each_filter_and_compression_level() -> Result<(), PngError> {
+		let (bmp, _) = RgbaBitmap::load_png_file(test_file(Path::new("large_1_rgb.png")).as_path())?;
+		let filters =
+			[PngFilter::None, PngFilter::Sub, PngFilter::Up, PngFilter::Average, PngFilter::Paeth, PngFilter::Adaptive];
+		for filter in filters {
+			for compression in [0, 6, 9] {
+				let settings = PngSettings { compression, filter, ..Default::default() };
+				let mut png_bytes = Vec::new();
+				bmp.to_png_bytes_with_settings(&mut png_bytes, PngFormat::RGBA, &settings)?;
+				let (reloaded_bmp, _) = RgbaBitmap::load_png_bytes(&mut png_bytes.as_slice())?;
+				assert_eq!(reloaded_bmp.pixels, bmp.pixels, "{:?}, compression {}", filter, compression);
+			}
+		}
+		Ok(())
+	}
+
+	#[test]
+	pub fn adaptive_filtering_reduces_size() -> Result<(), PngError> {
+		// smooth gradients are where filtering makes the biggest difference
+		let mut bmp = RgbaBitmap::new(256, 256).unwrap();
+		for y in 0..256 {
+			for x in 0..256 {
+				bmp.set_pixel(x, y, RGBA::from_rgb([x as u8, y as u8, ((x + y) / 2) as u8]));
+			}
+		}
+
+		let mut unfiltered = Vec::new();
+		let settings = PngSettings { filter: PngFilter::None, ..Default::default() };
+		bmp.to_png_bytes_with_settings(&mut unfiltered, PngFormat::RGB, &settings)?;
+
+		let mut adaptive = Vec::new();
+		let settings = PngSettings { filter: PngFilter::Adaptive, ..Default::default() };
+		bmp.to_png_bytes_with_settings(&mut adaptive, PngFormat::RGB, &settings)?;
+
+		assert!(adaptive.len() < unfiltered.len(), "adaptive: {}, unfiltered: {}", adaptive.len(), unfiltered.len());
+
+		// the default settings should keep writing unfiltered scanlines
+		let mut default_output = Vec::new();
+		bmp.to_png_bytes(&mut default_output, PngFormat::RGB)?;
+		assert_eq!(unfiltered, default_output);
+		Ok(())
+	}
+
+	#[test]
+	pub fn saves_rgba_as_indexed() -> Result<(), PngError> {
+		for file in ["indexed_8", "indexed_8bit_trns", "indexed_4bit"] {
+			let (bmp, _) = RgbaBitmap::load_png_file(test_file(Path::new(&format!("{}.png", file))).as_path())?;
+
+			let mut png_bytes = Vec::new();
+			bmp.to_png_bytes(&mut png_bytes, PngFormat::Indexed)?;
+			let (reloaded_bmp, reloaded_palette) = RgbaBitmap::load_png_bytes(&mut png_bytes.as_slice())?;
+			assert!(reloaded_palette.is_some(), "{}", file);
+			assert_eq!(reloaded_bmp.pixels, bmp.pixels, "{}", file);
+		}
+
+		let (bmp, _) = RgbaBitmap::load_png_file(test_file(Path::new("alpha_8bit.png")).as_path())?;
+		assert_matches!(bmp.to_png_bytes(&mut Vec::new(), PngFormat::Indexed), Err(PngError::TooManyColors(1024)));
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn saves_transparent_color() -> Result<(), PngError> {
+		let (bmp, palette) = IndexedBitmap::load_png_file(test_file(Path::new("indexed_8.png")).as_path())?;
+		let palette = palette.unwrap();
+
+		let mut png_bytes = Vec::new();
+		let settings = PngSettings { transparent_color: Some(3), ..Default::default() };
+		bmp.to_png_bytes_with_settings(&mut png_bytes, &palette, &settings)?;
+		let (reloaded_bmp, reloaded_palette) = IndexedBitmap::load_png_bytes(&mut png_bytes.as_slice())?;
+		let reloaded_palette = reloaded_palette.unwrap();
+		assert_eq!(reloaded_bmp.pixels, bmp.pixels);
+		assert_eq!(0, reloaded_palette[3].a());
+		assert_eq!(255, reloaded_palette[2].a());
+		assert_eq!(255, reloaded_palette[4].a());
+
+		// palette entries which are already partially transparent should be preserved
+		let (bmp, palette) = IndexedBitmap::load_png_file(test_file(Path::new("indexed_8bit_trns.png")).as_path())?;
+		let mut png_bytes = Vec::new();
+		bmp.to_png_bytes(&mut png_bytes, palette.as_ref().unwrap())?;
+		let (reloaded_bmp, reloaded_palette) = IndexedBitmap::load_png_bytes(&mut png_bytes.as_slice())?;
+		assert_eq!(reloaded_bmp.pixels, bmp.pixels);
+		assert_eq!(reloaded_palette, palette);
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn saves_text_chunks() -> Result<(), PngError> {
+		let (bmp, _) = RgbaBitmap::load_png_file(test_file(Path::new("rgb.png")).as_path())?;
+
+		let settings = PngSettings {
+			text: vec![
+				(String::from("Title"), String::from("Test Image")), //
+				(String::from("Author"), String::from("Gered")),
+			],
+			..Default::default()
+		};
+		let mut png_bytes = Vec::new();
+		bmp.to_png_bytes_with_settings(&mut png_bytes, PngFormat::RGB, &settings)?;
+		let contains = |bytes: &[u8]| png_bytes.windows(bytes.len()).any(|window| window == bytes);
+		assert!(contains(b"tEXtTitle\0Test Image"));
+		assert!(contains(b"tEXtAuthor\0Gered"));
+
+		// the text chunks should just be skipped over when loading
+		let (reloaded_bmp, _) = RgbaBitmap::load_png_bytes(&mut png_bytes.as_slice())?;
+		assert_eq!(reloaded_bmp.pixels, bmp.pixels);
+
+		for (keyword, text) in [("", "text"), ("Title", "bad\0text"), ("Title", "\u{263a}")] {
+			let settings =
+				PngSettings { text: vec![(String::from(keyword), String::from(text))], ..Default::default() };
+			let result = bmp.to_png_bytes_with_settings(&mut Vec::new(), PngFormat::RGB, &settings);
+			assert_matches!(result, Err(PngError::InvalidText(..)));
+		}
 
 		Ok(())
 	}
@@ -1,5 +1,6 @@
 use std::mem::swap;
 
+use crate::graphics::bitmap::shapes::{for_each_circle_point, for_each_filled_circle_span};
 use crate::graphics::{Bitmap, Character, Font, FontRenderOpts, Pixel};
 use crate::math::{lerp, Rect};
 
@@ -472,51 +473,15 @@ impl<PixelType: Pixel> Bitmap<PixelType> {
 	/// Draws the outline of a circle formed by the center point and radius given.
 	pub fn circle(&mut self, center_x: i32, center_y: i32, radius: u32, color: PixelType) {
 		// TODO: optimize
-		let mut x = 0;
-		let mut y = radius as i32;
-		let mut m = 5 - 4 * radius as i32;
-
-		while x <= y {
-			self.set_pixel(center_x + x, center_y + y, color);
-			self.set_pixel(center_x + x, center_y - y, color);
-			self.set_pixel(center_x - x, center_y + y, color);
-			self.set_pixel(center_x - x, center_y - y, color);
-			self.set_pixel(center_x + y, center_y + x, color);
-			self.set_pixel(center_x + y, center_y - x, color);
-			self.set_pixel(center_x - y, center_y + x, color);
-			self.set_pixel(center_x - y, center_y - x, color);
-
-			if m > 0 {
-				y -= 1;
-				m -= 8 * y;
-			}
-
-			x += 1;
-			m += 8 * x + 4;
-		}
+		for_each_circle_point(center_x, center_y, radius, |x, y| self.set_pixel(x, y, color));
 	}
 
 	/// Draws a filled circle formed by the center point and radius given.
 	pub fn filled_circle(&mut self, center_x: i32, center_y: i32, radius: u32, color: PixelType) {
 		// TODO: optimize
-		let mut x = 0;
-		let mut y = radius as i32;
-		let mut m = 5 - 4 * radius as i32;
-
-		while x <= y {
-			self.horiz_line(center_x - x, center_x + x, center_y - y, color);
-			self.horiz_line(center_x - y, center_x + y, center_y - x, color);
-			self.horiz_line(center_x - y, center_x + y, center_y + x, color);
-			self.horiz_line(center_x - x, center_x + x, center_y + y, color);
-
-			if m > 0 {
-				y -= 1;
-				m -= 8 * y;
-			}
-
-			x += 1;
-			m += 8 * x + 4;
-		}
+		for_each_filled_circle_span(center_x, center_y, radius, |left, right, y| {
+			self.horiz_line(left, right, y, color)
+		});
 	}
 }
 
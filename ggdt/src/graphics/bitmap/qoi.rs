@@ -0,0 +1,256 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use thiserror::Error;
+
+use crate::graphics::{RgbaBitmap, RGBA};
+
+const QOI_MAGIC: &[u8; 4] = b"qoif";
+const QOI_END_MARKER: [u8; 8] = [0, 0, 0, 0, 0, 0, 0, 1];
+
+const QOI_OP_INDEX: u8 = 0x00;
+const QOI_OP_DIFF: u8 = 0x40;
+const QOI_OP_LUMA: u8 = 0x80;
+const QOI_OP_RUN: u8 = 0xc0;
+const QOI_OP_RGB: u8 = 0xfe;
+const QOI_OP_RGBA: u8 = 0xff;
+const QOI_OP_MASK: u8 = 0xc0;
+
+const QOI_MAX_RUN_LENGTH: u8 = 62;
+
+#[derive(Error, Debug)]
+pub enum QoiError {
+	#[error("Bad or unsupported QOI file: {0}")]
+	BadFile(String),
+
+	#[error("QOI I/O error")]
+	IOError(#[from] std::io::Error),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum QoiFormat {
+	/// 24-bit color, with no alpha channel.
+	RGB,
+	/// 32-bit color, with an alpha channel.
+	RGBA,
+}
+
+#[inline]
+fn hash_index(color: [u8; 4]) -> usize {
+	let [r, g, b, a] = color;
+	(r as usize * 3 + g as usize * 5 + b as usize * 7 + a as usize * 11) % 64
+}
+
+impl RgbaBitmap {
+	pub fn load_qoi_bytes<T: ReadBytesExt>(reader: &mut T) -> Result<RgbaBitmap, QoiError> {
+		let mut magic = [0u8; 4];
+		reader.read_exact(&mut magic)?;
+		if magic != *QOI_MAGIC {
+			return Err(QoiError::BadFile(String::from("Unexpected magic number, probably not a QOI file")));
+		}
+		let width = reader.read_u32::<BigEndian>()?;
+		let height = reader.read_u32::<BigEndian>()?;
+		let channels = reader.read_u8()?;
+		let _colorspace = reader.read_u8()?;
+		if channels != 3 && channels != 4 {
+			return Err(QoiError::BadFile(format!("Unsupported number of channels: {}", channels)));
+		}
+		let num_pixels = (width as usize).checked_mul(height as usize);
+		if !num_pixels.map_or(false, |num_pixels| num_pixels <= super::MAX_LOADED_PIXELS) {
+			return Err(QoiError::BadFile(format!("Invalid image dimensions: {}x{}", width, height)));
+		}
+		let mut bitmap =
+			RgbaBitmap::new(width, height).map_err(|_| QoiError::BadFile(String::from("Invalid image dimensions")))?;
+
+		let mut index = [[0u8; 4]; 64];
+		let mut color = [0, 0, 0, 255];
+		let mut run = 0;
+		for pixel in bitmap.pixels_mut().iter_mut() {
+			if run > 0 {
+				run -= 1;
+			} else {
+				let op = reader.read_u8()?;
+				match op {
+					QOI_OP_RGB => {
+						color[0] = reader.read_u8()?;
+						color[1] = reader.read_u8()?;
+						color[2] = reader.read_u8()?;
+					}
+					QOI_OP_RGBA => {
+						reader.read_exact(&mut color)?;
+					}
+					_ => match op & QOI_OP_MASK {
+						QOI_OP_INDEX => color = index[op as usize],
+						QOI_OP_DIFF => {
+							color[0] = color[0].wrapping_add(((op >> 4) & 0x03).wrapping_sub(2));
+							color[1] = color[1].wrapping_add(((op >> 2) & 0x03).wrapping_sub(2));
+							color[2] = color[2].wrapping_add((op & 0x03).wrapping_sub(2));
+						}
+						QOI_OP_LUMA => {
+							let next = reader.read_u8()?;
+							let dg = (op & 0x3f).wrapping_sub(32);
+							color[0] = color[0].wrapping_add(dg.wrapping_add((next >> 4).wrapping_sub(8)));
+							color[1] = color[1].wrapping_add(dg);
+							color[2] = color[2].wrapping_add(dg.wrapping_add((next & 0x0f).wrapping_sub(8)));
+						}
+						_ => run = op & 0x3f, // QOI_OP_RUN, with a bias of -1 accounting for this pixel
+					},
+				}
+				index[hash_index(color)] = color;
+			}
+			*pixel = RGBA::from_rgba(color);
+		}
+
+		Ok(bitmap)
+	}
+
+	pub fn load_qoi_file(path: &Path) -> Result<RgbaBitmap, QoiError> {
+		let f = File::open(path)?;
+		let mut reader = BufReader::new(f);
+		Self::load_qoi_bytes(&mut reader)
+	}
+
+	/// Writes this bitmap out in QOI format. When writing in [`QoiFormat::RGB`], the alpha channel of all pixels is
+	/// written out as fully opaque.
+	pub fn to_qoi_bytes<T: WriteBytesExt>(&self, writer: &mut T, format: QoiFormat) -> Result<(), QoiError> {
+		writer.write_all(QOI_MAGIC)?;
+		writer.write_u32::<BigEndian>(self.width())?;
+		writer.write_u32::<BigEndian>(self.height())?;
+		writer.write_u8(match format {
+			QoiFormat::RGB => 3,
+			QoiFormat::RGBA => 4,
+		})?;
+		writer.write_u8(0)?; // srgb with linear alpha
+
+		let mut index = [[0u8; 4]; 64];
+		let mut previous = [0, 0, 0, 255];
+		let mut run = 0;
+		let pixels = self.pixels();
+		for (i, pixel) in pixels.iter().enumerate() {
+			let mut color = pixel.to_array();
+			if format == QoiFormat::RGB {
+				color[3] = 255;
+			}
+
+			if color == previous {
+				run += 1;
+				if run == QOI_MAX_RUN_LENGTH || i == pixels.len() - 1 {
+					writer.write_u8(QOI_OP_RUN | (run - 1))?;
+					run = 0;
+				}
+				continue;
+			}
+			if run > 0 {
+				writer.write_u8(QOI_OP_RUN | (run - 1))?;
+				run = 0;
+			}
+
+			let hash = hash_index(color);
+			if index[hash] == color {
+				writer.write_u8(QOI_OP_INDEX | hash as u8)?;
+			} else {
+				index[hash] = color;
+				if color[3] == previous[3] {
+					let dr = color[0].wrapping_sub(previous[0]) as i8;
+					let dg = color[1].wrapping_sub(previous[1]) as i8;
+					let db = color[2].wrapping_sub(previous[2]) as i8;
+					let dr_dg = dr.wrapping_sub(dg);
+					let db_dg = db.wrapping_sub(dg);
+					if (-2..=1).contains(&dr) && (-2..=1).contains(&dg) && (-2..=1).contains(&db) {
+						writer.write_u8(
+							QOI_OP_DIFF | (((dr + 2) as u8) << 4) | (((dg + 2) as u8) << 2) | (db + 2) as u8,
+						)?;
+					} else if (-32..=31).contains(&dg) && (-8..=7).contains(&dr_dg) && (-8..=7).contains(&db_dg) {
+						writer.write_u8(QOI_OP_LUMA | (dg + 32) as u8)?;
+						writer.write_u8((((dr_dg + 8) as u8) << 4) | (db_dg + 8) as u8)?;
+					} else {
+						writer.write_u8(QOI_OP_RGB)?;
+						writer.write_all(&color[0..3])?;
+					}
+				} else {
+					writer.write_u8(QOI_OP_RGBA)?;
+					writer.write_all(&color)?;
+				}
+			}
+			previous = color;
+		}
+
+		writer.write_all(&QOI_END_MARKER)?;
+		Ok(())
+	}
+
+	pub fn to_qoi_file(&self, path: &Path, format: QoiFormat) -> Result<(), QoiError> {
+		let f = File::create(path)?;
+		let mut writer = BufWriter::new(f);
+		self.to_qoi_bytes(&mut writer, format)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::PathBuf;
+
+	use claim::*;
+	use tempfile::TempDir;
+
+	use crate::tests::load_raw_rgba;
+
+	use super::*;
+
+	const BASE_PATH: &str = "./test-assets/qoi/";
+
+	fn test_file(file: &Path) -> PathBuf {
+		PathBuf::from(BASE_PATH).join(file)
+	}
+
+	#[test]
+	pub fn load_and_save() -> Result<(), QoiError> {
+		let tmp_dir = TempDir::new()?;
+
+		for (file, ref_file, format) in
+			[("rgba.qoi", "rgba.bin", QoiFormat::RGBA), ("rgb.qoi", "rgb_rgba.bin", QoiFormat::RGB)]
+		{
+			let ref_pixels = load_raw_rgba(test_file(Path::new(ref_file)).as_path())?;
+			let ref_bytes = std::fs::read(test_file(Path::new(file)))?;
+
+			let bmp = RgbaBitmap::load_qoi_file(test_file(Path::new(file)).as_path())?;
+			assert_eq!(37, bmp.width());
+			assert_eq!(23, bmp.height());
+			assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+
+			// the encoding is deterministic, so we should get back exactly the same file
+			let save_path = tmp_dir.path().join(file);
+			bmp.to_qoi_file(&save_path, format)?;
+			assert_eq!(ref_bytes, std::fs::read(&save_path)?);
+			let reloaded_bmp = RgbaBitmap::load_qoi_file(&save_path)?;
+			assert_eq!(37, reloaded_bmp.width());
+			assert_eq!(23, reloaded_bmp.height());
+			assert_eq!(reloaded_bmp.pixels(), ref_pixels.as_ref());
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn load_bad_files() {
+		assert_matches!(RgbaBitmap::load_qoi_bytes(&mut &b"GIF89a......"[..]), Err(QoiError::BadFile(..)));
+		assert_matches!(
+			RgbaBitmap::load_qoi_bytes(&mut &b"qoif\0\0\0\x01\0\0\0\x01\x05\0"[..]),
+			Err(QoiError::BadFile(..))
+		);
+		assert_matches!(
+			RgbaBitmap::load_qoi_bytes(&mut &b"qoif\0\0\0\x04\0\0\0\x04\x04\0"[..]),
+			Err(QoiError::IOError(..))
+		);
+		assert_matches!(
+			RgbaBitmap::load_qoi_bytes(&mut &b"qoif\xff\xff\xff\xff\xff\xff\xff\xff\x04\0"[..]),
+			Err(QoiError::BadFile(..))
+		);
+		assert_matches!(
+			RgbaBitmap::load_qoi_bytes(&mut &b"qoif\0\x01\0\0\0\x01\0\0\x04\0"[..]),
+			Err(QoiError::BadFile(..))
+		);
+	}
+}
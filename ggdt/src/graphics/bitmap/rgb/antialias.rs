@@ -0,0 +1,507 @@
+use crate::graphics::bitmap::shapes::{for_each_polygon_span, points_extents};
+use crate::graphics::{BitmapViewMut, BlendFunction, PolygonFillRule, RgbaBitmap, RGBA};
+use crate::math::{Rect, Vector2};
+
+// number of sub-scanlines sampled per pixel row when calculating polygon coverage
+const POLYGON_SUBSAMPLES: usize = 16;
+
+/// Returns the given color with its alpha component scaled by the given coverage amount (0.0 to 1.0).
+#[inline]
+fn with_coverage(color: RGBA, coverage: f32) -> RGBA {
+	let mut color = color;
+	color.set_a((color.a() as f32 * coverage.clamp(0.0, 1.0)).round() as u8);
+	color
+}
+
+impl RgbaBitmap {
+	/// Blends the given color onto the pixel at the given coordinates, with the color's alpha scaled by the given
+	/// coverage amount (0.0 to 1.0). If the coordinates lie outside of the bitmaps clipping region, or the coverage
+	/// is zero, no pixels will be changed.
+	#[inline]
+	fn set_coverage_pixel(&mut self, x: i32, y: i32, coverage: f32, color: RGBA, blend: BlendFunction) {
+		if coverage > 0.0 {
+			let color = with_coverage(color, coverage);
+			self.set_custom_pixel(x, y, |dest_color| blend.blend(color, dest_color));
+		}
+	}
+
+	/// Draws an anti-aliased line from x1,y1 to x2,y2 using Xiaolin Wu's line algorithm. The drawn pixels are
+	/// blended using the given blend function, with the color's alpha scaled by how much of each pixel is covered by
+	/// the line. Coordinates are given in pixels, where the center of a pixel at x,y is at x+0.5,y+0.5.
+	pub fn antialiased_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, color: RGBA, blend: BlendFunction) {
+		// wu's algorithm treats pixel centers as being at integer coordinates
+		let (mut x1, mut y1, mut x2, mut y2) = (x1 - 0.5, y1 - 0.5, x2 - 0.5, y2 - 0.5);
+
+		let steep = (y2 - y1).abs() > (x2 - x1).abs();
+		if steep {
+			std::mem::swap(&mut x1, &mut y1);
+			std::mem::swap(&mut x2, &mut y2);
+		}
+		if x1 > x2 {
+			std::mem::swap(&mut x1, &mut x2);
+			std::mem::swap(&mut y1, &mut y2);
+		}
+
+		let delta_x = x2 - x1;
+		let delta_y = y2 - y1;
+		let gradient = if delta_x == 0.0 { 1.0 } else { delta_y / delta_x };
+
+		let mut plot = |major: i32, minor: i32, coverage: f32| {
+			if steep {
+				self.set_coverage_pixel(minor, major, coverage, color, blend);
+			} else {
+				self.set_coverage_pixel(major, minor, coverage, color, blend);
+			}
+		};
+
+		// start point
+		let x_end = x1.round();
+		let y_end = y1 + gradient * (x_end - x1);
+		let x_gap = 1.0 - (x1 + 0.5).fract();
+		let x_start_pixel = x_end as i32;
+		let y_pixel = y_end.floor();
+
+		// end point
+		let x_end2 = x2.round();
+		let y_end2 = y2 + gradient * (x_end2 - x2);
+		let x_gap2 = (x2 + 0.5).fract();
+		let x_end_pixel = x_end2 as i32;
+		let y_pixel2 = y_end2.floor();
+
+		if x_start_pixel == x_end_pixel {
+			// the whole line falls within a single column of pixels
+			let x_gap = (x2 - x1).min(1.0);
+			let y_mid = (y1 + y2) / 2.0;
+			let y_pixel = y_mid.floor();
+			plot(x_start_pixel, y_pixel as i32, (1.0 - (y_mid - y_pixel)) * x_gap);
+			plot(x_start_pixel, y_pixel as i32 + 1, (y_mid - y_pixel) * x_gap);
+			return;
+		}
+
+		plot(x_start_pixel, y_pixel as i32, (1.0 - (y_end - y_pixel)) * x_gap);
+		plot(x_start_pixel, y_pixel as i32 + 1, (y_end - y_pixel) * x_gap);
+		plot(x_end_pixel, y_pixel2 as i32, (1.0 - (y_end2 - y_pixel2)) * x_gap2);
+		plot(x_end_pixel, y_pixel2 as i32 + 1, (y_end2 - y_pixel2) * x_gap2);
+
+		let mut intersect_y = y_end + gradient;
+		for x in (x_start_pixel + 1)..x_end_pixel {
+			let y_pixel = intersect_y.floor();
+			let fraction = intersect_y - y_pixel;
+			plot(x, y_pixel as i32, 1.0 - fraction);
+			plot(x, y_pixel as i32 + 1, fraction);
+			intersect_y += gradient;
+		}
+	}
+
+	/// Draws an anti-aliased ellipse, using the given function to determine how much of each pixel is covered
+	/// given the approximate signed distance from the pixel's center to the edge of the ellipse.
+	#[allow(clippy::too_many_arguments)]
+	fn antialiased_ellipse_coverage(
+		&mut self,
+		center_x: f32,
+		center_y: f32,
+		radius_x: f32,
+		radius_y: f32,
+		color: RGBA,
+		blend: BlendFunction,
+		coverage_fn: impl Fn(f32) -> f32,
+	) {
+		if radius_x <= 0.0 || radius_y <= 0.0 {
+			return;
+		}
+
+		let mut bounds = Rect::from_coords(
+			(center_x - radius_x - 1.0).floor() as i32,
+			(center_y - radius_y - 1.0).floor() as i32,
+			(center_x + radius_x + 1.0).ceil() as i32,
+			(center_y + radius_y + 1.0).ceil() as i32,
+		);
+		if !bounds.clamp_to(&self.clip_region) {
+			return;
+		}
+
+		let radius_x_squared = radius_x * radius_x;
+		let radius_y_squared = radius_y * radius_y;
+		for y in bounds.y..=bounds.bottom() {
+			let dy = y as f32 + 0.5 - center_y;
+			for x in bounds.x..=bounds.right() {
+				let dx = x as f32 + 0.5 - center_x;
+				let distance = if radius_x == radius_y {
+					(dx * dx + dy * dy).sqrt() - radius_x
+				} else {
+					// first order approximation of the distance, using the ellipse's implicit function and its
+					// gradient. this is exact enough near the edge of the ellipse for the purposes of anti-aliasing
+					let f = dx * dx / radius_x_squared + dy * dy / radius_y_squared - 1.0;
+					let gradient_x = 2.0 * dx / radius_x_squared;
+					let gradient_y = 2.0 * dy / radius_y_squared;
+					f / (gradient_x * gradient_x + gradient_y * gradient_y).sqrt()
+				};
+				self.set_coverage_pixel(x, y, coverage_fn(distance), color, blend);
+			}
+		}
+	}
+
+	/// Draws an anti-aliased, one pixel wide, outline of an ellipse formed by the center point and radii given. The
+	/// drawn pixels are blended using the given blend function, with the color's alpha scaled by how much of each
+	/// pixel is covered by the outline.
+	pub fn antialiased_ellipse(
+		&mut self,
+		center_x: f32,
+		center_y: f32,
+		radius_x: f32,
+		radius_y: f32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.antialiased_ellipse_coverage(center_x, center_y, radius_x, radius_y, color, blend, |distance| {
+			1.0 - distance.abs()
+		});
+	}
+
+	/// Draws an anti-aliased filled ellipse formed by the center point and radii given. The drawn pixels are
+	/// blended using the given blend function, with the color's alpha scaled by how much of each pixel is covered
+	/// by the ellipse.
+	pub fn antialiased_filled_ellipse(
+		&mut self,
+		center_x: f32,
+		center_y: f32,
+		radius_x: f32,
+		radius_y: f32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.antialiased_ellipse_coverage(center_x, center_y, radius_x, radius_y, color, blend, |distance| {
+			0.5 - distance
+		});
+	}
+
+	/// Draws an anti-aliased, one pixel wide, outline of a circle formed by the center point and radius given. The
+	/// drawn pixels are blended using the given blend function, with the color's alpha scaled by how much of each
+	/// pixel is covered by the outline.
+	#[inline]
+	pub fn antialiased_circle(&mut self, center_x: f32, center_y: f32, radius: f32, color: RGBA, blend: BlendFunction) {
+		self.antialiased_ellipse(center_x, center_y, radius, radius, color, blend);
+	}
+
+	/// Draws an anti-aliased filled circle formed by the center point and radius given. The drawn pixels are
+	/// blended using the given blend function, with the color's alpha scaled by how much of each pixel is covered
+	/// by the circle.
+	#[inline]
+	pub fn antialiased_filled_circle(
+		&mut self,
+		center_x: f32,
+		center_y: f32,
+		radius: f32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.antialiased_filled_ellipse(center_x, center_y, radius, radius, color, blend);
+	}
+
+	/// Draws an anti-aliased filled polygon formed by the given points. The polygon can be convex or concave and can
+	/// intersect itself, in which case the given fill rule determines which areas are filled. The drawn pixels are
+	/// blended using the given blend function, with the color's alpha scaled by how much of each pixel is covered
+	/// by the polygon.
+	pub fn antialiased_filled_polygon(
+		&mut self,
+		points: &[Vector2],
+		fill_rule: PolygonFillRule,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		if points.len() < 3 {
+			return;
+		}
+
+		let (min, max) = points_extents(points);
+		let mut bounds =
+			Rect::from_coords(min.x.floor() as i32, min.y.floor() as i32, max.x.ceil() as i32, max.y.ceil() as i32);
+		if !bounds.clamp_to(&self.clip_region) {
+			return;
+		}
+
+		let left = bounds.x as f32;
+		let right = (bounds.right() + 1) as f32;
+		let sample_weight = 1.0 / POLYGON_SUBSAMPLES as f32;
+		let mut coverage = vec![0.0f32; bounds.width as usize];
+		let mut crossings = Vec::with_capacity(points.len());
+
+		for y in bounds.y..=bounds.bottom() {
+			coverage.fill(0.0);
+
+			for sample in 0..POLYGON_SUBSAMPLES {
+				let sample_y = y as f32 + (sample as f32 + 0.5) * sample_weight;
+				for_each_polygon_span(points, fill_rule, sample_y, &mut crossings, |span_start, span_end| {
+					// accumulate the exact horizontal coverage of this span for each pixel it touches
+					let span_start = span_start.clamp(left, right);
+					let span_end = span_end.clamp(left, right);
+					if span_end <= span_start {
+						return;
+					}
+					let first = (span_start - left) as usize;
+					let last = ((span_end - left).ceil() as usize).min(coverage.len()) - 1;
+					for (index, pixel_coverage) in coverage[first..=last].iter_mut().enumerate() {
+						let pixel_left = left + (first + index) as f32;
+						let overlap = span_end.min(pixel_left + 1.0) - span_start.max(pixel_left);
+						*pixel_coverage += overlap * sample_weight;
+					}
+				});
+			}
+
+			for (offset, &pixel_coverage) in coverage.iter().enumerate() {
+				self.set_coverage_pixel(bounds.x + offset as i32, y, pixel_coverage, color, blend);
+			}
+		}
+	}
+}
+
+impl BitmapViewMut<'_, RGBA> {
+	/// Draws an anti-aliased line from x1,y1 to x2,y2, the same as [`RgbaBitmap::antialiased_line`].
+	pub fn antialiased_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, color: RGBA, blend: BlendFunction) {
+		let (x, y) = (self.x() as f32, self.y() as f32);
+		self.with_bitmap(|bitmap| bitmap.antialiased_line(x1 + x, y1 + y, x2 + x, y2 + y, color, blend));
+	}
+
+	/// Draws an anti-aliased outline of an ellipse, the same as [`RgbaBitmap::antialiased_ellipse`].
+	pub fn antialiased_ellipse(
+		&mut self,
+		center_x: f32,
+		center_y: f32,
+		radius_x: f32,
+		radius_y: f32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		let (x, y) = (self.x() as f32, self.y() as f32);
+		self.with_bitmap(|bitmap| {
+			bitmap.antialiased_ellipse(center_x + x, center_y + y, radius_x, radius_y, color, blend)
+		});
+	}
+
+	/// Draws an anti-aliased filled ellipse, the same as [`RgbaBitmap::antialiased_filled_ellipse`].
+	pub fn antialiased_filled_ellipse(
+		&mut self,
+		center_x: f32,
+		center_y: f32,
+		radius_x: f32,
+		radius_y: f32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		let (x, y) = (self.x() as f32, self.y() as f32);
+		self.with_bitmap(|bitmap| {
+			bitmap.antialiased_filled_ellipse(center_x + x, center_y + y, radius_x, radius_y, color, blend)
+		});
+	}
+
+	/// Draws an anti-aliased outline of a circle, the same as [`RgbaBitmap::antialiased_circle`].
+	#[inline]
+	pub fn antialiased_circle(&mut self, center_x: f32, center_y: f32, radius: f32, color: RGBA, blend: BlendFunction) {
+		self.antialiased_ellipse(center_x, center_y, radius, radius, color, blend);
+	}
+
+	/// Draws an anti-aliased filled circle, the same as [`RgbaBitmap::antialiased_filled_circle`].
+	#[inline]
+	pub fn antialiased_filled_circle(
+		&mut self,
+		center_x: f32,
+		center_y: f32,
+		radius: f32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.antialiased_filled_ellipse(center_x, center_y, radius, radius, color, blend);
+	}
+
+	/// Draws an anti-aliased filled polygon, the same as [`RgbaBitmap::antialiased_filled_polygon`].
+	pub fn antialiased_filled_polygon(
+		&mut self,
+		points: &[Vector2],
+		fill_rule: PolygonFillRule,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		let points = self.to_bitmap_points(points);
+		self.with_bitmap(|bitmap| bitmap.antialiased_filled_polygon(&points, fill_rule, color, blend));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const BACKGROUND: RGBA = RGBA::from_rgba([0, 0, 0, 255]);
+	const WHITE: RGBA = RGBA::from_rgba([255, 255, 255, 255]);
+
+	fn new_bitmap(width: u32, height: u32) -> RgbaBitmap {
+		let mut bmp = RgbaBitmap::new(width, height).unwrap();
+		bmp.clear(BACKGROUND);
+		bmp
+	}
+
+	fn intensity(bmp: &RgbaBitmap, x: i32, y: i32) -> u8 {
+		bmp.get_pixel(x, y).unwrap().r()
+	}
+
+	#[test]
+	pub fn antialiased_line() {
+		// a horizontal line running exactly through the center of a row of pixels, from the left edge of the first
+		// pixel to the right edge of the last
+		let mut bmp = new_bitmap(12, 5);
+		bmp.antialiased_line(1.0, 2.5, 11.0, 2.5, WHITE, BlendFunction::Blend);
+		for y in 0..5 {
+			for x in 0..12 {
+				let expected = if y == 2 && (1..=10).contains(&x) { 255 } else { 0 };
+				assert_eq!(expected, intensity(&bmp, x, y), "{}, {}", x, y);
+			}
+		}
+
+		// a horizontal line running between two rows of pixels covers both rows by half
+		let mut bmp = new_bitmap(12, 5);
+		bmp.antialiased_line(1.0, 2.0, 11.0, 2.0, WHITE, BlendFunction::Blend);
+		for x in 1..=10 {
+			assert_eq!(128, intensity(&bmp, x, 1), "{}", x);
+			assert_eq!(128, intensity(&bmp, x, 2), "{}", x);
+		}
+
+		// a diagonal line, swapping the end points has no effect
+		let mut bmp = new_bitmap(12, 12);
+		bmp.antialiased_line(1.5, 1.5, 10.5, 7.5, WHITE, BlendFunction::Blend);
+		let mut swapped = new_bitmap(12, 12);
+		swapped.antialiased_line(10.5, 7.5, 1.5, 1.5, WHITE, BlendFunction::Blend);
+		assert_eq!(bmp.pixels(), swapped.pixels());
+		// each column of the line has a total intensity of one pixel
+		for x in 2..=9 {
+			let total: u32 = (0..12).map(|y| intensity(&bmp, x, y) as u32).sum();
+			assert!((254..=256).contains(&total), "{} total {}", x, total);
+		}
+		// steep lines are drawn the same way, but transposed
+		let mut steep = new_bitmap(12, 12);
+		steep.antialiased_line(1.5, 1.5, 7.5, 10.5, WHITE, BlendFunction::Blend);
+		for y in 0..12 {
+			for x in 0..12 {
+				assert_eq!(intensity(&bmp, x, y), intensity(&steep, y, x), "{}, {}", x, y);
+			}
+		}
+	}
+
+	#[test]
+	pub fn antialiased_ellipses() {
+		let mut bmp = new_bitmap(20, 20);
+		bmp.antialiased_filled_circle(10.0, 10.0, 6.0, WHITE, BlendFunction::Blend);
+		assert_eq!(255, intensity(&bmp, 10, 10));
+		assert_eq!(255, intensity(&bmp, 7, 12));
+		assert_eq!(0, intensity(&bmp, 0, 0));
+		assert_eq!(0, intensity(&bmp, 17, 10));
+		assert_eq!(255, intensity(&bmp, 14, 10));
+		// the pixel at 15,10 has its center ~5.52 pixels away from the center of the circle
+		assert_eq!(249, intensity(&bmp, 15, 10));
+		// the pixel at 16,10 has its center ~6.52 pixels away from the center of the circle
+		assert_eq!(0, intensity(&bmp, 16, 10));
+		// the filled circle is symmetric
+		for y in 0..20 {
+			for x in 0..20 {
+				assert_eq!(intensity(&bmp, x, y), intensity(&bmp, 19 - x, y), "{}, {}", x, y);
+				assert_eq!(intensity(&bmp, x, y), intensity(&bmp, y, x), "{}, {}", x, y);
+			}
+		}
+
+		let mut bmp = new_bitmap(20, 20);
+		bmp.antialiased_circle(10.0, 10.0, 6.0, WHITE, BlendFunction::Blend);
+		assert_eq!(0, intensity(&bmp, 10, 10));
+		assert_eq!(0, intensity(&bmp, 0, 0));
+		assert_eq!(133, intensity(&bmp, 15, 10));
+		assert_eq!(123, intensity(&bmp, 16, 10));
+		assert_eq!(0, intensity(&bmp, 14, 10));
+
+		// an ellipse with equal radii is a circle
+		let mut ellipse = new_bitmap(20, 20);
+		ellipse.antialiased_ellipse(10.0, 10.0, 6.0, 6.0, WHITE, BlendFunction::Blend);
+		assert_eq!(bmp.pixels(), ellipse.pixels());
+
+		let mut bmp = new_bitmap(20, 20);
+		bmp.antialiased_filled_ellipse(10.0, 10.0, 8.0, 3.0, WHITE, BlendFunction::Blend);
+		assert_eq!(255, intensity(&bmp, 3, 10));
+		assert_eq!(0, intensity(&bmp, 10, 5));
+		assert_eq!(0, intensity(&bmp, 1, 10));
+		assert_eq!(255, intensity(&bmp, 10, 11));
+	}
+
+	#[test]
+	pub fn antialiased_filled_polygon() {
+		// a square exactly covering whole pixels
+		let square = [Vector2::new(2.0, 2.0), Vector2::new(6.0, 2.0), Vector2::new(6.0, 5.0), Vector2::new(2.0, 5.0)];
+		let mut bmp = new_bitmap(8, 8);
+		bmp.antialiased_filled_polygon(&square, PolygonFillRule::NonZero, WHITE, BlendFunction::Blend);
+		let mut expected = new_bitmap(8, 8);
+		expected.filled_rect(2, 2, 5, 4, WHITE);
+		assert_eq!(expected.pixels(), bmp.pixels());
+
+		// offset by half a pixel, so the edges only cover half of each pixel and the corners only a quarter
+		let square = [Vector2::new(1.5, 1.5), Vector2::new(5.5, 1.5), Vector2::new(5.5, 4.5), Vector2::new(1.5, 4.5)];
+		let mut bmp = new_bitmap(8, 8);
+		bmp.antialiased_filled_polygon(&square, PolygonFillRule::NonZero, WHITE, BlendFunction::Blend);
+		assert_eq!(64, intensity(&bmp, 1, 1));
+		assert_eq!(128, intensity(&bmp, 3, 1));
+		assert_eq!(64, intensity(&bmp, 5, 1));
+		assert_eq!(128, intensity(&bmp, 1, 3));
+		assert_eq!(255, intensity(&bmp, 3, 3));
+		assert_eq!(128, intensity(&bmp, 5, 3));
+		assert_eq!(64, intensity(&bmp, 1, 4));
+		assert_eq!(0, intensity(&bmp, 0, 0));
+		assert_eq!(0, intensity(&bmp, 6, 3));
+
+		// a triangle that covers half of a single pixel
+		let mut bmp = new_bitmap(4, 4);
+		bmp.antialiased_filled_polygon(
+			&[Vector2::new(1.0, 1.0), Vector2::new(2.0, 1.0), Vector2::new(1.0, 2.0)],
+			PolygonFillRule::EvenOdd,
+			WHITE,
+			BlendFunction::Blend,
+		);
+		assert_eq!(128, intensity(&bmp, 1, 1));
+		assert_eq!(0, intensity(&bmp, 2, 1));
+		assert_eq!(0, intensity(&bmp, 1, 2));
+
+		// an outer square with an inner square traced in the same direction, joined by a bridge
+		let points = [
+			Vector2::new(0.0, 0.0),
+			Vector2::new(8.0, 0.0),
+			Vector2::new(8.0, 8.0),
+			Vector2::new(0.0, 8.0),
+			Vector2::new(0.0, 0.0),
+			Vector2::new(2.0, 2.0),
+			Vector2::new(6.0, 2.0),
+			Vector2::new(6.0, 6.0),
+			Vector2::new(2.0, 6.0),
+			Vector2::new(2.0, 2.0),
+		];
+		let mut bmp = new_bitmap(8, 8);
+		bmp.antialiased_filled_polygon(&points, PolygonFillRule::EvenOdd, WHITE, BlendFunction::Blend);
+		assert_eq!(0, intensity(&bmp, 4, 4));
+		assert_eq!(255, intensity(&bmp, 7, 4));
+		let mut bmp = new_bitmap(8, 8);
+		bmp.antialiased_filled_polygon(&points, PolygonFillRule::NonZero, WHITE, BlendFunction::Blend);
+		assert_eq!(255, intensity(&bmp, 4, 4));
+		assert_eq!(255, intensity(&bmp, 7, 4));
+	}
+
+	#[test]
+	pub fn antialiasing_composes_with_alpha() {
+		let half_transparent = RGBA::from_rgba([255, 255, 255, 128]);
+
+		let mut bmp = new_bitmap(8, 8);
+		bmp.antialiased_filled_polygon(
+			&[Vector2::new(1.5, 1.0), Vector2::new(5.0, 1.0), Vector2::new(5.0, 5.0), Vector2::new(1.5, 5.0)],
+			PolygonFillRule::NonZero,
+			half_transparent,
+			BlendFunction::Blend,
+		);
+		assert_eq!(128, intensity(&bmp, 3, 3));
+		assert_eq!(64, intensity(&bmp, 1, 3));
+
+		let mut bmp = new_bitmap(12, 5);
+		bmp.antialiased_line(1.5, 2.5, 10.5, 2.5, WHITE, BlendFunction::BlendSourceWithAlpha(128));
+		assert_eq!(128, intensity(&bmp, 5, 2));
+	}
+}
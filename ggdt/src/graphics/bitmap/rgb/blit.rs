@@ -1,6 +1,7 @@
 use crate::graphics::{
-	clip_blit, per_pixel_blit, per_pixel_flipped_blit, per_pixel_rotozoom_blit, BitmapAtlas, BlendFunction, RgbaBitmap,
-	RGBA,
+	clip_blit, clip_scaled_blit, for_each_nine_slice_part, for_each_tile, per_pixel_blit, per_pixel_flipped_blit,
+	per_pixel_rotozoom_blit, per_pixel_scaled_flipped_blit, scaled_blit_region, BitmapAtlas, BitmapView, BitmapViewMut,
+	BlendFunction, NineSlice, RgbaBitmap, RGBA,
 };
 use crate::math::Rect;
 
@@ -108,6 +109,88 @@ pub enum RgbaBlitMethod {
 		transparent_color: RGBA,
 		blend: BlendFunction,
 	},
+	/// Scaled blit, works the same as [RgbaBlitMethod::Solid] except that the drawn image is scaled
+	/// independently along the x and y axis. Much faster than [RgbaBlitMethod::RotoZoom] for
+	/// scaling without any rotation.
+	Scaled {
+		scale_x: f32,
+		scale_y: f32,
+	},
+	ScaledTinted {
+		scale_x: f32,
+		scale_y: f32,
+		tint_color: RGBA,
+	},
+	ScaledBlended {
+		scale_x: f32,
+		scale_y: f32,
+		blend: BlendFunction,
+	},
+	/// Same as [RgbaBlitMethod::Scaled] but the drawn image can also be flipped horizontally
+	/// and/or vertically.
+	ScaledFlipped {
+		scale_x: f32,
+		scale_y: f32,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+	},
+	ScaledFlippedTinted {
+		scale_x: f32,
+		scale_y: f32,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+		tint_color: RGBA,
+	},
+	ScaledFlippedBlended {
+		scale_x: f32,
+		scale_y: f32,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+		blend: BlendFunction,
+	},
+	/// Same as [RgbaBlitMethod::Scaled] except that the specified source color pixels are skipped.
+	ScaledTransparent {
+		scale_x: f32,
+		scale_y: f32,
+		transparent_color: RGBA,
+	},
+	ScaledTransparentTinted {
+		scale_x: f32,
+		scale_y: f32,
+		transparent_color: RGBA,
+		tint_color: RGBA,
+	},
+	ScaledTransparentBlended {
+		scale_x: f32,
+		scale_y: f32,
+		transparent_color: RGBA,
+		blend: BlendFunction,
+	},
+	/// Same as [RgbaBlitMethod::ScaledTransparent] but the drawn image can also be flipped
+	/// horizontally and/or vertically.
+	ScaledTransparentFlipped {
+		scale_x: f32,
+		scale_y: f32,
+		transparent_color: RGBA,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+	},
+	ScaledTransparentFlippedTinted {
+		scale_x: f32,
+		scale_y: f32,
+		transparent_color: RGBA,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+		tint_color: RGBA,
+	},
+	ScaledTransparentFlippedBlended {
+		scale_x: f32,
+		scale_y: f32,
+		transparent_color: RGBA,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+		blend: BlendFunction,
+	},
 }
 
 impl RgbaBitmap {
@@ -439,6 +522,28 @@ impl RgbaBitmap {
 			RotoZoomTransparentTinted { .. } => {}
 			RotoZoomTransparentBlended { .. } => {}
 
+			// scaled blits are clipped as they are drawn (see clip_scaled_blit), but can still be skipped
+			// entirely here if they would be drawn completely outside of the clipping region
+			Scaled { scale_x, scale_y }
+			| ScaledTinted { scale_x, scale_y, .. }
+			| ScaledBlended { scale_x, scale_y, .. }
+			| ScaledFlipped { scale_x, scale_y, .. }
+			| ScaledFlippedTinted { scale_x, scale_y, .. }
+			| ScaledFlippedBlended { scale_x, scale_y, .. }
+			| ScaledTransparent { scale_x, scale_y, .. }
+			| ScaledTransparentTinted { scale_x, scale_y, .. }
+			| ScaledTransparentBlended { scale_x, scale_y, .. }
+			| ScaledTransparentFlipped { scale_x, scale_y, .. }
+			| ScaledTransparentFlippedTinted { scale_x, scale_y, .. }
+			| ScaledTransparentFlippedBlended { scale_x, scale_y, .. } => {
+				let mut dest_region = scaled_blit_region(&src_region, dest_x, dest_y, scale_x, scale_y);
+				let mut skipped_x = 0;
+				let mut skipped_y = 0;
+				if !clip_scaled_blit(self.clip_region(), &mut dest_region, &mut skipped_x, &mut skipped_y) {
+					return;
+				}
+			}
+
 			// set axis flip arguments
 			SolidFlipped { horizontal_flip, vertical_flip, .. }
 			| SolidFlippedTinted { horizontal_flip, vertical_flip, .. }
@@ -491,6 +596,24 @@ impl RgbaBitmap {
 	) {
 		use RgbaBlitMethod::*;
 		match method {
+			Scaled { scale_x, scale_y }
+			| ScaledTinted { scale_x, scale_y, .. }
+			| ScaledBlended { scale_x, scale_y, .. }
+			| ScaledTransparent { scale_x, scale_y, .. }
+			| ScaledTransparentTinted { scale_x, scale_y, .. }
+			| ScaledTransparentBlended { scale_x, scale_y, .. } => {
+				let dest_region = scaled_blit_region(src_region, dest_x, dest_y, scale_x, scale_y);
+				self.scaled_blit_region_unchecked(&method, src, src_region, &dest_region, false, false)
+			}
+			ScaledFlipped { scale_x, scale_y, horizontal_flip, vertical_flip }
+			| ScaledFlippedTinted { scale_x, scale_y, horizontal_flip, vertical_flip, .. }
+			| ScaledFlippedBlended { scale_x, scale_y, horizontal_flip, vertical_flip, .. }
+			| ScaledTransparentFlipped { scale_x, scale_y, horizontal_flip, vertical_flip, .. }
+			| ScaledTransparentFlippedTinted { scale_x, scale_y, horizontal_flip, vertical_flip, .. }
+			| ScaledTransparentFlippedBlended { scale_x, scale_y, horizontal_flip, vertical_flip, .. } => {
+				let dest_region = scaled_blit_region(src_region, dest_x, dest_y, scale_x, scale_y);
+				self.scaled_blit_region_unchecked(&method, src, src_region, &dest_region, horizontal_flip, vertical_flip)
+			}
 			Solid => self.solid_blit(src, src_region, dest_x, dest_y),
 			SolidTinted(tint_color) => self.solid_tinted_blit(src, src_region, dest_x, dest_y, tint_color),
 			SolidBlended(blend) => self.solid_blended_blit(src, src_region, dest_x, dest_y, blend),
@@ -561,6 +684,11 @@ impl RgbaBitmap {
 		}
 	}
 
+	#[inline]
+	pub fn blit_view(&mut self, method: RgbaBlitMethod, src: &BitmapView<RGBA>, x: i32, y: i32) {
+		self.blit_region(method, src.bitmap(), src.region(), x, y);
+	}
+
 	#[inline]
 	pub unsafe fn blit_unchecked(&mut self, method: RgbaBlitMethod, src: &Self, x: i32, y: i32) {
 		let src_region = Rect::new(0, 0, src.width, src.height);
@@ -580,4 +708,250 @@ impl RgbaBitmap {
 			self.blit_region_unchecked(method, src.bitmap(), src_region, x, y);
 		}
 	}
+
+	// draws the source region scaled to fit the destination region, applying the per-pixel behaviour of the given
+	// blit method. any flipping, rotation or scaling that the blit method itself specifies is ignored in favour of
+	// the destination region and flip arguments given.
+	unsafe fn scaled_blit_region_unchecked(
+		&mut self,
+		method: &RgbaBlitMethod,
+		src: &Self,
+		src_region: &Rect,
+		dest_region: &Rect,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+	) {
+		use RgbaBlitMethod::*;
+		match method {
+			Solid | SolidFlipped { .. } | RotoZoom { .. } | Scaled { .. } | ScaledFlipped { .. } => {
+				per_pixel_scaled_flipped_blit(
+					self,
+					src,
+					src_region,
+					dest_region,
+					horizontal_flip,
+					vertical_flip,
+					|src_pixels, dest_pixels| {
+						*dest_pixels = *src_pixels;
+					},
+				)
+			}
+			SolidTinted(tint_color)
+			| SolidFlippedTinted { tint_color, .. }
+			| RotoZoomTinted { tint_color, .. }
+			| ScaledTinted { tint_color, .. }
+			| ScaledFlippedTinted { tint_color, .. } => per_pixel_scaled_flipped_blit(
+				self,
+				src,
+				src_region,
+				dest_region,
+				horizontal_flip,
+				vertical_flip,
+				|src_pixels, dest_pixels| {
+					*dest_pixels = (*src_pixels).tint(*tint_color);
+				},
+			),
+			SolidBlended(blend)
+			| SolidFlippedBlended { blend, .. }
+			| RotoZoomBlended { blend, .. }
+			| ScaledBlended { blend, .. }
+			| ScaledFlippedBlended { blend, .. } => per_pixel_scaled_flipped_blit(
+				self,
+				src,
+				src_region,
+				dest_region,
+				horizontal_flip,
+				vertical_flip,
+				|src_pixels, dest_pixels| {
+					*dest_pixels = blend.blend(*src_pixels, *dest_pixels);
+				},
+			),
+			Transparent(transparent_color)
+			| TransparentFlipped { transparent_color, .. }
+			| RotoZoomTransparent { transparent_color, .. }
+			| ScaledTransparent { transparent_color, .. }
+			| ScaledTransparentFlipped { transparent_color, .. } => per_pixel_scaled_flipped_blit(
+				self,
+				src,
+				src_region,
+				dest_region,
+				horizontal_flip,
+				vertical_flip,
+				|src_pixels, dest_pixels| {
+					if *src_pixels != *transparent_color {
+						*dest_pixels = *src_pixels;
+					}
+				},
+			),
+			TransparentTinted { transparent_color, tint_color }
+			| TransparentFlippedTinted { transparent_color, tint_color, .. }
+			| RotoZoomTransparentTinted { transparent_color, tint_color, .. }
+			| ScaledTransparentTinted { transparent_color, tint_color, .. }
+			| ScaledTransparentFlippedTinted { transparent_color, tint_color, .. } => per_pixel_scaled_flipped_blit(
+				self,
+				src,
+				src_region,
+				dest_region,
+				horizontal_flip,
+				vertical_flip,
+				|src_pixels, dest_pixels| {
+					if *src_pixels != *transparent_color {
+						*dest_pixels = (*src_pixels).tint(*tint_color);
+					}
+				},
+			),
+			TransparentBlended { transparent_color, blend }
+			| TransparentFlippedBlended { transparent_color, blend, .. }
+			| RotoZoomTransparentBlended { transparent_color, blend, .. }
+			| ScaledTransparentBlended { transparent_color, blend, .. }
+			| ScaledTransparentFlippedBlended { transparent_color, blend, .. } => per_pixel_scaled_flipped_blit(
+				self,
+				src,
+				src_region,
+				dest_region,
+				horizontal_flip,
+				vertical_flip,
+				|src_pixels, dest_pixels| {
+					if *src_pixels != *transparent_color {
+						*dest_pixels = blend.blend(*src_pixels, *dest_pixels);
+					}
+				},
+			),
+			TransparentSingle { transparent_color, draw_color }
+			| TransparentFlippedSingle { transparent_color, draw_color, .. } => per_pixel_scaled_flipped_blit(
+				self,
+				src,
+				src_region,
+				dest_region,
+				horizontal_flip,
+				vertical_flip,
+				|src_pixels, dest_pixels| {
+					if *src_pixels != *transparent_color {
+						*dest_pixels = *draw_color;
+					}
+				},
+			),
+		}
+	}
+
+	/// Draws the source region as a nine-slice panel filling the given destination region. The corners of
+	/// the source region are drawn as-is, while the edges and center are stretched or tiled as specified by
+	/// the [`NineSlice`]. The per-pixel behaviour of the blit method (transparency, tinting, blending, etc) is
+	/// applied, but any flipping, rotation or scaling it would normally perform is ignored.
+	pub fn blit_nine_slice_region(
+		&mut self,
+		method: RgbaBlitMethod,
+		src: &Self,
+		src_region: &Rect,
+		slice: &NineSlice,
+		dest_region: &Rect,
+	) {
+		let mut src_region = *src_region;
+		if !src_region.clamp_to(&src.clip_region) {
+			return;
+		}
+
+		let clip_region = *self.clip_region();
+		for_each_nine_slice_part(slice, &src_region, dest_region, &clip_region, |src_part, dest_part| unsafe {
+			self.scaled_blit_region_unchecked(&method, src, src_part, dest_part, false, false);
+		});
+	}
+
+	#[inline]
+	pub fn blit_nine_slice(&mut self, method: RgbaBlitMethod, src: &Self, slice: &NineSlice, dest_region: &Rect) {
+		let src_region = Rect::new(0, 0, src.width, src.height);
+		self.blit_nine_slice_region(method, src, &src_region, slice, dest_region);
+	}
+
+	#[inline]
+	pub fn blit_atlas_nine_slice(
+		&mut self,
+		method: RgbaBlitMethod,
+		src: &BitmapAtlas<Self>,
+		index: usize,
+		slice: &NineSlice,
+		dest_region: &Rect,
+	) {
+		if let Some(src_region) = src.get(index) {
+			self.blit_nine_slice_region(method, src.bitmap(), src_region, slice, dest_region);
+		}
+	}
+
+	/// Fills the given destination region by repeatedly drawing the source region across it. The offsets
+	/// scroll the repeated pattern, such that the source pixel at (`offset_x`, `offset_y`) is drawn at the
+	/// top-left of the destination region. The per-pixel behaviour of the blit method (transparency,
+	/// tinting, blending, etc) is applied, but any flipping, rotation or scaling it would normally perform
+	/// is ignored.
+	pub fn blit_tiled_region(
+		&mut self,
+		method: RgbaBlitMethod,
+		src: &Self,
+		src_region: &Rect,
+		dest_region: &Rect,
+		offset_x: i32,
+		offset_y: i32,
+	) {
+		let mut src_region = *src_region;
+		if !src_region.clamp_to(&src.clip_region) {
+			return;
+		}
+
+		let clip_region = *self.clip_region();
+		for_each_tile(&src_region, dest_region, &clip_region, offset_x, offset_y, |src_part, dest_part| unsafe {
+			self.scaled_blit_region_unchecked(&method, src, src_part, dest_part, false, false);
+		});
+	}
+
+	#[inline]
+	pub fn blit_tiled(&mut self, method: RgbaBlitMethod, src: &Self, dest_region: &Rect, offset_x: i32, offset_y: i32) {
+		let src_region = Rect::new(0, 0, src.width, src.height);
+		self.blit_tiled_region(method, src, &src_region, dest_region, offset_x, offset_y);
+	}
+
+	#[inline]
+	pub fn blit_atlas_tiled(
+		&mut self,
+		method: RgbaBlitMethod,
+		src: &BitmapAtlas<Self>,
+		index: usize,
+		dest_region: &Rect,
+		offset_x: i32,
+		offset_y: i32,
+	) {
+		if let Some(src_region) = src.get(index) {
+			self.blit_tiled_region(method, src.bitmap(), src_region, dest_region, offset_x, offset_y);
+		}
+	}
+}
+
+impl BitmapViewMut<'_, RGBA> {
+	pub fn blit_region(
+		&mut self,
+		method: RgbaBlitMethod,
+		src: &RgbaBitmap,
+		src_region: &Rect,
+		dest_x: i32,
+		dest_y: i32,
+	) {
+		let (offset_x, offset_y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.blit_region(method, src, src_region, dest_x + offset_x, dest_y + offset_y));
+	}
+
+	#[inline]
+	pub fn blit(&mut self, method: RgbaBlitMethod, src: &RgbaBitmap, x: i32, y: i32) {
+		let src_region = Rect::new(0, 0, src.width(), src.height());
+		self.blit_region(method, src, &src_region, x, y);
+	}
+
+	#[inline]
+	pub fn blit_atlas(&mut self, method: RgbaBlitMethod, src: &BitmapAtlas<RgbaBitmap>, index: usize, x: i32, y: i32) {
+		if let Some(src_region) = src.get(index) {
+			self.blit_region(method, src.bitmap(), src_region, x, y);
+		}
+	}
+
+	#[inline]
+	pub fn blit_view(&mut self, method: RgbaBlitMethod, src: &BitmapView<RGBA>, x: i32, y: i32) {
+		self.blit_region(method, src.bitmap(), src.region(), x, y);
+	}
 }
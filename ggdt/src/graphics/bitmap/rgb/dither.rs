@@ -0,0 +1,235 @@
+use crate::graphics::{IndexedBitmap, Palette, PaletteLookup, RgbaBitmap};
+
+// (x offset, y offset, weight) of each neighbouring pixel that quantization error is spread to
+type ErrorDiffusionKernel = (&'static [(i32, i32, i32)], i32);
+
+static FLOYD_STEINBERG_KERNEL: ErrorDiffusionKernel = (&[(1, 0, 7), (-1, 1, 3), (0, 1, 5), (1, 1, 1)], 16);
+
+// note that atkinson dithering only diffuses 6/8ths of the error, on purpose
+static ATKINSON_KERNEL: ErrorDiffusionKernel =
+	(&[(1, 0, 1), (2, 0, 1), (-1, 1, 1), (0, 1, 1), (1, 1, 1), (0, 2, 1)], 8);
+
+#[rustfmt::skip]
+static BAYER_4X4_MATRIX: [[u8; 4]; 4] = [
+	[ 0,  8,  2, 10],
+	[12,  4, 14,  6],
+	[ 3, 11,  1,  9],
+	[15,  7, 13,  5],
+];
+
+#[rustfmt::skip]
+static BAYER_8X8_MATRIX: [[u8; 8]; 8] = [
+	[ 0, 32,  8, 40,  2, 34, 10, 42],
+	[48, 16, 56, 24, 50, 18, 58, 26],
+	[12, 44,  4, 36, 14, 46,  6, 38],
+	[60, 28, 52, 20, 62, 30, 54, 22],
+	[ 3, 35, 11, 43,  1, 33,  9, 41],
+	[51, 19, 59, 27, 49, 17, 57, 25],
+	[15, 47,  7, 39, 13, 45,  5, 37],
+	[63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// The amount that ordered dithering will adjust colors by, from the lowest to the highest threshold.
+const BAYER_SPREAD: f32 = 32.0;
+
+/// The dithering method to use when converting an [`RgbaBitmap`] to an [`IndexedBitmap`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DitherMode {
+	/// No dithering. Each pixel is converted to the closest matching palette color.
+	Nearest,
+	/// Floyd-Steinberg error diffusion dithering.
+	FloydSteinberg,
+	/// Atkinson error diffusion dithering. Only part of the error is diffused, giving results with more contrast
+	/// than Floyd-Steinberg dithering, at the cost of detail being lost in very bright and very dark areas.
+	Atkinson,
+	/// Ordered dithering using a 4x4 Bayer threshold matrix.
+	Bayer4x4,
+	/// Ordered dithering using an 8x8 Bayer threshold matrix.
+	Bayer8x8,
+}
+
+#[inline]
+fn clamp_component(value: i32) -> u8 {
+	value.clamp(0, 255) as u8
+}
+
+impl RgbaBitmap {
+	/// Makes an [`IndexedBitmap`] copy of this bitmap, converting each pixel to the closest matching color in the
+	/// given palette and optionally dithering the result. Alpha components are ignored.
+	///
+	/// # Arguments
+	///
+	/// * `palette`: the 256 colour palette to use during pixel conversion
+	/// * `mode`: the dithering method to use
+	///
+	/// returns: `IndexedBitmap`
+	pub fn to_indexed(&self, palette: &Palette, mode: DitherMode) -> IndexedBitmap {
+		let mut output = IndexedBitmap::new(self.width, self.height).unwrap();
+		self.copy_as_indexed_to(output.pixels_mut(), palette, mode);
+		output
+	}
+
+	/// Copies and converts the entire pixel data from this bitmap to a destination expecting 8-bit indexed pixels,
+	/// converting each pixel to the closest matching color in the given palette and optionally dithering the
+	/// result. Alpha components are ignored.
+	///
+	/// # Arguments
+	///
+	/// * `dest`: destination 8-bit indexed pixel buffer to copy converted pixels to
+	/// * `palette`: the 256 colour palette to use during pixel conversion
+	/// * `mode`: the dithering method to use
+	pub fn copy_as_indexed_to(&self, dest: &mut [u8], palette: &Palette, mode: DitherMode) {
+		let lookup = PaletteLookup::new(palette);
+		match mode {
+			DitherMode::Nearest => {
+				for (src, dest) in self.pixels().iter().zip(dest.iter_mut()) {
+					*dest = lookup.find_color(src.r(), src.g(), src.b());
+				}
+			}
+			DitherMode::FloydSteinberg => self.error_diffusion_dither(dest, palette, &lookup, &FLOYD_STEINBERG_KERNEL),
+			DitherMode::Atkinson => self.error_diffusion_dither(dest, palette, &lookup, &ATKINSON_KERNEL),
+			DitherMode::Bayer4x4 => self.ordered_dither(dest, &lookup, &BAYER_4X4_MATRIX),
+			DitherMode::Bayer8x8 => self.ordered_dither(dest, &lookup, &BAYER_8X8_MATRIX),
+		}
+	}
+
+	fn error_diffusion_dither(
+		&self,
+		dest: &mut [u8],
+		palette: &Palette,
+		lookup: &PaletteLookup,
+		kernel: &ErrorDiffusionKernel,
+	) {
+		let (offsets, divisor) = kernel;
+		let width = self.width as i32;
+		let height = self.height as i32;
+
+		// accumulated quantization error for each pixel (r, g, b)
+		let mut errors = vec![[0i32; 3]; self.pixels.len()];
+
+		for y in 0..height {
+			for x in 0..width {
+				let index = (y * width + x) as usize;
+				let src = self.pixels[index];
+				let error = errors[index];
+				let r = clamp_component(src.r() as i32 + error[0] / divisor);
+				let g = clamp_component(src.g() as i32 + error[1] / divisor);
+				let b = clamp_component(src.b() as i32 + error[2] / divisor);
+
+				let color = lookup.find_color(r, g, b);
+				dest[index] = color;
+
+				let found = palette[color];
+				let quantization_error =
+					[r as i32 - found.r() as i32, g as i32 - found.g() as i32, b as i32 - found.b() as i32];
+
+				for (offset_x, offset_y, weight) in offsets.iter() {
+					let error_x = x + offset_x;
+					let error_y = y + offset_y;
+					if error_x < 0 || error_x >= width || error_y >= height {
+						continue;
+					}
+					let error = &mut errors[(error_y * width + error_x) as usize];
+					for (error, quantization_error) in error.iter_mut().zip(quantization_error.iter()) {
+						*error += quantization_error * weight;
+					}
+				}
+			}
+		}
+	}
+
+	fn ordered_dither<const N: usize>(&self, dest: &mut [u8], lookup: &PaletteLookup, matrix: &[[u8; N]; N]) {
+		let levels = (N * N) as f32;
+		for (y, (src_row, dest_row)) in
+			self.pixels.chunks_exact(self.width as usize).zip(dest.chunks_exact_mut(self.width as usize)).enumerate()
+		{
+			let thresholds = &matrix[y % N];
+			for (x, (src, dest)) in src_row.iter().zip(dest_row.iter_mut()).enumerate() {
+				let threshold = thresholds[x % N] as f32;
+				let adjustment = (((threshold + 0.5) / levels) - 0.5) * BAYER_SPREAD;
+				let adjust = |value: u8| clamp_component((value as f32 + adjustment).round() as i32);
+				*dest = lookup.find_color(adjust(src.r()), adjust(src.g()), adjust(src.b()));
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::graphics::RGBA;
+
+	use super::*;
+
+	fn black_and_white_palette() -> Palette {
+		let mut palette = Palette::new_with_default(255, 255, 255);
+		palette[0] = RGBA::from_rgb([0, 0, 0]);
+		palette
+	}
+
+	fn grey_bitmap(value: u8) -> RgbaBitmap {
+		let mut bmp = RgbaBitmap::new(16, 16).unwrap();
+		bmp.clear(RGBA::from_rgb([value, value, value]));
+		bmp
+	}
+
+	fn count_white(bmp: &IndexedBitmap) -> usize {
+		bmp.pixels().iter().filter(|pixel| **pixel != 0).count()
+	}
+
+	#[test]
+	pub fn nearest_color_conversion() {
+		let palette = Palette::new_vga_palette().unwrap();
+		let mut bmp = RgbaBitmap::new(4, 1).unwrap();
+		bmp.pixels_mut().copy_from_slice(&[palette[0], palette[15], palette[40], palette[200]]);
+		let indexed = bmp.to_indexed(&palette, DitherMode::Nearest);
+		assert_eq!(&[0, 15, 40, 200], indexed.pixels());
+
+		let indexed = grey_bitmap(100).to_indexed(&black_and_white_palette(), DitherMode::Nearest);
+		assert_eq!(0, count_white(&indexed));
+		let indexed = grey_bitmap(160).to_indexed(&black_and_white_palette(), DitherMode::Nearest);
+		assert_eq!(256, count_white(&indexed));
+	}
+
+	#[test]
+	pub fn error_diffusion_dithering() {
+		let palette = black_and_white_palette();
+
+		let indexed = grey_bitmap(128).to_indexed(&palette, DitherMode::FloydSteinberg);
+		let white = count_white(&indexed);
+		assert!((120..=136).contains(&white), "{} white pixels", white);
+
+		let indexed = grey_bitmap(64).to_indexed(&palette, DitherMode::FloydSteinberg);
+		let white = count_white(&indexed);
+		assert!((56..=72).contains(&white), "{} white pixels", white);
+
+		// atkinson doesn't diffuse all of the error, but should still result in a reasonable mix
+		let indexed = grey_bitmap(128).to_indexed(&palette, DitherMode::Atkinson);
+		let white = count_white(&indexed);
+		assert!((96..=160).contains(&white), "{} white pixels", white);
+
+		// colors already in the palette should not be dithered at all
+		let indexed = grey_bitmap(255).to_indexed(&palette, DitherMode::FloydSteinberg);
+		assert_eq!(256, count_white(&indexed));
+		let indexed = grey_bitmap(0).to_indexed(&palette, DitherMode::Atkinson);
+		assert_eq!(0, count_white(&indexed));
+	}
+
+	#[test]
+	pub fn ordered_dithering() {
+		let mut palette = Palette::new_with_default(255, 255, 255);
+		palette[0] = RGBA::from_rgb([100, 100, 100]);
+		palette[1] = RGBA::from_rgb([110, 110, 110]);
+
+		// exactly halfway between two colors, so half of the thresholds should push it one way
+		let indexed = grey_bitmap(105).to_indexed(&palette, DitherMode::Bayer4x4);
+		assert_eq!(128, indexed.pixels().iter().filter(|pixel| **pixel == 1).count());
+		assert_eq!(indexed.pixels()[0..4], indexed.pixels()[64..68]);
+
+		let indexed = grey_bitmap(105).to_indexed(&palette, DitherMode::Bayer8x8);
+		let count = indexed.pixels().iter().filter(|pixel| **pixel == 1).count();
+		assert!((120..=136).contains(&count), "{} pixels", count);
+
+		let indexed = grey_bitmap(105).to_indexed(&palette, DitherMode::Nearest);
+		assert_eq!(0, indexed.pixels().iter().filter(|pixel| **pixel == 1).count());
+	}
+}
@@ -1,14 +1,22 @@
-use byteorder::ReadBytesExt;
+use std::fs::File;
+use std::io::{BufReader, Seek};
 use std::path::Path;
 
-use crate::graphics::{Bitmap, BitmapError, Palette, RGBA};
+use byteorder::ReadBytesExt;
+
+use crate::graphics::{Bitmap, BitmapError, BitmapFileType, Palette, RGBA};
 
+mod antialias;
 mod blit;
+mod dither;
 mod primitives;
+mod scale;
 mod triangles;
 
 pub use blit::*;
+pub use dither::*;
 pub use primitives::*;
+pub use scale::*;
 pub use triangles::*;
 
 pub type RgbaBitmap = Bitmap<RGBA>;
@@ -60,27 +68,62 @@ impl RgbaBitmap {
 		Ok(bitmap)
 	}
 
-	pub fn load_file(path: &Path) -> Result<(Self, Option<Palette>), BitmapError> {
-		if let Some(extension) = path.extension() {
-			let extension = extension.to_ascii_lowercase();
-			match extension.to_str() {
-				Some("png") => Ok(Self::load_png_file(path)?),
-				Some("pcx") => {
-					let (bmp, palette) = Self::load_pcx_file(path)?;
-					Ok((bmp, Some(palette)))
-				}
-				Some("gif") => {
-					let (bmp, palette) = Self::load_gif_file(path)?;
-					Ok((bmp, Some(palette)))
-				}
-				Some("iff") | Some("lbm") | Some("pbm") | Some("bbm") => {
-					let (bmp, palette) = Self::load_iff_file(path)?;
-					Ok((bmp, Some(palette)))
-				}
-				_ => Err(BitmapError::UnknownFileType(String::from("Unrecognized file extension"))),
+	/// Loads an image from the given reader, automatically detecting the file type from its contents. Any of the
+	/// file types listed in [`BitmapFileType`] can be loaded. A palette is also returned if the image was stored
+	/// using one.
+	///
+	/// # Arguments
+	///
+	/// * `reader`: the reader to load the image from
+	///
+	/// returns: `Result<(RgbaBitmap, Option<Palette>), BitmapError>`
+	pub fn load_bytes<T: ReadBytesExt + Seek>(reader: &mut T) -> Result<(Self, Option<Palette>), BitmapError> {
+		match BitmapFileType::detect(reader)? {
+			Some(file_type) => Self::load_bytes_as(reader, file_type),
+			None => Err(BitmapError::UnknownFileType(String::from("Unrecognized file contents"))),
+		}
+	}
+
+	fn load_bytes_as<T: ReadBytesExt + Seek>(
+		reader: &mut T,
+		file_type: BitmapFileType,
+	) -> Result<(Self, Option<Palette>), BitmapError> {
+		use BitmapFileType::*;
+		match file_type {
+			Png => Ok(Self::load_png_bytes(reader)?),
+			Pcx => {
+				let (bmp, palette) = Self::load_pcx_bytes(reader)?;
+				Ok((bmp, Some(palette)))
+			}
+			Gif => {
+				let (bmp, palette) = Self::load_gif_bytes(reader)?;
+				Ok((bmp, Some(palette)))
 			}
-		} else {
-			Err(BitmapError::UnknownFileType(String::from("No file extension")))
+			Iff => {
+				let (bmp, palette) = Self::load_iff_bytes(reader)?;
+				Ok((bmp, Some(palette)))
+			}
+			Bmp => Ok(Self::load_bmp_bytes(reader)?),
+			Tga => Ok(Self::load_tga_bytes(reader)?),
+			Netpbm => Ok((Self::load_netpbm_bytes(reader)?, None)),
+			Qoi => Ok((Self::load_qoi_bytes(reader)?, None)),
+		}
+	}
+
+	/// Loads an image from the given file. The file type is detected from the file's contents, falling back to
+	/// the file extension only if the contents could not be identified.
+	///
+	/// # Arguments
+	///
+	/// * `path`: the path of the file to load
+	///
+	/// returns: `Result<(RgbaBitmap, Option<Palette>), BitmapError>`
+	pub fn load_file(path: &Path) -> Result<(Self, Option<Palette>), BitmapError> {
+		let f = File::open(path)?;
+		let mut reader = BufReader::new(f);
+		match BitmapFileType::detect(&mut reader)?.or_else(|| BitmapFileType::from_extension(path)) {
+			Some(file_type) => Self::load_bytes_as(&mut reader, file_type),
+			None => Err(BitmapError::UnknownFileType(String::from("Unrecognized file contents and file extension"))),
 		}
 	}
 }
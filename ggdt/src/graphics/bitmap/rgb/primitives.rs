@@ -1,4 +1,5 @@
-use crate::graphics::{BlendFunction, RgbaBitmap, RGBA};
+use crate::graphics::{BitmapViewMut, BlendFunction, FillConnectivity, LineCap, PolygonFillRule, RgbaBitmap, RGBA};
+use crate::math::{Curve, Vector2};
 
 impl RgbaBitmap {
 	/// Sets the pixel at the given coordinates using a blended color via the specified blend function
@@ -87,4 +88,426 @@ impl RgbaBitmap {
 			|dest_color| blend.blend(color, dest_color),
 		);
 	}
+
+	/// Flood fills the area containing the given coordinates, the same as [`RgbaBitmap::flood_fill`], except that
+	/// pixels do not need to exactly match the color of the starting pixel to be filled. Any connected pixel where
+	/// each of its red, green, blue and alpha components are within the given tolerance of the starting pixel's
+	/// color will be filled. A tolerance of zero is equivalent to [`RgbaBitmap::flood_fill`].
+	pub fn tolerance_flood_fill(&mut self, x: i32, y: i32, color: RGBA, tolerance: u8, connectivity: FillConnectivity) {
+		if let Some(target) = self.get_pixel(x, y) {
+			let target = target.to_array();
+			self.fill_region(
+				x,
+				y,
+				connectivity,
+				|pixel| pixel.to_array().iter().zip(target.iter()).all(|(a, b)| a.abs_diff(*b) <= tolerance),
+				|_, _, _| color,
+			);
+		}
+	}
+
+	/// Draws the outline of an ellipse formed by the center point and radii given by blending the drawn pixels using
+	/// the given blend function.
+	#[inline]
+	pub fn blended_ellipse(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.ellipse_custom(center_x, center_y, radius_x, radius_y, |dest_color| blend.blend(color, dest_color));
+	}
+
+	/// Draws a filled ellipse formed by the center point and radii given by blending the drawn pixels using the given
+	/// blend function.
+	#[inline]
+	pub fn blended_filled_ellipse(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.filled_ellipse_custom(center_x, center_y, radius_x, radius_y, |dest_color| blend.blend(color, dest_color));
+	}
+
+	/// Draws an arc of an ellipse between the start and end angles (see [`RgbaBitmap::arc`]) by blending the drawn pixels
+	/// using the given blend function.
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_arc(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.arc_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |dest_color| {
+			blend.blend(color, dest_color)
+		});
+	}
+
+	/// Draws the outline of a pie slice of an ellipse between the start and end angles (see [`RgbaBitmap::pie`]) by
+	/// blending the drawn pixels using the given blend function.
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_pie(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.pie_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |dest_color| {
+			blend.blend(color, dest_color)
+		});
+	}
+
+	/// Draws a filled pie slice of an ellipse between the start and end angles (see [`RgbaBitmap::filled_pie`]) by blending
+	/// the drawn pixels using the given blend function.
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_filled_pie(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.filled_pie_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |dest_color| {
+			blend.blend(color, dest_color)
+		});
+	}
+
+	/// Draws an empty box (rectangle) with rounded corners using the points x1,y1 and x2,y2 to form the box to be drawn
+	/// by blending the drawn pixels using the given blend function.
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_rounded_rect(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		radius: u32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.rounded_rect_custom(x1, y1, x2, y2, radius, |dest_color| blend.blend(color, dest_color));
+	}
+
+	/// Draws a filled box (rectangle) with rounded corners using the points x1,y1 and x2,y2 to form the box to be drawn
+	/// by blending the drawn pixels using the given blend function.
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_filled_rounded_rect(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		radius: u32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.filled_rounded_rect_custom(x1, y1, x2, y2, radius, |dest_color| blend.blend(color, dest_color));
+	}
+
+	/// Draws a series of connected lines between each of the given points in turn by blending the drawn pixels using
+	/// the given blend function.
+	#[inline]
+	pub fn blended_polyline(&mut self, points: &[Vector2], color: RGBA, blend: BlendFunction) {
+		self.polyline_custom(points, |dest_color| blend.blend(color, dest_color));
+	}
+
+	/// Draws the outline of a polygon formed by the given points by blending the drawn pixels using the given blend
+	/// function.
+	#[inline]
+	pub fn blended_polygon(&mut self, points: &[Vector2], color: RGBA, blend: BlendFunction) {
+		self.polygon_custom(points, |dest_color| blend.blend(color, dest_color));
+	}
+
+	/// Draws a filled polygon formed by the given points, using the given fill rule by blending the drawn pixels using
+	/// the given blend function.
+	#[inline]
+	pub fn blended_filled_polygon(
+		&mut self,
+		points: &[Vector2],
+		fill_rule: PolygonFillRule,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.filled_polygon_custom(points, fill_rule, |dest_color| blend.blend(color, dest_color));
+	}
+
+	/// Draws a line from x1,y1 to x2,y2 with the given thickness and cap style by blending the drawn pixels using the
+	/// given blend function.
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_thick_line(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		thickness: u32,
+		cap: LineCap,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.thick_line_custom(x1, y1, x2, y2, thickness, cap, |dest_color| blend.blend(color, dest_color));
+	}
+
+	/// Draws the given curve by blending the drawn pixels using the given blend function.
+	#[inline]
+	pub fn blended_curve<C: Curve + ?Sized>(&mut self, curve: &C, color: RGBA, blend: BlendFunction) {
+		self.curve_custom(curve, |dest_color| blend.blend(color, dest_color));
+	}
+
+	/// Draws the given curve with the given thickness and cap style by blending the drawn pixels using the given
+	/// blend function.
+	#[inline]
+	pub fn blended_thick_curve<C: Curve + ?Sized>(
+		&mut self,
+		curve: &C,
+		thickness: u32,
+		cap: LineCap,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.thick_curve_custom(curve, thickness, cap, |dest_color| blend.blend(color, dest_color));
+	}
+}
+
+impl BitmapViewMut<'_, RGBA> {
+	/// Sets the pixel at the given local coordinates using a blended color via the specified blend function. If
+	/// the coordinates lie outside of the view's clipping region, no pixels will be changed.
+	#[inline]
+	pub fn set_blended_pixel(&mut self, x: i32, y: i32, color: RGBA, blend: BlendFunction) {
+		let (offset_x, offset_y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.set_blended_pixel(x + offset_x, y + offset_y, color, blend));
+	}
+
+	/// Draws a line from x1,y1 to x2,y2 by blending the drawn pixels using the given blend function.
+	pub fn blended_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: RGBA, blend: BlendFunction) {
+		let (x, y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.blended_line(x1 + x, y1 + y, x2 + x, y2 + y, color, blend));
+	}
+
+	/// Draws a horizontal line from x1,y to x2,y by blending the drawn pixels using the given
+	/// blend function.
+	pub fn blended_horiz_line(&mut self, x1: i32, x2: i32, y: i32, color: RGBA, blend: BlendFunction) {
+		let (offset_x, offset_y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.blended_horiz_line(x1 + offset_x, x2 + offset_x, y + offset_y, color, blend));
+	}
+
+	/// Draws a vertical line from x,y1 to x,y2 by blending the drawn pixels using the given blend
+	/// function.
+	pub fn blended_vert_line(&mut self, x: i32, y1: i32, y2: i32, color: RGBA, blend: BlendFunction) {
+		let (offset_x, offset_y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.blended_vert_line(x + offset_x, y1 + offset_y, y2 + offset_y, color, blend));
+	}
+
+	/// Draws an empty box (rectangle) using the points x1,y1 and x2,y2 to form the box to be
+	/// drawn, by blending the drawn pixels using the given blend function.
+	pub fn blended_rect(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: RGBA, blend: BlendFunction) {
+		let (x, y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.blended_rect(x1 + x, y1 + y, x2 + x, y2 + y, color, blend));
+	}
+
+	/// Draws a filled box (rectangle) using the points x1,y1 and x2,y2 to form the box to be
+	/// drawn, by blending the drawn pixels using the given blend function.
+	pub fn blended_filled_rect(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: RGBA, blend: BlendFunction) {
+		let (x, y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.blended_filled_rect(x1 + x, y1 + y, x2 + x, y2 + y, color, blend));
+	}
+
+	/// Flood fills the area containing the given coordinates, the same as [`RgbaBitmap::tolerance_flood_fill`].
+	pub fn tolerance_flood_fill(&mut self, x: i32, y: i32, color: RGBA, tolerance: u8, connectivity: FillConnectivity) {
+		let (offset_x, offset_y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| {
+			bitmap.tolerance_flood_fill(x + offset_x, y + offset_y, color, tolerance, connectivity)
+		});
+	}
+
+	#[inline]
+	pub fn blended_ellipse(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.ellipse_custom(center_x, center_y, radius_x, radius_y, |dest_color| blend.blend(color, dest_color));
+	}
+
+	#[inline]
+	pub fn blended_filled_ellipse(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.filled_ellipse_custom(center_x, center_y, radius_x, radius_y, |dest_color| blend.blend(color, dest_color));
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_arc(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.arc_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |dest_color| {
+			blend.blend(color, dest_color)
+		});
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_pie(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.pie_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |dest_color| {
+			blend.blend(color, dest_color)
+		});
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_filled_pie(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.filled_pie_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |dest_color| {
+			blend.blend(color, dest_color)
+		});
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_rounded_rect(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		radius: u32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.rounded_rect_custom(x1, y1, x2, y2, radius, |dest_color| blend.blend(color, dest_color));
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_filled_rounded_rect(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		radius: u32,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.filled_rounded_rect_custom(x1, y1, x2, y2, radius, |dest_color| blend.blend(color, dest_color));
+	}
+
+	#[inline]
+	pub fn blended_polyline(&mut self, points: &[Vector2], color: RGBA, blend: BlendFunction) {
+		self.polyline_custom(points, |dest_color| blend.blend(color, dest_color));
+	}
+
+	#[inline]
+	pub fn blended_polygon(&mut self, points: &[Vector2], color: RGBA, blend: BlendFunction) {
+		self.polygon_custom(points, |dest_color| blend.blend(color, dest_color));
+	}
+
+	#[inline]
+	pub fn blended_filled_polygon(
+		&mut self,
+		points: &[Vector2],
+		fill_rule: PolygonFillRule,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.filled_polygon_custom(points, fill_rule, |dest_color| blend.blend(color, dest_color));
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	pub fn blended_thick_line(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		thickness: u32,
+		cap: LineCap,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.thick_line_custom(x1, y1, x2, y2, thickness, cap, |dest_color| blend.blend(color, dest_color));
+	}
+
+	#[inline]
+	pub fn blended_curve<C: Curve + ?Sized>(&mut self, curve: &C, color: RGBA, blend: BlendFunction) {
+		self.curve_custom(curve, |dest_color| blend.blend(color, dest_color));
+	}
+
+	#[inline]
+	pub fn blended_thick_curve<C: Curve + ?Sized>(
+		&mut self,
+		curve: &C,
+		thickness: u32,
+		cap: LineCap,
+		color: RGBA,
+		blend: BlendFunction,
+	) {
+		self.thick_curve_custom(curve, thickness, cap, |dest_color| blend.blend(color, dest_color));
+	}
 }
@@ -0,0 +1,126 @@
+use std::simd;
+use std::simd::SimdUint;
+
+use crate::graphics::{BitmapError, RgbaBitmap, RGBA};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RgbaScaleMethod {
+	/// Nearest-neighbour sampling. Works for any scale factor, both up and down.
+	Nearest,
+	/// Bilinear filtering, smoothly interpolating between the four nearest source pixels. Best suited for
+	/// upscaling or for downscaling by less than half.
+	Bilinear,
+	/// Box filtering, averaging all of the source pixels covered by each destination pixel. Best suited for
+	/// downscaling (e.g. generating thumbnails). When upscaling, each destination pixel averages the one or two
+	/// source pixels (in each direction) that it overlaps.
+	Box,
+	/// Upscales using the Scale2x (a.k.a. EPX) pixel-art scaler, followed by nearest-neighbour sampling if the
+	/// final size is not exactly 2x the original size.
+	Scale2x,
+	/// Upscales using the Scale3x pixel-art scaler, followed by nearest-neighbour sampling if the final size is not
+	/// exactly 3x the original size.
+	Scale3x,
+	/// Upscales using the "hqx-style" pixel-art scaler, followed by nearest-neighbour sampling if the final size is
+	/// not exactly 2x the original size.
+	Hq2x,
+}
+
+impl RgbaBitmap {
+	/// Returns a copy of this bitmap scaled to the given size using the specified scaling method. The current
+	/// clipping region is ignored, the entire bitmap is always scaled.
+	///
+	/// # Arguments
+	///
+	/// * `width`: the width of the scaled bitmap
+	/// * `height`: the height of the scaled bitmap
+	/// * `method`: the scaling method to use
+	///
+	/// returns: `Result<RgbaBitmap, BitmapError>`
+	pub fn scaled(&self, width: u32, height: u32, method: RgbaScaleMethod) -> Result<Self, BitmapError> {
+		let mut dest = Self::new(width, height)?;
+		self.scale_into(&mut dest, method);
+		Ok(dest)
+	}
+
+	/// Scales this bitmap using the specified scaling method such that it fills the entire destination bitmap
+	/// given. The current clipping regions of both bitmaps are ignored.
+	///
+	/// # Arguments
+	///
+	/// * `dest`: the destination bitmap to scale this bitmap's pixels into
+	/// * `method`: the scaling method to use
+	pub fn scale_into(&self, dest: &mut Self, method: RgbaScaleMethod) {
+		use RgbaScaleMethod::*;
+		match method {
+			Nearest => self.nearest_scale_into(dest),
+			Bilinear => self.bilinear_scale_into(dest),
+			Box => self.box_scale_into(dest),
+			Scale2x => self.scale2x().nearest_scale_into(dest),
+			Scale3x => self.scale3x().nearest_scale_into(dest),
+			Hq2x => self.hq2x().nearest_scale_into(dest),
+		}
+	}
+
+	/// Scales the entire contents of this bitmap using bilinear filtering so that it fills the entire destination
+	/// bitmap given, regardless of its current clipping region.
+	///
+	/// # Arguments
+	///
+	/// * `dest`: the destination bitmap to scale this bitmap's pixels into
+	pub fn bilinear_scale_into(&self, dest: &mut Self) {
+		// calculates the two source coordinates to be sampled along one axis, and the interpolation amount between
+		// them, for the given destination coordinate. samples are taken from the center of each pixel
+		fn sample_coords(dest_coord: u32, src_size: u32, dest_size: u32) -> (i32, i32, f32) {
+			let src_coord = ((dest_coord as f32 + 0.5) * (src_size as f32 / dest_size as f32) - 0.5).max(0.0);
+			let coord1 = (src_coord as i32).min(src_size as i32 - 1);
+			let coord2 = (coord1 + 1).min(src_size as i32 - 1);
+			(coord1, coord2, src_coord - coord1 as f32)
+		}
+
+		let xs = (0..dest.width).map(|x| sample_coords(x, self.width, dest.width)).collect::<Vec<_>>();
+
+		for y in 0..dest.height {
+			let (y1, y2, ty) = sample_coords(y, self.height, dest.height);
+			for (x, (x1, x2, tx)) in xs.iter().enumerate() {
+				unsafe {
+					let top = self.get_pixel_unchecked(*x1, y1).lerp(self.get_pixel_unchecked(*x2, y1), *tx);
+					let bottom = self.get_pixel_unchecked(*x1, y2).lerp(self.get_pixel_unchecked(*x2, y2), *tx);
+					dest.set_pixel_unchecked(x as i32, y as i32, top.lerp(bottom, ty));
+				}
+			}
+		}
+	}
+
+	/// Scales the entire contents of this bitmap using box filtering so that it fills the entire destination
+	/// bitmap given, regardless of its current clipping region. Each destination pixel is the average of all of
+	/// the source pixels that it covers.
+	///
+	/// # Arguments
+	///
+	/// * `dest`: the destination bitmap to scale this bitmap's pixels into
+	pub fn box_scale_into(&self, dest: &mut Self) {
+		// calculates the range of source coordinates covered by the given destination coordinate along one axis.
+		// this always covers at least one source pixel
+		fn covered_range(dest_coord: u32, src_size: u32, dest_size: u32) -> (u32, u32) {
+			let start = ((dest_coord as u64 * src_size as u64) / dest_size as u64) as u32;
+			let end = (((dest_coord as u64 + 1) * src_size as u64).div_ceil(dest_size as u64)) as u32;
+			(start, end.max(start + 1).min(src_size))
+		}
+
+		let xs = (0..dest.width).map(|x| covered_range(x, self.width, dest.width)).collect::<Vec<_>>();
+
+		for y in 0..dest.height {
+			let (y1, y2) = covered_range(y, self.height, dest.height);
+			for (x, (x1, x2)) in xs.iter().enumerate() {
+				let mut sum = simd::u32x4::splat(0);
+				for src_y in y1..y2 {
+					for src_x in *x1..*x2 {
+						sum += unsafe { self.get_pixel_unchecked(src_x as i32, src_y as i32) }.0.cast();
+					}
+				}
+				let count = simd::u32x4::splat((x2 - x1) * (y2 - y1));
+				unsafe { dest.set_pixel_unchecked(x as i32, y as i32, RGBA((sum / count).cast())) };
+			}
+		}
+	}
+}
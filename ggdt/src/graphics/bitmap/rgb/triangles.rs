@@ -1,7 +1,7 @@
 use std::simd;
 use std::simd::{SimdFloat, SimdUint};
 
-use crate::graphics::{edge_function, per_pixel_triangle_2d, BlendFunction, RgbaBitmap, RGBA};
+use crate::graphics::{edge_function, per_pixel_triangle_2d, BitmapViewMut, BlendFunction, RgbaBitmap, RGBA};
 use crate::math::Vector2;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -69,6 +69,32 @@ pub enum RgbaTriangle2d<'a> {
 	},
 }
 
+impl RgbaTriangle2d<'_> {
+	// returns a copy of this triangle with all of its vertex positions moved by the given offset
+	fn translated(&self, offset: Vector2) -> Self {
+		use RgbaTriangle2d::*;
+		let mut triangle = *self;
+		match &mut triangle {
+			Solid { position, .. }
+			| SolidBlended { position, .. }
+			| SolidMultiColor { position, .. }
+			| SolidMultiColorBlended { position, .. }
+			| SolidTextured { position, .. }
+			| SolidTexturedColored { position, .. }
+			| SolidTexturedColoredBlended { position, .. }
+			| SolidTexturedMultiColored { position, .. }
+			| SolidTexturedMultiColoredBlended { position, .. }
+			| SolidTexturedTinted { position, .. }
+			| SolidTexturedBlended { position, .. } => {
+				for vertex in position.iter_mut() {
+					*vertex += offset;
+				}
+			}
+		}
+		triangle
+	}
+}
+
 impl RgbaBitmap {
 	pub fn solid_triangle_2d(&mut self, positions: &[Vector2; 3], color: RGBA) {
 		per_pixel_triangle_2d(
@@ -374,3 +400,16 @@ impl RgbaBitmap {
 		}
 	}
 }
+
+impl BitmapViewMut<'_, RGBA> {
+	pub fn triangle_2d(&mut self, triangle: &RgbaTriangle2d) {
+		let triangle = triangle.translated(Vector2::new(self.x() as f32, self.y() as f32));
+		self.with_bitmap(|bitmap| bitmap.triangle_2d(&triangle));
+	}
+
+	pub fn triangle_list_2d(&mut self, triangles: &[RgbaTriangle2d]) {
+		for triangle in triangles.iter() {
+			self.triangle_2d(triangle);
+		}
+	}
+}
@@ -0,0 +1,404 @@
+use crate::graphics::{Bitmap, Pixel, RGBA};
+
+/// Pixel operations required by the "smoothing" pixel-art upscalers, which need to be able to compare pixels for
+/// similarity and mix multiple pixels together.
+pub trait InterpolatablePixel: Pixel {
+	/// Returns true if this pixel and the other pixel given should be considered the "same" color for the purposes
+	/// of edge detection.
+	fn is_similar(&self, other: Self) -> bool;
+
+	/// Mixes the given pixels together using the associated weights. Pixel types which cannot represent arbitrary
+	/// colors (e.g. palette indices) instead return the pixel with the largest weight.
+	fn mix(pixels: &[(Self, u32)]) -> Self;
+}
+
+impl InterpolatablePixel for u8 {
+	#[inline]
+	fn is_similar(&self, other: Self) -> bool {
+		*self == other
+	}
+
+	#[inline]
+	fn mix(pixels: &[(Self, u32)]) -> Self {
+		// palette indices cannot be blended together, so the best we can do is pick the most dominant one.
+		// max_by_key() returns the last maximum element, but we want the first one in the case of a tie.
+		let mut result = pixels[0];
+		for &pixel in pixels.iter().skip(1) {
+			if pixel.1 > result.1 {
+				result = pixel;
+			}
+		}
+		result.0
+	}
+}
+
+// YUV difference thresholds, as used by the original hqx algorithms
+const SIMILAR_Y_THRESHOLD: i32 = 48;
+const SIMILAR_U_THRESHOLD: i32 = 7;
+const SIMILAR_V_THRESHOLD: i32 = 6;
+const SIMILAR_A_THRESHOLD: i32 = 16;
+
+#[inline]
+fn rgb_to_yuv(color: RGBA) -> (i32, i32, i32) {
+	let r = color.r() as i32;
+	let g = color.g() as i32;
+	let b = color.b() as i32;
+	let y = (r + g + b) >> 2;
+	let u = 128 + ((r - b) >> 2);
+	let v = 128 + ((2 * g - r - b) >> 3);
+	(y, u, v)
+}
+
+impl InterpolatablePixel for RGBA {
+	#[inline]
+	fn is_similar(&self, other: Self) -> bool {
+		if *self == other {
+			return true;
+		}
+		let (y1, u1, v1) = rgb_to_yuv(*self);
+		let (y2, u2, v2) = rgb_to_yuv(other);
+		(y1 - y2).abs() <= SIMILAR_Y_THRESHOLD
+			&& (u1 - u2).abs() <= SIMILAR_U_THRESHOLD
+			&& (v1 - v2).abs() <= SIMILAR_V_THRESHOLD
+			&& (self.a() as i32 - other.a() as i32).abs() <= SIMILAR_A_THRESHOLD
+	}
+
+	#[inline]
+	fn mix(pixels: &[(Self, u32)]) -> Self {
+		let mut sum = [0u32; 4];
+		let mut total_weight = 0;
+		for (pixel, weight) in pixels.iter() {
+			for (sum, component) in sum.iter_mut().zip(pixel.to_array()) {
+				*sum += component as u32 * *weight;
+			}
+			total_weight += *weight;
+		}
+		if total_weight == 0 {
+			return pixels[0].0;
+		}
+		RGBA::from_rgba(sum.map(|component| (component / total_weight) as u8))
+	}
+}
+
+/// Returns the 3x3 neighbourhood of pixels around the given coordinates (in the order: top-left, top, top-right,
+/// left, center, right, bottom-left, bottom, bottom-right). Coordinates outside of the bitmap are clamped to the
+/// nearest edge pixel.
+#[inline]
+fn neighbourhood<PixelType: Pixel>(src: &Bitmap<PixelType>, x: i32, y: i32) -> [PixelType; 9] {
+	let left = (x - 1).max(0);
+	let right = (x + 1).min(src.right() as i32);
+	let top = (y - 1).max(0);
+	let bottom = (y + 1).min(src.bottom() as i32);
+	unsafe {
+		[
+			src.get_pixel_unchecked(left, top),
+			src.get_pixel_unchecked(x, top),
+			src.get_pixel_unchecked(right, top),
+			src.get_pixel_unchecked(left, y),
+			src.get_pixel_unchecked(x, y),
+			src.get_pixel_unchecked(right, y),
+			src.get_pixel_unchecked(left, bottom),
+			src.get_pixel_unchecked(x, bottom),
+			src.get_pixel_unchecked(right, bottom),
+		]
+	}
+}
+
+/// Computes one corner of the 2x2 block of pixels output by the "hqx-style" upscaler for a single source pixel.
+/// `center` is the source pixel, `diagonal` is the source pixel diagonally adjacent to it in the direction of the
+/// corner, `side1` and `side2` are the source pixels vertically and horizontally adjacent to it in the direction of
+/// the corner, and `opposite1` and `opposite2` are the source pixels on the opposite sides of `side1` and `side2`.
+#[inline]
+fn hq2x_corner<PixelType: InterpolatablePixel>(
+	center: PixelType,
+	diagonal: PixelType,
+	side1: PixelType,
+	side2: PixelType,
+	opposite1: PixelType,
+	opposite2: PixelType,
+) -> PixelType {
+	if side1.is_similar(side2)
+		&& !center.is_similar(side1)
+		&& !side1.is_similar(opposite1)
+		&& !side2.is_similar(opposite2)
+	{
+		// an edge is crossing through this corner, so round it off. this is the same situation in which scale2x
+		// would replace this corner with the side pixel, so the side pixels dominate here
+		PixelType::mix(&[(side1, 3), (side2, 3), (center, 2)])
+	} else if center.is_similar(side1) && center.is_similar(side2) && !center.is_similar(diagonal) {
+		// the corner of some other shape is touching this one diagonally, soften it very slightly
+		PixelType::mix(&[(center, 3), (diagonal, 1)])
+	} else {
+		center
+	}
+}
+
+impl<PixelType: Pixel> Bitmap<PixelType> {
+	/// Scales the entire contents of this bitmap using nearest-neighbour sampling so that it fills the entire
+	/// destination bitmap given, regardless of its current clipping region.
+	///
+	/// # Arguments
+	///
+	/// * `dest`: the destination bitmap to scale this bitmap's pixels into
+	pub fn nearest_scale_into(&self, dest: &mut Self) {
+		if dest.width == self.width && dest.height == self.height {
+			dest.pixels.copy_from_slice(&self.pixels);
+			return;
+		}
+
+		// pre-calculate the source x coordinate for each destination x coordinate, sampling from the center
+		// of each destination pixel
+		let src_xs = (0..dest.width as u64)
+			.map(|x| (((x * 2 + 1) * self.width as u64) / (dest.width as u64 * 2)) as usize)
+			.collect::<Vec<_>>();
+
+		for (y, dest_row) in dest.pixels.chunks_exact_mut(dest.width as usize).enumerate() {
+			let src_y = ((y as u64 * 2 + 1) * self.height as u64) / (dest.height as u64 * 2);
+			let src_row = &self.pixels[(src_y as usize * self.width as usize)..];
+			for (dest_pixel, src_x) in dest_row.iter_mut().zip(src_xs.iter()) {
+				*dest_pixel = src_row[*src_x];
+			}
+		}
+	}
+
+	/// Returns a copy of this bitmap upscaled to twice its size using the Scale2x (also known as AdvMAME2x) pixel-art
+	/// scaling algorithm. This produces identical results to the EPX ("Eric's Pixel Expansion") algorithm. The
+	/// current clipping region is ignored.
+	pub fn scale2x(&self) -> Self {
+		let mut dest = Self::internal_new(self.width * 2, self.height * 2, Default::default()).unwrap();
+		for y in 0..self.height as i32 {
+			for x in 0..self.width as i32 {
+				let [_, b, _, d, e, f, _, h, _] = neighbourhood(self, x, y);
+
+				let (e0, e1, e2, e3) = if b != h && d != f {
+					(
+						if d == b { d } else { e },
+						if b == f { f } else { e },
+						if d == h { d } else { e },
+						if h == f { f } else { e },
+					)
+				} else {
+					(e, e, e, e)
+				};
+
+				unsafe {
+					dest.set_pixel_unchecked(x * 2, y * 2, e0);
+					dest.set_pixel_unchecked(x * 2 + 1, y * 2, e1);
+					dest.set_pixel_unchecked(x * 2, y * 2 + 1, e2);
+					dest.set_pixel_unchecked(x * 2 + 1, y * 2 + 1, e3);
+				}
+			}
+		}
+		dest
+	}
+
+	/// Returns a copy of this bitmap upscaled to three times its size using the Scale3x (also known as AdvMAME3x)
+	/// pixel-art scaling algorithm. The current clipping region is ignored.
+	pub fn scale3x(&self) -> Self {
+		let mut dest = Self::internal_new(self.width * 3, self.height * 3, Default::default()).unwrap();
+		for y in 0..self.height as i32 {
+			for x in 0..self.width as i32 {
+				let [a, b, c, d, e, f, g, h, i] = neighbourhood(self, x, y);
+
+				let output = if b != h && d != f {
+					[
+						if d == b { d } else { e },
+						if (d == b && e != c) || (b == f && e != a) { b } else { e },
+						if b == f { f } else { e },
+						if (d == b && e != g) || (d == h && e != a) { d } else { e },
+						e,
+						if (b == f && e != i) || (h == f && e != c) { f } else { e },
+						if d == h { d } else { e },
+						if (d == h && e != i) || (h == f && e != g) { h } else { e },
+						if h == f { f } else { e },
+					]
+				} else {
+					[e; 9]
+				};
+
+				for (index, pixel) in output.iter().enumerate() {
+					let dest_x = x * 3 + (index % 3) as i32;
+					let dest_y = y * 3 + (index / 3) as i32;
+					unsafe { dest.set_pixel_unchecked(dest_x, dest_y, *pixel) };
+				}
+			}
+		}
+		dest
+	}
+}
+
+impl<PixelType: InterpolatablePixel> Bitmap<PixelType> {
+	/// Returns a copy of this bitmap upscaled to twice its size using a simplified "hqx-style" pixel-art scaling
+	/// algorithm. Like hq2x, edges are detected by comparing each pixel against its neighbours for similarity and
+	/// are then smoothed by mixing the neighbouring pixels together. For bitmaps whose pixels cannot be mixed
+	/// (such as [`crate::graphics::IndexedBitmap`]), the most dominant pixel is chosen instead, which gives the
+	/// same results as [`Bitmap::scale2x`]. The current clipping region is ignored.
+	pub fn hq2x(&self) -> Self {
+		let mut dest = Self::internal_new(self.width * 2, self.height * 2, Default::default()).unwrap();
+		for y in 0..self.height as i32 {
+			for x in 0..self.width as i32 {
+				let [a, b, c, d, e, f, g, h, i] = neighbourhood(self, x, y);
+				unsafe {
+					dest.set_pixel_unchecked(x * 2, y * 2, hq2x_corner(e, a, b, d, h, f));
+					dest.set_pixel_unchecked(x * 2 + 1, y * 2, hq2x_corner(e, c, b, f, h, d));
+					dest.set_pixel_unchecked(x * 2, y * 2 + 1, hq2x_corner(e, g, h, d, b, f));
+					dest.set_pixel_unchecked(x * 2 + 1, y * 2 + 1, hq2x_corner(e, i, h, f, b, d));
+				}
+			}
+		}
+		dest
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use claim::assert_matches;
+
+	use crate::graphics::{BitmapError, IndexedBitmap, IndexedScaleMethod, RgbaBitmap, RgbaScaleMethod};
+
+	use super::*;
+
+	#[rustfmt::skip]
+	static DIAGONAL_PIXELS: &[u8] = &[
+		0, 0, 0, 0,
+		0, 1, 0, 0,
+		0, 0, 1, 0,
+		0, 0, 0, 0,
+	];
+
+	#[rustfmt::skip]
+	static DIAGONAL_SCALE2X_PIXELS: &[u8] = &[
+		0, 0, 0, 0, 0, 0, 0, 0,
+		0, 0, 0, 0, 0, 0, 0, 0,
+		0, 0, 1, 1, 0, 0, 0, 0,
+		0, 0, 1, 1, 1, 0, 0, 0,
+		0, 0, 0, 1, 1, 1, 0, 0,
+		0, 0, 0, 0, 1, 1, 0, 0,
+		0, 0, 0, 0, 0, 0, 0, 0,
+		0, 0, 0, 0, 0, 0, 0, 0,
+	];
+
+	#[rustfmt::skip]
+	static DIAGONAL_SCALE3X_PIXELS: &[u8] = &[
+		0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		0, 0, 0, 1, 1, 1, 0, 0, 0, 0, 0, 0,
+		0, 0, 0, 1, 1, 1, 0, 0, 0, 0, 0, 0,
+		0, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0, 0,
+		0, 0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 0,
+		0, 0, 0, 0, 0, 0, 1, 1, 1, 0, 0, 0,
+		0, 0, 0, 0, 0, 0, 1, 1, 1, 0, 0, 0,
+		0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+		0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+	];
+
+	fn diagonal_bitmap() -> IndexedBitmap {
+		let mut bmp = IndexedBitmap::new(4, 4).unwrap();
+		bmp.pixels_mut().copy_from_slice(DIAGONAL_PIXELS);
+		bmp
+	}
+
+	#[test]
+	pub fn nearest_scaling() {
+		let mut src = IndexedBitmap::new(2, 2).unwrap();
+		src.pixels_mut().copy_from_slice(&[1, 2, 3, 4]);
+
+		let mut dest = IndexedBitmap::new(4, 4).unwrap();
+		src.nearest_scale_into(&mut dest);
+		assert_eq!(dest.pixels(), &[1, 1, 2, 2, 1, 1, 2, 2, 3, 3, 4, 4, 3, 3, 4, 4]);
+
+		let mut dest = IndexedBitmap::new(3, 1).unwrap();
+		src.nearest_scale_into(&mut dest);
+		assert_eq!(dest.pixels(), &[3, 4, 4]);
+
+		let dest = IndexedBitmap::new(1, 1).unwrap();
+		dest.nearest_scale_into(&mut src);
+		assert_eq!(src.pixels(), &[0, 0, 0, 0]);
+	}
+
+	#[test]
+	pub fn scale2x() {
+		let scaled = diagonal_bitmap().scale2x();
+		assert_eq!(8, scaled.width());
+		assert_eq!(8, scaled.height());
+		assert_eq!(DIAGONAL_SCALE2X_PIXELS, scaled.pixels());
+	}
+
+	#[test]
+	pub fn scale3x() {
+		let scaled = diagonal_bitmap().scale3x();
+		assert_eq!(12, scaled.width());
+		assert_eq!(12, scaled.height());
+		assert_eq!(DIAGONAL_SCALE3X_PIXELS, scaled.pixels());
+	}
+
+	#[test]
+	pub fn hq2x() {
+		// indexed pixels cannot be mixed, so the dominant pixels chosen should match up with scale2x
+		let scaled = diagonal_bitmap().hq2x();
+		assert_eq!(DIAGONAL_SCALE2X_PIXELS, scaled.pixels());
+
+		let black = RGBA::from_rgb([0, 0, 0]);
+		let white = RGBA::from_rgb([255, 255, 255]);
+		let mut src = RgbaBitmap::new(4, 4).unwrap();
+		for (pixel, index) in src.pixels_mut().iter_mut().zip(DIAGONAL_PIXELS.iter()) {
+			*pixel = if *index == 1 { white } else { black };
+		}
+		let scaled = src.hq2x();
+		assert_eq!(black, scaled.get_pixel(0, 0).unwrap());
+		assert_eq!(white, scaled.get_pixel(2, 2).unwrap());
+		assert_eq!(white, scaled.get_pixel(5, 5).unwrap());
+		// the pixels filling in the "staircase" of the diagonal line get smoothed
+		assert_eq!(RGBA::from_rgb([191, 191, 191]), scaled.get_pixel(4, 3).unwrap());
+		assert_eq!(RGBA::from_rgb([191, 191, 191]), scaled.get_pixel(3, 4).unwrap());
+	}
+
+	#[test]
+	pub fn rgba_similarity_and_mixing() {
+		let color = RGBA::from_rgb([100, 100, 100]);
+		assert!(color.is_similar(RGBA::from_rgb([100, 100, 100])));
+		assert!(color.is_similar(RGBA::from_rgb([104, 104, 104])));
+		assert!(!color.is_similar(RGBA::from_rgb([200, 200, 200])));
+		assert!(!color.is_similar(RGBA::from_rgb([140, 100, 60])));
+
+		assert_eq!(
+			RGBA::from_rgba([50, 100, 127, 255]),
+			RGBA::mix(&[(RGBA::from_rgba([0, 0, 0, 255]), 1), (RGBA::from_rgba([100, 200, 255, 255]), 1)])
+		);
+		assert_eq!(3u8, u8::mix(&[(1, 1), (3, 3), (2, 3)]));
+	}
+
+	#[test]
+	pub fn rgba_filtered_scaling() {
+		let black = RGBA::from_rgb([0, 0, 0]);
+		let white = RGBA::from_rgb([255, 255, 255]);
+		let mut src = RgbaBitmap::new(2, 2).unwrap();
+		src.pixels_mut().copy_from_slice(&[black, white, white, black]);
+
+		let scaled = src.scaled(1, 1, RgbaScaleMethod::Box).unwrap();
+		assert_eq!(&[RGBA::from_rgb([127, 127, 127])], scaled.pixels());
+
+		let scaled = src.scaled(4, 4, RgbaScaleMethod::Box).unwrap();
+		assert_eq!(src.scaled(4, 4, RgbaScaleMethod::Nearest).unwrap().pixels(), scaled.pixels());
+
+		let scaled = src.scaled(4, 4, RgbaScaleMethod::Bilinear).unwrap();
+		assert_eq!(black, scaled.get_pixel(0, 0).unwrap());
+		assert_eq!(white, scaled.get_pixel(3, 0).unwrap());
+		assert_eq!(RGBA::from_rgb([63, 63, 63]), scaled.get_pixel(1, 0).unwrap());
+		assert_eq!(RGBA::from_rgb([191, 191, 191]), scaled.get_pixel(2, 0).unwrap());
+
+		assert_matches!(src.scaled(0, 4, RgbaScaleMethod::Bilinear), Err(BitmapError::InvalidDimensions));
+	}
+
+	#[test]
+	pub fn indexed_scaling_methods() {
+		let src = diagonal_bitmap();
+		assert_eq!(DIAGONAL_SCALE2X_PIXELS, src.scaled(8, 8, IndexedScaleMethod::Scale2x).unwrap().pixels());
+		assert_eq!(DIAGONAL_SCALE3X_PIXELS, src.scaled(12, 12, IndexedScaleMethod::Scale3x).unwrap().pixels());
+
+		let scaled = src.scaled(4, 4, IndexedScaleMethod::Scale2x).unwrap();
+		assert_eq!(src.pixels(), scaled.pixels());
+	}
+}
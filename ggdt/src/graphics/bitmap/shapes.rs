@@ -0,0 +1,1182 @@
+use std::f32::consts::TAU;
+
+use crate::graphics::{Bitmap, BitmapViewMut, Pixel};
+use crate::math::{Rect, Vector2};
+
+/// The rule used to determine which areas of a (possibly self-intersecting) polygon are considered to be inside
+/// of it when filling the polygon.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PolygonFillRule {
+	/// Areas are inside if a ray cast from them crosses an odd number of the polygon's edges. Overlapping regions
+	/// of the polygon become "holes".
+	EvenOdd,
+	/// Areas are inside if the polygon's edges wind around them a non-zero number of times, taking into account
+	/// the direction of each edge.
+	NonZero,
+}
+
+impl PolygonFillRule {
+	#[inline]
+	pub(crate) fn is_inside(&self, winding: i32) -> bool {
+		match self {
+			PolygonFillRule::EvenOdd => winding % 2 != 0,
+			PolygonFillRule::NonZero => winding != 0,
+		}
+	}
+}
+
+/// Returns the smallest and largest x and y coordinates found amongst the given points.
+pub(crate) fn points_extents(points: &[Vector2]) -> (Vector2, Vector2) {
+	points.iter().fold((Vector2::new(f32::MAX, f32::MAX), Vector2::new(f32::MIN, f32::MIN)), |(min, max), point| {
+		(Vector2::new(min.x.min(point.x), min.y.min(point.y)), Vector2::new(max.x.max(point.x), max.y.max(point.y)))
+	})
+}
+
+/// Finds each span of the polygon formed by the given points that is filled on the row at `sample_y`, according to
+/// the given fill rule, calling `span_fn` with the start and end x coordinates of each one. `crossings` is only used
+/// as scratch space, so that it can be reused between rows.
+pub(crate) fn for_each_polygon_span(
+	points: &[Vector2],
+	fill_rule: PolygonFillRule,
+	sample_y: f32,
+	crossings: &mut Vec<(f32, i32)>,
+	mut span_fn: impl FnMut(f32, f32),
+) {
+	// x coordinate and winding direction of each edge crossing the row
+	crossings.clear();
+	for (i, a) in points.iter().enumerate() {
+		let b = &points[(i + 1) % points.len()];
+		let (winding, upper, lower) = if a.y < b.y {
+			(1, a, b)
+		} else if a.y > b.y {
+			(-1, b, a)
+		} else {
+			continue; // horizontal edges never cross a row's sample points
+		};
+		if sample_y >= upper.y && sample_y < lower.y {
+			let x = upper.x + (sample_y - upper.y) * (lower.x - upper.x) / (lower.y - upper.y);
+			crossings.push((x, winding));
+		}
+	}
+	crossings.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+
+	let mut winding = 0;
+	let mut span_start = 0.0;
+	for &(x, direction) in crossings.iter() {
+		let was_inside = fill_rule.is_inside(winding);
+		winding += direction;
+		let is_inside = fill_rule.is_inside(winding);
+		if !was_inside && is_inside {
+			span_start = x;
+		} else if was_inside && !is_inside {
+			span_fn(span_start, x);
+		}
+	}
+}
+
+/// The shape drawn at each end of a thick line.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LineCap {
+	/// The line ends exactly at its end points.
+	Butt,
+	/// The line is extended past each of its end points by half of its thickness.
+	Square,
+	/// The line ends with a semi-circle centered on each of its end points.
+	Round,
+}
+
+/// Calls the given function for each point on a line from x1,y1 to x2,y2. The points visited are exactly the
+/// same as those drawn by [`Bitmap::line`].
+pub(crate) fn for_each_line_point(x1: i32, y1: i32, x2: i32, y2: i32, mut point_fn: impl FnMut(i32, i32)) {
+	let delta_x = x2 - x1;
+	let delta_y = y2 - y1;
+	let delta_x_abs = delta_x.abs();
+	let delta_y_abs = delta_y.abs();
+	let delta_x_sign = delta_x.signum();
+	let delta_y_sign = delta_y.signum();
+	let mut x = delta_x_abs / 2;
+	let mut y = delta_y_abs / 2;
+	let mut dx = x1;
+	let mut dy = y1;
+
+	point_fn(dx, dy);
+	if delta_x_abs >= delta_y_abs {
+		for _ in 0..delta_x_abs {
+			y += delta_y_abs;
+			if y >= delta_x_abs {
+				y -= delta_x_abs;
+				dy += delta_y_sign;
+			}
+			dx += delta_x_sign;
+			point_fn(dx, dy);
+		}
+	} else {
+		for _ in 0..delta_y_abs {
+			x += delta_x_abs;
+			if x >= delta_y_abs {
+				x -= delta_y_abs;
+				dx += delta_x_sign;
+			}
+			dy += delta_y_sign;
+			point_fn(dx, dy);
+		}
+	}
+}
+
+// steps through the first octant of a circle of the given radius using the midpoint circle algorithm, calling the
+// given function with each x,y offset from the center. all other points on the circle are reflections of these
+fn for_each_circle_octant_offset(radius: u32, mut offset_fn: impl FnMut(i32, i32)) {
+	let mut x = 0;
+	let mut y = radius as i32;
+	let mut m = 5 - 4 * radius as i32;
+
+	while x <= y {
+		offset_fn(x, y);
+
+		if m > 0 {
+			y -= 1;
+			m -= 8 * y;
+		}
+
+		x += 1;
+		m += 8 * x + 4;
+	}
+}
+
+/// Calls the given function for each point on the outline of a circle formed by the center point and radius given.
+/// The points visited are exactly the same as those drawn by [`Bitmap::circle`].
+pub(crate) fn for_each_circle_point(center_x: i32, center_y: i32, radius: u32, mut point_fn: impl FnMut(i32, i32)) {
+	for_each_circle_octant_offset(radius, |x, y| {
+		point_fn(center_x + x, center_y + y);
+		point_fn(center_x + x, center_y - y);
+		point_fn(center_x - x, center_y + y);
+		point_fn(center_x - x, center_y - y);
+		point_fn(center_x + y, center_y + x);
+		point_fn(center_x + y, center_y - x);
+		point_fn(center_x - y, center_y + x);
+		point_fn(center_x - y, center_y - x);
+	});
+}
+
+/// Calls the given function with the left x, right x and y coordinates of each horizontal span of a filled circle
+/// formed by the center point and radius given. The spans are exactly the same as those drawn by
+/// [`Bitmap::filled_circle`].
+pub(crate) fn for_each_filled_circle_span(
+	center_x: i32,
+	center_y: i32,
+	radius: u32,
+	mut span_fn: impl FnMut(i32, i32, i32),
+) {
+	for_each_circle_octant_offset(radius, |x, y| {
+		span_fn(center_x - x, center_x + x, center_y - y);
+		span_fn(center_x - y, center_x + y, center_y - x);
+		span_fn(center_x - y, center_x + y, center_y + x);
+		span_fn(center_x - x, center_x + x, center_y + y);
+	});
+}
+
+/// Returns the half-width of the row of an ellipse (centered at 0,0) that is `y` pixels from the center, or `None`
+/// if the row lies entirely outside of the ellipse.
+#[inline]
+fn ellipse_half_width(radius_x: u32, radius_y: u32, y: i32) -> Option<i32> {
+	if y.unsigned_abs() > radius_y {
+		return None;
+	}
+	// the extra half pixel added to each radius makes the outermost pixels of each row fall on the edge of the
+	// ellipse in the same way that the midpoint circle algorithm does
+	let radius_x = radius_x as f32 + 0.5;
+	let radius_y = radius_y as f32 + 0.5;
+	let t = y as f32 / radius_y;
+	Some((radius_x * (1.0 - t * t).sqrt()).floor() as i32)
+}
+
+/// Returns true if the given angle lies within the range of angles that are swept through when moving clockwise
+/// (as seen on screen) from `start_angle` to `end_angle`.
+#[inline]
+fn is_angle_within(angle: f32, start_angle: f32, end_angle: f32) -> bool {
+	let sweep = end_angle - start_angle;
+	if sweep >= TAU {
+		true
+	} else {
+		(angle - start_angle).rem_euclid(TAU) <= sweep.rem_euclid(TAU)
+	}
+}
+
+/// Calls the given function with each horizontal span of pixels making up the outline of a shape, where the shape
+/// is described by the given function returning the span of pixels (left x, right x) that are inside the shape for
+/// any given row. Outline pixels are those inside the shape that have one or more horizontally or vertically
+/// adjacent pixels that are outside of it. Each outline pixel is included in exactly one span.
+fn for_each_outline_span(
+	top: i32,
+	bottom: i32,
+	row_span: impl Fn(i32) -> Option<(i32, i32)>,
+	mut span_fn: impl FnMut(i32, i32, i32),
+) {
+	for y in top..=bottom {
+		if let Some((left, right)) = row_span(y) {
+			let interior = match (row_span(y - 1), row_span(y + 1)) {
+				(Some((prev_left, prev_right)), Some((next_left, next_right))) => {
+					Some(((left + 1).max(prev_left).max(next_left), (right - 1).min(prev_right).min(next_right)))
+				}
+				_ => None,
+			};
+			match interior {
+				Some((interior_left, interior_right)) if interior_left <= interior_right => {
+					span_fn(left, interior_left - 1, y);
+					span_fn(interior_right + 1, right, y);
+				}
+				_ => span_fn(left, right, y),
+			}
+		}
+	}
+}
+
+impl<PixelType: Pixel> Bitmap<PixelType> {
+	/// Calls the given pixel function for every pixel within the given bounds (and the bitmap's clipping region)
+	/// for which the `is_inside` function returns true. If `outline_only` is true, only those pixels which also have
+	/// at least one horizontally or vertically adjacent pixel that is not inside are drawn.
+	fn region_custom(
+		&mut self,
+		mut bounds: Rect,
+		outline_only: bool,
+		is_inside: impl Fn(i32, i32) -> bool,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		if !bounds.clamp_to(&self.clip_region) {
+			return;
+		}
+		for y in bounds.y..=bounds.bottom() {
+			for x in bounds.x..=bounds.right() {
+				if is_inside(x, y)
+					&& (!outline_only
+						|| !is_inside(x - 1, y) || !is_inside(x + 1, y)
+						|| !is_inside(x, y - 1) || !is_inside(x, y + 1))
+				{
+					// safety: the bounds were clamped to the clipping region above
+					unsafe { self.set_custom_pixel_unchecked(x, y, &pixel_fn) };
+				}
+			}
+		}
+	}
+
+	/// Draws the outline of an ellipse formed by the center point and radii given.
+	pub fn ellipse(&mut self, center_x: i32, center_y: i32, radius_x: u32, radius_y: u32, color: PixelType) {
+		self.ellipse_custom(center_x, center_y, radius_x, radius_y, |_| color);
+	}
+
+	pub fn ellipse_custom(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		for_each_outline_span(
+			center_y - radius_y as i32,
+			center_y + radius_y as i32,
+			|y| ellipse_half_width(radius_x, radius_y, y - center_y).map(|w| (center_x - w, center_x + w)),
+			|left, right, y| self.horiz_line_custom(left, right, y, &pixel_fn),
+		);
+	}
+
+	/// Draws a filled ellipse formed by the center point and radii given.
+	pub fn filled_ellipse(&mut self, center_x: i32, center_y: i32, radius_x: u32, radius_y: u32, color: PixelType) {
+		self.filled_ellipse_custom(center_x, center_y, radius_x, radius_y, |_| color);
+	}
+
+	pub fn filled_ellipse_custom(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		for y in -(radius_y as i32)..=radius_y as i32 {
+			if let Some(w) = ellipse_half_width(radius_x, radius_y, y) {
+				self.horiz_line_custom(center_x - w, center_x + w, center_y + y, &pixel_fn);
+			}
+		}
+	}
+
+	/// Draws an arc, which is the section of the outline of an ellipse (formed by the center point and radii given)
+	/// lying between the start and end angles. Angles are in radians, where 0 points to the right, and the arc is
+	/// drawn clockwise (as seen on screen) from the start angle to the end angle.
+	#[allow(clippy::too_many_arguments)]
+	pub fn arc(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: PixelType,
+	) {
+		self.arc_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |_| color);
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn arc_custom(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		for_each_outline_span(
+			center_y - radius_y as i32,
+			center_y + radius_y as i32,
+			|y| ellipse_half_width(radius_x, radius_y, y - center_y).map(|w| (center_x - w, center_x + w)),
+			|left, right, y| {
+				for x in left..=right {
+					let angle = ((y - center_y) as f32).atan2((x - center_x) as f32);
+					if is_angle_within(angle, start_angle, end_angle) {
+						self.set_custom_pixel(x, y, &pixel_fn);
+					}
+				}
+			},
+		);
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	fn pie_region_custom(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		outline_only: bool,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		let bounds = Rect::from_coords(
+			center_x - radius_x as i32,
+			center_y - radius_y as i32,
+			center_x + radius_x as i32,
+			center_y + radius_y as i32,
+		);
+		self.region_custom(
+			bounds,
+			outline_only,
+			|x, y| {
+				let (x, y) = (x - center_x, y - center_y);
+				match ellipse_half_width(radius_x, radius_y, y) {
+					Some(w) if x.abs() <= w => {
+						(x == 0 && y == 0) || is_angle_within((y as f32).atan2(x as f32), start_angle, end_angle)
+					}
+					_ => false,
+				}
+			},
+			pixel_fn,
+		);
+	}
+
+	/// Draws the outline of a pie slice, which is the section of an ellipse (formed by the center point and radii
+	/// given) lying between the start and end angles. Angles are specified the same as with [`Bitmap::arc`].
+	#[allow(clippy::too_many_arguments)]
+	pub fn pie(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: PixelType,
+	) {
+		self.pie_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |_| color);
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn pie_custom(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		self.pie_region_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, true, pixel_fn);
+	}
+
+	/// Draws a filled pie slice, which is the section of an ellipse (formed by the center point and radii given)
+	/// lying between the start and end angles. Angles are specified the same as with [`Bitmap::arc`].
+	#[allow(clippy::too_many_arguments)]
+	pub fn filled_pie(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: PixelType,
+	) {
+		self.filled_pie_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |_| color);
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn filled_pie_custom(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		self.pie_region_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, false, pixel_fn);
+	}
+
+	/// Returns the span of pixels (left x, right x) inside the given row of a rounded rectangle.
+	fn rounded_rect_row_span(x1: i32, y1: i32, x2: i32, y2: i32, radius: u32, y: i32) -> Option<(i32, i32)> {
+		if y < y1 || y > y2 {
+			return None;
+		}
+		let radius = radius as i32;
+		let corner_y = if y < y1 + radius {
+			y1 + radius - y
+		} else if y > y2 - radius {
+			y - (y2 - radius)
+		} else {
+			0
+		};
+		let inset = radius - ellipse_half_width(radius as u32, radius as u32, corner_y).unwrap_or(0);
+		Some((x1 + inset, x2 - inset))
+	}
+
+	/// Clamps the given rounded rect coordinates and radius so that the coordinates specify the top-left and
+	/// bottom-right corners and the radius is not larger than half the width or height.
+	fn normalize_rounded_rect(x1: i32, y1: i32, x2: i32, y2: i32, radius: u32) -> (i32, i32, i32, i32, u32) {
+		let (x1, x2) = (x1.min(x2), x1.max(x2));
+		let (y1, y2) = (y1.min(y2), y1.max(y2));
+		let max_radius = ((x2 - x1).min(y2 - y1) / 2) as u32;
+		(x1, y1, x2, y2, radius.min(max_radius))
+	}
+
+	/// Draws an empty box (rectangle) with rounded corners using the points x1,y1 and x2,y2 to form the box to be
+	/// drawn, assuming they are specifying the top-left and bottom-right corners respectively. The radius of the
+	/// corners is limited to half of the width or height of the box, whichever is smaller.
+	pub fn rounded_rect(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, radius: u32, color: PixelType) {
+		self.rounded_rect_custom(x1, y1, x2, y2, radius, |_| color);
+	}
+
+	pub fn rounded_rect_custom(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		radius: u32,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		let (x1, y1, x2, y2, radius) = Self::normalize_rounded_rect(x1, y1, x2, y2, radius);
+		for_each_outline_span(
+			y1,
+			y2,
+			|y| Self::rounded_rect_row_span(x1, y1, x2, y2, radius, y),
+			|left, right, y| self.horiz_line_custom(left, right, y, &pixel_fn),
+		);
+	}
+
+	/// Draws a filled box (rectangle) with rounded corners using the points x1,y1 and x2,y2 to form the box to be
+	/// drawn, assuming they are specifying the top-left and bottom-right corners respectively. The radius of the
+	/// corners is limited to half of the width or height of the box, whichever is smaller.
+	pub fn filled_rounded_rect(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, radius: u32, color: PixelType) {
+		self.filled_rounded_rect_custom(x1, y1, x2, y2, radius, |_| color);
+	}
+
+	pub fn filled_rounded_rect_custom(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		radius: u32,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		let (x1, y1, x2, y2, radius) = Self::normalize_rounded_rect(x1, y1, x2, y2, radius);
+		for y in y1..=y2 {
+			if let Some((left, right)) = Self::rounded_rect_row_span(x1, y1, x2, y2, radius, y) {
+				self.horiz_line_custom(left, right, y, &pixel_fn);
+			}
+		}
+	}
+
+	fn lines_custom(&mut self, points: &[Vector2], closed: bool, pixel_fn: impl Fn(PixelType) -> PixelType) {
+		let to_pixel = |point: &Vector2| (point.x.floor() as i32, point.y.floor() as i32);
+		match points.len() {
+			0 => (),
+			1 => {
+				let (x, y) = to_pixel(&points[0]);
+				self.set_custom_pixel(x, y, &pixel_fn);
+			}
+			_ => {
+				// each line segment is drawn without its end point, as that is the start point of the next segment.
+				// this avoids drawing the same pixel twice, which matters for blended drawing
+				let num_segments = if closed { points.len() } else { points.len() - 1 };
+				for i in 0..num_segments {
+					let (x1, y1) = to_pixel(&points[i]);
+					let (x2, y2) = to_pixel(&points[(i + 1) % points.len()]);
+					for_each_line_point(x1, y1, x2, y2, |x, y| {
+						if x != x2 || y != y2 {
+							self.set_custom_pixel(x, y, &pixel_fn);
+						}
+					});
+				}
+				if !closed {
+					let (x, y) = to_pixel(&points[points.len() - 1]);
+					self.set_custom_pixel(x, y, &pixel_fn);
+				}
+			}
+		}
+	}
+
+	/// Draws a series of connected lines between each of the given points in turn.
+	pub fn polyline(&mut self, points: &[Vector2], color: PixelType) {
+		self.polyline_custom(points, |_| color);
+	}
+
+	pub fn polyline_custom(&mut self, points: &[Vector2], pixel_fn: impl Fn(PixelType) -> PixelType) {
+		self.lines_custom(points, false, pixel_fn);
+	}
+
+	/// Draws the outline of a polygon formed by the given points. The last point is connected back to the first.
+	pub fn polygon(&mut self, points: &[Vector2], color: PixelType) {
+		self.polygon_custom(points, |_| color);
+	}
+
+	pub fn polygon_custom(&mut self, points: &[Vector2], pixel_fn: impl Fn(PixelType) -> PixelType) {
+		self.lines_custom(points, true, pixel_fn);
+	}
+
+	/// Draws a filled polygon formed by the given points. The polygon can be convex or concave and can intersect
+	/// itself, in which case the given fill rule determines which areas are filled. As with triangles, pixels are
+	/// filled if their centers lie inside the polygon, so that polygons sharing an edge do not overlap.
+	pub fn filled_polygon(&mut self, points: &[Vector2], fill_rule: PolygonFillRule, color: PixelType) {
+		self.filled_polygon_custom(points, fill_rule, |_| color);
+	}
+
+	pub fn filled_polygon_custom(
+		&mut self,
+		points: &[Vector2],
+		fill_rule: PolygonFillRule,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		if points.len() < 3 {
+			return;
+		}
+
+		let (min, max) = points_extents(points);
+		let top = (min.y.floor() as i32).max(self.clip_region.y);
+		let bottom = (max.y.ceil() as i32).min(self.clip_region.bottom());
+
+		let mut crossings = Vec::with_capacity(points.len());
+		for y in top..=bottom {
+			for_each_polygon_span(points, fill_rule, y as f32 + 0.5, &mut crossings, |span_start, span_end| {
+				// the pixels whose centers lie within span_start <= x < span_end
+				let left = (span_start - 0.5).ceil() as i32;
+				let right = (span_end - 0.5).ceil() as i32 - 1;
+				if left <= right {
+					self.horiz_line_custom(left, right, y, &pixel_fn);
+				}
+			});
+		}
+	}
+
+	/// Draws a line from x1,y1 to x2,y2 with the given thickness, using the given cap style for the ends of the
+	/// line.
+	#[allow(clippy::too_many_arguments)]
+	pub fn thick_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, thickness: u32, cap: LineCap, color: PixelType) {
+		self.thick_line_custom(x1, y1, x2, y2, thickness, cap, |_| color);
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn thick_line_custom(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		thickness: u32,
+		cap: LineCap,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		if thickness == 0 {
+			return;
+		}
+
+		let half_thickness = thickness as f32 / 2.0;
+		let delta_x = (x2 - x1) as f32;
+		let delta_y = (y2 - y1) as f32;
+		let length = (delta_x * delta_x + delta_y * delta_y).sqrt();
+		let (dir_x, dir_y) = if length > 0.0 { (delta_x / length, delta_y / length) } else { (1.0, 0.0) };
+		let (along_min, along_max) = match cap {
+			LineCap::Square => (-half_thickness, length + half_thickness),
+			LineCap::Butt | LineCap::Round => (0.0, length),
+		};
+
+		let extent = thickness as i32;
+		let bounds =
+			Rect::from_coords(x1.min(x2) - extent, y1.min(y2) - extent, x1.max(x2) + extent, y1.max(y2) + extent);
+		self.region_custom(
+			bounds,
+			false,
+			|x, y| {
+				let px = (x - x1) as f32;
+				let py = (y - y1) as f32;
+				// distance along the line from the start point, and distance from the line itself
+				let along = px * dir_x + py * dir_y;
+				let across = px * -dir_y + py * dir_x;
+				if along >= along_min && along <= along_max && across >= -half_thickness && across < half_thickness {
+					true
+				} else if cap == LineCap::Round {
+					let qx = (x - x2) as f32;
+					let qy = (y - y2) as f32;
+					let radius_squared = half_thickness * half_thickness;
+					(px * px + py * py) < radius_squared || (qx * qx + qy * qy) < radius_squared
+				} else {
+					false
+				}
+			},
+			pixel_fn,
+		);
+	}
+}
+
+impl<PixelType: Pixel> BitmapViewMut<'_, PixelType> {
+	/// Draws the outline of an ellipse formed by the center point and radii given.
+	pub fn ellipse(&mut self, center_x: i32, center_y: i32, radius_x: u32, radius_y: u32, color: PixelType) {
+		self.ellipse_custom(center_x, center_y, radius_x, radius_y, |_| color);
+	}
+
+	pub fn ellipse_custom(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		let (x, y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.ellipse_custom(center_x + x, center_y + y, radius_x, radius_y, pixel_fn));
+	}
+
+	/// Draws a filled ellipse formed by the center point and radii given.
+	pub fn filled_ellipse(&mut self, center_x: i32, center_y: i32, radius_x: u32, radius_y: u32, color: PixelType) {
+		self.filled_ellipse_custom(center_x, center_y, radius_x, radius_y, |_| color);
+	}
+
+	pub fn filled_ellipse_custom(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		let (x, y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| {
+			bitmap.filled_ellipse_custom(center_x + x, center_y + y, radius_x, radius_y, pixel_fn)
+		});
+	}
+
+	/// Draws an arc, which is the section of the outline of an ellipse (formed by the center point and radii given)
+	/// lying between the start and end angles. Angles are specified the same as with [`Bitmap::arc`].
+	#[allow(clippy::too_many_arguments)]
+	pub fn arc(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: PixelType,
+	) {
+		self.arc_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |_| color);
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn arc_custom(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		let (x, y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| {
+			bitmap.arc_custom(center_x + x, center_y + y, radius_x, radius_y, start_angle, end_angle, pixel_fn)
+		});
+	}
+
+	/// Draws the outline of a pie slice, which is the section of an ellipse (formed by the center point and radii
+	/// given) lying between the start and end angles. Angles are specified the same as with [`Bitmap::arc`].
+	#[allow(clippy::too_many_arguments)]
+	pub fn pie(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: PixelType,
+	) {
+		self.pie_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |_| color);
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn pie_custom(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		let (x, y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| {
+			bitmap.pie_custom(center_x + x, center_y + y, radius_x, radius_y, start_angle, end_angle, pixel_fn)
+		});
+	}
+
+	/// Draws a filled pie slice, which is the section of an ellipse (formed by the center point and radii given)
+	/// lying between the start and end angles. Angles are specified the same as with [`Bitmap::arc`].
+	#[allow(clippy::too_many_arguments)]
+	pub fn filled_pie(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		color: PixelType,
+	) {
+		self.filled_pie_custom(center_x, center_y, radius_x, radius_y, start_angle, end_angle, |_| color);
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn filled_pie_custom(
+		&mut self,
+		center_x: i32,
+		center_y: i32,
+		radius_x: u32,
+		radius_y: u32,
+		start_angle: f32,
+		end_angle: f32,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		let (x, y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| {
+			bitmap.filled_pie_custom(center_x + x, center_y + y, radius_x, radius_y, start_angle, end_angle, pixel_fn)
+		});
+	}
+
+	/// Draws an empty box (rectangle) with rounded corners using the points x1,y1 and x2,y2 to form the box to be
+	/// drawn, assuming they are specifying the top-left and bottom-right corners respectively.
+	pub fn rounded_rect(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, radius: u32, color: PixelType) {
+		self.rounded_rect_custom(x1, y1, x2, y2, radius, |_| color);
+	}
+
+	pub fn rounded_rect_custom(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		radius: u32,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		let (x, y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.rounded_rect_custom(x1 + x, y1 + y, x2 + x, y2 + y, radius, pixel_fn));
+	}
+
+	/// Draws a filled box (rectangle) with rounded corners using the points x1,y1 and x2,y2 to form the box to be
+	/// drawn, assuming they are specifying the top-left and bottom-right corners respectively.
+	pub fn filled_rounded_rect(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, radius: u32, color: PixelType) {
+		self.filled_rounded_rect_custom(x1, y1, x2, y2, radius, |_| color);
+	}
+
+	pub fn filled_rounded_rect_custom(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		radius: u32,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		let (x, y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.filled_rounded_rect_custom(x1 + x, y1 + y, x2 + x, y2 + y, radius, pixel_fn));
+	}
+
+	/// Draws a series of connected lines between each of the given points in turn.
+	pub fn polyline(&mut self, points: &[Vector2], color: PixelType) {
+		self.polyline_custom(points, |_| color);
+	}
+
+	pub fn polyline_custom(&mut self, points: &[Vector2], pixel_fn: impl Fn(PixelType) -> PixelType) {
+		let points = self.to_bitmap_points(points);
+		self.with_bitmap(|bitmap| bitmap.polyline_custom(&points, pixel_fn));
+	}
+
+	/// Draws the outline of a polygon formed by the given points. The last point is connected back to the first.
+	pub fn polygon(&mut self, points: &[Vector2], color: PixelType) {
+		self.polygon_custom(points, |_| color);
+	}
+
+	pub fn polygon_custom(&mut self, points: &[Vector2], pixel_fn: impl Fn(PixelType) -> PixelType) {
+		let points = self.to_bitmap_points(points);
+		self.with_bitmap(|bitmap| bitmap.polygon_custom(&points, pixel_fn));
+	}
+
+	/// Draws a filled polygon formed by the given points, using the given fill rule. See
+	/// [`Bitmap::filled_polygon`].
+	pub fn filled_polygon(&mut self, points: &[Vector2], fill_rule: PolygonFillRule, color: PixelType) {
+		self.filled_polygon_custom(points, fill_rule, |_| color);
+	}
+
+	pub fn filled_polygon_custom(
+		&mut self,
+		points: &[Vector2],
+		fill_rule: PolygonFillRule,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		let points = self.to_bitmap_points(points);
+		self.with_bitmap(|bitmap| bitmap.filled_polygon_custom(&points, fill_rule, pixel_fn));
+	}
+
+	/// Draws a line from x1,y1 to x2,y2 with the given thickness, using the given cap style for the ends of the
+	/// line.
+	#[allow(clippy::too_many_arguments)]
+	pub fn thick_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, thickness: u32, cap: LineCap, color: PixelType) {
+		self.thick_line_custom(x1, y1, x2, y2, thickness, cap, |_| color);
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub fn thick_line_custom(
+		&mut self,
+		x1: i32,
+		y1: i32,
+		x2: i32,
+		y2: i32,
+		thickness: u32,
+		cap: LineCap,
+		pixel_fn: impl Fn(PixelType) -> PixelType,
+	) {
+		let (x, y) = (self.x(), self.y());
+		self.with_bitmap(|bitmap| bitmap.thick_line_custom(x1 + x, y1 + y, x2 + x, y2 + y, thickness, cap, pixel_fn));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f32::consts::{FRAC_PI_2, PI};
+
+	use crate::graphics::IndexedBitmap;
+	use crate::tests::assert_no_overdraw;
+
+	use super::*;
+
+	#[rustfmt::skip]
+	#[test]
+	pub fn ellipse() {
+		let mut bmp = IndexedBitmap::new(11, 7).unwrap();
+		bmp.ellipse(5, 3, 5, 3, 1);
+		assert_eq!(
+			bmp.pixels(),
+			&[
+				0, 0, 0, 1, 1, 1, 1, 1, 0, 0, 0,
+				0, 1, 1, 0, 0, 0, 0, 0, 1, 1, 0,
+				1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+				1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+				1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+				0, 1, 1, 0, 0, 0, 0, 0, 1, 1, 0,
+				0, 0, 0, 1, 1, 1, 1, 1, 0, 0, 0,
+			]
+		);
+
+		let mut bmp = IndexedBitmap::new(11, 7).unwrap();
+		bmp.filled_ellipse(5, 3, 5, 3, 1);
+		assert_eq!(
+			bmp.pixels(),
+			&[
+				0, 0, 0, 1, 1, 1, 1, 1, 0, 0, 0,
+				0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0,
+				1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+				1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+				1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+				0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0,
+				0, 0, 0, 1, 1, 1, 1, 1, 0, 0, 0,
+			]
+		);
+
+		// degenerate ellipses
+		let mut bmp = IndexedBitmap::new(5, 3).unwrap();
+		bmp.ellipse(2, 1, 2, 0, 1);
+		bmp.ellipse(2, 1, 0, 1, 2);
+		assert_eq!(bmp.pixels(), &[0, 0, 2, 0, 0, 1, 1, 2, 1, 1, 0, 0, 2, 0, 0]);
+
+		assert_no_overdraw(|bmp| bmp.ellipse_custom(16, 16, 12, 7, |pixel| pixel + 1));
+		assert_no_overdraw(|bmp| bmp.ellipse_custom(16, 16, 40, 3, |pixel| pixel + 1));
+		assert_no_overdraw(|bmp| bmp.filled_ellipse_custom(16, 16, 7, 12, |pixel| pixel + 1));
+	}
+
+	#[test]
+	pub fn arc_and_pie() {
+		// a quarter of a circle, moving clockwise from the right to the bottom
+		let mut bmp = IndexedBitmap::new(21, 21).unwrap();
+		bmp.filled_pie(10, 10, 8, 8, 0.0, FRAC_PI_2, 1);
+		bmp.pie(10, 10, 8, 8, 0.0, FRAC_PI_2, 2);
+		bmp.arc(10, 10, 8, 8, 0.0, FRAC_PI_2, 3);
+		for y in 0..21 {
+			for x in 0..21 {
+				let pixel = bmp.get_pixel(x, y).unwrap();
+				if pixel != 0 {
+					assert!(x >= 10 && y >= 10, "{}, {} should not have been drawn", x, y);
+				}
+				if x == 10 || y == 10 {
+					// the straight edges of the pie
+					if pixel != 0 {
+						assert!(pixel == 2 || pixel == 3, "{}, {}", x, y);
+					}
+				}
+			}
+		}
+		assert_eq!(Some(2), bmp.get_pixel(10, 10));
+		assert_eq!(Some(1), bmp.get_pixel(13, 13));
+		assert_eq!(Some(3), bmp.get_pixel(18, 10));
+		assert_eq!(Some(3), bmp.get_pixel(10, 18));
+		assert_eq!(Some(2), bmp.get_pixel(14, 10));
+		assert_eq!(Some(2), bmp.get_pixel(10, 14));
+
+		// a pie covering everything except the top-left quarter. end angle is less than the start angle
+		let mut bmp = IndexedBitmap::new(21, 21).unwrap();
+		bmp.filled_pie(10, 10, 8, 8, -FRAC_PI_2, PI, 1);
+		let mut expected = IndexedBitmap::new(21, 21).unwrap();
+		expected.filled_ellipse(10, 10, 8, 8, 1);
+		expected.filled_rect(0, 0, 9, 9, 0);
+		assert_eq!(expected.pixels(), bmp.pixels());
+
+		// sweeps of a full circle or more
+		let mut bmp = IndexedBitmap::new(21, 21).unwrap();
+		bmp.arc(10, 10, 8, 5, 1.0, 1.0 + TAU, 1);
+		let mut expected = IndexedBitmap::new(21, 21).unwrap();
+		expected.ellipse(10, 10, 8, 5, 1);
+		assert_eq!(expected.pixels(), bmp.pixels());
+
+		assert_no_overdraw(|bmp| bmp.arc_custom(16, 16, 12, 8, 0.5, 4.0, |pixel| pixel + 1));
+		assert_no_overdraw(|bmp| bmp.pie_custom(16, 16, 12, 8, 0.5, 4.0, |pixel| pixel + 1));
+		assert_no_overdraw(|bmp| bmp.filled_pie_custom(16, 16, 12, 8, 0.5, 4.0, |pixel| pixel + 1));
+	}
+
+	#[rustfmt::skip]
+	#[test]
+	pub fn rounded_rect() {
+		let mut bmp = IndexedBitmap::new(10, 8).unwrap();
+		bmp.rounded_rect(1, 1, 8, 6, 2, 1);
+		assert_eq!(
+			bmp.pixels(),
+			&[
+				0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+				0, 0, 1, 1, 1, 1, 1, 1, 0, 0,
+				0, 1, 0, 0, 0, 0, 0, 0, 1, 0,
+				0, 1, 0, 0, 0, 0, 0, 0, 1, 0,
+				0, 1, 0, 0, 0, 0, 0, 0, 1, 0,
+				0, 1, 0, 0, 0, 0, 0, 0, 1, 0,
+				0, 0, 1, 1, 1, 1, 1, 1, 0, 0,
+				0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+			]
+		);
+
+		// a radius of zero is the same as a normal rect
+		let mut bmp = IndexedBitmap::new(10, 8).unwrap();
+		let mut expected = IndexedBitmap::new(10, 8).unwrap();
+		bmp.rounded_rect(8, 6, 1, 1, 0, 1);
+		expected.rect(1, 1, 8, 6, 1);
+		assert_eq!(expected.pixels(), bmp.pixels());
+		bmp.filled_rounded_rect(2, 2, 7, 5, 0, 2);
+		expected.filled_rect(2, 2, 7, 5, 2);
+		assert_eq!(expected.pixels(), bmp.pixels());
+
+		// the radius is limited to half of the smallest dimension
+		let mut bmp = IndexedBitmap::new(10, 8).unwrap();
+		let mut expected = IndexedBitmap::new(10, 8).unwrap();
+		bmp.filled_rounded_rect(1, 1, 8, 6, 100, 1);
+		expected.filled_rounded_rect(1, 1, 8, 6, 2, 1);
+		assert_eq!(expected.pixels(), bmp.pixels());
+
+		assert_no_overdraw(|bmp| bmp.rounded_rect_custom(2, 3, 28, 20, 6, |pixel| pixel + 1));
+		assert_no_overdraw(|bmp| bmp.filled_rounded_rect_custom(2, 3, 28, 20, 6, |pixel| pixel + 1));
+	}
+
+	#[rustfmt::skip]
+	#[test]
+	pub fn polyline_and_polygon() {
+		let points = [Vector2::new(1.0, 1.0), Vector2::new(6.0, 1.0), Vector2::new(6.0, 4.0), Vector2::new(1.0, 4.0)];
+
+		let mut bmp = IndexedBitmap::new(8, 6).unwrap();
+		bmp.polyline(&points, 1);
+		assert_eq!(
+			bmp.pixels(),
+			&[
+				0, 0, 0, 0, 0, 0, 0, 0,
+				0, 1, 1, 1, 1, 1, 1, 0,
+				0, 0, 0, 0, 0, 0, 1, 0,
+				0, 0, 0, 0, 0, 0, 1, 0,
+				0, 1, 1, 1, 1, 1, 1, 0,
+				0, 0, 0, 0, 0, 0, 0, 0,
+			]
+		);
+
+		let mut bmp = IndexedBitmap::new(8, 6).unwrap();
+		bmp.polygon(&points, 1);
+		let mut expected = IndexedBitmap::new(8, 6).unwrap();
+		expected.rect(1, 1, 6, 4, 1);
+		assert_eq!(expected.pixels(), bmp.pixels());
+
+		assert_no_overdraw(|bmp| bmp.polyline_custom(&points, |pixel| pixel + 1));
+		assert_no_overdraw(|bmp| bmp.polygon_custom(&points, |pixel| pixel + 1));
+		assert_no_overdraw(|bmp| bmp.polygon_custom(&points[0..1], |pixel| pixel + 1));
+	}
+
+	#[rustfmt::skip]
+	#[test]
+	pub fn filled_polygon() {
+		// a square covering exactly the pixels with centers inside of it
+		let mut bmp = IndexedBitmap::new(8, 8).unwrap();
+		let mut expected = IndexedBitmap::new(8, 8).unwrap();
+		bmp.filled_polygon(
+			&[Vector2::new(1.0, 1.0), Vector2::new(6.0, 1.0), Vector2::new(6.0, 5.0), Vector2::new(1.0, 5.0)],
+			PolygonFillRule::EvenOdd,
+			1,
+		);
+		expected.filled_rect(1, 1, 5, 4, 1);
+		assert_eq!(expected.pixels(), bmp.pixels());
+
+		// a concave "u" shape
+		let mut bmp = IndexedBitmap::new(8, 6).unwrap();
+		bmp.filled_polygon(
+			&[
+				Vector2::new(0.0, 0.0),
+				Vector2::new(3.0, 0.0),
+				Vector2::new(3.0, 3.0),
+				Vector2::new(5.0, 3.0),
+				Vector2::new(5.0, 0.0),
+				Vector2::new(8.0, 0.0),
+				Vector2::new(8.0, 6.0),
+				Vector2::new(0.0, 6.0),
+			],
+			PolygonFillRule::EvenOdd,
+			1,
+		);
+		assert_eq!(
+			bmp.pixels(),
+			&[
+				1, 1, 1, 0, 0, 1, 1, 1,
+				1, 1, 1, 0, 0, 1, 1, 1,
+				1, 1, 1, 0, 0, 1, 1, 1,
+				1, 1, 1, 1, 1, 1, 1, 1,
+				1, 1, 1, 1, 1, 1, 1, 1,
+				1, 1, 1, 1, 1, 1, 1, 1,
+			]
+		);
+
+		// an outer square with an inner square traced in the same direction, joined by a bridge
+		let points = [
+			Vector2::new(0.0, 0.0),
+			Vector2::new(8.0, 0.0),
+			Vector2::new(8.0, 8.0),
+			Vector2::new(0.0, 8.0),
+			Vector2::new(0.0, 0.0),
+			Vector2::new(2.0, 2.0),
+			Vector2::new(6.0, 2.0),
+			Vector2::new(6.0, 6.0),
+			Vector2::new(2.0, 6.0),
+			Vector2::new(2.0, 2.0),
+		];
+
+		let mut bmp = IndexedBitmap::new(8, 8).unwrap();
+		let mut expected = IndexedBitmap::new(8, 8).unwrap();
+		bmp.filled_polygon(&points, PolygonFillRule::EvenOdd, 1);
+		expected.clear(1);
+		expected.filled_rect(2, 2, 5, 5, 0);
+		assert_eq!(expected.pixels(), bmp.pixels());
+
+		let mut bmp = IndexedBitmap::new(8, 8).unwrap();
+		bmp.filled_polygon(&points, PolygonFillRule::NonZero, 1);
+		assert!(bmp.pixels().iter().all(|&pixel| pixel == 1));
+
+		assert_no_overdraw(|bmp| bmp.filled_polygon_custom(&points, PolygonFillRule::NonZero, |pixel| pixel + 1));
+		assert_no_overdraw(|bmp| {
+			bmp.filled_polygon_custom(
+				&[Vector2::new(3.0, 1.0), Vector2::new(30.0, 12.5), Vector2::new(1.0, 30.0), Vector2::new(25.0, 28.0)],
+				PolygonFillRule::EvenOdd,
+				|pixel| pixel + 1,
+			)
+		});
+	}
+
+	#[rustfmt::skip]
+	#[test]
+	pub fn thick_line() {
+		let mut bmp = IndexedBitmap::new(12, 7).unwrap();
+		bmp.thick_line(3, 3, 8, 3, 3, LineCap::Butt, 1);
+		let mut expected = IndexedBitmap::new(12, 7).unwrap();
+		expected.filled_rect(3, 2, 8, 4, 1);
+		assert_eq!(expected.pixels(), bmp.pixels());
+
+		let mut bmp = IndexedBitmap::new(12, 7).unwrap();
+		bmp.thick_line(3, 3, 8, 3, 3, LineCap::Square, 1);
+		let mut expected = IndexedBitmap::new(12, 7).unwrap();
+		expected.filled_rect(2, 2, 9, 4, 1);
+		assert_eq!(expected.pixels(), bmp.pixels());
+
+		let mut bmp = IndexedBitmap::new(12, 7).unwrap();
+		bmp.thick_line(3, 3, 8, 3, 5, LineCap::Round, 1);
+		assert_eq!(
+			bmp.pixels(),
+			&[
+				0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+				0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0,
+				0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0,
+				0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0,
+				0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0,
+				0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0,
+				0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+			]
+		);
+
+		// a thickness of 1 along an axis is the same as a normal line
+		let mut bmp = IndexedBitmap::new(12, 7).unwrap();
+		let mut expected = IndexedBitmap::new(12, 7).unwrap();
+		bmp.thick_line(2, 5, 2, 1, 1, LineCap::Butt, 1);
+		expected.line(2, 5, 2, 1, 1);
+		assert_eq!(expected.pixels(), bmp.pixels());
+
+		let mut bmp = IndexedBitmap::new(12, 7).unwrap();
+		bmp.thick_line(2, 5, 2, 1, 0, LineCap::Round, 1);
+		assert!(bmp.pixels().iter().all(|&pixel| pixel == 0));
+
+		for cap in [LineCap::Butt, LineCap::Square, LineCap::Round] {
+			assert_no_overdraw(|bmp| bmp.thick_line_custom(3, 4, 27, 19, 6, cap, |pixel| pixel + 1));
+		}
+	}
+}
@@ -0,0 +1,629 @@
+use std::fs::File;
+use std::io;
+use std::io::{BufReader, BufWriter, Read};
+use std::path::Path;
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use thiserror::Error;
+
+use crate::graphics::{IndexedBitmap, Palette, PaletteError, RgbaBitmap, RGBA};
+
+const TGA_FOOTER_SIGNATURE: &[u8; 18] = b"TRUEVISION-XFILE.\0";
+
+// maximum number of pixels in a single rle packet
+const TGA_MAX_PACKET_LENGTH: usize = 128;
+
+// image descriptor bits
+const TGA_ALPHA_BITS_MASK: u8 = 0x0f;
+const TGA_RIGHT_TO_LEFT: u8 = 0x10;
+const TGA_TOP_TO_BOTTOM: u8 = 0x20;
+
+#[derive(Error, Debug)]
+pub enum TgaError {
+	#[error("Bad or unsupported TGA file: {0}")]
+	BadFile(String),
+
+	#[error("TGA palette data error")]
+	BadPalette(#[from] PaletteError),
+
+	#[error("Bitmap dimensions are too large to write a TGA file: {0}x{1}")]
+	TooLarge(u32, u32),
+
+	#[error("TGA I/O error")]
+	IOError(#[from] std::io::Error),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TgaFormat {
+	/// 24-bit color, with no alpha channel.
+	RGB,
+	/// 32-bit color, with an alpha channel.
+	RGBA,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum ImageType {
+	ColorMapped = 1,
+	TrueColor = 2,
+	Grayscale = 3,
+	RleColorMapped = 9,
+	RleTrueColor = 10,
+	RleGrayscale = 11,
+}
+
+impl ImageType {
+	pub fn from(value: u8) -> Result<Self, TgaError> {
+		use ImageType::*;
+		match value {
+			1 => Ok(ColorMapped),
+			2 => Ok(TrueColor),
+			3 => Ok(Grayscale),
+			9 => Ok(RleColorMapped),
+			10 => Ok(RleTrueColor),
+			11 => Ok(RleGrayscale),
+			_ => Err(TgaError::BadFile(format!("Unsupported image type: {}", value))),
+		}
+	}
+
+	pub fn is_rle(&self) -> bool {
+		use ImageType::*;
+		matches!(self, RleColorMapped | RleTrueColor | RleGrayscale)
+	}
+}
+
+#[derive(Debug, Copy, Clone)]
+struct TgaHeader {
+	id_length: u8,
+	color_map_type: u8,
+	image_type: u8,
+	color_map_first_entry: u16,
+	color_map_length: u16,
+	color_map_entry_size: u8,
+	x_origin: u16,
+	y_origin: u16,
+	width: u16,
+	height: u16,
+	bpp: u8,
+	descriptor: u8,
+}
+
+impl TgaHeader {
+	pub fn read<T: ReadBytesExt>(reader: &mut T) -> Result<Self, TgaError> {
+		Ok(TgaHeader {
+			id_length: reader.read_u8()?,
+			color_map_type: reader.read_u8()?,
+			image_type: reader.read_u8()?,
+			color_map_first_entry: reader.read_u16::<LittleEndian>()?,
+			color_map_length: reader.read_u16::<LittleEndian>()?,
+			color_map_entry_size: reader.read_u8()?,
+			x_origin: reader.read_u16::<LittleEndian>()?,
+			y_origin: reader.read_u16::<LittleEndian>()?,
+			width: reader.read_u16::<LittleEndian>()?,
+			height: reader.read_u16::<LittleEndian>()?,
+			bpp: reader.read_u8()?,
+			descriptor: reader.read_u8()?,
+		})
+	}
+
+	pub fn write<T: WriteBytesExt>(&self, writer: &mut T) -> Result<(), TgaError> {
+		writer.write_u8(self.id_length)?;
+		writer.write_u8(self.color_map_type)?;
+		writer.write_u8(self.image_type)?;
+		writer.write_u16::<LittleEndian>(self.color_map_first_entry)?;
+		writer.write_u16::<LittleEndian>(self.color_map_length)?;
+		writer.write_u8(self.color_map_entry_size)?;
+		writer.write_u16::<LittleEndian>(self.x_origin)?;
+		writer.write_u16::<LittleEndian>(self.y_origin)?;
+		writer.write_u16::<LittleEndian>(self.width)?;
+		writer.write_u16::<LittleEndian>(self.height)?;
+		writer.write_u8(self.bpp)?;
+		writer.write_u8(self.descriptor)?;
+		Ok(())
+	}
+}
+
+/// Converts a single 15/16/24/32-bit pixel or color map entry to an RGBA color. Alpha values are only used when
+/// `has_alpha` is true, otherwise the color is fully opaque.
+fn decode_color(bytes: &[u8], has_alpha: bool) -> RGBA {
+	match *bytes {
+		[lo, hi] => {
+			// 5 bits per color component, with the top bit being used for alpha (if at all)
+			let value = u16::from_le_bytes([lo, hi]);
+			let expand = |component: u16| ((component as u32 * 255 + 15) / 31) as u8;
+			let a = if !has_alpha || (value & 0x8000) != 0 { 255 } else { 0 };
+			RGBA::from_rgba([expand((value >> 10) & 0x1f), expand((value >> 5) & 0x1f), expand(value & 0x1f), a])
+		}
+		[b, g, r] => RGBA::from_rgb([r, g, b]),
+		[b, g, r, a] => RGBA::from_rgba([r, g, b, if has_alpha { a } else { 255 }]),
+		_ => RGBA::from_rgb([0, 0, 0]),
+	}
+}
+
+/// Reads the pixel data for the entire image, as raw bytes in the order they appear in the file, decompressing it
+/// first if needed.
+fn read_pixel_data<T: ReadBytesExt>(
+	reader: &mut T,
+	num_pixels: usize,
+	bytes_per_pixel: usize,
+	rle: bool,
+) -> Result<Vec<u8>, TgaError> {
+	let mut data = vec![0u8; num_pixels * bytes_per_pixel];
+	if !rle {
+		reader.read_exact(&mut data)?;
+		return Ok(data);
+	}
+
+	// note that packets are allowed to cross scanline boundaries, so the whole image is treated as one long run
+	let mut pixel = vec![0u8; bytes_per_pixel];
+	let mut offset = 0;
+	while offset < data.len() {
+		let packet = reader.read_u8()?;
+		let count = (packet & 0x7f) as usize + 1;
+		let end = (offset + count * bytes_per_pixel).min(data.len());
+		if packet & 0x80 != 0 {
+			// run-length packet. a single pixel value, repeated
+			reader.read_exact(&mut pixel)?;
+			for dest in data[offset..end].chunks_exact_mut(bytes_per_pixel) {
+				dest.copy_from_slice(&pixel);
+			}
+		} else {
+			// raw packet
+			reader.read_exact(&mut data[offset..end])?;
+		}
+		offset = end;
+	}
+	Ok(data)
+}
+
+enum DecodedTga {
+	Indexed(IndexedBitmap, Box<Palette>),
+	Rgba(RgbaBitmap),
+}
+
+fn load_tga_bytes<T: ReadBytesExt>(reader: &mut T) -> Result<DecodedTga, TgaError> {
+	let header = TgaHeader::read(reader)?;
+	let image_type = ImageType::from(header.image_type)?;
+	if header.width == 0 || header.height == 0 {
+		return Err(TgaError::BadFile(String::from("Invalid TGA image dimensions")));
+	}
+
+	let bytes_per_pixel = (header.bpp as usize).div_ceil(8);
+	match (image_type, header.bpp) {
+		(ImageType::ColorMapped | ImageType::RleColorMapped, 8) => {
+			if header.color_map_type != 1 {
+				return Err(TgaError::BadFile(String::from("No color map found in a color-mapped TGA file")));
+			}
+		}
+		(ImageType::TrueColor | ImageType::RleTrueColor, 15 | 16 | 24 | 32) => {}
+		(ImageType::Grayscale | ImageType::RleGrayscale, 8 | 16) => {}
+		(image_type, bpp) => {
+			return Err(TgaError::BadFile(format!("Unsupported bit depth {} for image type {:?}", bpp, image_type)))
+		}
+	}
+
+	// skip over the optional image id, which is just a (usually empty) bit of arbitrary data
+
+	io::copy(&mut reader.take(header.id_length as u64), &mut io::sink())?;
+
+	// read the color map, if any. other image types are allowed to have one, but we don't need it in those cases
+
+	let mut palette = None;
+	if header.color_map_type == 1 {
+		let entry_size = (header.color_map_entry_size as usize).div_ceil(8);
+		if !(2..=4).contains(&entry_size) {
+			return Err(TgaError::BadFile(format!(
+				"Unsupported color map entry size: {}",
+				header.color_map_entry_size
+			)));
+		}
+		let mut color_map = vec![0u8; header.color_map_length as usize * entry_size];
+		reader.read_exact(&mut color_map)?;
+
+		let mut tga_palette = Palette::new();
+		for (index, entry) in color_map.chunks_exact(entry_size).enumerate() {
+			let index = header.color_map_first_entry as usize + index;
+			if index > 255 {
+				break;
+			}
+			tga_palette[index as u8] = decode_color(entry, entry_size == 4);
+		}
+		if image_type == ImageType::ColorMapped || image_type == ImageType::RleColorMapped {
+			palette = Some(tga_palette);
+		}
+	}
+
+	// read the pixel data and convert it to our bitmap's format, taking into account the image origin
+
+	let width = header.width as usize;
+	let height = header.height as usize;
+	let data = read_pixel_data(reader, width * height, bytes_per_pixel, image_type.is_rle())?;
+
+	let has_alpha = header.descriptor & TGA_ALPHA_BITS_MASK != 0;
+	let right_to_left = header.descriptor & TGA_RIGHT_TO_LEFT != 0;
+	let top_to_bottom = header.descriptor & TGA_TOP_TO_BOTTOM != 0;
+	let position = |index: usize| {
+		let x = index % width;
+		let y = index / width;
+		let x = if right_to_left { width - 1 - x } else { x };
+		let y = if top_to_bottom { y } else { height - 1 - y };
+		(x as i32, y as i32)
+	};
+
+	if let Some(palette) = palette {
+		let mut bmp = IndexedBitmap::new(width as u32, height as u32).unwrap();
+		for (index, color) in data.iter().enumerate() {
+			let (x, y) = position(index);
+			unsafe { bmp.set_pixel_unchecked(x, y, *color) };
+		}
+		Ok(DecodedTga::Indexed(bmp, Box::new(palette)))
+	} else {
+		let grayscale = image_type == ImageType::Grayscale || image_type == ImageType::RleGrayscale;
+		let mut bmp = RgbaBitmap::new(width as u32, height as u32).unwrap();
+		for (index, bytes) in data.chunks_exact(bytes_per_pixel).enumerate() {
+			let color = if grayscale {
+				let value = bytes[0];
+				// 16-bit grayscale has an 8-bit alpha value following the 8-bit grayscale value
+				let a = if has_alpha && bytes.len() == 2 { bytes[1] } else { 255 };
+				RGBA::from_rgba([value, value, value, a])
+			} else {
+				decode_color(bytes, has_alpha)
+			};
+			let (x, y) = position(index);
+			unsafe { bmp.set_pixel_unchecked(x, y, color) };
+		}
+		Ok(DecodedTga::Rgba(bmp))
+	}
+}
+
+/// Writes out RLE packets for a single scanline. Packets are never allowed to cross scanline boundaries when
+/// writing, as recommended by the TGA specification.
+fn write_rle_row<T: WriteBytesExt>(writer: &mut T, row: &[u8], bytes_per_pixel: usize) -> Result<(), TgaError> {
+	let pixels = row.chunks_exact(bytes_per_pixel).collect::<Vec<&[u8]>>();
+	let mut i = 0;
+	while i < pixels.len() {
+		let mut run_length = 1;
+		while i + run_length < pixels.len() && run_length < TGA_MAX_PACKET_LENGTH && pixels[i + run_length] == pixels[i]
+		{
+			run_length += 1;
+		}
+
+		if run_length > 1 {
+			writer.write_u8(0x80 | (run_length - 1) as u8)?;
+			writer.write_all(pixels[i])?;
+			i += run_length;
+		} else {
+			// raw packet, continuing until the start of the next run of identical pixels
+			let mut end = i + 1;
+			while end < pixels.len()
+				&& end - i < TGA_MAX_PACKET_LENGTH
+				&& !(end + 1 < pixels.len() && pixels[end] == pixels[end + 1])
+			{
+				end += 1;
+			}
+			writer.write_u8((end - i - 1) as u8)?;
+			for pixel in pixels[i..end].iter() {
+				writer.write_all(pixel)?;
+			}
+			i = end;
+		}
+	}
+	Ok(())
+}
+
+/// Returns the given bitmap dimensions as the 16-bit values stored in a TGA header.
+fn header_dimensions(width: u32, height: u32) -> Result<(u16, u16), TgaError> {
+	match (u16::try_from(width), u16::try_from(height)) {
+		(Ok(width), Ok(height)) => Ok((width, height)),
+		_ => Err(TgaError::TooLarge(width, height)),
+	}
+}
+
+fn write_tga_bytes<T: WriteBytesExt>(
+	writer: &mut T,
+	header: TgaHeader,
+	palette: Option<&Palette>,
+	mut write_row: impl FnMut(&mut Vec<u8>, u32),
+) -> Result<(), TgaError> {
+	header.write(writer)?;
+
+	if let Some(palette) = palette {
+		for i in 0..=255 {
+			let color = palette[i];
+			writer.write_all(&[color.b(), color.g(), color.r()])?;
+		}
+	}
+
+	// always written top-to-bottom, as indicated in the header's image descriptor
+	let bytes_per_pixel = (header.bpp as usize).div_ceil(8);
+	let mut row = Vec::with_capacity(header.width as usize * bytes_per_pixel);
+	for y in 0..header.height as u32 {
+		row.clear();
+		write_row(&mut row, y);
+		write_rle_row(writer, &row, bytes_per_pixel)?;
+	}
+
+	// tga 2.0 footer, with no extension area or developer directory
+	writer.write_u32::<LittleEndian>(0)?;
+	writer.write_u32::<LittleEndian>(0)?;
+	writer.write_all(TGA_FOOTER_SIGNATURE)?;
+
+	Ok(())
+}
+
+impl IndexedBitmap {
+	pub fn load_tga_bytes<T: ReadBytesExt>(reader: &mut T) -> Result<(IndexedBitmap, Palette), TgaError> {
+		match load_tga_bytes(reader)? {
+			DecodedTga::Indexed(bmp, palette) => Ok((bmp, *palette)),
+			DecodedTga::Rgba(_) => {
+				Err(TgaError::BadFile(String::from("Only color-mapped TGA files can be loaded into an IndexedBitmap")))
+			}
+		}
+	}
+
+	pub fn load_tga_file(path: &Path) -> Result<(IndexedBitmap, Palette), TgaError> {
+		let f = File::open(path)?;
+		let mut reader = BufReader::new(f);
+		Self::load_tga_bytes(&mut reader)
+	}
+
+	pub fn to_tga_bytes<T: WriteBytesExt>(&self, writer: &mut T, palette: &Palette) -> Result<(), TgaError> {
+		let (header_width, header_height) = header_dimensions(self.width(), self.height())?;
+		let header = TgaHeader {
+			id_length: 0,
+			color_map_type: 1,
+			image_type: ImageType::RleColorMapped as u8,
+			color_map_first_entry: 0,
+			color_map_length: 256,
+			color_map_entry_size: 24,
+			x_origin: 0,
+			y_origin: 0,
+			width: header_width,
+			height: header_height,
+			bpp: 8,
+			descriptor: TGA_TOP_TO_BOTTOM,
+		};
+		let width = self.width() as usize;
+		write_tga_bytes(writer, header, Some(palette), |row, y| {
+			let start = y as usize * width;
+			row.extend_from_slice(&self.pixels()[start..start + width]);
+		})
+	}
+
+	pub fn to_tga_file(&self, path: &Path, palette: &Palette) -> Result<(), TgaError> {
+		let f = File::create(path)?;
+		let mut writer = BufWriter::new(f);
+		self.to_tga_bytes(&mut writer, palette)
+	}
+}
+
+impl RgbaBitmap {
+	pub fn load_tga_bytes<T: ReadBytesExt>(reader: &mut T) -> Result<(RgbaBitmap, Option<Palette>), TgaError> {
+		match load_tga_bytes(reader)? {
+			DecodedTga::Indexed(bmp, palette) => Ok((bmp.to_rgba(&palette), Some(*palette))),
+			DecodedTga::Rgba(bmp) => Ok((bmp, None)),
+		}
+	}
+
+	pub fn load_tga_file(path: &Path) -> Result<(RgbaBitmap, Option<Palette>), TgaError> {
+		let f = File::open(path)?;
+		let mut reader = BufReader::new(f);
+		Self::load_tga_bytes(&mut reader)
+	}
+
+	pub fn to_tga_bytes<T: WriteBytesExt>(&self, writer: &mut T, format: TgaFormat) -> Result<(), TgaError> {
+		let (bpp, descriptor) = match format {
+			TgaFormat::RGB => (24, TGA_TOP_TO_BOTTOM),
+			TgaFormat::RGBA => (32, TGA_TOP_TO_BOTTOM | 8),
+		};
+		let (header_width, header_height) = header_dimensions(self.width(), self.height())?;
+		let header = TgaHeader {
+			id_length: 0,
+			color_map_type: 0,
+			image_type: ImageType::RleTrueColor as u8,
+			color_map_first_entry: 0,
+			color_map_length: 0,
+			color_map_entry_size: 0,
+			x_origin: 0,
+			y_origin: 0,
+			width: header_width,
+			height: header_height,
+			bpp,
+			descriptor,
+		};
+		let width = self.width() as usize;
+		write_tga_bytes(writer, header, None, |row, y| {
+			let start = y as usize * width;
+			for pixel in self.pixels()[start..start + width].iter() {
+				match format {
+					TgaFormat::RGB => row.extend_from_slice(&[pixel.b(), pixel.g(), pixel.r()]),
+					TgaFormat::RGBA => row.extend_from_slice(&[pixel.b(), pixel.g(), pixel.r(), pixel.a()]),
+				}
+			}
+		})
+	}
+
+	pub fn to_tga_file(&self, path: &Path, format: TgaFormat) -> Result<(), TgaError> {
+		let f = File::create(path)?;
+		let mut writer = BufWriter::new(f);
+		self.to_tga_bytes(&mut writer, format)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::PathBuf;
+
+	use claim::*;
+	use tempfile::TempDir;
+
+	use crate::graphics::PaletteFormat;
+	use crate::tests::{load_raw_indexed, load_raw_rgba, test_assets_file};
+
+	use super::*;
+
+	const BASE_PATH: &str = "./test-assets/tga/";
+
+	fn test_file(file: &Path) -> PathBuf {
+		PathBuf::from(BASE_PATH).join(file)
+	}
+
+	#[test]
+	pub fn load_and_save() -> Result<(), TgaError> {
+		let tmp_dir = TempDir::new()?;
+
+		let ref_pixels = load_raw_indexed(test_file(Path::new("small.bin")).as_path())?;
+		let dp2_palette = Palette::load_from_file(
+			test_assets_file(Path::new("dp2.pal")).as_path(), //
+			PaletteFormat::Normal,
+		)
+		.unwrap();
+
+		let (bmp, palette) = IndexedBitmap::load_tga_file(test_file(Path::new("small.tga")).as_path())?;
+		assert_eq!(16, bmp.width());
+		assert_eq!(16, bmp.height());
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+		assert_eq!(palette, dp2_palette);
+
+		let save_path = tmp_dir.path().join("test_save.tga");
+		bmp.to_tga_file(&save_path, &palette)?;
+		let (reloaded_bmp, reloaded_palette) = IndexedBitmap::load_tga_file(&save_path)?;
+		assert_eq!(16, reloaded_bmp.width());
+		assert_eq!(16, reloaded_bmp.height());
+		assert_eq!(reloaded_bmp.pixels(), ref_pixels.as_ref());
+		assert_eq!(reloaded_palette, dp2_palette);
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn load_and_save_larger_image() -> Result<(), TgaError> {
+		let tmp_dir = TempDir::new()?;
+
+		// first image
+
+		let ref_pixels = load_raw_indexed(test_file(Path::new("large_1.bin")).as_path())?;
+
+		let (bmp, palette) = IndexedBitmap::load_tga_file(test_file(Path::new("large_1.tga")).as_path())?;
+		assert_eq!(320, bmp.width());
+		assert_eq!(200, bmp.height());
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+
+		let save_path = tmp_dir.path().join("test_save.tga");
+		bmp.to_tga_file(&save_path, &palette)?;
+		let (reloaded_bmp, _) = IndexedBitmap::load_tga_file(&save_path)?;
+		assert_eq!(320, reloaded_bmp.width());
+		assert_eq!(200, reloaded_bmp.height());
+		assert_eq!(reloaded_bmp.pixels(), ref_pixels.as_ref());
+
+		// second image
+
+		let ref_pixels = load_raw_indexed(test_file(Path::new("large_2.bin")).as_path())?;
+
+		let (bmp, palette) = IndexedBitmap::load_tga_file(test_file(Path::new("large_2.tga")).as_path())?;
+		assert_eq!(320, bmp.width());
+		assert_eq!(200, bmp.height());
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+
+		let save_path = tmp_dir.path().join("test_save_2.tga");
+		bmp.to_tga_file(&save_path, &palette)?;
+		let (reloaded_bmp, _) = IndexedBitmap::load_tga_file(&save_path)?;
+		assert_eq!(320, reloaded_bmp.width());
+		assert_eq!(200, reloaded_bmp.height());
+		assert_eq!(reloaded_bmp.pixels(), ref_pixels.as_ref());
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn loads_color_mapped_variants() -> Result<(), TgaError> {
+		let ref_pixels = load_raw_indexed(test_file(Path::new("large_1.bin")).as_path())?;
+		let (_, ref_palette) = IndexedBitmap::load_tga_file(test_file(Path::new("large_1.tga")).as_path())?;
+		for file in ["large_1_rle.tga", "large_1_topdown.tga"] {
+			let (bmp, palette) = IndexedBitmap::load_tga_file(test_file(Path::new(file)).as_path())?;
+			assert_eq!(bmp.pixels(), ref_pixels.as_ref(), "{}", file);
+			assert_eq!(palette, ref_palette, "{}", file);
+		}
+
+		// 16-bit color map entries, starting at index 16
+		let ref_pixels = load_raw_indexed(test_file(Path::new("indexed_cmap16.bin")).as_path())?;
+		let (bmp, palette) = IndexedBitmap::load_tga_file(test_file(Path::new("indexed_cmap16.tga")).as_path())?;
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+		assert_eq!(RGBA::from_rgb([0, 0, 0]), palette[15]);
+		let ref_pixels = load_raw_rgba(test_file(Path::new("indexed_cmap16_rgba.bin")).as_path())?;
+		let (bmp, _) = RgbaBitmap::load_tga_file(test_file(Path::new("indexed_cmap16.tga")).as_path())?;
+		assert_eq!(bmp.pixels(), ref_pixels.as_ref());
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn loads_truecolor_and_grayscale() -> Result<(), TgaError> {
+		for (file, bin_file) in [
+			("rgb_24bit.tga", "rgb_rgba.bin"),
+			("rgb_24bit_rle.tga", "rgb_rgba.bin"),
+			("rgb_24bit_right_to_left.tga", "rgb_rgba.bin"),
+			("rgba_32bit.tga", "rgba_rgba.bin"),
+			("rgba_32bit_rle.tga", "rgba_rgba.bin"),
+			("rgb_16bit.tga", "rgb_16bit_rgba.bin"),
+			("greyscale.tga", "greyscale_rgba.bin"),
+			("greyscale_rle.tga", "greyscale_rgba.bin"),
+		] {
+			let ref_pixels = load_raw_rgba(test_file(Path::new(bin_file)).as_path())?;
+			let (bmp, palette) = RgbaBitmap::load_tga_file(test_file(Path::new(file)).as_path())?;
+			assert_eq!(37, bmp.width());
+			assert_eq!(23, bmp.height());
+			assert!(palette.is_none());
+			assert_eq!(bmp.pixels(), ref_pixels.as_ref(), "{}", file);
+
+			assert_matches!(
+				IndexedBitmap::load_tga_file(test_file(Path::new(file)).as_path()),
+				Err(TgaError::BadFile(..))
+			);
+		}
+		Ok(())
+	}
+
+	#[test]
+	pub fn load_and_save_rgb_and_rgba() -> Result<(), TgaError> {
+		let tmp_dir = TempDir::new()?;
+
+		let ref_pixels = load_raw_rgba(test_file(Path::new("rgb_rgba.bin")).as_path())?;
+		let (bmp, _) = RgbaBitmap::load_tga_file(test_file(Path::new("rgb_24bit.tga")).as_path())?;
+		let save_path = tmp_dir.path().join("test_save_rgb.tga");
+		bmp.to_tga_file(&save_path, TgaFormat::RGB)?;
+		let (reloaded_bmp, reloaded_palette) = RgbaBitmap::load_tga_file(&save_path)?;
+		assert_eq!(37, reloaded_bmp.width());
+		assert_eq!(23, reloaded_bmp.height());
+		assert!(reloaded_palette.is_none());
+		assert_eq!(reloaded_bmp.pixels(), ref_pixels.as_ref());
+
+		let ref_pixels = load_raw_rgba(test_file(Path::new("rgba_rgba.bin")).as_path())?;
+		let (bmp, _) = RgbaBitmap::load_tga_file(test_file(Path::new("rgba_32bit.tga")).as_path())?;
+		let save_path = tmp_dir.path().join("test_save_rgba.tga");
+		bmp.to_tga_file(&save_path, TgaFormat::RGBA)?;
+		let (reloaded_bmp, reloaded_palette) = RgbaBitmap::load_tga_file(&save_path)?;
+		assert_eq!(37, reloaded_bmp.width());
+		assert_eq!(23, reloaded_bmp.height());
+		assert!(reloaded_palette.is_none());
+		assert_eq!(reloaded_bmp.pixels(), ref_pixels.as_ref());
+
+		Ok(())
+	}
+
+	#[test]
+	pub fn save_rejects_oversized_bitmaps() -> Result<(), TgaError> {
+		let mut bytes = Vec::new();
+		let bmp = IndexedBitmap::new(65536, 1).unwrap();
+		assert_matches!(bmp.to_tga_bytes(&mut bytes, &Palette::new()), Err(TgaError::TooLarge(65536, 1)));
+		let bmp = RgbaBitmap::new(1, 65536).unwrap();
+		assert_matches!(bmp.to_tga_bytes(&mut bytes, TgaFormat::RGBA), Err(TgaError::TooLarge(1, 65536)));
+		assert!(bytes.is_empty());
+
+		let bmp = IndexedBitmap::new(65535, 1).unwrap();
+		bmp.to_tga_bytes(&mut bytes, &Palette::new())?;
+		let (reloaded_bmp, _) = IndexedBitmap::load_tga_bytes(&mut bytes.as_slice())?;
+		assert_eq!(65535, reloaded_bmp.width());
+
+		Ok(())
+	}
+}
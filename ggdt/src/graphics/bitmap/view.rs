@@ -0,0 +1,540 @@
+use crate::graphics::{Bitmap, BitmapError, Font, FontRenderOpts, Pixel};
+use crate::math::{Rect, Vector2};
+
+// returns true if the region is non-empty and lies entirely within the given bounds
+fn is_region_within(region: &Rect, bounds: &Rect) -> bool {
+	let mut clamped = *region;
+	region.width > 0 && region.height > 0 && clamped.clamp_to(bounds) && clamped == *region
+}
+
+/// A lightweight, read-only view of a rectangular region of a [`Bitmap`], borrowing the bitmap's
+/// pixels instead of copying them. Coordinates used with a view are local to the view, with 0,0
+/// being the top-left corner of the region it covers.
+#[derive(Debug, Copy, Clone)]
+pub struct BitmapView<'a, PixelType: Pixel> {
+	bitmap: &'a Bitmap<PixelType>,
+	region: Rect,
+}
+
+impl<'a, PixelType: Pixel> BitmapView<'a, PixelType> {
+	/// Creates a new view of the given region of a bitmap. The region must lie entirely within
+	/// the bitmap's boundaries.
+	pub fn new(bitmap: &'a Bitmap<PixelType>, region: &Rect) -> Result<Self, BitmapError> {
+		if !is_region_within(region, &bitmap.full_bounds()) {
+			return Err(BitmapError::OutOfBounds);
+		}
+		Ok(BitmapView { bitmap, region: *region })
+	}
+
+	/// Returns the bitmap this view is of.
+	#[inline]
+	pub fn bitmap(&self) -> &'a Bitmap<PixelType> {
+		self.bitmap
+	}
+
+	/// Returns the region of the bitmap this view covers, in the bitmap's coordinates.
+	#[inline]
+	pub fn region(&self) -> &Rect {
+		&self.region
+	}
+
+	/// Returns the x coordinate on the bitmap that this view's left edge is at.
+	#[inline]
+	pub fn x(&self) -> i32 {
+		self.region.x
+	}
+
+	/// Returns the y coordinate on the bitmap that this view's top edge is at.
+	#[inline]
+	pub fn y(&self) -> i32 {
+		self.region.y
+	}
+
+	/// Returns the width of the view in pixels.
+	#[inline]
+	pub fn width(&self) -> u32 {
+		self.region.width
+	}
+
+	/// Returns the height of the view in pixels.
+	#[inline]
+	pub fn height(&self) -> u32 {
+		self.region.height
+	}
+
+	/// Returns a rect representing the full view boundaries, in the view's local coordinates.
+	#[inline]
+	pub fn full_bounds(&self) -> Rect {
+		Rect::new(0, 0, self.region.width, self.region.height)
+	}
+
+	/// Creates a new view of a region within this view. The region is given in this view's local
+	/// coordinates and must lie entirely within this view's boundaries.
+	pub fn view(&self, region: &Rect) -> Result<BitmapView<'a, PixelType>, BitmapError> {
+		if !is_region_within(region, &self.full_bounds()) {
+			return Err(BitmapError::OutOfBounds);
+		}
+		Ok(BitmapView {
+			bitmap: self.bitmap,
+			region: Rect::new(self.region.x + region.x, self.region.y + region.y, region.width, region.height),
+		})
+	}
+
+	/// Returns the pixels making up the given row of this view, or None if the row is outside of
+	/// the view's boundaries.
+	pub fn row(&self, y: i32) -> Option<&'a [PixelType]> {
+		if y < 0 || y >= self.region.height as i32 {
+			return None;
+		}
+		let offset = ((self.region.y + y) * self.bitmap.width() as i32 + self.region.x) as usize;
+		Some(&self.bitmap.pixels()[offset..offset + self.region.width as usize])
+	}
+
+	/// Gets the pixel at the given local coordinates. If the coordinates lie outside of the view's
+	/// boundaries, None is returned.
+	#[inline]
+	pub fn get_pixel(&self, x: i32, y: i32) -> Option<PixelType> {
+		if self.full_bounds().contains_point(x, y) {
+			Some(unsafe { self.get_pixel_unchecked(x, y) })
+		} else {
+			None
+		}
+	}
+
+	/// Gets the pixel at the given local coordinates.
+	///
+	/// # Safety
+	///
+	/// The coordinates are not checked for validity, so it is up to you to ensure they lie within
+	/// the bounds of the view.
+	#[inline]
+	pub unsafe fn get_pixel_unchecked(&self, x: i32, y: i32) -> PixelType {
+		self.bitmap.get_pixel_unchecked(self.region.x + x, self.region.y + y)
+	}
+
+	/// Creates a new bitmap containing a copy of the pixels covered by this view.
+	pub fn to_bitmap(&self) -> Bitmap<PixelType> {
+		// the region was already checked to be within the bitmap's boundaries
+		Bitmap::from(self.bitmap, &self.region).unwrap()
+	}
+}
+
+/// A lightweight, mutable view of a rectangular region of a [`Bitmap`], borrowing the bitmap's
+/// pixels instead of copying them. Coordinates used with a view are local to the view, with 0,0
+/// being the top-left corner of the region it covers. Each view has its own clipping region which
+/// is used for all drawing done through the view, regardless of the clipping region set on the
+/// bitmap itself.
+#[derive(Debug)]
+pub struct BitmapViewMut<'a, PixelType: Pixel> {
+	bitmap: &'a mut Bitmap<PixelType>,
+	region: Rect,
+	clip_region: Rect,
+}
+
+impl<'a, PixelType: Pixel> BitmapViewMut<'a, PixelType> {
+	/// Creates a new mutable view of the given region of a bitmap. The region must lie entirely
+	/// within the bitmap's boundaries.
+	pub fn new(bitmap: &'a mut Bitmap<PixelType>, region: &Rect) -> Result<Self, BitmapError> {
+		if !is_region_within(region, &bitmap.full_bounds()) {
+			return Err(BitmapError::OutOfBounds);
+		}
+		Ok(BitmapViewMut {
+			bitmap, //
+			region: *region,
+			clip_region: Rect::new(0, 0, region.width, region.height),
+		})
+	}
+
+	/// Returns the region of the bitmap this view covers, in the bitmap's coordinates.
+	#[inline]
+	pub fn region(&self) -> &Rect {
+		&self.region
+	}
+
+	/// Returns the x coordinate on the bitmap that this view's left edge is at.
+	#[inline]
+	pub fn x(&self) -> i32 {
+		self.region.x
+	}
+
+	/// Returns the y coordinate on the bitmap that this view's top edge is at.
+	#[inline]
+	pub fn y(&self) -> i32 {
+		self.region.y
+	}
+
+	/// Returns the width of the view in pixels.
+	#[inline]
+	pub fn width(&self) -> u32 {
+		self.region.width
+	}
+
+	/// Returns the height of the view in pixels.
+	#[inline]
+	pub fn height(&self) -> u32 {
+		self.region.height
+	}
+
+	/// Returns a rect representing the full view boundaries, in the view's local coordinates,
+	/// ignoring the current clipping region set on this view.
+	#[inline]
+	pub fn full_bounds(&self) -> Rect {
+		Rect::new(0, 0, self.region.width, self.region.height)
+	}
+
+	/// Returns the current clipping region set on this view, in the view's local coordinates.
+	#[inline]
+	pub fn clip_region(&self) -> &Rect {
+		&self.clip_region
+	}
+
+	/// Sets a new clipping region on this view, in the view's local coordinates. The region will
+	/// be automatically clamped to the view's boundaries if the supplied region extends beyond it.
+	pub fn set_clip_region(&mut self, region: &Rect) {
+		self.clip_region = *region;
+		self.clip_region.clamp_to(&self.full_bounds());
+	}
+
+	/// Resets the view's clipping region back to the default (full boundaries of the view).
+	#[inline]
+	pub fn reset_clip_region(&mut self) {
+		self.clip_region = self.full_bounds();
+	}
+
+	/// Returns a read-only view of the same region of the bitmap as this view.
+	#[inline]
+	pub fn as_view(&self) -> BitmapView<'_, PixelType> {
+		BitmapView { bitmap: self.bitmap, region: self.region }
+	}
+
+	/// Creates a new mutable view of a region within this view. The region is given in this view's
+	/// local coordinates and must lie entirely within this view's boundaries.
+	pub fn view_mut(&mut self, region: &Rect) -> Result<BitmapViewMut<'_, PixelType>, BitmapError> {
+		if !is_region_within(region, &self.full_bounds()) {
+			return Err(BitmapError::OutOfBounds);
+		}
+		Ok(BitmapViewMut {
+			bitmap: self.bitmap,
+			region: Rect::new(self.region.x + region.x, self.region.y + region.y, region.width, region.height),
+			clip_region: Rect::new(0, 0, region.width, region.height),
+		})
+	}
+
+	/// Calls the given function with the bitmap this view is of, with the bitmap's clipping region
+	/// temporarily set to match this view's clipping region. This allows any of the bitmap's
+	/// drawing operations to be used to draw into this view. Note that coordinates given to the
+	/// bitmap's drawing operations are _not_ local to this view, and so need to be offset by
+	/// [`BitmapViewMut::x`] and [`BitmapViewMut::y`]. The given function must only draw to the
+	/// bitmap and never replace it, as the view's own pixel access relies on its region always
+	/// lying within the bitmap's bounds.
+	pub(crate) fn with_bitmap<R>(&mut self, f: impl FnOnce(&mut Bitmap<PixelType>) -> R) -> R {
+		let bitmap_clip_region = *self.bitmap.clip_region();
+		self.bitmap.clip_region = Rect::new(
+			self.region.x + self.clip_region.x,
+			self.region.y + self.clip_region.y,
+			self.clip_region.width,
+			self.clip_region.height,
+		);
+		let result = f(self.bitmap);
+		self.bitmap.clip_region = bitmap_clip_region;
+		result
+	}
+
+	/// Returns the given points (in coordinates local to this view) translated to the coordinates of the bitmap this
+	/// view is of, for use with the bitmap's drawing operations.
+	pub(crate) fn to_bitmap_points(&self, points: &[Vector2]) -> Vec<Vector2> {
+		let offset = Vector2::new(self.region.x as f32, self.region.y as f32);
+		points.iter().map(|&point| point + offset).collect()
+	}
+
+	/// Fills the entire view with the given color.
+	pub fn clear(&mut self, color: PixelType) {
+		let clip_region = self.clip_region;
+		self.reset_clip_region();
+		self.filled_rect(0, 0, self.region.width as i32 - 1, self.region.height as i32 - 1, color);
+		self.clip_region = clip_region;
+	}
+
+	/// Sets the pixel at the given local coordinates to the color specified. If the coordinates
+	/// lie outside of the view's clipping region, no pixels will be changed.
+	#[inline]
+	pub fn set_pixel(&mut self, x: i32, y: i32, color: PixelType) {
+		if self.clip_region.contains_point(x, y) {
+			unsafe { self.bitmap.set_pixel_unchecked(self.region.x + x, self.region.y + y, color) };
+		}
+	}
+
+	/// Gets the pixel at the given local coordinates. If the coordinates lie outside of the view's
+	/// clipping region, None is returned.
+	#[inline]
+	pub fn get_pixel(&self, x: i32, y: i32) -> Option<PixelType> {
+		if self.clip_region.contains_point(x, y) {
+			Some(unsafe { self.bitmap.get_pixel_unchecked(self.region.x + x, self.region.y + y) })
+		} else {
+			None
+		}
+	}
+
+	/// Draws a line from x1,y1 to x2,y2.
+	pub fn line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: PixelType) {
+		let (x, y) = (self.region.x, self.region.y);
+		self.with_bitmap(|bitmap| bitmap.line(x1 + x, y1 + y, x2 + x, y2 + y, color));
+	}
+
+	/// Draws a horizontal line from x1,y to x2,y.
+	pub fn horiz_line(&mut self, x1: i32, x2: i32, y: i32, color: PixelType) {
+		let (offset_x, offset_y) = (self.region.x, self.region.y);
+		self.with_bitmap(|bitmap| bitmap.horiz_line(x1 + offset_x, x2 + offset_x, y + offset_y, color));
+	}
+
+	/// Draws a vertical line from x,y1 to x,y2.
+	pub fn vert_line(&mut self, x: i32, y1: i32, y2: i32, color: PixelType) {
+		let (offset_x, offset_y) = (self.region.x, self.region.y);
+		self.with_bitmap(|bitmap| bitmap.vert_line(x + offset_x, y1 + offset_y, y2 + offset_y, color));
+	}
+
+	/// Draws an empty box (rectangle) using the points x1,y1 and x2,y2 to form the box to be
+	/// drawn, assuming they are specifying the top-left and bottom-right corners respectively.
+	pub fn rect(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: PixelType) {
+		let (x, y) = (self.region.x, self.region.y);
+		self.with_bitmap(|bitmap| bitmap.rect(x1 + x, y1 + y, x2 + x, y2 + y, color));
+	}
+
+	/// Draws a filled box (rectangle) using the points x1,y1 and x2,y2 to form the box to be
+	/// drawn, assuming they are specifying the top-left and bottom-right corners respectively.
+	pub fn filled_rect(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: PixelType) {
+		let (x, y) = (self.region.x, self.region.y);
+		self.with_bitmap(|bitmap| bitmap.filled_rect(x1 + x, y1 + y, x2 + x, y2 + y, color));
+	}
+
+	/// Draws the outline of a circle formed by the center point and radius given.
+	pub fn circle(&mut self, center_x: i32, center_y: i32, radius: u32, color: PixelType) {
+		let (x, y) = (self.region.x, self.region.y);
+		self.with_bitmap(|bitmap| bitmap.circle(center_x + x, center_y + y, radius, color));
+	}
+
+	/// Draws a filled circle formed by the center point and radius given.
+	pub fn filled_circle(&mut self, center_x: i32, center_y: i32, radius: u32, color: PixelType) {
+		let (x, y) = (self.region.x, self.region.y);
+		self.with_bitmap(|bitmap| bitmap.filled_circle(center_x + x, center_y + y, radius, color));
+	}
+
+	/// Renders a single character using the font given.
+	pub fn print_char<T: Font>(&mut self, ch: char, x: i32, y: i32, opts: FontRenderOpts<PixelType>, font: &T) {
+		let (offset_x, offset_y) = (self.region.x, self.region.y);
+		self.with_bitmap(|bitmap| bitmap.print_char(ch, x + offset_x, y + offset_y, opts, font));
+	}
+
+	/// Renders the string of text using the font given.
+	pub fn print_string<T: Font>(&mut self, text: &str, x: i32, y: i32, opts: FontRenderOpts<PixelType>, font: &T) {
+		let (offset_x, offset_y) = (self.region.x, self.region.y);
+		self.with_bitmap(|bitmap| bitmap.print_string(text, x + offset_x, y + offset_y, opts, font));
+	}
+}
+
+impl<PixelType: Pixel> Bitmap<PixelType> {
+	/// Returns a read-only view of the given region of this bitmap, without copying any pixels.
+	/// The region must lie entirely within the bitmap's boundaries.
+	#[inline]
+	pub fn view(&self, region: &Rect) -> Result<BitmapView<'_, PixelType>, BitmapError> {
+		BitmapView::new(self, region)
+	}
+
+	/// Returns a mutable view of the given region of this bitmap, without copying any pixels.
+	/// The region must lie entirely within the bitmap's boundaries.
+	#[inline]
+	pub fn view_mut(&mut self, region: &Rect) -> Result<BitmapViewMut<'_, PixelType>, BitmapError> {
+		BitmapViewMut::new(self, region)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use claim::*;
+
+	use crate::graphics::{BlendFunction, FillConnectivity, IndexedBitmap, LineCap, PolygonFillRule, RgbaBitmap, RGBA};
+	use crate::math::QuadraticBezier;
+
+	use super::*;
+
+	#[rustfmt::skip]
+	static RAW_BMP_PIXELS: &[u8] = &[
+		0, 0, 0, 0, 0, 0, 0, 0,
+		0, 1, 1, 1, 1, 1, 1, 0,
+		0, 1, 2, 2, 2, 2, 1, 0,
+		0, 1, 2, 3, 3, 2, 1, 0,
+		0, 1, 2, 3, 3, 2, 1, 0,
+		0, 1, 2, 2, 2, 2, 1, 0,
+		0, 1, 1, 1, 1, 1, 1, 0,
+		0, 0, 0, 0, 0, 0, 0, 0,
+	];
+
+	#[test]
+	pub fn creating_views() {
+		let mut bmp = IndexedBitmap::new(8, 8).unwrap();
+		assert_ok!(bmp.view(&Rect::new(0, 0, 8, 8)));
+		assert_ok!(bmp.view(&Rect::new(7, 7, 1, 1)));
+		assert_matches!(bmp.view(&Rect::new(4, 4, 5, 1)), Err(BitmapError::OutOfBounds));
+		assert_matches!(bmp.view(&Rect::new(-1, 0, 2, 2)), Err(BitmapError::OutOfBounds));
+		assert_matches!(bmp.view(&Rect::new(0, 0, 0, 2)), Err(BitmapError::OutOfBounds));
+		assert_ok!(bmp.view_mut(&Rect::new(2, 3, 4, 5)));
+		assert_matches!(bmp.view_mut(&Rect::new(2, 3, 4, 6)), Err(BitmapError::OutOfBounds));
+
+		let view = bmp.view(&Rect::new(2, 2, 4, 4)).unwrap();
+		assert_ok!(view.view(&Rect::new(1, 1, 3, 3)));
+		assert_matches!(view.view(&Rect::new(1, 1, 4, 3)), Err(BitmapError::OutOfBounds));
+	}
+
+	#[test]
+	pub fn reading_views() {
+		let mut bmp = IndexedBitmap::new(8, 8).unwrap();
+		bmp.pixels_mut().copy_from_slice(RAW_BMP_PIXELS);
+
+		let view = bmp.view(&Rect::new(1, 2, 4, 3)).unwrap();
+		assert_eq!(4, view.width());
+		assert_eq!(3, view.height());
+		assert_eq!(Some(1), view.get_pixel(0, 0));
+		assert_eq!(Some(2), view.get_pixel(1, 0));
+		assert_eq!(Some(3), view.get_pixel(3, 2));
+		assert_eq!(None, view.get_pixel(4, 0));
+		assert_eq!(None, view.get_pixel(0, -1));
+		assert_eq!(Some(&[1, 2, 3, 3][..]), view.row(1));
+		assert_eq!(None, view.row(3));
+
+		let sub_view = view.view(&Rect::new(2, 1, 2, 2)).unwrap();
+		assert_eq!(Rect::new(3, 3, 2, 2), *sub_view.region());
+		assert_eq!(Some(3), sub_view.get_pixel(0, 0));
+
+		let copy = view.to_bitmap();
+		assert_eq!(4, copy.width());
+		assert_eq!(3, copy.height());
+		assert_eq!(&[1, 2, 2, 2, 1, 2, 3, 3, 1, 2, 3, 3], copy.pixels());
+	}
+
+	#[test]
+	pub fn drawing_to_views() {
+		let mut bmp = IndexedBitmap::new(8, 8).unwrap();
+		bmp.set_clip_region(&Rect::new(0, 0, 1, 1));
+
+		let mut view = bmp.view_mut(&Rect::new(2, 1, 5, 6)).unwrap();
+		view.clear(1);
+		view.set_pixel(0, 0, 2);
+		view.set_pixel(-1, 0, 9);
+		view.set_pixel(5, 0, 9);
+		view.horiz_line(-3, 10, 5, 3);
+		view.set_clip_region(&Rect::new(1, 1, 3, 3));
+		view.filled_rect(0, 0, 10, 10, 4);
+		assert_eq!(Some(4), view.get_pixel(1, 1));
+		assert_eq!(None, view.get_pixel(0, 0));
+
+		// the bitmap's own clipping region is left as it was
+		assert_eq!(Rect::new(0, 0, 1, 1), *bmp.clip_region());
+
+		#[rustfmt::skip]
+		assert_eq!(
+			bmp.pixels(),
+			&[
+				0, 0, 0, 0, 0, 0, 0, 0,
+				0, 0, 2, 1, 1, 1, 1, 0,
+				0, 0, 1, 4, 4, 4, 1, 0,
+				0, 0, 1, 4, 4, 4, 1, 0,
+				0, 0, 1, 4, 4, 4, 1, 0,
+				0, 0, 1, 1, 1, 1, 1, 0,
+				0, 0, 3, 3, 3, 3, 3, 0,
+				0, 0, 0, 0, 0, 0, 0, 0,
+			]
+		);
+	}
+
+	#[test]
+	pub fn nested_views() {
+		let mut bmp = IndexedBitmap::new(8, 8).unwrap();
+		let mut view = bmp.view_mut(&Rect::new(1, 1, 6, 6)).unwrap();
+		view.set_clip_region(&Rect::new(0, 0, 3, 6));
+		{
+			// nested views have their own clipping region, independent of their parent view
+			let mut sub_view = view.view_mut(&Rect::new(2, 2, 3, 3)).unwrap();
+			sub_view.clear(5);
+			sub_view.line(0, 0, 2, 2, 6);
+		}
+		view.set_pixel(4, 4, 7);
+
+		#[rustfmt::skip]
+		assert_eq!(
+			bmp.pixels(),
+			&[
+				0, 0, 0, 0, 0, 0, 0, 0,
+				0, 0, 0, 0, 0, 0, 0, 0,
+				0, 0, 0, 0, 0, 0, 0, 0,
+				0, 0, 0, 6, 5, 5, 0, 0,
+				0, 0, 0, 5, 6, 5, 0, 0,
+				0, 0, 0, 5, 5, 6, 0, 0,
+				0, 0, 0, 0, 0, 0, 0, 0,
+				0, 0, 0, 0, 0, 0, 0, 0,
+			]
+		);
+	}
+
+	#[test]
+	pub fn view_drawing_matches_offset_bitmap_drawing() {
+		// drawing into a view should give the same result as drawing directly into the bitmap, using coordinates
+		// offset by the view's position and clipped to the view's region
+		let region = Rect::new(5, 7, 30, 20);
+		let points = [Vector2::new(-3.0, 2.0), Vector2::new(20.0, 4.0), Vector2::new(12.0, 25.0)];
+		let curve = QuadraticBezier::new(Vector2::new(1.0, 18.0), Vector2::new(15.0, -10.0), Vector2::new(34.0, 15.0));
+		let offset = |points: &[Vector2]| points.iter().map(|&p| p + Vector2::new(5.0, 7.0)).collect::<Vec<_>>();
+		let offset_curve = QuadraticBezier::new(
+			curve.start + Vector2::new(5.0, 7.0),
+			curve.control + Vector2::new(5.0, 7.0),
+			curve.end + Vector2::new(5.0, 7.0),
+		);
+
+		let mut expected = IndexedBitmap::new(40, 30).unwrap();
+		expected.set_clip_region(&region);
+		expected.ellipse(15, 17, 12, 6, 1);
+		expected.arc(15, 17, 8, 8, 0.0, 3.0, 2);
+		expected.filled_pie(25, 12, 6, 6, 1.0, 4.0, 3);
+		expected.rounded_rect(7, 9, 30, 22, 4, 4);
+		expected.polygon(&offset(&points), 5);
+		expected.filled_polygon(&offset(&points), PolygonFillRule::EvenOdd, 6);
+		expected.thick_line(3, 25, 30, 10, 3, LineCap::Square, 7);
+		expected.curve(&offset_curve, 8);
+		expected.thick_curve(&offset_curve, 2, LineCap::Round, 9);
+		expected.flood_fill(6, 8, 10, FillConnectivity::Four);
+
+		let mut bmp = IndexedBitmap::new(40, 30).unwrap();
+		let mut view = bmp.view_mut(&region).unwrap();
+		view.ellipse(10, 10, 12, 6, 1);
+		view.arc(10, 10, 8, 8, 0.0, 3.0, 2);
+		view.filled_pie(20, 5, 6, 6, 1.0, 4.0, 3);
+		view.rounded_rect(2, 2, 25, 15, 4, 4);
+		view.polygon(&points, 5);
+		view.filled_polygon(&points, PolygonFillRule::EvenOdd, 6);
+		view.thick_line(-2, 18, 25, 3, 3, LineCap::Square, 7);
+		view.curve(&curve, 8);
+		view.thick_curve(&curve, 2, LineCap::Round, 9);
+		view.flood_fill(1, 1, 10, FillConnectivity::Four);
+		assert_eq!(expected.pixels(), bmp.pixels());
+
+		let color = RGBA::from_rgba([255, 0, 0, 128]);
+		let blend = BlendFunction::Blend;
+		let mut expected = RgbaBitmap::new(40, 30).unwrap();
+		expected.set_clip_region(&region);
+		expected.blended_filled_rect(0, 0, 20, 20, color, blend);
+		expected.blended_thick_line(3, 25, 30, 10, 3, LineCap::Butt, color, blend);
+		expected.blended_curve(&offset_curve, color, blend);
+		expected.antialiased_line(4.5, 8.0, 33.5, 21.0, color, blend);
+		expected.antialiased_filled_circle(20.5, 17.0, 6.5, color, blend);
+		expected.antialiased_filled_polygon(&offset(&points), PolygonFillRule::NonZero, color, blend);
+
+		let mut bmp = RgbaBitmap::new(40, 30).unwrap();
+		let mut view = bmp.view_mut(&region).unwrap();
+		view.blended_filled_rect(-5, -7, 15, 13, color, blend);
+		view.blended_thick_line(-2, 18, 25, 3, 3, LineCap::Butt, color, blend);
+		view.blended_curve(&curve, color, blend);
+		view.antialiased_line(-0.5, 1.0, 28.5, 14.0, color, blend);
+		view.antialiased_filled_circle(15.5, 10.0, 6.5, color, blend);
+		view.antialiased_filled_polygon(&points, PolygonFillRule::NonZero, color, blend);
+		assert_eq!(expected.pixels(), bmp.pixels());
+	}
+}
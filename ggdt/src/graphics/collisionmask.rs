@@ -0,0 +1,382 @@
+use std::ops::Index;
+
+use thiserror::Error;
+
+use crate::graphics::{BitmapAtlas, IndexedBitmap, RgbaBitmap};
+use crate::math::Rect;
+
+const BITS_PER_WORD: usize = u64::BITS as usize;
+
+#[derive(Error, Debug)]
+pub enum CollisionMaskError {
+	#[error("Region is out of bounds for the source Bitmap")]
+	OutOfBounds,
+
+	#[error("Invalid dimensions for collision mask")]
+	InvalidDimensions,
+}
+
+/// A 1-bit-per-pixel mask used for pixel-perfect collision tests between sprites. Each row is
+/// bit-packed into 64-bit words so that overlap tests can AND 64 pixels at a time. A horizontally
+/// mirrored copy of the mask is kept alongside so that flipped overlap tests are just as fast.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CollisionMask {
+	width: u32,
+	height: u32,
+	words_per_row: usize,
+	bits: Box<[u64]>,
+	mirrored_bits: Box<[u64]>,
+}
+
+impl CollisionMask {
+	/// Creates a new collision mask with the given dimensions where each pixel is set according to
+	/// the result of the provided function.
+	pub fn from_fn(width: u32, height: u32, f: impl Fn(u32, u32) -> bool) -> Result<Self, CollisionMaskError> {
+		if width == 0 || height == 0 {
+			return Err(CollisionMaskError::InvalidDimensions);
+		}
+
+		let words_per_row = (width as usize + BITS_PER_WORD - 1) / BITS_PER_WORD;
+		let mut bits = vec![0u64; words_per_row * height as usize].into_boxed_slice();
+		let mut mirrored_bits = vec![0u64; words_per_row * height as usize].into_boxed_slice();
+		for y in 0..height {
+			let row_offset = y as usize * words_per_row;
+			for x in 0..width {
+				if f(x, y) {
+					let mirrored_x = (width - 1 - x) as usize;
+					bits[row_offset + x as usize / BITS_PER_WORD] |= 1 << (x as usize % BITS_PER_WORD);
+					mirrored_bits[row_offset + mirrored_x / BITS_PER_WORD] |= 1 << (mirrored_x % BITS_PER_WORD);
+				}
+			}
+		}
+
+		Ok(CollisionMask { width, height, words_per_row, bits, mirrored_bits })
+	}
+
+	/// Creates a collision mask from the given region of an indexed bitmap. All pixels that are
+	/// not the specified transparent color are considered solid.
+	pub fn from_indexed_bitmap(
+		bitmap: &IndexedBitmap,
+		region: &Rect,
+		transparent_color: u8,
+	) -> Result<Self, CollisionMaskError> {
+		if !is_region_in_bounds(region, bitmap.width(), bitmap.height()) {
+			return Err(CollisionMaskError::OutOfBounds);
+		}
+		let pixels = bitmap.pixels();
+		let stride = bitmap.width() as usize;
+		CollisionMask::from_fn(region.width, region.height, |x, y| {
+			let offset = (region.y as usize + y as usize) * stride + region.x as usize + x as usize;
+			pixels[offset] != transparent_color
+		})
+	}
+
+	/// Creates a collision mask from the given region of an RGBA bitmap. All pixels with an alpha
+	/// component greater than or equal to the specified alpha threshold are considered solid.
+	pub fn from_rgba_bitmap(
+		bitmap: &RgbaBitmap,
+		region: &Rect,
+		alpha_threshold: u8,
+	) -> Result<Self, CollisionMaskError> {
+		if !is_region_in_bounds(region, bitmap.width(), bitmap.height()) {
+			return Err(CollisionMaskError::OutOfBounds);
+		}
+		let pixels = bitmap.pixels();
+		let stride = bitmap.width() as usize;
+		CollisionMask::from_fn(region.width, region.height, |x, y| {
+			let offset = (region.y as usize + y as usize) * stride + region.x as usize + x as usize;
+			pixels[offset].a() >= alpha_threshold
+		})
+	}
+
+	#[inline]
+	pub fn width(&self) -> u32 {
+		self.width
+	}
+
+	#[inline]
+	pub fn height(&self) -> u32 {
+		self.height
+	}
+
+	/// Returns a rect representing the full boundaries of this mask.
+	#[inline]
+	pub fn full_bounds(&self) -> Rect {
+		Rect::new(0, 0, self.width, self.height)
+	}
+
+	/// Returns true if the pixel at the given coordinates is solid. Coordinates outside of the
+	/// mask are never solid.
+	#[inline]
+	pub fn get(&self, x: i32, y: i32) -> bool {
+		if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+			return false;
+		}
+		let word = self.bits[y as usize * self.words_per_row + x as usize / BITS_PER_WORD];
+		(word >> (x as usize % BITS_PER_WORD)) & 1 != 0
+	}
+
+	/// Returns the number of solid pixels in this mask.
+	pub fn count(&self) -> u32 {
+		self.bits.iter().map(|word| word.count_ones()).sum()
+	}
+
+	/// Returns true if this mask overlaps the other mask, where the other mask is positioned at
+	/// the given offset relative to the top-left corner of this mask.
+	#[inline]
+	pub fn overlaps(&self, other: &CollisionMask, offset_x: i32, offset_y: i32) -> bool {
+		self.overlaps_flipped(false, false, other, offset_x, offset_y, false, false)
+	}
+
+	/// Returns true if this mask overlaps the other mask, where the other mask is positioned at
+	/// the given offset relative to the top-left corner of this mask. Either mask can be flipped
+	/// horizontally and/or vertically, matching how the sprites they were created from are drawn.
+	#[allow(clippy::too_many_arguments)]
+	pub fn overlaps_flipped(
+		&self,
+		horizontal_flip: bool,
+		vertical_flip: bool,
+		other: &CollisionMask,
+		offset_x: i32,
+		offset_y: i32,
+		other_horizontal_flip: bool,
+		other_vertical_flip: bool,
+	) -> bool {
+		// the intersection of both masks, in this mask's coordinate space
+		let x1 = offset_x.max(0);
+		let y1 = offset_y.max(0);
+		let x2 = (offset_x + other.width as i32).min(self.width as i32);
+		let y2 = (offset_y + other.height as i32).min(self.height as i32);
+		if x1 >= x2 || y1 >= y2 {
+			return false;
+		}
+
+		for y in y1..y2 {
+			let row = self.row(y as u32, horizontal_flip, vertical_flip);
+			let other_row = other.row((y - offset_y) as u32, other_horizontal_flip, other_vertical_flip);
+
+			let mut x = x1 as usize;
+			let mut other_x = (x1 - offset_x) as usize;
+			while x < x2 as usize {
+				let remaining = x2 as usize - x;
+				let mask = if remaining < BITS_PER_WORD { (1u64 << remaining) - 1 } else { u64::MAX };
+				if extract_bits(row, x) & extract_bits(other_row, other_x) & mask != 0 {
+					return true;
+				}
+				x += BITS_PER_WORD;
+				other_x += BITS_PER_WORD;
+			}
+		}
+
+		false
+	}
+
+	#[inline]
+	fn row(&self, y: u32, horizontal_flip: bool, vertical_flip: bool) -> &[u64] {
+		let y = if vertical_flip { self.height - 1 - y } else { y };
+		let offset = y as usize * self.words_per_row;
+		let bits = if horizontal_flip { &self.mirrored_bits } else { &self.bits };
+		&bits[offset..offset + self.words_per_row]
+	}
+}
+
+#[inline]
+fn is_region_in_bounds(region: &Rect, width: u32, height: u32) -> bool {
+	region.x >= 0
+		&& region.y >= 0
+		&& region.x as u32 + region.width <= width
+		&& region.y as u32 + region.height <= height
+}
+
+/// Returns the 64 bits of the given bit-packed row starting at bit index `start`. Bits past the
+/// end of the row are returned as zero.
+#[inline]
+fn extract_bits(row: &[u64], start: usize) -> u64 {
+	let index = start / BITS_PER_WORD;
+	let shift = start % BITS_PER_WORD;
+	let mut bits = row[index] >> shift;
+	if shift > 0 && index + 1 < row.len() {
+		bits |= row[index + 1] << (BITS_PER_WORD - shift);
+	}
+	bits
+}
+
+/// A set of collision masks, one for each tile in a [`BitmapAtlas`], generated once up-front so
+/// that they can be re-used for every collision test against the atlas's tiles.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CollisionMaskAtlas {
+	masks: Vec<CollisionMask>,
+}
+
+impl CollisionMaskAtlas {
+	/// Generates collision masks for every tile in the given indexed bitmap atlas. All pixels that
+	/// are not the specified transparent color are considered solid.
+	pub fn from_indexed_atlas(
+		atlas: &BitmapAtlas<IndexedBitmap>,
+		transparent_color: u8,
+	) -> Result<Self, CollisionMaskError> {
+		let masks = (0..atlas.len())
+			.map(|index| CollisionMask::from_indexed_bitmap(atlas.bitmap(), &atlas[index], transparent_color))
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(CollisionMaskAtlas { masks })
+	}
+
+	/// Generates collision masks for every tile in the given RGBA bitmap atlas. All pixels with
+	/// an alpha component greater than or equal to the specified alpha threshold are considered
+	/// solid.
+	pub fn from_rgba_atlas(atlas: &BitmapAtlas<RgbaBitmap>, alpha_threshold: u8) -> Result<Self, CollisionMaskError> {
+		let masks = (0..atlas.len())
+			.map(|index| CollisionMask::from_rgba_bitmap(atlas.bitmap(), &atlas[index], alpha_threshold))
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(CollisionMaskAtlas { masks })
+	}
+
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.masks.len()
+	}
+
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.masks.is_empty()
+	}
+
+	#[inline]
+	pub fn get(&self, index: usize) -> Option<&CollisionMask> {
+		self.masks.get(index)
+	}
+}
+
+impl Index<usize> for CollisionMaskAtlas {
+	type Output = CollisionMask;
+
+	#[inline]
+	fn index(&self, index: usize) -> &Self::Output {
+		self.get(index).unwrap()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use claim::*;
+
+	use crate::graphics::RGBA;
+
+	use super::*;
+
+	#[rustfmt::skip]
+	static MASK_PIXELS: &[u8] = &[
+		1, 1, 0, 0,
+		1, 0, 0, 0,
+		0, 0, 0, 0,
+		0, 0, 0, 2,
+	];
+
+	fn test_mask() -> CollisionMask {
+		let mut bmp = IndexedBitmap::new(4, 4).unwrap();
+		bmp.pixels_mut().copy_from_slice(MASK_PIXELS);
+		CollisionMask::from_indexed_bitmap(&bmp, &bmp.full_bounds(), 0).unwrap()
+	}
+
+	#[test]
+	pub fn creation() {
+		let mask = test_mask();
+		assert_eq!(4, mask.width());
+		assert_eq!(4, mask.height());
+		assert_eq!(4, mask.count());
+		assert!(mask.get(0, 0));
+		assert!(mask.get(1, 0));
+		assert!(mask.get(0, 1));
+		assert!(!mask.get(1, 1));
+		assert!(mask.get(3, 3));
+		assert!(!mask.get(-1, 0));
+		assert!(!mask.get(4, 3));
+
+		let mut bmp = RgbaBitmap::new(3, 1).unwrap();
+		bmp.set_pixel(0, 0, RGBA::from_rgba([255, 255, 255, 0]));
+		bmp.set_pixel(1, 0, RGBA::from_rgba([255, 255, 255, 127]));
+		bmp.set_pixel(2, 0, RGBA::from_rgba([255, 255, 255, 128]));
+		let mask = CollisionMask::from_rgba_bitmap(&bmp, &bmp.full_bounds(), 128).unwrap();
+		assert!(!mask.get(0, 0));
+		assert!(!mask.get(1, 0));
+		assert!(mask.get(2, 0));
+
+		assert_matches!(
+			CollisionMask::from_rgba_bitmap(&bmp, &Rect::new(1, 0, 3, 1), 128),
+			Err(CollisionMaskError::OutOfBounds)
+		);
+		assert_matches!(CollisionMask::from_fn(0, 4, |_, _| true), Err(CollisionMaskError::InvalidDimensions));
+	}
+
+	#[test]
+	pub fn overlaps() {
+		let mask = test_mask();
+
+		assert!(mask.overlaps(&mask, 0, 0));
+		assert!(mask.overlaps(&mask, 3, 3));
+		assert!(mask.overlaps(&mask, -3, -3));
+		assert!(mask.overlaps(&mask, 1, 0));
+		assert!(!mask.overlaps(&mask, 2, 0));
+		assert!(!mask.overlaps(&mask, 2, 2));
+		assert!(!mask.overlaps(&mask, 4, 4));
+		assert!(!mask.overlaps(&mask, -4, 0));
+	}
+
+	#[test]
+	pub fn flipped_overlaps() {
+		let mask = test_mask();
+		let dot = CollisionMask::from_fn(1, 1, |_, _| true).unwrap();
+
+		// top-left corner is solid, top-right is not
+		assert!(mask.overlaps(&dot, 0, 0));
+		assert!(!mask.overlaps(&dot, 3, 0));
+
+		assert!(!mask.overlaps_flipped(true, false, &dot, 0, 0, false, false));
+		assert!(mask.overlaps_flipped(true, false, &dot, 3, 0, false, false));
+		assert!(mask.overlaps_flipped(true, false, &dot, 0, 3, false, false));
+		assert!(mask.overlaps_flipped(false, true, &dot, 0, 3, false, false));
+		assert!(mask.overlaps_flipped(false, true, &dot, 3, 0, false, false));
+		assert!(mask.overlaps_flipped(true, true, &dot, 3, 3, false, false));
+		assert!(!mask.overlaps_flipped(true, true, &dot, 0, 3, false, false));
+
+		// flipping both masks the same way is the same as not flipping either
+		assert!(!mask.overlaps_flipped(true, true, &mask, -2, -2, true, true));
+		assert!(mask.overlaps_flipped(true, true, &mask, -3, -3, true, true));
+	}
+
+	#[test]
+	pub fn wide_overlaps() {
+		// masks wider than a single word, with solid pixels straddling word boundaries
+		let a = CollisionMask::from_fn(150, 2, |x, y| y == 1 && x == 70).unwrap();
+		let b = CollisionMask::from_fn(100, 2, |x, y| y == 0 && x == 99).unwrap();
+
+		assert!(a.overlaps(&b, -29, 1));
+		assert!(!a.overlaps(&b, -28, 1));
+		assert!(!a.overlaps(&b, -29, 0));
+		assert!(b.overlaps(&a, 29, -1));
+
+		assert!(a.overlaps_flipped(true, false, &b, -20, 1, false, false));
+		assert!(a.overlaps_flipped(true, true, &b, 79, -1, true, true));
+		assert!(!a.overlaps_flipped(true, true, &b, 79, 0, true, true));
+	}
+
+	#[test]
+	pub fn atlas_masks() {
+		let mut bmp = IndexedBitmap::new(8, 4).unwrap();
+		bmp.clear(0);
+		bmp.set_pixel(1, 1, 5);
+		bmp.set_pixel(6, 2, 5);
+		bmp.set_pixel(7, 3, 5);
+		let mut atlas = BitmapAtlas::new(bmp);
+		atlas.add_grid(4, 4).unwrap();
+
+		let masks = CollisionMaskAtlas::from_indexed_atlas(&atlas, 0).unwrap();
+		assert_eq!(2, masks.len());
+		assert_eq!(1, masks[0].count());
+		assert_eq!(2, masks[1].count());
+		assert!(masks[0].get(1, 1));
+		assert!(masks[1].get(2, 2));
+		assert!(masks[1].get(3, 3));
+		assert_none!(masks.get(2));
+	}
+}
@@ -1,6 +1,6 @@
 use std::ops::{Mul, MulAssign};
 use std::simd;
-use std::simd::{SimdFloat, SimdUint};
+use std::simd::{SimdFloat, SimdOrd, SimdPartialOrd, SimdUint};
 
 use byteorder::{ReadBytesExt, WriteBytesExt};
 
@@ -32,6 +32,24 @@ pub enum BlendFunction {
 	BlendSourceWithAlpha(u8),
 	TintedBlend(RGBA),
 	MultipliedBlend(RGBA),
+	/// Adds the source color to the destination color, brightening it. Useful for glows, explosions and lighting.
+	Additive,
+	/// Subtracts the source color from the destination color, darkening it.
+	Subtractive,
+	/// Multiplies the source and destination colors together, darkening the destination.
+	Multiply,
+	/// Multiplies the inverses of the source and destination colors together, brightening the destination.
+	Screen,
+	/// Multiplies dark parts of the destination and screens light parts of it, increasing contrast.
+	Overlay,
+	/// Keeps the darkest of the source and destination color components.
+	Darken,
+	/// Keeps the lightest of the source and destination color components.
+	Lighten,
+	/// Blends a source color which has already had its RGB components multiplied by its alpha component.
+	PremultipliedBlend,
+	/// The same as `PremultipliedBlend`, but the source color is made more transparent by the given alpha value.
+	PremultipliedBlendSourceWithAlpha(u8),
 }
 
 impl BlendFunction {
@@ -52,10 +70,32 @@ impl BlendFunction {
 			BlendSourceWithAlpha(opacity) => src.blend_with_alpha(dest, *opacity),
 			TintedBlend(tint) => src.tint(*tint).blend(dest),
 			MultipliedBlend(color) => src.mul(*color).blend(dest),
+			Additive => src.blend_mode(dest, |s, d| (s + d).simd_min(simd::u32x4::splat(255))),
+			Subtractive => src.blend_mode(dest, |s, d| d.saturating_sub(s)),
+			Multiply => src.blend_mode(dest, |s, d| s * d / simd::u32x4::splat(255)),
+			Screen => src.blend_mode(dest, screen_components),
+			Overlay => src.blend_mode(dest, |s, d| {
+				let max = simd::u32x4::splat(255);
+				let multiplied = simd::u32x4::splat(2) * s * d / max;
+				let screened = max - simd::u32x4::splat(2) * (max - s) * (max - d) / max;
+				d.simd_lt(simd::u32x4::splat(128)).select(multiplied, screened)
+			}),
+			Darken => src.blend_mode(dest, |s, d| s.simd_min(d)),
+			Lighten => src.blend_mode(dest, |s, d| s.simd_max(d)),
+			PremultipliedBlend => src.blend_premultiplied(dest),
+			PremultipliedBlendSourceWithAlpha(opacity) => {
+				src.mul(RGBA::from_rgba([*opacity; 4])).blend_premultiplied(dest)
+			}
 		}
 	}
 }
 
+#[inline]
+fn screen_components(s: simd::u32x4, d: simd::u32x4) -> simd::u32x4 {
+	let max = simd::u32x4::splat(255);
+	max - (max - s) * (max - d) / max
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 
 pub trait BytesAsColors<T> {
@@ -249,6 +289,48 @@ impl RGBA {
 		RGBA::blend_components(strength, tint, *self)
 	}
 
+	/// Blends two colors together using a blend mode, using this color as the source color and the other provided
+	/// color as the destination color. The blend mode function is given the source and destination RGB components
+	/// (0-255) and returns the resulting components (0-255), which are then blended over the destination color using
+	/// the source color's alpha component. The alpha component of the result is calculated in the same way as for
+	/// [`RGBA::blend_premultiplied`].
+	#[inline]
+	fn blend_mode(&self, dest: Self, mode_fn: impl Fn(simd::u32x4, simd::u32x4) -> simd::u32x4) -> Self {
+		let src_components = self.0.cast::<u32>();
+		let dest_components = dest.0.cast::<u32>();
+		let alpha = simd::u32x4::splat(self.a() as u32);
+		let max = simd::u32x4::splat(255);
+		let blended = mode_fn(src_components, dest_components);
+		let mut result = RGBA(((blended * alpha + dest_components * (max - alpha)) / max).cast());
+		result.set_a(screen_components(alpha, dest_components)[3] as u8);
+		result
+	}
+
+	/// Blends two colors together, where this color (the source color) has already had its RGB components
+	/// multiplied by its alpha component (see [`RGBA::premultiplied`]). The resulting alpha component is the
+	/// combined opacity of both colors.
+	///
+	/// # Arguments
+	///
+	/// * `dest`: the destination color that this color is being blended into
+	///
+	/// returns: the blended color result
+	#[inline]
+	pub fn blend_premultiplied(&self, dest: Self) -> Self {
+		let inverse_alpha = simd::u32x4::splat(255 - self.a() as u32);
+		let dest_components = (dest.0.cast::<u32>() * inverse_alpha) / simd::u32x4::splat(255);
+		RGBA(self.0.saturating_add(dest_components.cast()))
+	}
+
+	/// Returns this color with its RGB components multiplied by its alpha component, for use with
+	/// [`RGBA::blend_premultiplied`].
+	#[inline]
+	pub fn premultiplied(&self) -> Self {
+		let mut result = self.mul(RGBA::from_rgba([self.a(); 4]));
+		result.set_a(self.a());
+		result
+	}
+
 	/// Linearly interpolates between this color and another color.
 	///
 	/// # Arguments
@@ -276,6 +358,57 @@ impl RGBA {
 	pub fn greyscale(&self) -> u8 {
 		(brightness(self.luminance()) * 255.0) as u8
 	}
+
+	/// Converts this color to HSV color components, returned in the order: hue (in degrees, 0.0 to 360.0),
+	/// saturation (0.0 to 1.0), value (0.0 to 1.0). The alpha component is ignored.
+	#[inline]
+	pub fn to_hsv(&self) -> [f32; 3] {
+		rgb_to_hsv(self.to_rgbf())
+	}
+
+	/// Returns a color value composed from the provided HSV color components. Substitutes a value of 255 for the
+	/// missing alpha component.
+	///
+	/// # Arguments
+	///
+	/// * `hsv`: the 3 color components in the order: hue (in degrees), saturation (0.0 to 1.0), value (0.0 to 1.0)
+	///
+	/// returns: the composed color value
+	#[inline]
+	pub fn from_hsv(hsv: [f32; 3]) -> Self {
+		RGBA::from_rgbf(hsv_to_rgb(hsv))
+	}
+
+	/// Converts this color to HSL color components, returned in the order: hue (in degrees, 0.0 to 360.0),
+	/// saturation (0.0 to 1.0), lightness (0.0 to 1.0). The alpha component is ignored.
+	#[inline]
+	pub fn to_hsl(&self) -> [f32; 3] {
+		rgb_to_hsl(self.to_rgbf())
+	}
+
+	/// Returns a color value composed from the provided HSL color components. Substitutes a value of 255 for the
+	/// missing alpha component.
+	///
+	/// # Arguments
+	///
+	/// * `hsl`: the 3 color components in the order: hue (in degrees), saturation (0.0 to 1.0), lightness (0.0 to
+	///          1.0)
+	///
+	/// returns: the composed color value
+	#[inline]
+	pub fn from_hsl(hsl: [f32; 3]) -> Self {
+		RGBA::from_rgbf(hsl_to_rgb(hsl))
+	}
+
+	#[inline]
+	fn to_rgbf(self) -> [f32; 3] {
+		[self.r() as f32 / 255.0, self.g() as f32 / 255.0, self.b() as f32 / 255.0]
+	}
+
+	#[inline]
+	fn from_rgbf(rgb: [f32; 3]) -> Self {
+		RGBA::from_rgb(rgb.map(|component| (component.clamp(0.0, 1.0) * 255.0).round() as u8))
+	}
 }
 
 impl Mul for RGBA {
@@ -510,6 +643,47 @@ impl RGBAf {
 	pub const fn to_array(&self) -> [f32; 4] {
 		self.0.to_array()
 	}
+
+	/// Converts this color to HSV color components, returned in the order: hue (in degrees, 0.0 to 360.0),
+	/// saturation (0.0 to 1.0), value (0.0 to 1.0). The alpha component is ignored.
+	#[inline]
+	pub fn to_hsv(&self) -> [f32; 3] {
+		rgb_to_hsv([self.r(), self.g(), self.b()])
+	}
+
+	/// Returns a color value composed from the provided HSV color components. Substitutes a value of 1.0 for the
+	/// missing alpha component.
+	///
+	/// # Arguments
+	///
+	/// * `hsv`: the 3 color components in the order: hue (in degrees), saturation (0.0 to 1.0), value (0.0 to 1.0)
+	///
+	/// returns: the composed color value
+	#[inline]
+	pub fn from_hsv(hsv: [f32; 3]) -> Self {
+		RGBAf::from_rgb(hsv_to_rgb(hsv))
+	}
+
+	/// Converts this color to HSL color components, returned in the order: hue (in degrees, 0.0 to 360.0),
+	/// saturation (0.0 to 1.0), lightness (0.0 to 1.0). The alpha component is ignored.
+	#[inline]
+	pub fn to_hsl(&self) -> [f32; 3] {
+		rgb_to_hsl([self.r(), self.g(), self.b()])
+	}
+
+	/// Returns a color value composed from the provided HSL color components. Substitutes a value of 1.0 for the
+	/// missing alpha component.
+	///
+	/// # Arguments
+	///
+	/// * `hsl`: the 3 color components in the order: hue (in degrees), saturation (0.0 to 1.0), lightness (0.0 to
+	///          1.0)
+	///
+	/// returns: the composed color value
+	#[inline]
+	pub fn from_hsl(hsl: [f32; 3]) -> Self {
+		RGBAf::from_rgb(hsl_to_rgb(hsl))
+	}
 }
 
 impl From<u32> for RGBAf {
@@ -553,6 +727,264 @@ impl std::fmt::Display for RGBAf {
 
 ///////////////////////////////////////////////////////////////////////////////
 
+// expands a 5 or 6-bit color component back out to the full 0-255 range, replicating the high bits into the
+// low bits so that 0 maps to 0 and the maximum component value maps to 255
+#[inline]
+const fn expand_component(value: u16, bits: u32) -> u8 {
+	let value = value << (8 - bits);
+	(value | (value >> bits)) as u8
+}
+
+/// Packed 16-bit color with 5 bits for red, 6 bits for green and 5 bits for blue, stored as 0bRRRRRGGGGGGBBBBB.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+#[repr(transparent)]
+pub struct RGB565(pub u16);
+
+impl RGB565 {
+	pub const SIZE: usize = std::mem::size_of::<Self>();
+
+	/// Returns a color value composed of the provided RGB color components. The low bits of each component that
+	/// cannot be represented in this format are discarded.
+	///
+	/// # Arguments
+	///
+	/// * `rgb`: the 3 color components (0-255) in the order: red, green, blue
+	///
+	/// returns: the composed color value
+	#[inline]
+	pub const fn from_rgb(rgb: [u8; 3]) -> Self {
+		RGB565(((rgb[0] as u16 >> 3) << 11) | ((rgb[1] as u16 >> 2) << 5) | (rgb[2] as u16 >> 3))
+	}
+
+	/// Returns the current red component value of this color, expanded to the range 0-255.
+	#[inline]
+	pub const fn r(&self) -> u8 {
+		expand_component((self.0 >> 11) & 0x1f, 5)
+	}
+
+	/// Returns the current green component value of this color, expanded to the range 0-255.
+	#[inline]
+	pub const fn g(&self) -> u8 {
+		expand_component((self.0 >> 5) & 0x3f, 6)
+	}
+
+	/// Returns the current blue component value of this color, expanded to the range 0-255.
+	#[inline]
+	pub const fn b(&self) -> u8 {
+		expand_component(self.0 & 0x1f, 5)
+	}
+
+	/// Returns an array containing this color's components (expanded to the range 0-255) in the order: red,
+	/// green, blue.
+	#[inline]
+	pub const fn to_rgb(&self) -> [u8; 3] {
+		[self.r(), self.g(), self.b()]
+	}
+}
+
+impl From<RGBA> for RGB565 {
+	/// Converts a [`RGBA`] color to the nearest [`RGB565`] color value. The alpha component is discarded.
+	#[inline]
+	fn from(value: RGBA) -> Self {
+		RGB565::from_rgb([value.r(), value.g(), value.b()])
+	}
+}
+
+impl From<RGB565> for RGBA {
+	/// Converts a [`RGB565`] color to an equivalent [`RGBA`] color value, which will always be fully opaque.
+	#[inline]
+	fn from(value: RGB565) -> Self {
+		RGBA::from_rgb(value.to_rgb())
+	}
+}
+
+impl std::fmt::Debug for RGB565 {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "0x{:04x}", self.0)
+	}
+}
+
+impl std::fmt::Display for RGB565 {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "0x{:04x}", self.0)
+	}
+}
+
+/// Packed 16-bit color with a single alpha bit followed by 5 bits each for red, green and blue, stored as
+/// 0bARRRRRGGGGGBBBBB. A pixel is either fully opaque or fully transparent.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+#[repr(transparent)]
+pub struct ARGB1555(pub u16);
+
+impl ARGB1555 {
+	pub const SIZE: usize = std::mem::size_of::<Self>();
+
+	/// Returns a color value composed of the provided RGBA color components. Alpha values of 128 or more are
+	/// treated as opaque, anything less is treated as transparent.
+	///
+	/// # Arguments
+	///
+	/// * `rgba`: the 4 color components (0-255) in the order: red, green, blue, alpha
+	///
+	/// returns: the composed color value
+	#[inline]
+	pub const fn from_rgba(rgba: [u8; 4]) -> Self {
+		ARGB1555(
+			((rgba[3] as u16 >> 7) << 15)
+				| ((rgba[0] as u16 >> 3) << 10)
+				| ((rgba[1] as u16 >> 3) << 5)
+				| (rgba[2] as u16 >> 3),
+		)
+	}
+
+	/// Returns a fully opaque color value composed of the provided RGB color components.
+	///
+	/// # Arguments
+	///
+	/// * `rgb`: the 3 color components (0-255) in the order: red, green, blue
+	///
+	/// returns: the composed color value
+	#[inline]
+	pub const fn from_rgb(rgb: [u8; 3]) -> Self {
+		ARGB1555::from_rgba([rgb[0], rgb[1], rgb[2], 255])
+	}
+
+	/// Returns the current red component value of this color, expanded to the range 0-255.
+	#[inline]
+	pub const fn r(&self) -> u8 {
+		expand_component((self.0 >> 10) & 0x1f, 5)
+	}
+
+	/// Returns the current green component value of this color, expanded to the range 0-255.
+	#[inline]
+	pub const fn g(&self) -> u8 {
+		expand_component((self.0 >> 5) & 0x1f, 5)
+	}
+
+	/// Returns the current blue component value of this color, expanded to the range 0-255.
+	#[inline]
+	pub const fn b(&self) -> u8 {
+		expand_component(self.0 & 0x1f, 5)
+	}
+
+	/// Returns the current alpha component value of this color, which is either 0 or 255.
+	#[inline]
+	pub const fn a(&self) -> u8 {
+		if self.0 & 0x8000 != 0 {
+			255
+		} else {
+			0
+		}
+	}
+
+	/// Returns an array containing this color's components (expanded to the range 0-255) in the order: red,
+	/// green, blue, alpha.
+	#[inline]
+	pub const fn to_array(&self) -> [u8; 4] {
+		[self.r(), self.g(), self.b(), self.a()]
+	}
+}
+
+impl From<RGBA> for ARGB1555 {
+	/// Converts a [`RGBA`] color to the nearest [`ARGB1555`] color value.
+	#[inline]
+	fn from(value: RGBA) -> Self {
+		ARGB1555::from_rgba(value.to_array())
+	}
+}
+
+impl From<ARGB1555> for RGBA {
+	/// Converts a [`ARGB1555`] color to an equivalent [`RGBA`] color value.
+	#[inline]
+	fn from(value: ARGB1555) -> Self {
+		RGBA::from_rgba(value.to_array())
+	}
+}
+
+impl std::fmt::Debug for ARGB1555 {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "0x{:04x}", self.0)
+	}
+}
+
+impl std::fmt::Display for ARGB1555 {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "0x{:04x}", self.0)
+	}
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
+/// Returns the hue (in degrees, 0.0 to 360.0) for the given RGB components, along with the largest and smallest of
+/// those components.
+fn hue_and_extents(rgb: [f32; 3]) -> (f32, f32, f32) {
+	let [r, g, b] = rgb;
+	let max = r.max(g).max(b);
+	let min = r.min(g).min(b);
+	let delta = max - min;
+	let hue = if delta <= 0.0 {
+		0.0
+	} else if max == r {
+		60.0 * ((g - b) / delta).rem_euclid(6.0)
+	} else if max == g {
+		60.0 * ((b - r) / delta + 2.0)
+	} else {
+		60.0 * ((r - g) / delta + 4.0)
+	};
+	(hue, max, min)
+}
+
+/// Returns RGB components for the given hue (in degrees), chroma and amount to add to each component.
+fn rgb_from_hue_and_chroma(hue: f32, chroma: f32, m: f32) -> [f32; 3] {
+	let sector = hue.rem_euclid(360.0) / 60.0;
+	let x = chroma * (1.0 - ((sector % 2.0) - 1.0).abs());
+	let [r, g, b] = match sector as u32 {
+		0 => [chroma, x, 0.0],
+		1 => [x, chroma, 0.0],
+		2 => [0.0, chroma, x],
+		3 => [0.0, x, chroma],
+		4 => [x, 0.0, chroma],
+		_ => [chroma, 0.0, x],
+	};
+	[r + m, g + m, b + m]
+}
+
+/// Converts the given RGB color components (0.0 to 1.0) to HSV color components, returned in the order: hue (in
+/// degrees, 0.0 to 360.0), saturation (0.0 to 1.0), value (0.0 to 1.0).
+pub fn rgb_to_hsv(rgb: [f32; 3]) -> [f32; 3] {
+	let (hue, max, min) = hue_and_extents(rgb);
+	let saturation = if max <= 0.0 { 0.0 } else { (max - min) / max };
+	[hue, saturation, max]
+}
+
+/// Converts the given HSV color components, in the order: hue (in degrees), saturation (0.0 to 1.0), value (0.0 to
+/// 1.0), to RGB color components (0.0 to 1.0).
+pub fn hsv_to_rgb(hsv: [f32; 3]) -> [f32; 3] {
+	let [hue, saturation, value] = hsv;
+	let chroma = value * saturation;
+	rgb_from_hue_and_chroma(hue, chroma, value - chroma)
+}
+
+/// Converts the given RGB color components (0.0 to 1.0) to HSL color components, returned in the order: hue (in
+/// degrees, 0.0 to 360.0), saturation (0.0 to 1.0), lightness (0.0 to 1.0).
+pub fn rgb_to_hsl(rgb: [f32; 3]) -> [f32; 3] {
+	let (hue, max, min) = hue_and_extents(rgb);
+	let lightness = (max + min) / 2.0;
+	let divisor = 1.0 - (2.0 * lightness - 1.0).abs();
+	let saturation = if divisor <= 0.0 { 0.0 } else { (max - min) / divisor };
+	[hue, saturation, lightness]
+}
+
+/// Converts the given HSL color components, in the order: hue (in degrees), saturation (0.0 to 1.0), lightness (0.0
+/// to 1.0), to RGB color components (0.0 to 1.0).
+pub fn hsl_to_rgb(hsl: [f32; 3]) -> [f32; 3] {
+	let [hue, saturation, lightness] = hsl;
+	let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+	rgb_from_hue_and_chroma(hue, chroma, lightness - chroma / 2.0)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+
 const LUMINANCE_RED: f32 = 0.212655;
 const LUMINANCE_GREEN: f32 = 0.715158;
 const LUMINANCE_BLUE: f32 = 0.072187;
@@ -697,6 +1129,59 @@ mod tests {
 		assert_eq!([0xff, 0xff, 0xff, 0xff], RGBA::from(0xffffffff).tint(RGBA::from(0x11223300)).to_array());
 	}
 
+	#[test]
+	#[rustfmt::skip]
+	fn rgba_premultiplied_blending() {
+		assert_eq!([0x11, 0x22, 0x33, 0xff], RGBA::from(0x112233ff).premultiplied().to_array());
+		assert_eq!([0x08, 0x10, 0x19, 0x7f], RGBA::from(0x1122337f).premultiplied().to_array());
+		assert_eq!([0x00, 0x00, 0x00, 0x00], RGBA::from(0x11223300).premultiplied().to_array());
+
+		assert_eq!([0x11, 0x22, 0x33, 0xff], RGBA::from(0x112233ff).blend_premultiplied(RGBA::from(0x555555ff)).to_array());
+		assert_eq!([0x32, 0x3a, 0x43, 0xff], RGBA::from(0x0810197f).blend_premultiplied(RGBA::from(0x555555ff)).to_array());
+		assert_eq!([0x55, 0x55, 0x55, 0xff], RGBA::from(0x00000000).blend_premultiplied(RGBA::from(0x555555ff)).to_array());
+		assert_eq!([0x32, 0x3a, 0x43, 0xbe], RGBA::from(0x0810197f).blend_premultiplied(RGBA::from(0x5555557f)).to_array());
+
+		// premultiplied blending gives the same result as regular blending when the destination is opaque
+		let src = RGBA::from(0x1122337f);
+		let dest = RGBA::from(0x555555ff);
+		let blended = src.blend(dest).to_array();
+		let premultiplied = src.premultiplied().blend_premultiplied(dest).to_array();
+		for i in 0..3 {
+			assert!(blended[i].abs_diff(premultiplied[i]) <= 1, "{:?} {:?}", blended, premultiplied);
+		}
+	}
+
+	#[test]
+	#[rustfmt::skip]
+	fn blend_function_modes() {
+		use BlendFunction::*;
+
+		let src = RGBA::from(0x4080c0ff);
+		let dest = RGBA::from(0x606060ff);
+		assert_eq!([0xa0, 0xe0, 0xff, 0xff], Additive.blend(src, dest).to_array());
+		assert_eq!([0x20, 0x00, 0x00, 0xff], Subtractive.blend(src, dest).to_array());
+		assert_eq!([0x18, 0x30, 0x48, 0xff], Multiply.blend(src, dest).to_array());
+		assert_eq!([0x88, 0xb0, 0xd8, 0xff], Screen.blend(src, dest).to_array());
+		assert_eq!([0x30, 0x60, 0x90, 0xff], Overlay.blend(src, dest).to_array());
+		assert_eq!([0x40, 0x60, 0x60, 0xff], Darken.blend(src, dest).to_array());
+		assert_eq!([0x60, 0x80, 0xc0, 0xff], Lighten.blend(src, dest).to_array());
+		assert_eq!([0x40, 0x80, 0xc0, 0xff], PremultipliedBlend.blend(src, dest).to_array());
+		assert_eq!([0x4f, 0x6f, 0x8f, 0xff], PremultipliedBlendSourceWithAlpha(128).blend(src, dest).to_array());
+
+		// overlay uses screen for lighter destination colors
+		assert_eq!([0x71, 0xa1, 0xd1, 0xff], Overlay.blend(src, RGBA::from(0xa0a0a0ff)).to_array());
+
+		// the source color's alpha controls the strength of the blend
+		let src = RGBA::from(0x4080c080);
+		assert_eq!([0x80, 0xa0, 0xaf, 0xff], Additive.blend(src, dest).to_array());
+		assert_eq!([0x3f, 0x2f, 0x2f, 0xff], Subtractive.blend(src, dest).to_array());
+		assert_eq!([0x60, 0x60, 0x60, 0xff], Additive.blend(RGBA::from(0x4080c000), dest).to_array());
+
+		// the resulting alpha is the combined opacity of both colors
+		assert_eq!(0xc0, Additive.blend(src, RGBA::from(0x60606080)).a());
+		assert_eq!(0xff, Multiply.blend(RGBA::from(0x4080c0ff), RGBA::from(0x60606000)).a());
+	}
+
 	#[test]
 	fn rgba_bytes_to_colors_casting() {
 		let mut bytes =
@@ -811,4 +1296,82 @@ mod tests {
 		assert!(color.g().nearly_equal(0.2, 0.01));
 		assert!(color.b().nearly_equal(0.3, 0.01));
 	}
+
+	#[test]
+	fn hsv_and_hsl() {
+		let assert_components = |expected: [f32; 3], actual: [f32; 3]| {
+			for (expected, actual) in expected.iter().zip(actual.iter()) {
+				assert!(expected.nearly_equal(*actual, 0.01), "expected {:?}, got {:?}", expected, actual);
+			}
+		};
+
+		assert_components([0.0, 1.0, 1.0], RGBA::from_rgb([255, 0, 0]).to_hsv());
+		assert_components([120.0, 1.0, 0.5], RGBA::from_rgb([0, 128, 0]).to_hsv());
+		assert_components([240.0, 0.5, 1.0], RGBA::from_rgb([128, 128, 255]).to_hsv());
+		assert_components([300.0, 1.0, 1.0], RGBA::from_rgb([255, 0, 255]).to_hsv());
+		assert_components([0.0, 0.0, 0.5], RGBA::from_rgb([128, 128, 128]).to_hsv());
+		assert_components([0.0, 0.0, 0.0], RGBA::from_rgb([0, 0, 0]).to_hsv());
+
+		assert_components([0.0, 1.0, 0.5], RGBA::from_rgb([255, 0, 0]).to_hsl());
+		assert_components([120.0, 1.0, 0.25], RGBA::from_rgb([0, 128, 0]).to_hsl());
+		assert_components([240.0, 1.0, 0.75], RGBA::from_rgb([128, 128, 255]).to_hsl());
+		assert_components([0.0, 0.0, 1.0], RGBA::from_rgb([255, 255, 255]).to_hsl());
+
+		assert_eq!(RGBA::from_rgb([255, 0, 0]), RGBA::from_hsv([0.0, 1.0, 1.0]));
+		assert_eq!(RGBA::from_rgb([255, 0, 0]), RGBA::from_hsv([360.0, 1.0, 1.0]));
+		assert_eq!(RGBA::from_rgb([0, 128, 0]), RGBA::from_hsv([120.0, 1.0, 0.5]));
+		assert_eq!(RGBA::from_rgb([255, 0, 255]), RGBA::from_hsv([-60.0, 1.0, 1.0]));
+		assert_eq!(RGBA::from_rgb([128, 128, 255]), RGBA::from_hsl([240.0, 1.0, 0.75]));
+		assert_eq!(RGBA::from_rgb([255, 255, 255]), RGBA::from_hsl([0.0, 0.0, 1.0]));
+
+		for color in [0x336699ffu32, 0xfa8020ff, 0x10e0a0ff, 0x000000ff, 0xffffffff] {
+			let color = RGBA::from(color);
+			assert_eq!(color, RGBA::from_hsv(color.to_hsv()));
+			assert_eq!(color, RGBA::from_hsl(color.to_hsl()));
+		}
+
+		let color = RGBAf::from_rgba([0.2, 0.4, 0.6, 0.5]);
+		assert_components([210.0, 0.667, 0.6], color.to_hsv());
+		assert_components([210.0, 0.5, 0.4], color.to_hsl());
+		assert_components([0.2, 0.4, 0.6], {
+			let [r, g, b, a] = RGBAf::from_hsv(color.to_hsv()).to_array();
+			assert_eq!(1.0, a);
+			[r, g, b]
+		});
+		assert_components([0.2, 0.4, 0.6], {
+			let [r, g, b, _] = RGBAf::from_hsl(color.to_hsl()).to_array();
+			[r, g, b]
+		});
+	}
+
+	#[test]
+	fn rgb565() {
+		let color = RGB565::from_rgb([0xff, 0x80, 0x00]);
+		assert_eq!(0xfc00, color.0);
+		assert_eq!(0xff, color.r());
+		assert_eq!(0x82, color.g());
+		assert_eq!(0x00, color.b());
+
+		assert_eq!(RGBA::from_rgb([0xff, 0xff, 0xff]), RGBA::from(RGB565(0xffff)));
+		assert_eq!(RGBA::from_rgb([0x00, 0x00, 0x00]), RGBA::from(RGB565(0x0000)));
+		assert_eq!(RGB565(0x1234), RGB565::from(RGBA::from(RGB565(0x1234))));
+		assert_eq!(RGB565(0xf81f), RGB565::from(RGBA::from_rgba([0xff, 0x00, 0xff, 0x00])));
+	}
+
+	#[test]
+	fn argb1555() {
+		let color = ARGB1555::from_rgba([0xff, 0x80, 0x00, 0xff]);
+		assert_eq!(0xfe00, color.0);
+		assert_eq!(0xff, color.r());
+		assert_eq!(0x84, color.g());
+		assert_eq!(0x00, color.b());
+		assert_eq!(0xff, color.a());
+
+		assert_eq!(0x00, ARGB1555::from_rgba([0xff, 0xff, 0xff, 0x7f]).a());
+		assert_eq!(0xff, ARGB1555::from_rgba([0xff, 0xff, 0xff, 0x80]).a());
+		assert_eq!(ARGB1555(0xffff), ARGB1555::from_rgb([0xff, 0xff, 0xff]));
+
+		assert_eq!(RGBA::from_rgba([0xff, 0xff, 0xff, 0x00]), RGBA::from(ARGB1555(0x7fff)));
+		assert_eq!(ARGB1555(0x9234), ARGB1555::from(RGBA::from(ARGB1555(0x9234))));
+	}
 }
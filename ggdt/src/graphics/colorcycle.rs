@@ -0,0 +1,212 @@
+use crate::graphics::Palette;
+
+/// A range of palette colors that are rotated ("cycled") at a fixed rate, as used by Deluxe Paint and other
+/// Amiga-era art packages to animate static images by changing only the palette.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ColorCycle {
+	/// The first color in the range (inclusive).
+	pub start: u8,
+	/// The last color in the range (inclusive). Ranges where this is less than `start` are left alone.
+	pub end: u8,
+	/// The number of times per second that the colors in the range are rotated by one position.
+	pub rate: f32,
+	/// If true, colors are rotated towards the start of the range instead of towards the end.
+	pub reverse: bool,
+	/// Whether this range should be cycled at all. Inactive ranges are left alone by [`PaletteCycler`].
+	pub active: bool,
+}
+
+impl ColorCycle {
+	/// Creates a new, active, color cycling range.
+	///
+	/// # Arguments
+	///
+	/// * `start`: the first color in the range (inclusive)
+	/// * `end`: the last color in the range (inclusive)
+	/// * `rate`: the number of times per second to rotate the colors in the range by one position
+	/// * `reverse`: whether to rotate colors towards the start of the range instead of towards the end
+	pub fn new(start: u8, end: u8, rate: f32, reverse: bool) -> Self {
+		let (start, end) = if start > end { (end, start) } else { (start, end) };
+		ColorCycle { start, end, rate, reverse, active: true }
+	}
+
+	/// Rotates the colors in this cycle's range on the given palette by the given number of steps, in the direction
+	/// this cycle is set to rotate in.
+	pub fn rotate(&self, palette: &mut Palette, steps: usize) {
+		if self.end < self.start {
+			return;
+		}
+		let length = (self.end - self.start) as usize + 1;
+		let mut steps = steps % length;
+		while steps > 0 {
+			let step = steps.min(i8::MAX as usize);
+			let step = if self.reverse { -(step as i8) } else { step as i8 };
+			palette.rotate_colors(self.start..=self.end, step);
+			steps -= step.unsigned_abs() as usize;
+		}
+	}
+}
+
+/// Animates a set of [`ColorCycle`] ranges on a [`Palette`] over time. Each range is rotated independently at its
+/// own rate.
+#[derive(Debug, Clone, Default)]
+pub struct PaletteCycler {
+	cycles: Vec<ColorCycle>,
+	elapsed: Vec<f32>,
+}
+
+impl PaletteCycler {
+	/// Creates a new palette cycler that will animate the given color cycling ranges.
+	pub fn new(cycles: &[ColorCycle]) -> Self {
+		PaletteCycler {
+			cycles: cycles.to_vec(), //
+			elapsed: vec![0.0; cycles.len()],
+		}
+	}
+
+	/// Returns the color cycling ranges being animated.
+	#[inline]
+	pub fn cycles(&self) -> &[ColorCycle] {
+		&self.cycles
+	}
+
+	/// Returns the color cycling ranges being animated, allowing them to be modified (e.g. to toggle them on or
+	/// off or to change their rates).
+	#[inline]
+	pub fn cycles_mut(&mut self) -> &mut [ColorCycle] {
+		&mut self.cycles
+	}
+
+	/// Adds another color cycling range to be animated.
+	pub fn add(&mut self, cycle: ColorCycle) {
+		self.cycles.push(cycle);
+		self.elapsed.push(0.0);
+	}
+
+	/// Removes all color cycling ranges.
+	pub fn clear(&mut self) {
+		self.cycles.clear();
+		self.elapsed.clear();
+	}
+
+	/// Resets the timing of all color cycling ranges. Note that this does not undo any rotations already applied
+	/// to a palette.
+	pub fn reset(&mut self) {
+		self.elapsed.fill(0.0);
+	}
+
+	/// Advances all active color cycling ranges by the given amount of time, rotating colors in the palette for
+	/// each range whose rate dictates that it is time to do so.
+	///
+	/// # Arguments
+	///
+	/// * `palette`: the palette to rotate colors in
+	/// * `delta`: the time elapsed since the last update, in seconds
+	///
+	/// returns: true if any colors in the palette were changed
+	pub fn update(&mut self, palette: &mut Palette, delta: f32) -> bool {
+		let mut changed = false;
+		for (cycle, elapsed) in self.cycles.iter().zip(self.elapsed.iter_mut()) {
+			if !cycle.active || cycle.rate <= 0.0 || cycle.start >= cycle.end {
+				continue;
+			}
+			*elapsed += delta;
+			let interval = 1.0 / cycle.rate;
+			if *elapsed >= interval {
+				let steps = (*elapsed / interval) as usize;
+				*elapsed -= steps as f32 * interval;
+				cycle.rotate(palette, steps);
+				changed = true;
+			}
+		}
+		changed
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::graphics::RGBA;
+
+	use super::*;
+
+	fn test_palette() -> Palette {
+		let mut palette = Palette::new();
+		for i in 0..=255 {
+			palette[i] = RGBA::from_rgb([i, i, i]);
+		}
+		palette
+	}
+
+	#[test]
+	pub fn rotates_colors_over_time() {
+		let mut palette = test_palette();
+		let mut cycler = PaletteCycler::new(&[ColorCycle::new(10, 13, 4.0, false)]);
+
+		assert!(!cycler.update(&mut palette, 0.2));
+		assert_eq!(test_palette(), palette);
+
+		assert!(cycler.update(&mut palette, 0.1));
+		assert_eq!(RGBA::from_rgb([13, 13, 13]), palette[10]);
+		assert_eq!(RGBA::from_rgb([10, 10, 10]), palette[11]);
+		assert_eq!(RGBA::from_rgb([12, 12, 12]), palette[13]);
+		assert_eq!(RGBA::from_rgb([9, 9, 9]), palette[9]);
+		assert_eq!(RGBA::from_rgb([14, 14, 14]), palette[14]);
+
+		// a full second at 4 steps per second, with a 4 color range, should bring us back to where we were
+		assert!(cycler.update(&mut palette, 1.0));
+		assert_eq!(RGBA::from_rgb([13, 13, 13]), palette[10]);
+	}
+
+	#[test]
+	pub fn rotates_in_reverse() {
+		let mut palette = test_palette();
+		let mut cycler = PaletteCycler::new(&[ColorCycle::new(10, 13, 4.0, true)]);
+
+		assert!(cycler.update(&mut palette, 0.5));
+		assert_eq!(RGBA::from_rgb([12, 12, 12]), palette[10]);
+		assert_eq!(RGBA::from_rgb([13, 13, 13]), palette[11]);
+		assert_eq!(RGBA::from_rgb([10, 10, 10]), palette[12]);
+		assert_eq!(RGBA::from_rgb([11, 11, 11]), palette[13]);
+	}
+
+	#[test]
+	pub fn independent_ranges_and_rates() {
+		let mut palette = test_palette();
+		let mut inactive = ColorCycle::new(100, 200, 60.0, false);
+		inactive.active = false;
+		let mut cycler = PaletteCycler::new(&[
+			ColorCycle::new(0, 1, 1.0, false), //
+			ColorCycle::new(20, 29, 10.0, false),
+			inactive,
+		]);
+
+		assert!(cycler.update(&mut palette, 0.5));
+		assert_eq!(RGBA::from_rgb([0, 0, 0]), palette[0]);
+		assert_eq!(RGBA::from_rgb([25, 25, 25]), palette[20]);
+		assert_eq!(RGBA::from_rgb([100, 100, 100]), palette[100]);
+
+		assert!(cycler.update(&mut palette, 0.5));
+		assert_eq!(RGBA::from_rgb([1, 1, 1]), palette[0]);
+		assert_eq!(RGBA::from_rgb([20, 20, 20]), palette[20]);
+		assert_eq!(RGBA::from_rgb([100, 100, 100]), palette[100]);
+
+		// rotations larger than what rotate_colors can do in one go
+		let mut palette = test_palette();
+		ColorCycle::new(0, 255, 1.0, false).rotate(&mut palette, 200);
+		assert_eq!(RGBA::from_rgb([56, 56, 56]), palette[0]);
+	}
+
+	#[test]
+	pub fn backwards_ranges_are_left_alone() {
+		let mut palette = test_palette();
+		let mut cycle = ColorCycle::new(10, 13, 4.0, false);
+		cycle.start = 13;
+		cycle.end = 10;
+		cycle.rotate(&mut palette, 1);
+		assert_eq!(test_palette(), palette);
+
+		let mut cycler = PaletteCycler::new(&[cycle]);
+		assert!(!cycler.update(&mut palette, 1.0));
+		assert_eq!(test_palette(), palette);
+	}
+}
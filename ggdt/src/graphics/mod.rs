@@ -1,16 +1,22 @@
 use std::fmt::Display;
 
+mod aseprite;
 mod bitmap;
 mod bitmapatlas;
 mod blendmap;
+mod collisionmask;
 mod color;
+mod colorcycle;
 mod font;
 mod palette;
 
+pub use aseprite::*;
 pub use bitmap::*;
 pub use bitmapatlas::*;
 pub use blendmap::*;
+pub use collisionmask::*;
 pub use color::*;
+pub use colorcycle::*;
 pub use font::*;
 pub use palette::*;
 
@@ -1,4 +1,5 @@
 use std::cmp::min;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Cursor};
 use std::ops::{Bound, Index, IndexMut, RangeBounds};
@@ -7,7 +8,7 @@ use std::path::Path;
 use byteorder::{ReadBytesExt, WriteBytesExt};
 use thiserror::Error;
 
-use crate::graphics::{IndexedBitmap, RGBA};
+use crate::graphics::{IndexedBitmap, RgbaBitmap, RGBA};
 use crate::utils::abs_diff;
 
 const NUM_COLORS: usize = 256;
@@ -16,6 +17,22 @@ const NUM_COLORS: usize = 256;
 pub trait ColorRange: RangeBounds<u8> + Iterator<Item = u8> {}
 impl<T> ColorRange for T where T: RangeBounds<u8> + Iterator<Item = u8> {}
 
+/// Returns the first and last color indices (inclusive) covered by the given color range.
+fn range_extents<T: ColorRange>(colors: &T) -> (usize, usize) {
+	use Bound::*;
+	let start = match colors.start_bound() {
+		Excluded(&start) => start + 1,
+		Included(&start) => start,
+		Unbounded => 0,
+	} as usize;
+	let end = match colors.end_bound() {
+		Excluded(&end) => end - 1,
+		Included(&end) => end,
+		Unbounded => 255,
+	} as usize;
+	(start, end)
+}
+
 pub static VGA_PALETTE_BYTES: &[u8] = include_bytes!("../../assets/vga.pal");
 
 #[inline]
@@ -108,6 +125,79 @@ pub enum PaletteFormat {
 	Normal,
 }
 
+// a group of unique colors, along with the number of pixels using each color
+type ColorBox = Vec<([u8; 3], u32)>;
+
+// returns the color component (0 = red, 1 = green, 2 = blue) with the widest range of values in the box, as well
+// as the size of that range
+fn widest_component(colors: &ColorBox) -> (usize, u8) {
+	let mut min = [255u8; 3];
+	let mut max = [0u8; 3];
+	for (color, _) in colors.iter() {
+		for component in 0..3 {
+			min[component] = min[component].min(color[component]);
+			max[component] = max[component].max(color[component]);
+		}
+	}
+	(0..3).map(|component| (component, max[component] - min[component])).max_by_key(|(_, range)| *range).unwrap()
+}
+
+// returns the average color of all of the pixels represented by the box
+fn average_color(colors: &ColorBox) -> RGBA {
+	let mut sum = [0u64; 3];
+	let mut count = 0u64;
+	for (color, pixels) in colors.iter() {
+		for component in 0..3 {
+			sum[component] += color[component] as u64 * *pixels as u64;
+		}
+		count += *pixels as u64;
+	}
+	let average = sum.map(|sum| ((sum + count / 2) / count) as u8);
+	RGBA::from_rgb(average)
+}
+
+// reduces the given unique colors down to at most `num_colors` colors using median cut quantization
+fn median_cut(colors: ColorBox, num_colors: usize) -> Vec<RGBA> {
+	if num_colors == 0 || colors.is_empty() {
+		return Vec::new();
+	} else if colors.len() <= num_colors {
+		return colors.iter().map(|(color, _)| RGBA::from_rgb(*color)).collect();
+	}
+
+	let mut boxes = vec![colors];
+	while boxes.len() < num_colors {
+		// split the box which has the widest range of values in any single color component
+		let split = boxes
+			.iter()
+			.enumerate()
+			.filter(|(_, colors)| colors.len() > 1)
+			.map(|(index, colors)| (index, widest_component(colors)))
+			.max_by_key(|(_, (_, range))| *range);
+		let Some((index, (component, _))) = split else {
+			break;
+		};
+
+		// split at the median pixel (not the median unique color) along that component
+		let mut colors = boxes.swap_remove(index);
+		colors.sort_unstable_by_key(|(color, _)| (color[component], *color));
+		let half = colors.iter().map(|(_, pixels)| *pixels as u64).sum::<u64>() / 2;
+		let mut count = 0;
+		let mut median = colors.len() - 1;
+		for (index, (_, pixels)) in colors.iter().enumerate() {
+			count += *pixels as u64;
+			if count >= half {
+				median = index + 1;
+				break;
+			}
+		}
+		let other = colors.split_off(median.clamp(1, colors.len() - 1));
+		boxes.push(colors);
+		boxes.push(other);
+	}
+
+	boxes.iter().map(average_color).collect()
+}
+
 /// Contains a 256 color palette, and provides methods useful for working with palettes. The
 /// colors are all stored individually as 32-bit packed values in the format 0xAARRGGBB.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -202,6 +292,212 @@ impl Palette {
 		Ok(Palette { colors })
 	}
 
+	/// Generates a palette containing up to `num_colors` colors which best represent all of the colors used in the
+	/// given bitmaps, using median cut color quantization. Passing multiple bitmaps results in one palette that is
+	/// shared by all of them. Fully transparent pixels are ignored. Any palette entries beyond `num_colors` are
+	/// left black.
+	///
+	/// # Arguments
+	///
+	/// * `bitmaps`: the bitmaps whose colors are to be represented by the palette
+	/// * `num_colors`: the number of colors to generate (<= 256)
+	pub fn from_rgba_bitmaps(bitmaps: &[&RgbaBitmap], num_colors: usize) -> Result<Palette, PaletteError> {
+		Self::from_rgba_bitmaps_with_reserved(bitmaps, num_colors, &[])
+	}
+
+	/// Generates a palette the same as [`Palette::from_rgba_bitmaps`], but with some palette entries reserved for
+	/// fixed colors which will not be changed (for example, a transparent color at index 0, or colors used by UI
+	/// elements). The reserved entries count towards `num_colors`. Generated colors are placed in the lowest
+	/// palette indices that are not reserved.
+	///
+	/// # Arguments
+	///
+	/// * `bitmaps`: the bitmaps whose colors are to be represented by the palette
+	/// * `num_colors`: the total number of colors in the palette, including the reserved ones (<= 256)
+	/// * `reserved`: the palette indices and colors to be reserved
+	pub fn from_rgba_bitmaps_with_reserved(
+		bitmaps: &[&RgbaBitmap],
+		num_colors: usize,
+		reserved: &[(u8, RGBA)],
+	) -> Result<Palette, PaletteError> {
+		let mut histogram = HashMap::new();
+		for bitmap in bitmaps.iter() {
+			for pixel in bitmap.pixels().iter().filter(|pixel| pixel.a() > 0) {
+				*histogram.entry([pixel.r(), pixel.g(), pixel.b()]).or_insert(0u32) += 1;
+			}
+		}
+		Ok(Self::from_histogram_with_reserved(histogram, num_colors, reserved)?.0)
+	}
+
+	// generates a palette from the given histogram of unique colors, also returning which palette entries were
+	// actually assigned a color (either reserved or generated)
+	fn from_histogram_with_reserved(
+		mut histogram: HashMap<[u8; 3], u32>,
+		num_colors: usize,
+		reserved: &[(u8, RGBA)],
+	) -> Result<(Palette, [bool; NUM_COLORS]), PaletteError> {
+		if num_colors > NUM_COLORS {
+			return Err(PaletteError::OutOfRange(num_colors));
+		} else if reserved.len() > num_colors {
+			return Err(PaletteError::OutOfRange(reserved.len()));
+		}
+
+		let mut palette = Palette::new();
+		let mut is_reserved = [false; NUM_COLORS];
+		for (index, color) in reserved.iter() {
+			palette[*index] = *color;
+			is_reserved[*index as usize] = true;
+		}
+
+		// colors which exactly match a reserved color are already going to be in the palette
+		for (_, color) in reserved.iter() {
+			histogram.remove(&[color.r(), color.g(), color.b()]);
+		}
+		// sorted so that the results are always the same for the same input, regardless of hashing
+		let mut histogram = histogram.into_iter().collect::<Vec<_>>();
+		histogram.sort_unstable();
+
+		let colors = median_cut(histogram, num_colors - reserved.len());
+		let free_indices = (0..NUM_COLORS).filter(|index| !is_reserved[*index]);
+		let mut is_assigned = is_reserved;
+		for (index, color) in free_indices.zip(colors.into_iter()) {
+			palette.colors[index] = color;
+			is_assigned[index] = true;
+		}
+
+		Ok((palette, is_assigned))
+	}
+
+	/// Generates a single palette that can be shared by all of the given indexed bitmaps, which may each be using
+	/// a different palette, along with the remap tables needed to convert each bitmap to the new palette. Only the
+	/// colors actually used by the bitmaps are included. If there are more of these than `num_colors`, the colors
+	/// are reduced using median cut color quantization. Any palette entries beyond `num_colors` are left black.
+	///
+	/// # Arguments
+	///
+	/// * `sources`: the bitmaps, each paired with the palette it is currently using
+	/// * `num_colors`: the maximum number of colors in the merged palette (<= 256)
+	///
+	/// returns: the merged palette, and one remap table for each of the given bitmaps, in the same order
+	pub fn merge(
+		sources: &[(&IndexedBitmap, &Palette)],
+		num_colors: usize,
+	) -> Result<(Palette, Vec<RemapTable>), PaletteError> {
+		Self::merge_with_reserved(sources, num_colors, &[])
+	}
+
+	/// Merges palettes the same as [`Palette::merge`], but with some palette entries reserved for fixed colors
+	/// which will not be changed (for example, a transparent color at index 0). The reserved entries count towards
+	/// `num_colors`. Merged colors are placed in the lowest palette indices that are not reserved.
+	///
+	/// Note that source bitmap pixels are remapped by their color, so a bitmap's transparent color will only be
+	/// remapped to a reserved transparent color if the two colors are the same. Otherwise, the returned remap
+	/// tables can be adjusted as needed before being used.
+	///
+	/// # Arguments
+	///
+	/// * `sources`: the bitmaps, each paired with the palette it is currently using
+	/// * `num_colors`: the total number of colors in the merged palette, including the reserved ones (<= 256)
+	/// * `reserved`: the palette indices and colors to be reserved
+	///
+	/// returns: the merged palette, and one remap table for each of the given bitmaps, in the same order
+	pub fn merge_with_reserved(
+		sources: &[(&IndexedBitmap, &Palette)],
+		num_colors: usize,
+		reserved: &[(u8, RGBA)],
+	) -> Result<(Palette, Vec<RemapTable>), PaletteError> {
+		let mut histogram = HashMap::new();
+		for (bitmap, palette) in sources.iter() {
+			for (index, count) in bitmap.color_histogram().iter().enumerate().filter(|(_, count)| **count > 0) {
+				let color = palette[index as u8];
+				*histogram.entry([color.r(), color.g(), color.b()]).or_insert(0u32) += *count;
+			}
+		}
+		let (merged, is_assigned) = Self::from_histogram_with_reserved(histogram, num_colors, reserved)?;
+
+		// only the palette entries that were assigned a color should ever be remapped to. so the lookup is done
+		// against a palette containing just those colors, packed together, and then converted back to the real
+		// palette indices
+		let assigned_indices = (0..NUM_COLORS).filter(|index| is_assigned[*index]).collect::<Vec<_>>();
+		let mut lookup_palette = Palette::new();
+		if let Some(first_index) = assigned_indices.first() {
+			lookup_palette.colors.fill(merged.colors[*first_index]);
+		}
+		for (packed_index, index) in assigned_indices.iter().enumerate() {
+			lookup_palette.colors[packed_index] = merged.colors[*index];
+		}
+		let lookup = PaletteLookup::new(&lookup_palette);
+		let tables = sources
+			.iter()
+			.map(|(_, palette)| {
+				let mut table = RemapTable::identity();
+				for (index, color) in palette.colors.iter().enumerate() {
+					let packed_index = lookup.find_color(color.r(), color.g(), color.b()) as usize;
+					table.0[index] = assigned_indices.get(packed_index).copied().unwrap_or(0) as u8;
+				}
+				table
+			})
+			.collect();
+
+		Ok((merged, tables))
+	}
+
+	/// Removes all of the colors from this palette that are not used by any of the given bitmaps, moving all of
+	/// the remaining colors down to the lowest palette indices (keeping them in the same order). Any palette
+	/// entries after the remaining colors are set to black.
+	///
+	/// # Arguments
+	///
+	/// * `bitmaps`: the bitmaps using this palette
+	///
+	/// returns: the compacted palette, the remap table to convert the bitmaps to use it, and the number of palette
+	/// entries in use by the compacted palette
+	pub fn compact(&self, bitmaps: &[&IndexedBitmap]) -> (Palette, RemapTable, usize) {
+		self.compact_with_reserved(bitmaps, &[])
+	}
+
+	/// Compacts this palette the same as [`Palette::compact`], but the given palette indices are always kept at
+	/// the same index, whether they are used by the bitmaps or not (for example, a transparent color at index 0).
+	/// The other remaining colors are placed in the lowest palette indices that are not reserved. Colors that
+	/// were not used by any of the bitmaps are remapped to index 0.
+	///
+	/// # Arguments
+	///
+	/// * `bitmaps`: the bitmaps using this palette
+	/// * `reserved`: the palette indices to be kept as-is
+	///
+	/// returns: the compacted palette, the remap table to convert the bitmaps to use it, and the number of palette
+	/// entries in use by the compacted palette, including the reserved ones
+	pub fn compact_with_reserved(&self, bitmaps: &[&IndexedBitmap], reserved: &[u8]) -> (Palette, RemapTable, usize) {
+		let mut is_used = [false; NUM_COLORS];
+		for bitmap in bitmaps.iter() {
+			for (index, count) in bitmap.color_histogram().iter().enumerate() {
+				is_used[index] |= *count > 0;
+			}
+		}
+
+		let mut compacted = Palette::new();
+		let mut table = RemapTable([0; NUM_COLORS]);
+		let mut is_reserved = [false; NUM_COLORS];
+		for index in reserved.iter() {
+			compacted[*index] = self[*index];
+			table[*index] = *index;
+			is_reserved[*index as usize] = true;
+		}
+
+		let mut free_indices = (0..NUM_COLORS).filter(|index| !is_reserved[*index]);
+		let mut num_colors = reserved.iter().map(|index| *index as usize + 1).max().unwrap_or(0);
+		for index in (0..NUM_COLORS).filter(|index| is_used[*index] && !is_reserved[*index]) {
+			// there are always enough free indices, as every used color only needs one
+			let new_index = free_indices.next().unwrap();
+			compacted.colors[new_index] = self.colors[index];
+			table.0[index] = new_index as u8;
+			num_colors = num_colors.max(new_index + 1);
+		}
+
+		(compacted, table, num_colors)
+	}
+
 	/// Writes the palette to a file on disk. If the file already exists, it will be overwritten.
 	///
 	/// # Arguments
@@ -409,17 +705,7 @@ impl Palette {
 	/// * `colors`: the range of colors to be rotated
 	/// * `step`: the number of positions (and direction) to rotate all colors by
 	pub fn rotate_colors<T: ColorRange>(&mut self, colors: T, step: i8) {
-		use Bound::*;
-		let start = match colors.start_bound() {
-			Excluded(&start) => start + 1,
-			Included(&start) => start,
-			Unbounded => 0,
-		} as usize;
-		let end = match colors.end_bound() {
-			Excluded(&end) => end - 1,
-			Included(&end) => end,
-			Unbounded => 255,
-		} as usize;
+		let (start, end) = range_extents(&colors);
 		let subset = &mut self.colors[start..=end];
 		match step.signum() {
 			-1 => subset.rotate_left(step.unsigned_abs() as usize),
@@ -428,6 +714,204 @@ impl Palette {
 		}
 	}
 
+	/// Fills a range of colors in the palette with a gradient which is linearly interpolated between two colors.
+	/// The first and last colors in the range are set to exactly the start and end colors given.
+	///
+	/// # Arguments
+	///
+	/// * `colors`: the range of colors to be filled
+	/// * `start`: the color to start the gradient with
+	/// * `end`: the color to end the gradient with
+	pub fn gradient<T: ColorRange>(&mut self, colors: T, start: RGBA, end: RGBA) {
+		self.fill_ramp(colors, |t| {
+			let start = start.to_array();
+			let end = end.to_array();
+			RGBA::from_rgba(std::array::from_fn(|i| {
+				(start[i] as f32 + (end[i] as f32 - start[i] as f32) * t).round() as u8
+			}))
+		});
+	}
+
+	/// Fills a range of colors in the palette with a gradient which is interpolated between two colors in HSV
+	/// color space, taking the shortest way around the hue "circle". This can give more vibrant results than
+	/// [`Palette::gradient`] for gradients between two very different hues.
+	///
+	/// # Arguments
+	///
+	/// * `colors`: the range of colors to be filled
+	/// * `start`: the color to start the gradient with
+	/// * `end`: the color to end the gradient with
+	pub fn hsv_gradient<T: ColorRange>(&mut self, colors: T, start: RGBA, end: RGBA) {
+		let [mut start_h, start_s, start_v] = start.to_hsv();
+		let [mut end_h, end_s, end_v] = end.to_hsv();
+		// greys have no meaningful hue, so just take the hue of the other end of the gradient
+		if start_s <= 0.0 {
+			start_h = end_h;
+		} else if end_s <= 0.0 {
+			end_h = start_h;
+		}
+		let hue_delta = (end_h - start_h + 540.0).rem_euclid(360.0) - 180.0;
+		self.fill_ramp(colors, |t| {
+			let mut color = RGBA::from_hsv([
+				start_h + hue_delta * t,
+				start_s + (end_s - start_s) * t,
+				start_v + (end_v - start_v) * t,
+			]);
+			color.set_a((start.a() as f32 + (end.a() as f32 - start.a() as f32) * t).round() as u8);
+			color
+		});
+	}
+
+	/// Fills a range of colors in the palette with a shading ramp for the given base color. The ramp goes from a
+	/// dark shade of the base color at the start of the range, through the base color itself in the middle, up to
+	/// a light shade of the base color at the end of the range. The hue of the darker shades is shifted away from
+	/// the base color's hue in one direction, and the lighter shades in the other direction, by up to the given
+	/// hue shift amount.
+	///
+	/// # Arguments
+	///
+	/// * `colors`: the range of colors to be filled
+	/// * `base`: the base color the ramp is built around
+	/// * `hue_shift`: the maximum number of degrees to shift the hue by at either end of the ramp, where positive
+	///                values shift lighter shades towards higher hue angles (and darker shades towards lower ones)
+	pub fn shade_ramp<T: ColorRange>(&mut self, colors: T, base: RGBA, hue_shift: f32) {
+		let [hue, saturation, lightness] = base.to_hsl();
+		let darkest = lightness * 0.2;
+		let lightest = lightness + (1.0 - lightness) * 0.8;
+		self.fill_ramp(colors, |t| {
+			let shaded_lightness = if t < 0.5 {
+				darkest + (lightness - darkest) * (t * 2.0)
+			} else {
+				lightness + (lightest - lightness) * ((t - 0.5) * 2.0)
+			};
+			let mut color = RGBA::from_hsl([hue + hue_shift * (t * 2.0 - 1.0), saturation, shaded_lightness]);
+			color.set_a(base.a());
+			color
+		});
+	}
+
+	/// Shifts the hue of a range of colors in the palette.
+	///
+	/// # Arguments
+	///
+	/// * `colors`: the range of colors to be adjusted
+	/// * `degrees`: the number of degrees to shift the hue by, which can be negative
+	pub fn shift_hue<T: ColorRange>(&mut self, colors: T, degrees: f32) {
+		self.adjust_hsl(colors, |[h, s, l]| [h + degrees, s, l]);
+	}
+
+	/// Adjusts the saturation of a range of colors in the palette.
+	///
+	/// # Arguments
+	///
+	/// * `colors`: the range of colors to be adjusted
+	/// * `factor`: the amount to multiply each color's saturation by, where 0.0 will turn the colors grey, 1.0
+	///             leaves them unchanged, and values above 1.0 increase saturation
+	pub fn adjust_saturation<T: ColorRange>(&mut self, colors: T, factor: f32) {
+		self.adjust_hsl(colors, |[h, s, l]| [h, (s * factor).clamp(0.0, 1.0), l]);
+	}
+
+	/// Adjusts the lightness of a range of colors in the palette.
+	///
+	/// # Arguments
+	///
+	/// * `colors`: the range of colors to be adjusted
+	/// * `amount`: the amount (-1.0 to 1.0) to add to each color's lightness, where -1.0 will turn the colors
+	///             black, and 1.0 will turn them white
+	pub fn adjust_lightness<T: ColorRange>(&mut self, colors: T, amount: f32) {
+		self.adjust_hsl(colors, |[h, s, l]| [h, s, (l + amount).clamp(0.0, 1.0)]);
+	}
+
+	/// Adjusts the brightness of a range of colors in the palette. Unlike [`Palette::adjust_lightness`], this will
+	/// never wash colors out towards white.
+	///
+	/// # Arguments
+	///
+	/// * `colors`: the range of colors to be adjusted
+	/// * `factor`: the amount to multiply each color's brightness (HSV value) by, where 0.0 will turn the colors
+	///             black, and 1.0 leaves them unchanged
+	pub fn adjust_brightness<T: ColorRange>(&mut self, colors: T, factor: f32) {
+		self.map_colors(colors, |color| {
+			let [h, s, v] = color.to_hsv();
+			RGBA::from_hsv([h, s, (v * factor).clamp(0.0, 1.0)])
+		});
+	}
+
+	/// Adjusts the contrast of a range of colors in the palette, by scaling each color's RGB components towards
+	/// or away from the middle of their range.
+	///
+	/// # Arguments
+	///
+	/// * `colors`: the range of colors to be adjusted
+	/// * `factor`: the amount to scale the contrast by, where 0.0 will turn the colors a medium grey, 1.0 leaves
+	///             them unchanged, and values above 1.0 increase contrast
+	pub fn adjust_contrast<T: ColorRange>(&mut self, colors: T, factor: f32) {
+		self.map_colors(colors, |color| {
+			RGBA::from_rgb(
+				[color.r(), color.g(), color.b()]
+					.map(|component| ((component as f32 - 127.5) * factor + 127.5).clamp(0.0, 255.0).round() as u8),
+			)
+		});
+	}
+
+	/// Tints a range of colors in the palette, by multiplying them with the given tint color. This can be used to
+	/// build tinted variants of a palette for things like day/night cycles or underwater scenes, which can then be
+	/// transitioned between with [`Palette::lerp`] or [`Palette::fade_colors_toward_palette`].
+	///
+	/// # Arguments
+	///
+	/// * `colors`: the range of colors to be tinted
+	/// * `tint`: the tint color, where for example a dark blue tint could be used for "night"
+	/// * `amount`: the strength (0.0 to 1.0) of the tint to apply, where 0.0 leaves the colors unchanged
+	pub fn tint_colors<T: ColorRange>(&mut self, colors: T, tint: RGBA, amount: f32) {
+		self.map_colors(colors, |color| {
+			let tinted = color * tint;
+			RGBA::from_rgb(std::array::from_fn(|i| {
+				let (from, to) = (color.0[i] as f32, tinted.0[i] as f32);
+				(from + (to - from) * amount).round() as u8
+			}))
+		});
+	}
+
+	/// Returns a copy of this palette with a range of colors tinted by the given tint color. See
+	/// [`Palette::tint_colors`] for more details.
+	///
+	/// # Arguments
+	///
+	/// * `colors`: the range of colors to be tinted
+	/// * `tint`: the tint color
+	/// * `amount`: the strength (0.0 to 1.0) of the tint to apply, where 0.0 leaves the colors unchanged
+	///
+	/// returns: the tinted palette
+	pub fn tinted<T: ColorRange>(&self, colors: T, tint: RGBA, amount: f32) -> Palette {
+		let mut palette = self.clone();
+		palette.tint_colors(colors, tint, amount);
+		palette
+	}
+
+	/// Sets each color in the range to the color returned by the given function, which is passed the fractional
+	/// position (0.0 to 1.0) of the color within the range.
+	fn fill_ramp<T: ColorRange>(&mut self, colors: T, color_at: impl Fn(f32) -> RGBA) {
+		let (start, end) = range_extents(&colors);
+		let steps = end.saturating_sub(start).max(1) as f32;
+		for index in start..=end {
+			self.colors[index] = color_at((index - start) as f32 / steps);
+		}
+	}
+
+	/// Replaces each color in the range with the color returned by the given function, preserving alpha.
+	fn map_colors<T: ColorRange>(&mut self, colors: T, f: impl Fn(RGBA) -> RGBA) {
+		for color in colors {
+			let mut adjusted = f(self[color]);
+			adjusted.set_a(self[color].a());
+			self[color] = adjusted;
+		}
+	}
+
+	fn adjust_hsl<T: ColorRange>(&mut self, colors: T, f: impl Fn([f32; 3]) -> [f32; 3]) {
+		self.map_colors(colors, |color| RGBA::from_hsl(f(color.to_hsl())));
+	}
+
 	/// Finds and returns the index of the closest color in this palette to the RGB values provided.
 	/// This will not always return great results. It depends largely on the palette and the RGB
 	/// values being searched (for example, searching for bright green 0,255,0 in a palette which
@@ -487,13 +971,145 @@ impl IndexMut<u8> for Palette {
 	}
 }
 
+/// A lookup structure built from a [`Palette`] which can find the closest matching palette color to arbitrary RGB
+/// values much faster than [`Palette::find_color`] can, while returning exactly the same results. This is useful
+/// when matching large numbers of colors against the same palette, such as when converting entire bitmaps.
+///
+/// Note that the lookup structure is a snapshot of the palette at the time it was created. If the palette is
+/// modified afterwards, a new lookup structure needs to be created.
+#[derive(Debug, Clone)]
+pub struct PaletteLookup {
+	// palette colors as (r, g, b, index), sorted by red component and then by palette index
+	colors: Vec<[u8; 4]>,
+}
+
+impl PaletteLookup {
+	/// Creates a new lookup structure for the colors in the given palette.
+	pub fn new(palette: &Palette) -> PaletteLookup {
+		let mut colors = palette
+			.colors
+			.iter()
+			.enumerate()
+			.map(|(index, color)| [color.r(), color.g(), color.b(), index as u8])
+			.collect::<Vec<_>>();
+		colors.sort_by_key(|color| (color[0], color[3]));
+		PaletteLookup { colors }
+	}
+
+	/// Finds and returns the index of the closest color in the palette to the RGB values provided. This returns
+	/// the same result as [`Palette::find_color`] would for the palette this lookup was created from.
+	pub fn find_color(&self, r: u8, g: u8, b: u8) -> u8 {
+		// colors are sorted by their red component, so we can start with the colors that have the closest red
+		// value and work outwards in both directions. the difference in red alone is a lower bound on the total
+		// distance, so once it exceeds the best distance found so far, no further colors in that direction can
+		// possibly be closer
+		let start = self.colors.partition_point(|color| color[0] < r);
+		let mut closest_distance = u32::MAX;
+		let mut closest = 0;
+
+		let mut check = |color: &[u8; 4]| -> bool {
+			if abs_diff(color[0], r) as u32 > closest_distance {
+				return false;
+			}
+			let distance = abs_diff(color[0], r) as u32 + abs_diff(color[1], g) as u32 + abs_diff(color[2], b) as u32;
+			// ties are resolved in favour of the lowest palette index, the same as Palette::find_color
+			if distance < closest_distance || (distance == closest_distance && color[3] < closest) {
+				closest = color[3];
+				closest_distance = distance;
+			}
+			true
+		};
+
+		for color in self.colors[start..].iter() {
+			if !check(color) {
+				break;
+			}
+		}
+		for color in self.colors[..start].iter().rev() {
+			if !check(color) {
+				break;
+			}
+		}
+
+		closest
+	}
+}
+
+/// A table mapping each of the 256 color indices of one palette to a color index in another palette. This can
+/// be used to convert an [`IndexedBitmap`] from one palette to another. See [`IndexedBitmap::remap_with_table`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RemapTable([u8; NUM_COLORS]);
+
+impl RemapTable {
+	/// Creates a new remap table which maps every color index to itself.
+	pub fn identity() -> RemapTable {
+		let mut table = [0u8; NUM_COLORS];
+		for (index, value) in table.iter_mut().enumerate() {
+			*value = index as u8;
+		}
+		RemapTable(table)
+	}
+
+	/// Creates a new remap table which maps every color in the `from` palette to the closest color in the `to`
+	/// palette, as found by [`Palette::find_color`].
+	///
+	/// # Arguments
+	///
+	/// * `from`: the palette being remapped from
+	/// * `to`: the palette being remapped to
+	pub fn from_palettes(from: &Palette, to: &Palette) -> RemapTable {
+		let lookup = PaletteLookup::new(to);
+		let mut table = [0u8; NUM_COLORS];
+		for (value, color) in table.iter_mut().zip(from.colors.iter()) {
+			*value = lookup.find_color(color.r(), color.g(), color.b());
+		}
+		RemapTable(table)
+	}
+
+	/// Returns a new remap table which has the same effect as remapping with this table followed by the other
+	/// table given.
+	pub fn then(&self, other: &RemapTable) -> RemapTable {
+		RemapTable(self.0.map(|index| other.0[index as usize]))
+	}
+}
+
+impl Default for RemapTable {
+	fn default() -> Self {
+		Self::identity()
+	}
+}
+
+impl From<[u8; NUM_COLORS]> for RemapTable {
+	fn from(value: [u8; NUM_COLORS]) -> Self {
+		RemapTable(value)
+	}
+}
+
+impl Index<u8> for RemapTable {
+	type Output = u8;
+
+	#[inline]
+	fn index(&self, index: u8) -> &Self::Output {
+		&self.0[index as usize]
+	}
+}
+
+impl IndexMut<u8> for RemapTable {
+	#[inline]
+	fn index_mut(&mut self, index: u8) -> &mut Self::Output {
+		&mut self.0[index as usize]
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use std::path::PathBuf;
 
+	use claim::assert_matches;
 	use tempfile::TempDir;
 
 	use crate::graphics::color::*;
+	use crate::math::NearlyEqual;
 
 	use super::*;
 
@@ -588,4 +1204,245 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn palette_lookup_matches_find_color() {
+		let palette = Palette::new_vga_palette().unwrap();
+		let lookup = PaletteLookup::new(&palette);
+		for r in (0..=255).step_by(15) {
+			for g in (0..=255).step_by(17) {
+				for b in (0..=255).step_by(13) {
+					assert_eq!(palette.find_color(r, g, b), lookup.find_color(r, g, b), "color {}, {}, {}", r, g, b);
+				}
+			}
+		}
+
+		// duplicate colors should always resolve to the lowest palette index
+		let palette = Palette::new_with_default(10, 20, 30);
+		let lookup = PaletteLookup::new(&palette);
+		assert_eq!(0, lookup.find_color(10, 20, 30));
+		assert_eq!(0, lookup.find_color(200, 200, 200));
+	}
+
+	#[test]
+	fn generate_palette_from_few_colors() -> Result<(), PaletteError> {
+		let mut bmp = RgbaBitmap::new(4, 1).unwrap();
+		bmp.pixels_mut().copy_from_slice(&[COLOR_RED, COLOR_BLUE, COLOR_RED, RGBA::from_rgba([1, 2, 3, 0])]);
+		let palette = Palette::from_rgba_bitmaps(&[&bmp], 16)?;
+		assert_eq!(COLOR_BLUE, palette[0]);
+		assert_eq!(COLOR_RED, palette[1]);
+		assert_eq!(COLOR_BLACK, palette[2]);
+		Ok(())
+	}
+
+	#[test]
+	fn generate_palette_with_median_cut() -> Result<(), PaletteError> {
+		// two images with a cluster of reds and a cluster of blues, together
+		let mut reds = RgbaBitmap::new(8, 1).unwrap();
+		for (i, pixel) in reds.pixels_mut().iter_mut().enumerate() {
+			*pixel = RGBA::from_rgb([200 + i as u8 * 2, 10, 10]);
+		}
+		let mut blues = RgbaBitmap::new(8, 1).unwrap();
+		for (i, pixel) in blues.pixels_mut().iter_mut().enumerate() {
+			*pixel = RGBA::from_rgb([10, 10, 200 + i as u8 * 2]);
+		}
+
+		let palette = Palette::from_rgba_bitmaps(&[&reds, &blues], 2)?;
+		let mut generated = [palette[0], palette[1]];
+		generated.sort_by_key(|color| color.r());
+		assert_eq!([RGBA::from_rgb([10, 10, 207]), RGBA::from_rgb([207, 10, 10])], generated);
+		assert_eq!(COLOR_BLACK, palette[2]);
+
+		let palette = Palette::from_rgba_bitmaps(&[&reds, &blues], 8)?;
+		let lookup = PaletteLookup::new(&palette);
+		for pixel in reds.pixels().iter().chain(blues.pixels().iter()) {
+			let found = palette[lookup.find_color(pixel.r(), pixel.g(), pixel.b())];
+			assert!(abs_diff(found.r(), pixel.r()) <= 2 && abs_diff(found.b(), pixel.b()) <= 2);
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn generate_palette_with_reserved_colors() -> Result<(), PaletteError> {
+		let mut bmp = RgbaBitmap::new(4, 1).unwrap();
+		bmp.pixels_mut().copy_from_slice(&[COLOR_RED, COLOR_BLUE, COLOR_GREEN, COLOR_BRIGHT_WHITE]);
+		let reserved = [(0, RGBA::from_rgb([255, 0, 255])), (2, COLOR_BRIGHT_WHITE)];
+		let palette = Palette::from_rgba_bitmaps_with_reserved(&[&bmp], 5, &reserved)?;
+		assert_eq!(RGBA::from_rgb([255, 0, 255]), palette[0]);
+		assert_eq!(COLOR_BLUE, palette[1]);
+		assert_eq!(COLOR_BRIGHT_WHITE, palette[2]);
+		assert_eq!(COLOR_GREEN, palette[3]);
+		assert_eq!(COLOR_RED, palette[4]);
+		assert_eq!(COLOR_BLACK, palette[5]);
+
+		assert_matches!(Palette::from_rgba_bitmaps(&[&bmp], 257), Err(PaletteError::OutOfRange(257)));
+		assert_matches!(
+			Palette::from_rgba_bitmaps_with_reserved(&[&bmp], 1, &reserved),
+			Err(PaletteError::OutOfRange(2))
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn remap_tables() {
+		let identity = RemapTable::identity();
+		assert_eq!(0, identity[0]);
+		assert_eq!(123, identity[123]);
+		assert_eq!(255, identity[255]);
+
+		let mut from = Palette::new_vga_palette().unwrap();
+		from[16] = RGBA::from_rgb([250, 250, 80]);
+		let to = Palette::new_vga_palette().unwrap();
+		let table = RemapTable::from_palettes(&from, &to);
+		assert_eq!(4, table[4]);
+		assert_eq!(14, table[16]);
+
+		let mut shift = RemapTable::identity();
+		shift[14] = 100;
+		let combined = table.then(&shift);
+		assert_eq!(100, combined[16]);
+		assert_eq!(100, combined[14]);
+		assert_eq!(4, combined[4]);
+	}
+
+	#[test]
+	fn compact_palette() {
+		let palette = Palette::new_vga_palette().unwrap();
+		let mut a = IndexedBitmap::new(2, 1).unwrap();
+		a.pixels_mut().copy_from_slice(&[40, 9]);
+		let mut b = IndexedBitmap::new(2, 1).unwrap();
+		b.pixels_mut().copy_from_slice(&[200, 40]);
+
+		let (compacted, table, num_colors) = palette.compact(&[&a, &b]);
+		assert_eq!(3, num_colors);
+		assert_eq!(palette[9], compacted[0]);
+		assert_eq!(palette[40], compacted[1]);
+		assert_eq!(palette[200], compacted[2]);
+		assert_eq!(COLOR_BLACK, compacted[3]);
+		assert_eq!([0, 1, 2], [table[9], table[40], table[200]]);
+
+		let (compacted, table, num_colors) = palette.compact_with_reserved(&[&a, &b], &[0, 2]);
+		assert_eq!(5, num_colors);
+		assert_eq!(palette[0], compacted[0]);
+		assert_eq!(palette[9], compacted[1]);
+		assert_eq!(palette[2], compacted[2]);
+		assert_eq!(palette[40], compacted[3]);
+		assert_eq!(palette[200], compacted[4]);
+		assert_eq!([0, 2, 1, 3, 4], [table[0], table[2], table[9], table[40], table[200]]);
+
+		b.remap_with_table(&table);
+		assert_eq!(&[4, 3], b.pixels());
+	}
+
+	#[test]
+	fn merge_palettes() -> Result<(), PaletteError> {
+		let mut palette_a = Palette::new();
+		palette_a[1] = COLOR_RED;
+		palette_a[2] = COLOR_GREEN;
+		palette_a[3] = COLOR_BRIGHT_WHITE;
+		let mut a = IndexedBitmap::new(3, 1).unwrap();
+		a.pixels_mut().copy_from_slice(&[0, 1, 2]);
+
+		let mut palette_b = Palette::new();
+		palette_b[5] = COLOR_BLUE;
+		palette_b[6] = COLOR_RED;
+		let mut b = IndexedBitmap::new(2, 1).unwrap();
+		b.pixels_mut().copy_from_slice(&[5, 6]);
+
+		let reserved = [(0, RGBA::from_rgb([255, 0, 255])), (255, COLOR_BRIGHT_WHITE)];
+		let (merged, tables) = Palette::merge_with_reserved(&[(&a, &palette_a), (&b, &palette_b)], 16, &reserved)?;
+		assert_eq!(RGBA::from_rgb([255, 0, 255]), merged[0]);
+		assert_eq!(COLOR_BLACK, merged[1]);
+		assert_eq!(COLOR_BLUE, merged[2]);
+		assert_eq!(COLOR_GREEN, merged[3]);
+		assert_eq!(COLOR_RED, merged[4]);
+		assert_eq!(COLOR_BLACK, merged[5]);
+		assert_eq!(COLOR_BRIGHT_WHITE, merged[255]);
+
+		assert_eq!(2, tables.len());
+		assert_eq!([1, 4, 3, 255], [tables[0][0], tables[0][1], tables[0][2], tables[0][3]]);
+		assert_eq!([2, 4, 1], [tables[1][5], tables[1][6], tables[1][7]]);
+
+		a.remap_with_table(&tables[0]);
+		b.remap_with_table(&tables[1]);
+		assert_eq!(&[1, 4, 3], a.pixels());
+		assert_eq!(&[2, 4], b.pixels());
+
+		assert_matches!(Palette::merge(&[(&a, &palette_a)], 257), Err(PaletteError::OutOfRange(257)));
+
+		Ok(())
+	}
+
+	#[test]
+	fn gradients_and_ramps() {
+		let mut palette = Palette::new_with_default(255, 255, 255);
+		palette.gradient(10..15, RGBA::from_rgb([0, 0, 0]), RGBA::from_rgb([255, 100, 40]));
+		assert_eq!(RGBA::from_rgb([255, 255, 255]), palette[9]);
+		assert_eq!(RGBA::from_rgb([0, 0, 0]), palette[10]);
+		assert_eq!(RGBA::from_rgb([64, 25, 10]), palette[11]);
+		assert_eq!(RGBA::from_rgb([128, 50, 20]), palette[12]);
+		assert_eq!(RGBA::from_rgb([191, 75, 30]), palette[13]);
+		assert_eq!(RGBA::from_rgb([255, 100, 40]), palette[14]);
+		assert_eq!(RGBA::from_rgb([255, 255, 255]), palette[15]);
+
+		palette.hsv_gradient(0..=2, RGBA::from_rgb([255, 0, 0]), RGBA::from_rgb([0, 0, 255]));
+		assert_eq!(RGBA::from_rgb([255, 0, 0]), palette[0]);
+		assert_eq!(RGBA::from_rgb([255, 0, 255]), palette[1]);
+		assert_eq!(RGBA::from_rgb([0, 0, 255]), palette[2]);
+
+		let base = RGBA::from_rgb([204, 0, 0]);
+		palette.shade_ramp(20..=24, base, 0.0);
+		assert_eq!(RGBA::from_rgb([41, 0, 0]), palette[20]);
+		assert_eq!(RGBA::from_rgb([122, 0, 0]), palette[21]);
+		assert_eq!(base, palette[22]);
+		assert_eq!(RGBA::from_rgb([255, 71, 71]), palette[23]);
+		assert_eq!(RGBA::from_rgb([255, 194, 194]), palette[24]);
+
+		palette.shade_ramp(30..=32, base, 30.0);
+		assert!(palette[30].to_hsl()[0].nearly_equal(330.0, 3.0));
+		assert_eq!(base, palette[31]);
+		assert!(palette[32].to_hsl()[0].nearly_equal(30.0, 3.0));
+	}
+
+	#[test]
+	fn adjust_colors() {
+		let mut palette = Palette::new_with_default(255, 0, 0);
+
+		palette.shift_hue(0..=0, 120.0);
+		assert_eq!(RGBA::from_rgb([0, 255, 0]), palette[0]);
+		palette.adjust_saturation(1..=1, 0.0);
+		assert_eq!(RGBA::from_rgb([128, 128, 128]), palette[1]);
+		palette.adjust_lightness(2..=2, 0.25);
+		assert_eq!(RGBA::from_rgb([255, 128, 128]), palette[2]);
+		palette.adjust_brightness(3..=3, 0.5);
+		assert_eq!(RGBA::from_rgb([128, 0, 0]), palette[3]);
+		assert_eq!(RGBA::from_rgb([255, 0, 0]), palette[4]);
+
+		palette[5] = RGBA::from_rgb([64, 128, 192]);
+		palette[6] = RGBA::from_rgb([64, 128, 192]);
+		palette.adjust_contrast(5..=5, 2.0);
+		palette.adjust_contrast(6..=6, 0.0);
+		assert_eq!(RGBA::from_rgb([1, 129, 255]), palette[5]);
+		assert_eq!(RGBA::from_rgb([128, 128, 128]), palette[6]);
+
+		palette[7] = RGBA::from_rgba([10, 20, 30, 100]);
+		palette.shift_hue(7..=7, 0.0);
+		assert_eq!(RGBA::from_rgba([10, 20, 30, 100]), palette[7]);
+	}
+
+	#[test]
+	fn tint_colors() {
+		let day = Palette::new_with_default(200, 100, 50);
+		let night = day.tinted(0..=127, RGBA::from_rgb([128, 128, 255]), 0.5);
+		assert_eq!(RGBA::from_rgb([200, 100, 50]), day[0]);
+		assert_eq!(RGBA::from_rgb([150, 75, 50]), night[0]);
+		assert_eq!(RGBA::from_rgb([150, 75, 50]), night[127]);
+		assert_eq!(RGBA::from_rgb([200, 100, 50]), night[128]);
+
+		let mut palette = day.clone();
+		palette.tint_colors(0..=0, RGBA::from_rgb([128, 128, 255]), 1.0);
+		assert_eq!(RGBA::from_rgb([100, 50, 50]), palette[0]);
+	}
 }
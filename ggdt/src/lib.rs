@@ -21,7 +21,7 @@ mod tests {
 	use std::io::{BufReader, Read};
 	use std::path::{Path, PathBuf};
 
-	use crate::graphics::RGBA;
+	use crate::graphics::{IndexedBitmap, RGBA};
 	use crate::utils::ReadType;
 
 	#[allow(dead_code)]
@@ -38,6 +38,15 @@ mod tests {
 		PathBuf::from(TEST_ASSETS_PATH).join(file)
 	}
 
+	/// Draws onto a blank bitmap using the given function, asserting that something was drawn and that no pixel was
+	/// drawn more than once. The drawing function is expected to increment the color of each pixel it draws.
+	pub fn assert_no_overdraw(draw_fn: impl Fn(&mut IndexedBitmap)) {
+		let mut bmp = IndexedBitmap::new(32, 32).unwrap();
+		draw_fn(&mut bmp);
+		assert!(bmp.pixels().iter().any(|&pixel| pixel > 0), "nothing was drawn");
+		assert!(bmp.pixels().iter().all(|&pixel| pixel <= 1), "some pixels were drawn more than once");
+	}
+
 	pub fn load_raw_indexed(bin_file: &Path) -> Result<Box<[u8]>, io::Error> {
 		let f = File::open(bin_file)?;
 		let mut reader = BufReader::new(f);
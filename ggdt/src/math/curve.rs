@@ -0,0 +1,491 @@
+use crate::math::{inverse_lerp, lerp, Vector2};
+
+// limits on how many times a curve segment will be recursively split in half when flattening it. at least one split
+// is always performed so that "S" shaped cubic curves, whose midpoint can lie on the chord, are handled correctly
+const MIN_FLATTEN_DEPTH: u32 = 1;
+const MAX_FLATTEN_DEPTH: u32 = 16;
+
+// the number of evenly spaced points per curve segment checked before refining a closest point search
+const CLOSEST_POINT_SAMPLES: usize = 16;
+const CLOSEST_POINT_REFINE_ITERATIONS: usize = 24;
+
+// the tolerance used when flattening a curve to calculate its length
+const LENGTH_TOLERANCE: f32 = 0.01;
+
+/// Returns the distance from the point to the line segment between a and b.
+fn distance_to_segment(point: Vector2, a: Vector2, b: Vector2) -> f32 {
+	let ab = b - a;
+	let length_squared = ab.length_squared();
+	if length_squared == 0.0 {
+		return point.distance(&a);
+	}
+	let t = ((point - a).dot(&ab) / length_squared).clamp(0.0, 1.0);
+	point.distance(&(a + ab * t))
+}
+
+/// Common functionality for 2D parametric curves. Curves are evaluated using a parameter, `t`, which runs from 0.0 at
+/// the start of the curve to 1.0 at its end. Note that, in general, equal steps of `t` do not correspond to equal
+/// distances along the curve. Use an [`ArcLengthTable`] to find positions at specific distances along the curve.
+pub trait Curve {
+	/// Returns the point on the curve at `t` (0.0 to 1.0).
+	fn point_at(&self, t: f32) -> Vector2;
+
+	/// Returns the tangent (first derivative) of the curve at `t` (0.0 to 1.0). This points in the direction that
+	/// the curve is travelling at that point, and is not normalized.
+	fn tangent_at(&self, t: f32) -> Vector2;
+
+	/// Returns the number of individual segments that this curve is made up of, each of which cover an equal range of
+	/// `t`. Used to guide sampling of the curve.
+	#[inline]
+	fn segment_count(&self) -> usize {
+		1
+	}
+
+	/// Approximates the curve as a series of connected points, such that lines drawn between each of the points
+	/// do not deviate from the curve by more than the given tolerance. The first and last points returned are always
+	/// the start and end points of the curve. Flat parts of the curve will be approximated using fewer points than
+	/// parts that bend sharply.
+	fn flatten(&self, tolerance: f32) -> Vec<Vector2> {
+		let mut points = Vec::new();
+		self.flatten_into(tolerance, &mut points);
+		points
+	}
+
+	/// The same as [`Curve::flatten`], but adds the points to the given `Vec` instead of allocating a new one.
+	fn flatten_into(&self, tolerance: f32, points: &mut Vec<Vector2>) {
+		let segments = self.segment_count().max(1);
+		let mut start = self.point_at(0.0);
+		points.push(start);
+		for segment in 0..segments {
+			let t0 = segment as f32 / segments as f32;
+			let t1 = (segment + 1) as f32 / segments as f32;
+			let end = self.point_at(t1);
+			subdivide(self, t0, start, t1, end, tolerance, 0, points);
+			start = end;
+		}
+	}
+
+	/// Returns the approximate length of the curve.
+	fn length(&self) -> f32 {
+		let points = self.flatten(LENGTH_TOLERANCE);
+		points.windows(2).map(|pair| pair[0].distance(&pair[1])).sum()
+	}
+
+	/// Finds the point on the curve closest to the given point.
+	///
+	/// returns: a tuple containing the `t` value of the closest point along the curve, and the closest point itself
+	fn closest_point(&self, point: Vector2) -> (f32, Vector2) {
+		let samples = self.segment_count().max(1) * CLOSEST_POINT_SAMPLES;
+		let step = 1.0 / samples as f32;
+
+		let mut closest_t = 0.0;
+		let mut closest_distance = f32::MAX;
+		for i in 0..=samples {
+			let t = i as f32 * step;
+			let distance = self.point_at(t).distance_squared(&point);
+			if distance < closest_distance {
+				closest_t = t;
+				closest_distance = distance;
+			}
+		}
+
+		// refine the closest sample found using a ternary search of the neighbouring range
+		let mut low = (closest_t - step).max(0.0);
+		let mut high = (closest_t + step).min(1.0);
+		for _ in 0..CLOSEST_POINT_REFINE_ITERATIONS {
+			let a = lerp(low, high, 1.0 / 3.0);
+			let b = lerp(low, high, 2.0 / 3.0);
+			if self.point_at(a).distance_squared(&point) < self.point_at(b).distance_squared(&point) {
+				high = b;
+			} else {
+				low = a;
+			}
+		}
+
+		// the closest point may lie exactly at the start or end of the curve, which the search range can only approach
+		[low, (low + high) / 2.0, high]
+			.into_iter()
+			.map(|t| (t, self.point_at(t)))
+			.min_by(|a, b| a.1.distance_squared(&point).total_cmp(&b.1.distance_squared(&point)))
+			.unwrap()
+	}
+}
+
+#[allow(clippy::too_many_arguments)]
+fn subdivide<C: Curve + ?Sized>(
+	curve: &C,
+	t0: f32,
+	p0: Vector2,
+	t1: f32,
+	p1: Vector2,
+	tolerance: f32,
+	depth: u32,
+	points: &mut Vec<Vector2>,
+) {
+	let t_mid = (t0 + t1) / 2.0;
+	let p_mid = curve.point_at(t_mid);
+	if depth >= MAX_FLATTEN_DEPTH || (depth >= MIN_FLATTEN_DEPTH && distance_to_segment(p_mid, p0, p1) <= tolerance) {
+		points.push(p1);
+	} else {
+		subdivide(curve, t0, p0, t_mid, p_mid, tolerance, depth + 1, points);
+		subdivide(curve, t_mid, p_mid, t1, p1, tolerance, depth + 1, points);
+	}
+}
+
+/// A quadratic Bézier curve, running from the start point to the end point and bending towards a single control point.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct QuadraticBezier {
+	pub start: Vector2,
+	pub control: Vector2,
+	pub end: Vector2,
+}
+
+impl QuadraticBezier {
+	#[inline]
+	pub fn new(start: Vector2, control: Vector2, end: Vector2) -> Self {
+		QuadraticBezier { start, control, end }
+	}
+}
+
+impl Curve for QuadraticBezier {
+	#[inline]
+	fn point_at(&self, t: f32) -> Vector2 {
+		let u = 1.0 - t;
+		self.start * (u * u) + self.control * (2.0 * u * t) + self.end * (t * t)
+	}
+
+	#[inline]
+	fn tangent_at(&self, t: f32) -> Vector2 {
+		(self.control - self.start) * (2.0 * (1.0 - t)) + (self.end - self.control) * (2.0 * t)
+	}
+}
+
+/// A cubic Bézier curve, running from the start point to the end point and bending towards two control points.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CubicBezier {
+	pub start: Vector2,
+	pub control1: Vector2,
+	pub control2: Vector2,
+	pub end: Vector2,
+}
+
+impl CubicBezier {
+	#[inline]
+	pub fn new(start: Vector2, control1: Vector2, control2: Vector2, end: Vector2) -> Self {
+		CubicBezier { start, control1, control2, end }
+	}
+}
+
+impl Curve for CubicBezier {
+	#[inline]
+	fn point_at(&self, t: f32) -> Vector2 {
+		let u = 1.0 - t;
+		self.start * (u * u * u)
+			+ self.control1 * (3.0 * u * u * t)
+			+ self.control2 * (3.0 * u * t * t)
+			+ self.end * (t * t * t)
+	}
+
+	#[inline]
+	fn tangent_at(&self, t: f32) -> Vector2 {
+		let u = 1.0 - t;
+		(self.control1 - self.start) * (3.0 * u * u)
+			+ (self.control2 - self.control1) * (6.0 * u * t)
+			+ (self.end - self.control2) * (3.0 * t * t)
+	}
+}
+
+/// A (uniform) Catmull-Rom spline, which is a smooth curve that passes through every one of its points. An open
+/// spline starts at the first point and ends at the last, while a closed spline also connects the last point back
+/// around to the first, forming a loop. The spline is made up of one segment between each pair of points, and each
+/// segment covers an equal range of `t`, regardless of the distance between the points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatmullRomSpline {
+	pub points: Vec<Vector2>,
+	pub closed: bool,
+}
+
+impl CatmullRomSpline {
+	#[inline]
+	pub fn new(points: Vec<Vector2>, closed: bool) -> Self {
+		CatmullRomSpline { points, closed }
+	}
+
+	/// Returns the four points controlling the given segment, and the `t` value (0.0 to 1.0) local to that segment
+	/// corresponding to the given `t` value for the entire spline.
+	fn segment_at(&self, t: f32) -> ([Vector2; 4], f32) {
+		let segments = self.segment_count();
+		let position = t.clamp(0.0, 1.0) * segments as f32;
+		let segment = (position as usize).min(segments - 1);
+		let local_t = position - segment as f32;
+
+		let count = self.points.len() as isize;
+		let point = |index: isize| {
+			if self.closed {
+				self.points[index.rem_euclid(count) as usize]
+			} else {
+				// the first and last points are repeated so that the spline runs through all of the points
+				self.points[index.clamp(0, count - 1) as usize]
+			}
+		};
+		let i = segment as isize;
+		([point(i - 1), point(i), point(i + 1), point(i + 2)], local_t)
+	}
+}
+
+impl Curve for CatmullRomSpline {
+	fn point_at(&self, t: f32) -> Vector2 {
+		match self.points.len() {
+			0 => Vector2::ZERO,
+			1 => self.points[0],
+			_ => {
+				let ([p0, p1, p2, p3], t) = self.segment_at(t);
+				let t2 = t * t;
+				let t3 = t2 * t;
+				(p1 * 2.0
+					+ (p2 - p0) * t + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2
+					+ (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3)
+					* 0.5
+			}
+		}
+	}
+
+	fn tangent_at(&self, t: f32) -> Vector2 {
+		match self.points.len() {
+			0 | 1 => Vector2::ZERO,
+			_ => {
+				let ([p0, p1, p2, p3], t) = self.segment_at(t);
+				let t2 = t * t;
+				// the derivative of each segment is scaled as each one only covers part of the spline's range of t
+				((p2 - p0)
+					+ (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * (2.0 * t)
+					+ (p1 * 3.0 - p0 - p2 * 3.0 + p3) * (3.0 * t2))
+					* (0.5 * self.segment_count() as f32)
+			}
+		}
+	}
+
+	#[inline]
+	fn segment_count(&self) -> usize {
+		match self.points.len() {
+			0 | 1 => 1,
+			n if self.closed => n,
+			n => n - 1,
+		}
+	}
+}
+
+/// A lookup table which maps distances along a curve to `t` values for that curve, allowing positions to be found
+/// that are evenly spaced along the curve. This is useful for moving things along a curve at a constant speed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArcLengthTable {
+	// the accumulated distance along the curve at evenly spaced t values from 0.0 to 1.0
+	distances: Vec<f32>,
+}
+
+impl ArcLengthTable {
+	/// Builds a table for the given curve by measuring the distance between the given number of evenly spaced samples
+	/// per segment of the curve. More samples give more accurate results.
+	pub fn new<C: Curve + ?Sized>(curve: &C, samples_per_segment: usize) -> Self {
+		let samples = curve.segment_count().max(1) * samples_per_segment.max(1);
+		let mut distances = Vec::with_capacity(samples + 1);
+		let mut previous = curve.point_at(0.0);
+		let mut total = 0.0;
+		distances.push(total);
+		for i in 1..=samples {
+			let point = curve.point_at(i as f32 / samples as f32);
+			total += point.distance(&previous);
+			distances.push(total);
+			previous = point;
+		}
+		ArcLengthTable { distances }
+	}
+
+	/// Returns the total length of the curve.
+	#[inline]
+	pub fn length(&self) -> f32 {
+		*self.distances.last().unwrap()
+	}
+
+	/// Returns the `t` value of the point at the given distance along the curve. The distance is clamped to the
+	/// length of the curve.
+	pub fn t_at_distance(&self, distance: f32) -> f32 {
+		let samples = self.distances.len() - 1;
+		if distance <= 0.0 || self.length() == 0.0 {
+			return 0.0;
+		} else if distance >= self.length() {
+			return 1.0;
+		}
+
+		let index = self.distances.partition_point(|&d| d < distance).max(1);
+		let low = self.distances[index - 1];
+		let high = self.distances[index];
+		let fraction = if high > low { inverse_lerp(low, high, distance) } else { 0.0 };
+		(index as f32 - 1.0 + fraction) / samples as f32
+	}
+
+	/// Returns the `t` value of the point at the given fraction (0.0 to 1.0) of the total length of the curve.
+	#[inline]
+	pub fn t_at_fraction(&self, fraction: f32) -> f32 {
+		self.t_at_distance(fraction * self.length())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::math::*;
+
+	use super::*;
+
+	fn assert_nearly_equal(expected: Vector2, actual: Vector2, epsilon: f32) {
+		assert!(expected.nearly_equal(actual, epsilon), "expected {:?}, got {:?}", expected, actual);
+	}
+
+	#[test]
+	pub fn test_quadratic_bezier() {
+		let curve = QuadraticBezier::new(Vector2::new(0.0, 0.0), Vector2::new(10.0, 20.0), Vector2::new(20.0, 0.0));
+		assert_nearly_equal(Vector2::new(0.0, 0.0), curve.point_at(0.0), 0.0001);
+		assert_nearly_equal(Vector2::new(10.0, 10.0), curve.point_at(0.5), 0.0001);
+		assert_nearly_equal(Vector2::new(20.0, 0.0), curve.point_at(1.0), 0.0001);
+		assert_nearly_equal(Vector2::new(20.0, 40.0), curve.tangent_at(0.0), 0.0001);
+		assert_nearly_equal(Vector2::new(20.0, 0.0), curve.tangent_at(0.5), 0.0001);
+		assert_nearly_equal(Vector2::new(20.0, -40.0), curve.tangent_at(1.0), 0.0001);
+	}
+
+	#[test]
+	pub fn test_cubic_bezier() {
+		let curve = CubicBezier::new(
+			Vector2::new(0.0, 0.0),
+			Vector2::new(0.0, 10.0),
+			Vector2::new(20.0, 10.0),
+			Vector2::new(20.0, 0.0),
+		);
+		assert_nearly_equal(Vector2::new(0.0, 0.0), curve.point_at(0.0), 0.0001);
+		assert_nearly_equal(Vector2::new(10.0, 7.5), curve.point_at(0.5), 0.0001);
+		assert_nearly_equal(Vector2::new(20.0, 0.0), curve.point_at(1.0), 0.0001);
+		assert_nearly_equal(Vector2::new(0.0, 30.0), curve.tangent_at(0.0), 0.0001);
+		assert_nearly_equal(Vector2::new(30.0, 0.0), curve.tangent_at(0.5), 0.0001);
+		assert_nearly_equal(Vector2::new(0.0, -30.0), curve.tangent_at(1.0), 0.0001);
+
+		// the tangent should match the change in position between two nearby points
+		let t = 0.3;
+		let delta = (curve.point_at(t + 0.0005) - curve.point_at(t - 0.0005)) / 0.001;
+		assert_nearly_equal(delta, curve.tangent_at(t), 0.01);
+	}
+
+	#[test]
+	pub fn test_catmull_rom_spline() {
+		let points =
+			vec![Vector2::new(0.0, 0.0), Vector2::new(10.0, 10.0), Vector2::new(20.0, 0.0), Vector2::new(30.0, 10.0)];
+
+		// passes through every point, each of which are an equal step of t apart
+		let spline = CatmullRomSpline::new(points.clone(), false);
+		assert_eq!(3, spline.segment_count());
+		for (i, point) in points.iter().enumerate() {
+			assert_nearly_equal(*point, spline.point_at(i as f32 / 3.0), 0.0001);
+		}
+		let t = 0.5;
+		let delta = (spline.point_at(t + 0.0005) - spline.point_at(t - 0.0005)) / 0.001;
+		assert_nearly_equal(delta, spline.tangent_at(t), 0.01);
+
+		let spline = CatmullRomSpline::new(points.clone(), true);
+		assert_eq!(4, spline.segment_count());
+		for (i, point) in points.iter().enumerate() {
+			assert_nearly_equal(*point, spline.point_at(i as f32 / 4.0), 0.0001);
+		}
+		assert_nearly_equal(points[0], spline.point_at(1.0), 0.0001);
+		// the loop is smooth where it joins back up with the start
+		assert_nearly_equal(spline.tangent_at(0.0), spline.tangent_at(1.0), 0.0001);
+
+		let spline = CatmullRomSpline::new(vec![Vector2::new(3.0, 4.0)], false);
+		assert_nearly_equal(Vector2::new(3.0, 4.0), spline.point_at(0.5), 0.0001);
+		let spline = CatmullRomSpline::new(vec![], false);
+		assert_nearly_equal(Vector2::ZERO, spline.point_at(0.5), 0.0001);
+	}
+
+	#[test]
+	pub fn test_flatten_and_length() {
+		// a straight line only needs its end points (plus the one forced split)
+		let line = CubicBezier::new(
+			Vector2::new(0.0, 0.0),
+			Vector2::new(10.0, 0.0),
+			Vector2::new(20.0, 0.0),
+			Vector2::new(30.0, 0.0),
+		);
+		let points = line.flatten(0.1);
+		assert_eq!(3, points.len());
+		assert_nearly_equal(Vector2::new(0.0, 0.0), points[0], 0.0001);
+		assert_nearly_equal(Vector2::new(30.0, 0.0), points[2], 0.0001);
+		assert!(nearly_equal(30.0, line.length(), 0.001));
+
+		// a quarter circle approximated by a cubic bezier
+		let k = 0.5522848 * 100.0;
+		let arc = CubicBezier::new(
+			Vector2::new(100.0, 0.0),
+			Vector2::new(100.0, k),
+			Vector2::new(k, 100.0),
+			Vector2::new(0.0, 100.0),
+		);
+		let coarse = arc.flatten(1.0);
+		let fine = arc.flatten(0.01);
+		assert!(coarse.len() < fine.len());
+		for points in [&coarse, &fine] {
+			assert_nearly_equal(arc.start, points[0], 0.0001);
+			assert_nearly_equal(arc.end, points[points.len() - 1], 0.0001);
+		}
+		for pair in coarse.windows(2) {
+			// the middle of each line should be within the tolerance of the circle
+			let middle = (pair[0] + pair[1]) / 2.0;
+			assert!(middle.length() > 100.0 - 1.05, "{:?}", middle);
+		}
+		assert!(nearly_equal(TWO_PI * 100.0 / 4.0, arc.length(), 0.1));
+	}
+
+	#[test]
+	pub fn test_arc_length_table() {
+		// a straight line where the control points are bunched up towards the end, so equal steps of t are not equal
+		// distances along the line
+		let line = CubicBezier::new(
+			Vector2::new(0.0, 0.0),
+			Vector2::new(20.0, 0.0),
+			Vector2::new(25.0, 0.0),
+			Vector2::new(30.0, 0.0),
+		);
+		assert!(!nearly_equal(15.0, line.point_at(0.5).x, 1.0));
+
+		let table = ArcLengthTable::new(&line, 64);
+		assert!(nearly_equal(30.0, table.length(), 0.001));
+		for distance in [0.0, 5.0, 10.0, 15.0, 20.0, 25.0, 30.0] {
+			let t = table.t_at_distance(distance);
+			assert!(nearly_equal(distance, line.point_at(t).x, 0.05), "{} {}", distance, line.point_at(t).x);
+		}
+		assert!(nearly_equal(15.0, line.point_at(table.t_at_fraction(0.5)).x, 0.05));
+		assert_eq!(0.0, table.t_at_distance(-10.0));
+		assert_eq!(1.0, table.t_at_distance(100.0));
+	}
+
+	#[test]
+	pub fn test_closest_point() {
+		let curve = QuadraticBezier::new(Vector2::new(0.0, 0.0), Vector2::new(10.0, 20.0), Vector2::new(20.0, 0.0));
+
+		let (t, point) = curve.closest_point(Vector2::new(10.0, 30.0));
+		assert!(nearly_equal(0.5, t, 0.0005));
+		assert_nearly_equal(Vector2::new(10.0, 10.0), point, 0.01);
+
+		let (t, point) = curve.closest_point(Vector2::new(-5.0, -5.0));
+		assert_eq!(0.0, t);
+		assert_nearly_equal(Vector2::new(0.0, 0.0), point, 0.001);
+
+		let (t, point) = curve.closest_point(curve.point_at(0.8));
+		assert!(nearly_equal(0.8, t, 0.001));
+		assert_nearly_equal(curve.point_at(0.8), point, 0.001);
+
+		let spline = CatmullRomSpline::new(
+			vec![Vector2::new(0.0, 0.0), Vector2::new(10.0, 10.0), Vector2::new(20.0, 0.0), Vector2::new(30.0, 10.0)],
+			false,
+		);
+		let (t, point) = spline.closest_point(Vector2::new(20.0, -5.0));
+		assert!(nearly_equal(2.0 / 3.0, t, 0.001));
+		assert_nearly_equal(Vector2::new(20.0, 0.0), point, 0.001);
+	}
+}
@@ -3,11 +3,13 @@ use std::simd;
 use std::simd::{SimdFloat, SimdPartialOrd};
 
 mod circle;
+mod curve;
 mod matrix3x3;
 mod rect;
 mod vector2;
 
 pub use circle::*;
+pub use curve::*;
 pub use matrix3x3::*;
 pub use rect::*;
 pub use vector2::*;
@@ -0,0 +1,307 @@
+use std::simd;
+use std::simd::SimdUint;
+
+use crate::graphics::RGBA;
+
+const BLOOM_RADIUS: i32 = 2;
+
+/// The type of phosphor mask pattern a [`CrtFilter`] overlays onto the final image.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CrtMask {
+	/// No phosphor mask is applied.
+	None,
+	/// Vertical red, green and blue phosphor stripes, like a Trinitron-style aperture grille.
+	ApertureGrille,
+	/// Red, green and blue phosphor triads which are staggered on alternating rows, like a shadow mask tube.
+	ShadowMask,
+}
+
+/// Configuration for the optional CPU post-processing stage that can be applied to the system backbuffer when
+/// it is displayed, giving the final image on screen the appearance of an old CRT monitor.
+///
+/// Most of the effects work on the pre-scaled image, which is the backbuffer up-scaled by an integer factor
+/// (`prescale`) before it is uploaded to SDL. Scanlines and phosphor masks need a `prescale` of at least 2 to be
+/// visible at all, and look best with 3 or more. Note that larger `prescale` values also increase the amount of
+/// work needing to be done by the CPU every frame.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CrtFilter {
+	/// The integer factor that the backbuffer is up-scaled by before post-processing. A value of 1 disables
+	/// pre-scaling.
+	pub prescale: u32,
+	/// Whether the pre-scaled image is scaled to the final window size using bilinear filtering ("sharp
+	/// bilinear") instead of nearest-neighbour sampling. This avoids uneven pixel sizes when scaling by
+	/// non-integer amounts, while keeping the image mostly sharp.
+	pub sharp_bilinear: bool,
+	/// How much to darken the gaps between scanlines, from 0.0 (no scanlines) to 1.0 (fully black gaps).
+	pub scanlines: f32,
+	/// The type of phosphor mask to overlay on the image.
+	pub mask: CrtMask,
+	/// How strongly the phosphor mask is applied, from 0.0 (not visible) to 1.0 (each pixel only shows its
+	/// mask color).
+	pub mask_strength: f32,
+	/// The amount of barrel distortion to apply, simulating a curved screen. 0.0 gives a flat screen, while
+	/// values around 0.1 or less give a subtle curvature.
+	pub curvature: f32,
+	/// How much of a blurred copy of the image is added back on top of it, making bright areas glow, from 0.0
+	/// (no bloom) to 1.0. This also helps to compensate for the brightness lost to scanlines and masks.
+	pub bloom: f32,
+}
+
+impl Default for CrtFilter {
+	/// Returns a [`CrtFilter`] with a reasonably subtle set of effects enabled.
+	fn default() -> Self {
+		CrtFilter {
+			prescale: 3,
+			sharp_bilinear: true,
+			scanlines: 0.5,
+			mask: CrtMask::ApertureGrille,
+			mask_strength: 0.25,
+			curvature: 0.04,
+			bloom: 0.2,
+		}
+	}
+}
+
+impl CrtFilter {
+	/// Returns a [`CrtFilter`] which does no post-processing, other than integer pre-scaling with sharp bilinear
+	/// output.
+	pub fn sharp_bilinear(prescale: u32) -> Self {
+		CrtFilter {
+			prescale,
+			sharp_bilinear: true,
+			scanlines: 0.0,
+			mask: CrtMask::None,
+			mask_strength: 0.0,
+			curvature: 0.0,
+			bloom: 0.0,
+		}
+	}
+
+	/// Returns the size of the post-processed image that this filter will output for the given backbuffer size.
+	pub fn output_size(&self, width: u32, height: u32) -> (u32, u32) {
+		let prescale = self.prescale.max(1);
+		(width * prescale, height * prescale)
+	}
+}
+
+// pre-calculated information about where a single output pixel comes from
+#[derive(Debug, Copy, Clone)]
+struct OutputPixel {
+	// index of the source pixel, or None if this output pixel lies outside the curved screen area
+	src_index: Option<u32>,
+	// combined scanline and mask brightness to be multiplied with the source pixel
+	multiplier: RGBA,
+}
+
+/// Applies a [`CrtFilter`] to backbuffer images of a fixed size. Everything which does not change from frame to
+/// frame (source pixel locations, scanline and mask brightness) is calculated once up-front.
+pub(crate) struct CrtProcessor {
+	src_width: u32,
+	src_height: u32,
+	pixels: Box<[OutputPixel]>,
+	bloom: RGBA,
+	blurred: Box<[RGBA]>,
+	blur_scratch: Box<[RGBA]>,
+	output: Box<[RGBA]>,
+}
+
+impl CrtProcessor {
+	pub fn new(filter: &CrtFilter, src_width: u32, src_height: u32) -> Self {
+		let (width, height) = filter.output_size(src_width, src_height);
+		let scanlines = filter.scanlines.clamp(0.0, 1.0);
+		let mask_strength = filter.mask_strength.clamp(0.0, 1.0);
+		let curvature = filter.curvature.max(0.0);
+
+		let mut pixels = Vec::with_capacity((width * height) as usize);
+		for y in 0..height {
+			for x in 0..width {
+				// normalized coordinates, -1.0 to 1.0 across the screen, sampled from the center of each pixel
+				let nx = ((x as f32 + 0.5) / width as f32) * 2.0 - 1.0;
+				let ny = ((y as f32 + 0.5) / height as f32) * 2.0 - 1.0;
+				let cx = nx * (1.0 + curvature * ny * ny);
+				let cy = ny * (1.0 + curvature * nx * nx);
+				if !(-1.0..1.0).contains(&cx) || !(-1.0..1.0).contains(&cy) {
+					pixels.push(OutputPixel { src_index: None, multiplier: RGBA::from_rgba([0, 0, 0, 0]) });
+					continue;
+				}
+
+				let src_x = (cx + 1.0) * 0.5 * src_width as f32;
+				let src_y = (cy + 1.0) * 0.5 * src_height as f32;
+				let src_index = (src_y as u32).min(src_height - 1) * src_width + (src_x as u32).min(src_width - 1);
+
+				// the bottom half of each source line fades towards the dark gap before the next line
+				let line_position = src_y.fract();
+				let scanline = 1.0 - scanlines * ((line_position - 0.5) * 2.0).clamp(0.0, 1.0);
+
+				let mut multiplier = [scanline; 3];
+				let phosphor = match filter.mask {
+					CrtMask::None => None,
+					CrtMask::ApertureGrille => Some(x % 3),
+					CrtMask::ShadowMask => Some((x + (y % 2) * 2) % 3),
+				};
+				if let Some(phosphor) = phosphor {
+					for (channel, value) in multiplier.iter_mut().enumerate() {
+						if channel as u32 != phosphor {
+							*value *= 1.0 - mask_strength;
+						}
+					}
+				}
+
+				pixels.push(OutputPixel {
+					src_index: Some(src_index),
+					multiplier: RGBA::from_rgba([
+						(multiplier[0] * 255.0) as u8,
+						(multiplier[1] * 255.0) as u8,
+						(multiplier[2] * 255.0) as u8,
+						255,
+					]),
+				});
+			}
+		}
+
+		let bloom = (filter.bloom.clamp(0.0, 1.0) * 255.0) as u8;
+		let src_size = (src_width * src_height) as usize;
+
+		CrtProcessor {
+			src_width,
+			src_height,
+			pixels: pixels.into_boxed_slice(),
+			bloom: RGBA::from_rgba([bloom, bloom, bloom, 0]),
+			blurred: vec![RGBA::default(); src_size].into_boxed_slice(),
+			blur_scratch: vec![RGBA::default(); src_size].into_boxed_slice(),
+			output: vec![RGBA::default(); (width * height) as usize].into_boxed_slice(),
+		}
+	}
+
+	/// Applies the filter to the given source pixels, which must be the same size this processor was created
+	/// for, returning the post-processed output pixels.
+	pub fn process(&mut self, src: &[RGBA]) -> &[RGBA] {
+		assert_eq!(src.len(), (self.src_width * self.src_height) as usize);
+
+		let bloom_enabled = self.bloom.r() > 0;
+		if bloom_enabled {
+			self.blur(src);
+		}
+
+		for (dest, pixel) in self.output.iter_mut().zip(self.pixels.iter()) {
+			*dest = match pixel.src_index {
+				None => RGBA::from_rgba([0, 0, 0, 255]),
+				Some(index) => {
+					let mut color = src[index as usize] * pixel.multiplier;
+					if bloom_enabled {
+						color = RGBA(color.0.saturating_add((self.blurred[index as usize] * self.bloom).0));
+					}
+					color.set_a(255);
+					color
+				}
+			};
+		}
+
+		&self.output
+	}
+
+	// separable box blur of the source image, written into `blurred`
+	fn blur(&mut self, src: &[RGBA]) {
+		let width = self.src_width as i32;
+		let height = self.src_height as i32;
+		let divisor = simd::u32x4::splat((BLOOM_RADIUS * 2 + 1) as u32);
+
+		for y in 0..height {
+			let row = (y * width) as usize;
+			for x in 0..width {
+				let mut sum = simd::u32x4::splat(0);
+				for offset in -BLOOM_RADIUS..=BLOOM_RADIUS {
+					let sample_x = (x + offset).clamp(0, width - 1) as usize;
+					sum += src[row + sample_x].0.cast();
+				}
+				self.blur_scratch[row + x as usize] = RGBA((sum / divisor).cast());
+			}
+		}
+
+		for y in 0..height {
+			for x in 0..width {
+				let mut sum = simd::u32x4::splat(0);
+				for offset in -BLOOM_RADIUS..=BLOOM_RADIUS {
+					let sample_y = (y + offset).clamp(0, height - 1);
+					sum += self.blur_scratch[(sample_y * width + x) as usize].0.cast();
+				}
+				self.blurred[(y * width + x) as usize] = RGBA((sum / divisor).cast());
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const WHITE: RGBA = RGBA::from_rgb([255, 255, 255]);
+
+	#[test]
+	pub fn sharp_bilinear_only_prescales() {
+		let filter = CrtFilter::sharp_bilinear(2);
+		assert_eq!((8, 6), filter.output_size(4, 3));
+
+		let src = (0..12).map(|i| RGBA::from_rgb([i * 10, i * 10, i * 10])).collect::<Vec<_>>();
+		let mut processor = CrtProcessor::new(&filter, 4, 3);
+		let output = processor.process(&src);
+		assert_eq!(48, output.len());
+		for y in 0..6 {
+			for x in 0..8 {
+				assert_eq!(src[(y / 2) * 4 + (x / 2)], output[y * 8 + x], "pixel at {}, {}", x, y);
+			}
+		}
+	}
+
+	#[test]
+	pub fn scanlines_and_masks() {
+		let src = vec![WHITE; 4];
+
+		let filter = CrtFilter { scanlines: 1.0, ..CrtFilter::sharp_bilinear(3) };
+		let mut processor = CrtProcessor::new(&filter, 2, 2);
+		let output = processor.process(&src);
+		assert_eq!(WHITE, output[0]);
+		assert_eq!(WHITE, output[6]);
+		assert!(output[12].r() < 255 / 2);
+		assert_eq!(WHITE, output[18]);
+
+		let filter = CrtFilter { mask: CrtMask::ApertureGrille, mask_strength: 1.0, ..CrtFilter::sharp_bilinear(3) };
+		let mut processor = CrtProcessor::new(&filter, 2, 2);
+		let output = processor.process(&src);
+		assert_eq!(RGBA::from_rgb([255, 0, 0]), output[0]);
+		assert_eq!(RGBA::from_rgb([0, 255, 0]), output[1]);
+		assert_eq!(RGBA::from_rgb([0, 0, 255]), output[2]);
+		assert_eq!(RGBA::from_rgb([255, 0, 0]), output[6]);
+
+		let filter = CrtFilter { mask: CrtMask::ShadowMask, mask_strength: 1.0, ..CrtFilter::sharp_bilinear(3) };
+		let mut processor = CrtProcessor::new(&filter, 2, 2);
+		let output = processor.process(&src);
+		assert_eq!(RGBA::from_rgb([255, 0, 0]), output[0]);
+		assert_eq!(RGBA::from_rgb([0, 0, 255]), output[6]);
+		assert_eq!(RGBA::from_rgb([255, 0, 0]), output[7]);
+	}
+
+	#[test]
+	pub fn curvature_blanks_corners() {
+		let src = vec![WHITE; 16];
+		let filter = CrtFilter { curvature: 0.5, ..CrtFilter::sharp_bilinear(2) };
+		let mut processor = CrtProcessor::new(&filter, 4, 4);
+		let output = processor.process(&src);
+		assert_eq!(RGBA::from_rgb([0, 0, 0]), output[0]);
+		assert_eq!(RGBA::from_rgb([0, 0, 0]), output[63]);
+		assert_eq!(WHITE, output[3 * 8 + 3]);
+	}
+
+	#[test]
+	pub fn bloom_brightens_surroundings() {
+		let mut src = vec![RGBA::from_rgb([0, 0, 0]); 25];
+		src[12] = WHITE;
+		let filter = CrtFilter { bloom: 1.0, ..CrtFilter::sharp_bilinear(1) };
+		let mut processor = CrtProcessor::new(&filter, 5, 5);
+		let output = processor.process(&src);
+		assert_eq!(WHITE, output[12]);
+		assert!(output[11].r() > 0);
+		assert!(output[7].r() > 0);
+		assert_eq!(output[11], output[13]);
+	}
+}
@@ -1,6 +1,7 @@
 use thiserror::Error;
 
 use crate::graphics::{ColorsAsBytes, IndexedBitmap, Palette, RgbaBitmap, RGBA};
+use crate::system::{CrtFilter, CrtProcessor};
 
 pub fn calculate_logical_screen_size(window_width: u32, window_height: u32, scale_factor: u32) -> (u32, u32) {
 	let logical_width = (window_width as f32 / scale_factor as f32).ceil() as u32;
@@ -24,6 +25,7 @@ pub struct SdlFramebuffer {
 	sdl_texture: sdl2::render::Texture,
 	sdl_texture_pitch: usize,
 	intermediate_texture: Option<Box<[RGBA]>>,
+	crt_processor: Option<CrtProcessor>,
 }
 
 // TODO: i'm not totally happy with this implementation. i don't like the two display methods and how the caller
@@ -36,6 +38,7 @@ impl SdlFramebuffer {
 		logical_screen_width: u32,
 		logical_screen_height: u32,
 		create_intermediate_texture: bool,
+		crt_filter: Option<&CrtFilter>,
 	) -> Result<Self, SdlFramebufferError> {
 		// this sets up screen/window resolution independant rendering on the SDL-side of things
 		// which we may or may not actually need, but this ALSO changes the way that SDL reports things
@@ -47,11 +50,28 @@ impl SdlFramebuffer {
 
 		let format = sdl2::pixels::PixelFormatEnum::ABGR8888;
 
-		let sdl_texture =
-			match canvas.create_texture_streaming(Some(format), logical_screen_width, logical_screen_height) {
-				Ok(texture) => texture,
-				Err(error) => return Err(SdlFramebufferError::SDLError(error.to_string())),
-			};
+		// when post-processing is enabled, the texture holds the pre-scaled and post-processed image instead,
+		// which SDL will then scale to the window size as usual
+		let (texture_width, texture_height) = match crt_filter {
+			Some(crt_filter) => crt_filter.output_size(logical_screen_width, logical_screen_height),
+			None => (logical_screen_width, logical_screen_height),
+		};
+
+		// SDL only reads the scale quality hint when textures are created, so we temporarily switch it to get
+		// linear filtering for the "sharp bilinear" pre-scaled texture only
+		let sharp_bilinear = crt_filter.map_or(false, |crt_filter| crt_filter.sharp_bilinear);
+		let previous_scale_quality = sdl2::hint::get("SDL_RENDER_SCALE_QUALITY");
+		if sharp_bilinear {
+			sdl2::hint::set("SDL_RENDER_SCALE_QUALITY", "linear");
+		}
+		let sdl_texture = canvas.create_texture_streaming(Some(format), texture_width, texture_height);
+		if sharp_bilinear {
+			sdl2::hint::set("SDL_RENDER_SCALE_QUALITY", previous_scale_quality.as_deref().unwrap_or("nearest"));
+		}
+		let sdl_texture = match sdl_texture {
+			Ok(texture) => texture,
+			Err(error) => return Err(SdlFramebufferError::SDLError(error.to_string())),
+		};
 		let sdl_texture_pitch = sdl_texture.query().width as usize * SCREEN_TEXTURE_PIXEL_SIZE;
 
 		let intermediate_texture = if create_intermediate_texture {
@@ -65,7 +85,10 @@ impl SdlFramebuffer {
 			None
 		};
 
-		Ok(SdlFramebuffer { sdl_texture, sdl_texture_pitch, intermediate_texture })
+		let crt_processor =
+			crt_filter.map(|crt_filter| CrtProcessor::new(crt_filter, logical_screen_width, logical_screen_height));
+
+		Ok(SdlFramebuffer { sdl_texture, sdl_texture_pitch, intermediate_texture, crt_processor })
 	}
 
 	pub fn display_indexed_bitmap(
@@ -80,17 +103,11 @@ impl SdlFramebuffer {
 
 		src.copy_as_rgba_to(intermediate_texture, palette);
 
-		let texture_pixels = intermediate_texture.as_bytes();
-		if let Err(error) = self.sdl_texture.update(None, texture_pixels, self.sdl_texture_pitch) {
-			return Err(SdlFramebufferError::SDLError(error.to_string()));
-		}
-		canvas.clear();
-		if let Err(error) = canvas.copy(&self.sdl_texture, None, None) {
-			return Err(SdlFramebufferError::SDLError(error));
-		}
-		canvas.present();
-
-		Ok(())
+		let texture_pixels = match &mut self.crt_processor {
+			Some(crt_processor) => crt_processor.process(intermediate_texture),
+			None => intermediate_texture,
+		};
+		Self::present(&mut self.sdl_texture, self.sdl_texture_pitch, canvas, texture_pixels)
 	}
 
 	pub fn display(
@@ -103,12 +120,24 @@ impl SdlFramebuffer {
 			"Calls to display should only occur on SdlFramebuffers without an intermediate_texture"
 		);
 
-		let texture_pixels = src.pixels().as_bytes();
-		if let Err(error) = self.sdl_texture.update(None, texture_pixels, self.sdl_texture_pitch) {
+		let texture_pixels = match &mut self.crt_processor {
+			Some(crt_processor) => crt_processor.process(src.pixels()),
+			None => src.pixels(),
+		};
+		Self::present(&mut self.sdl_texture, self.sdl_texture_pitch, canvas, texture_pixels)
+	}
+
+	fn present(
+		sdl_texture: &mut sdl2::render::Texture,
+		sdl_texture_pitch: usize,
+		canvas: &mut sdl2::render::WindowCanvas,
+		texture_pixels: &[RGBA],
+	) -> Result<(), SdlFramebufferError> {
+		if let Err(error) = sdl_texture.update(None, texture_pixels.as_bytes(), sdl_texture_pitch) {
 			return Err(SdlFramebufferError::SDLError(error.to_string()));
 		}
 		canvas.clear();
-		if let Err(error) = canvas.copy(&self.sdl_texture, None, None) {
+		if let Err(error) = canvas.copy(sdl_texture, None, None) {
 			return Err(SdlFramebufferError::SDLError(error));
 		}
 		canvas.present();
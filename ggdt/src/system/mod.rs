@@ -2,11 +2,13 @@ use thiserror::Error;
 
 use crate::audio::AudioError;
 
+mod crt;
 mod event;
 mod framebuffer;
 mod input_devices;
 mod res;
 
+pub use crt::*;
 pub use event::*;
 pub use input_devices::*;
 pub use res::*;
@@ -30,7 +30,7 @@ use crate::audio::{Audio, AudioQueue, TARGET_AUDIO_CHANNELS, TARGET_AUDIO_FREQUE
 use crate::graphics::{BitmaskFont, IndexedBitmap, Palette};
 use crate::system::framebuffer::{calculate_logical_screen_size, SdlFramebuffer};
 use crate::system::{
-	CustomMouseCursor, InputDevice, Keyboard, Mouse, SystemEvent, SystemEventHandler, SystemResources,
+	CrtFilter, CustomMouseCursor, InputDevice, Keyboard, Mouse, SystemEvent, SystemEventHandler, SystemResources,
 	SystemResourcesConfig, SystemResourcesError, WindowEvent,
 };
 
@@ -45,6 +45,7 @@ pub struct DosLikeConfig {
 	fixed_screen_size: bool,
 	initial_scale_factor: u32,
 	integer_scaling: bool,
+	crt_filter: Option<CrtFilter>,
 }
 
 impl Default for DosLikeConfig {
@@ -65,6 +66,7 @@ impl DosLikeConfig {
 			initial_scale_factor: DEFAULT_SCALE_FACTOR,
 			integer_scaling,
 			fixed_screen_size: true,
+			crt_filter: None,
 		}
 	}
 
@@ -82,6 +84,7 @@ impl DosLikeConfig {
 			initial_scale_factor: DEFAULT_SCALE_FACTOR,
 			integer_scaling: false,
 			fixed_screen_size: false,
+			crt_filter: None,
 		}
 	}
 
@@ -91,6 +94,13 @@ impl DosLikeConfig {
 		self.initial_scale_factor = scale_factor;
 		self
 	}
+
+	/// Enables CRT-style post-processing of the virtual framebuffer using the given filter settings whenever it
+	/// is displayed on screen. This can also be changed at runtime via [`DosLike::set_crt_filter`].
+	pub fn crt_filter(mut self, crt_filter: CrtFilter) -> Self {
+		self.crt_filter = Some(crt_filter);
+		self
+	}
 }
 
 impl SystemResourcesConfig for DosLikeConfig {
@@ -130,7 +140,13 @@ impl SystemResourcesConfig for DosLikeConfig {
 
 		// create the SDL framebuffer at the initial logical screen size
 
-		let framebuffer = SdlFramebuffer::new(&mut sdl_canvas, self.screen_width, self.screen_height, true)?;
+		let framebuffer = SdlFramebuffer::new(
+			&mut sdl_canvas,
+			self.screen_width,
+			self.screen_height,
+			true,
+			self.crt_filter.as_ref(),
+		)?;
 
 		// create the Bitmap object that will be exposed to the application acting as the system
 		// backbuffer
@@ -177,6 +193,7 @@ impl SystemResourcesConfig for DosLikeConfig {
 			framebuffer,
 			scale_factor: self.initial_scale_factor,
 			fixed_screen_size: self.fixed_screen_size,
+			crt_filter: self.crt_filter,
 			audio,
 			audio_queue,
 			palette,
@@ -196,6 +213,7 @@ pub struct DosLike {
 	framebuffer: SdlFramebuffer,
 	scale_factor: u32,
 	fixed_screen_size: bool,
+	crt_filter: Option<CrtFilter>,
 
 	/// An [`Audio`] instance that allows interacting with the system's audio output device.
 	pub audio: Audio,
@@ -301,10 +319,31 @@ impl SystemResources for DosLike {
 }
 
 impl DosLike {
+	/// Returns the CRT-style post-processing filter currently being applied to the `video` backbuffer when it is
+	/// displayed, if any.
+	pub fn crt_filter(&self) -> Option<&CrtFilter> {
+		self.crt_filter.as_ref()
+	}
+
+	/// Sets or clears the CRT-style post-processing filter that is applied to the `video` backbuffer whenever it
+	/// is displayed. The `video` backbuffer itself is left unchanged.
+	pub fn set_crt_filter(&mut self, crt_filter: Option<CrtFilter>) -> Result<(), SystemResourcesError> {
+		self.framebuffer = SdlFramebuffer::new(
+			&mut self.sdl_canvas,
+			self.video.width(),
+			self.video.height(),
+			true,
+			crt_filter.as_ref(),
+		)?;
+		self.crt_filter = crt_filter;
+		Ok(())
+	}
+
 	fn resize_screen(&mut self, new_width: u32, new_height: u32) -> Result<(), SystemResourcesError> {
 		let (logical_width, logical_height) = calculate_logical_screen_size(new_width, new_height, self.scale_factor);
 
-		let framebuffer = SdlFramebuffer::new(&mut self.sdl_canvas, logical_width, logical_height, true)?;
+		let framebuffer =
+			SdlFramebuffer::new(&mut self.sdl_canvas, logical_width, logical_height, true, self.crt_filter.as_ref())?;
 
 		let screen_bitmap = match IndexedBitmap::new(logical_width, logical_height) {
 			Ok(bmp) => bmp,
@@ -2,7 +2,7 @@ use crate::audio::{Audio, AudioQueue, TARGET_AUDIO_CHANNELS, TARGET_AUDIO_FREQUE
 use crate::graphics::{BitmaskFont, RgbaBitmap};
 use crate::system::framebuffer::{calculate_logical_screen_size, SdlFramebuffer};
 use crate::system::{
-	CustomMouseCursor, InputDevice, Keyboard, Mouse, SystemEvent, SystemEventHandler, SystemResources,
+	CrtFilter, CustomMouseCursor, InputDevice, Keyboard, Mouse, SystemEvent, SystemEventHandler, SystemResources,
 	SystemResourcesConfig, SystemResourcesError, WindowEvent,
 };
 
@@ -16,6 +16,7 @@ pub struct StandardConfig {
 	fixed_screen_size: bool,
 	initial_scale_factor: u32,
 	integer_scaling: bool,
+	crt_filter: Option<CrtFilter>,
 }
 
 impl Default for StandardConfig {
@@ -36,6 +37,7 @@ impl StandardConfig {
 			initial_scale_factor: DEFAULT_SCALE_FACTOR,
 			integer_scaling,
 			fixed_screen_size: true,
+			crt_filter: None,
 		}
 	}
 
@@ -53,6 +55,7 @@ impl StandardConfig {
 			initial_scale_factor: DEFAULT_SCALE_FACTOR,
 			integer_scaling: false,
 			fixed_screen_size: false,
+			crt_filter: None,
 		}
 	}
 
@@ -62,6 +65,13 @@ impl StandardConfig {
 		self.initial_scale_factor = scale_factor;
 		self
 	}
+
+	/// Enables CRT-style post-processing of the virtual framebuffer using the given filter settings whenever it
+	/// is displayed on screen. This can also be changed at runtime via [`Standard::set_crt_filter`].
+	pub fn crt_filter(mut self, crt_filter: CrtFilter) -> Self {
+		self.crt_filter = Some(crt_filter);
+		self
+	}
 }
 
 impl SystemResourcesConfig for StandardConfig {
@@ -101,7 +111,13 @@ impl SystemResourcesConfig for StandardConfig {
 
 		// create the SDL framebuffer at the initial logical screen size
 
-		let framebuffer = SdlFramebuffer::new(&mut sdl_canvas, self.screen_width, self.screen_height, false)?;
+		let framebuffer = SdlFramebuffer::new(
+			&mut sdl_canvas,
+			self.screen_width,
+			self.screen_height,
+			false,
+			self.crt_filter.as_ref(),
+		)?;
 
 		// create the Bitmap object that will be exposed to the application acting as the system
 		// backbuffer
@@ -140,6 +156,7 @@ impl SystemResourcesConfig for StandardConfig {
 			framebuffer,
 			scale_factor: self.initial_scale_factor,
 			fixed_screen_size: self.fixed_screen_size,
+			crt_filter: self.crt_filter,
 			audio,
 			audio_queue,
 			video: screen_bitmap,
@@ -156,6 +173,7 @@ pub struct Standard {
 	framebuffer: SdlFramebuffer,
 	scale_factor: u32,
 	fixed_screen_size: bool,
+	crt_filter: Option<CrtFilter>,
 
 	/// An [`Audio`] instance that allows interacting with the system's audio output device.
 	pub audio: Audio,
@@ -254,10 +272,31 @@ impl SystemResources for Standard {
 }
 
 impl Standard {
+	/// Returns the CRT-style post-processing filter currently being applied to the `video` backbuffer when it is
+	/// displayed, if any.
+	pub fn crt_filter(&self) -> Option<&CrtFilter> {
+		self.crt_filter.as_ref()
+	}
+
+	/// Sets or clears the CRT-style post-processing filter that is applied to the `video` backbuffer whenever it
+	/// is displayed. The `video` backbuffer itself is left unchanged.
+	pub fn set_crt_filter(&mut self, crt_filter: Option<CrtFilter>) -> Result<(), SystemResourcesError> {
+		self.framebuffer = SdlFramebuffer::new(
+			&mut self.sdl_canvas,
+			self.video.width(),
+			self.video.height(),
+			false,
+			crt_filter.as_ref(),
+		)?;
+		self.crt_filter = crt_filter;
+		Ok(())
+	}
+
 	fn resize_screen(&mut self, new_width: u32, new_height: u32) -> Result<(), SystemResourcesError> {
 		let (logical_width, logical_height) = calculate_logical_screen_size(new_width, new_height, self.scale_factor);
 
-		let framebuffer = SdlFramebuffer::new(&mut self.sdl_canvas, logical_width, logical_height, false)?;
+		let framebuffer =
+			SdlFramebuffer::new(&mut self.sdl_canvas, logical_width, logical_height, false, self.crt_filter.as_ref())?;
 
 		let screen_bitmap = match RgbaBitmap::new(logical_width, logical_height) {
 			Ok(bmp) => bmp,
@@ -1996,6 +1996,346 @@ fn rotozoom_transparent_offset_blits() {
 	assert!(verify_visual(&screen, &palette, &path), "bitmap differs from source image: {:?}", path);
 }
 
+fn draw_scaled_blits(
+	screen: &mut IndexedBitmap,
+	bitmap_fn: fn(i32, i32) -> IndexedBitmap,
+	method: impl Fn(f32, f32) -> IndexedBlitMethod,
+) {
+	let bmp16 = bitmap_fn(16, 16);
+	let bmp12 = bitmap_fn(12, 12);
+	let bmp21 = bitmap_fn(21, 21);
+	let bmp3 = bitmap_fn(3, 3);
+
+	let y = 30;
+	screen.blit(method(2.0, 2.0), &bmp16, 40, y);
+	screen.blit(method(3.0, 1.5), &bmp12, 80, y);
+	screen.blit(method(0.5, 1.0), &bmp21, 125, y);
+	screen.blit(method(8.0, 8.0), &bmp3, 145, y);
+	screen.blit(method(1.3, 2.7), &bmp16, 180, y);
+	screen.blit(method(1.0, 1.0), &bmp16, 210, y);
+	screen.blit(method(3.0, 1.0), &bmp16, 235, y);
+
+	let y = 85;
+	unsafe {
+		screen.blit_unchecked(method(2.0, 2.0), &bmp16, 40, y);
+		screen.blit_unchecked(method(3.0, 1.5), &bmp12, 80, y);
+		screen.blit_unchecked(method(0.5, 1.0), &bmp21, 125, y);
+		screen.blit_unchecked(method(8.0, 8.0), &bmp3, 145, y);
+		screen.blit_unchecked(method(1.3, 2.7), &bmp16, 180, y);
+		screen.blit_unchecked(method(1.0, 1.0), &bmp16, 210, y);
+		screen.blit_unchecked(method(3.0, 1.0), &bmp16, 235, y);
+	}
+
+	screen.blit(method(4.0, 3.0), &bmp21, 110, 140);
+
+	//////
+
+	screen.blit(method(2.0, 2.0), &bmp16, -5, 140);
+	screen.blit(method(2.0, 2.0), &bmp16, -20, 175);
+	screen.blit(method(2.0, 2.0), &bmp16, -32, 205);
+
+	screen.blit(method(2.0, 2.0), &bmp16, 293, 140);
+	screen.blit(method(2.0, 2.0), &bmp16, 308, 175);
+	screen.blit(method(2.0, 2.0), &bmp16, 320, 205);
+
+	screen.blit(method(2.0, 2.0), &bmp16, 60, -5);
+	screen.blit(method(2.0, 2.0), &bmp16, 200, -20);
+	screen.blit(method(2.0, 2.0), &bmp16, 250, -32);
+
+	screen.blit(method(2.0, 2.0), &bmp16, 40, 213);
+	screen.blit(method(2.0, 2.0), &bmp16, 220, 228);
+	screen.blit(method(2.0, 2.0), &bmp16, 260, 240);
+}
+
+#[test]
+fn scaled_blits() {
+	use IndexedBlitMethod::*;
+
+	let (mut screen, palette) = setup();
+	screen.clear(247);
+
+	draw_scaled_blits(&mut screen, generate_bitmap, |scale_x, scale_y| Scaled { scale_x, scale_y });
+
+	let path = reference_file(Path::new("scaled_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), &palette).unwrap();
+	}
+	assert!(verify_visual(&screen, &palette, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn blended_scaled_blits() {
+	use IndexedBlitMethod::*;
+
+	let (mut screen, palette, blend_map) = setup_for_blending();
+	let blend_map = Rc::new(blend_map);
+
+	draw_scaled_blits(&mut screen, generate_bitmap, |scale_x, scale_y| ScaledBlended {
+		scale_x,
+		scale_y,
+		blend_map: blend_map.clone(),
+	});
+
+	let path = reference_file(Path::new("blended_scaled_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), &palette).unwrap();
+	}
+	assert!(verify_visual(&screen, &palette, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn scaled_flipped_blits() {
+	use IndexedBlitMethod::*;
+
+	let (mut screen, palette) = setup();
+	screen.clear(247);
+
+	draw_scaled_blits(&mut screen, generate_bitmap, |scale_x, scale_y| ScaledFlipped {
+		scale_x,
+		scale_y,
+		horizontal_flip: true,
+		vertical_flip: false,
+	});
+
+	let path = reference_file(Path::new("scaled_flipped_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), &palette).unwrap();
+	}
+	assert!(verify_visual(&screen, &palette, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn scaled_offset_blits() {
+	use IndexedBlitMethod::*;
+
+	let (mut screen, palette) = setup();
+	screen.clear(247);
+
+	draw_scaled_blits(&mut screen, generate_bitmap, |scale_x, scale_y| ScaledOffset { scale_x, scale_y, offset: 42 });
+
+	let path = reference_file(Path::new("scaled_offset_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), &palette).unwrap();
+	}
+	assert!(verify_visual(&screen, &palette, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn scaled_transparent_blits() {
+	use IndexedBlitMethod::*;
+
+	let (mut screen, palette) = setup();
+	screen.clear(247);
+
+	draw_scaled_blits(&mut screen, generate_bitmap, |scale_x, scale_y| ScaledTransparent {
+		scale_x,
+		scale_y,
+		transparent_color: 0,
+	});
+
+	let path = reference_file(Path::new("scaled_transparent_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), &palette).unwrap();
+	}
+	assert!(verify_visual(&screen, &palette, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn scaled_transparent_flipped_blits() {
+	use IndexedBlitMethod::*;
+
+	let (mut screen, palette) = setup();
+	screen.clear(247);
+
+	draw_scaled_blits(&mut screen, generate_bitmap, |scale_x, scale_y| ScaledTransparentFlipped {
+		scale_x,
+		scale_y,
+		transparent_color: 0,
+		horizontal_flip: false,
+		vertical_flip: true,
+	});
+
+	let path = reference_file(Path::new("scaled_transparent_flipped_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), &palette).unwrap();
+	}
+	assert!(verify_visual(&screen, &palette, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn scaled_blits_at_original_size() {
+	use IndexedBlitMethod::*;
+
+	// scaled blits at their original size should draw exactly the same as the equivalent unscaled blits,
+	// including when clipped
+	let bmp = generate_bitmap(21, 17);
+	for (x, y) in [(10, 10), (-5, 10), (-20, -16), (310, 10), (10, 230), (315, 235), (-21, 10), (320, 240)] {
+		for (horizontal_flip, vertical_flip) in [(false, false), (true, false), (false, true), (true, true)] {
+			let (mut expected, _) = setup();
+			expected.blit(SolidFlipped { horizontal_flip, vertical_flip }, &bmp, x, y);
+			let (mut screen, _) = setup();
+			screen.blit(ScaledFlipped { scale_x: 1.0, scale_y: 1.0, horizontal_flip, vertical_flip }, &bmp, x, y);
+			assert_eq!(expected.pixels(), screen.pixels(), "({}, {}) {} {}", x, y, horizontal_flip, vertical_flip);
+		}
+	}
+}
+
+#[test]
+fn nine_slice_blits() {
+	use IndexedBlitMethod::*;
+
+	let (mut screen, palette, blend_map) = setup_for_blending();
+
+	let bmp = generate_bitmap(21, 21);
+	let stretched = NineSlice::uniform(7);
+	let tiled = NineSlice::uniform(7).with_fill(NineSliceFill::Tile, NineSliceFill::Tile);
+	let tiled_edges = NineSlice::new(7, 7, 7, 7).with_fill(NineSliceFill::Tile, NineSliceFill::Stretch);
+
+	screen.blit_nine_slice(Solid, &bmp, &stretched, &Rect::new(10, 10, 90, 50));
+	screen.blit_nine_slice(Solid, &bmp, &tiled, &Rect::new(115, 10, 90, 50));
+	screen.blit_nine_slice(Solid, &bmp, &tiled_edges, &Rect::new(220, 10, 90, 50));
+
+	screen.blit_nine_slice(Transparent(0), &bmp, &stretched, &Rect::new(10, 70, 90, 50));
+	screen.blit_nine_slice(
+		TransparentFlipped { transparent_color: 0, horizontal_flip: true, vertical_flip: false },
+		&bmp,
+		&tiled,
+		&Rect::new(115, 70, 90, 50),
+	);
+	screen.blit_nine_slice(SolidOffset(8), &bmp, &tiled_edges, &Rect::new(220, 70, 90, 50));
+	screen.blit_nine_slice(
+		TransparentBlended { transparent_color: 0, blend_map: Rc::new(blend_map) },
+		&bmp,
+		&tiled,
+		&Rect::new(10, 130, 90, 50),
+	);
+
+	// destinations too small to fit the corners
+	screen.blit_nine_slice(Solid, &bmp, &stretched, &Rect::new(115, 130, 10, 9));
+	screen.blit_nine_slice(Solid, &bmp, &stretched, &Rect::new(135, 130, 30, 5));
+
+	let mut atlas = BitmapAtlas::new(generate_bitmap(42, 21));
+	atlas.add(Rect::new(21, 0, 21, 21)).unwrap();
+	screen.blit_atlas_nine_slice(Solid, &atlas, 0, &NineSlice::new(7, 3, 3, 7), &Rect::new(220, 130, 60, 40));
+
+	// clipped
+	screen.blit_nine_slice(Solid, &bmp, &tiled, &Rect::new(-20, 200, 70, 60));
+	screen.blit_nine_slice(Solid, &bmp, &stretched, &Rect::new(280, 195, 60, 60));
+
+	let path = reference_file(Path::new("nine_slice_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), &palette).unwrap();
+	}
+	assert!(verify_visual(&screen, &palette, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn tiled_blits() {
+	use IndexedBlitMethod::*;
+
+	let (mut screen, palette) = setup();
+	screen.clear(247);
+
+	let bmp = generate_bitmap(16, 12);
+
+	screen.blit_tiled(Solid, &bmp, &Rect::new(10, 10, 90, 50), 0, 0);
+	screen.blit_tiled(Solid, &bmp, &Rect::new(115, 10, 90, 50), 5, 3);
+	screen.blit_tiled(Solid, &bmp, &Rect::new(220, 10, 90, 50), -21, -40);
+
+	screen.blit_tiled(Transparent(0), &bmp, &Rect::new(10, 70, 90, 50), 0, 0);
+	screen.blit_tiled(
+		TransparentFlipped { transparent_color: 0, horizontal_flip: true, vertical_flip: false },
+		&bmp,
+		&Rect::new(115, 70, 90, 50),
+		8,
+		0,
+	);
+	screen.blit_tiled(SolidOffset(8), &bmp, &Rect::new(220, 70, 90, 50), 0, 6);
+
+	let mut atlas = BitmapAtlas::new(generate_bitmap(32, 32));
+	atlas.add(Rect::new(4, 4, 12, 12)).unwrap();
+	screen.blit_atlas_tiled(Solid, &atlas, 0, &Rect::new(10, 130, 90, 50), 0, 0);
+	screen.blit_tiled_region(Solid, &bmp, &Rect::new(0, 0, 8, 6), &Rect::new(115, 130, 90, 50), 2, 2);
+
+	// clipped
+	screen.blit_tiled(Solid, &bmp, &Rect::new(-30, 190, 100, 80), 0, 0);
+	screen.blit_tiled(Solid, &bmp, &Rect::new(250, -30, 100, 300), 3, 7);
+
+	let path = reference_file(Path::new("tiled_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), &palette).unwrap();
+	}
+	assert!(verify_visual(&screen, &palette, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn view_drawing() {
+	let (mut screen, palette) = setup();
+	screen.clear(247);
+
+	let font = BitmaskFont::new_vga_font().unwrap();
+	let bmp = generate_bitmap(16, 16);
+	let message = "Hello, world!\nTesting 123";
+
+	// two side-by-side "split-screen" views, drawn to with the same local coordinates
+	for (index, region) in [Rect::new(10, 10, 145, 140), Rect::new(165, 10, 145, 140)].iter().enumerate() {
+		let mut view = screen.view_mut(region).unwrap();
+		view.clear(if index == 0 { 1 } else { 4 });
+		view.rect(0, 0, view.width() as i32 - 1, view.height() as i32 - 1, 15);
+		view.line(-20, -20, 200, 100, 14);
+		view.filled_circle(130, 120, 25, 2);
+		view.print_string(message, 10, 10, FontRenderOpts::Color(15), &font);
+		view.blit(IndexedBlitMethod::Solid, &bmp, -8, 40);
+		view.blit(IndexedBlitMethod::Scaled { scale_x: 3.0, scale_y: 2.0 }, &bmp, 100, 40);
+		view.triangle_2d(&IndexedTriangle2d::Solid {
+			position: [Vector2::new(20.0, 80.0), Vector2::new(10.0, 130.0), Vector2::new(60.0, 110.0)],
+			color: 5,
+		});
+		view.filled_ellipse(95, 125, 30, 12, 6);
+		view.thick_line(70, 60, 140, 70, 4, LineCap::Round, 10);
+		view.filled_polygon(
+			&[Vector2::new(-10.0, 100.0), Vector2::new(30.0, 90.0), Vector2::new(15.0, 150.0)],
+			PolygonFillRule::NonZero,
+			13,
+		);
+		view.curve(
+			&QuadraticBezier::new(Vector2::new(10.0, 135.0), Vector2::new(70.0, 50.0), Vector2::new(140.0, 135.0)),
+			11,
+		);
+
+		// nested view with its own clipping region
+		let mut panel = view.view_mut(&Rect::new(70, 75, 50, 30)).unwrap();
+		panel.clear(8);
+		panel.set_clip_region(&Rect::new(2, 2, 46, 26));
+		panel.print_string("clipped!", 0, 12, FontRenderOpts::Color(11), &font);
+		panel.rounded_rect(4, 4, 45, 25, 5, 12);
+		panel.flood_fill(10, 20, 3, FillConnectivity::Four);
+	}
+
+	// views used as blit sources
+	let copy = screen.clone();
+	let view = copy.view(&Rect::new(20, 20, 60, 30)).unwrap();
+	screen.blit_view(IndexedBlitMethod::Solid, &view, 10, 170);
+	let mut dest = screen.view_mut(&Rect::new(100, 160, 100, 70)).unwrap();
+	dest.blit_view(IndexedBlitMethod::Scaled { scale_x: 2.0, scale_y: 2.0 }, &view, -20, 10);
+	dest.triangle_list_2d(&[
+		IndexedTriangle2d::Solid {
+			position: [Vector2::new(80.0, 5.0), Vector2::new(60.0, 40.0), Vector2::new(110.0, 60.0)],
+			color: 9,
+		},
+		IndexedTriangle2d::Solid {
+			position: [Vector2::new(-10.0, 50.0), Vector2::new(-10.0, 90.0), Vector2::new(30.0, 80.0)],
+			color: 12,
+		},
+	]);
+
+	let path = reference_file(Path::new("view_drawing.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), &palette).unwrap();
+	}
+	assert!(verify_visual(&screen, &palette, &path), "bitmap differs from source image: {:?}", path);
+}
+
 #[test]
 fn triangle_2d() {
 	use IndexedTriangle2d::*;
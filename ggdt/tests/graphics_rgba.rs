@@ -2075,6 +2075,324 @@ fn blended_rotozoom_transparent_blits() {
 	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
 }
 
+fn draw_scaled_blits(
+	screen: &mut RgbaBitmap,
+	bitmap_fn: fn(i32, i32) -> RgbaBitmap,
+	method: impl Fn(f32, f32) -> RgbaBlitMethod,
+) {
+	let bmp16 = bitmap_fn(16, 16);
+	let bmp12 = bitmap_fn(12, 12);
+	let bmp21 = bitmap_fn(21, 21);
+	let bmp3 = bitmap_fn(3, 3);
+
+	let y = 30;
+	screen.blit(method(2.0, 2.0), &bmp16, 40, y);
+	screen.blit(method(3.0, 1.5), &bmp12, 80, y);
+	screen.blit(method(0.5, 1.0), &bmp21, 125, y);
+	screen.blit(method(8.0, 8.0), &bmp3, 145, y);
+	screen.blit(method(1.3, 2.7), &bmp16, 180, y);
+	screen.blit(method(1.0, 1.0), &bmp16, 210, y);
+	screen.blit(method(3.0, 1.0), &bmp16, 235, y);
+
+	let y = 85;
+	unsafe {
+		screen.blit_unchecked(method(2.0, 2.0), &bmp16, 40, y);
+		screen.blit_unchecked(method(3.0, 1.5), &bmp12, 80, y);
+		screen.blit_unchecked(method(0.5, 1.0), &bmp21, 125, y);
+		screen.blit_unchecked(method(8.0, 8.0), &bmp3, 145, y);
+		screen.blit_unchecked(method(1.3, 2.7), &bmp16, 180, y);
+		screen.blit_unchecked(method(1.0, 1.0), &bmp16, 210, y);
+		screen.blit_unchecked(method(3.0, 1.0), &bmp16, 235, y);
+	}
+
+	screen.blit(method(4.0, 3.0), &bmp21, 110, 140);
+
+	//////
+
+	screen.blit(method(2.0, 2.0), &bmp16, -5, 140);
+	screen.blit(method(2.0, 2.0), &bmp16, -20, 175);
+	screen.blit(method(2.0, 2.0), &bmp16, -32, 205);
+
+	screen.blit(method(2.0, 2.0), &bmp16, 293, 140);
+	screen.blit(method(2.0, 2.0), &bmp16, 308, 175);
+	screen.blit(method(2.0, 2.0), &bmp16, 320, 205);
+
+	screen.blit(method(2.0, 2.0), &bmp16, 60, -5);
+	screen.blit(method(2.0, 2.0), &bmp16, 200, -20);
+	screen.blit(method(2.0, 2.0), &bmp16, 250, -32);
+
+	screen.blit(method(2.0, 2.0), &bmp16, 40, 213);
+	screen.blit(method(2.0, 2.0), &bmp16, 220, 228);
+	screen.blit(method(2.0, 2.0), &bmp16, 260, 240);
+}
+
+#[test]
+fn scaled_blits() {
+	use RgbaBlitMethod::*;
+
+	let mut screen = setup();
+	screen.clear(LIGHTER_BACKGROUND);
+
+	draw_scaled_blits(&mut screen, generate_bitmap, |scale_x, scale_y| Scaled { scale_x, scale_y });
+
+	let path = reference_file(Path::new("scaled_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn scaled_tinted_blits() {
+	use RgbaBlitMethod::*;
+
+	let mut screen = setup();
+	screen.clear(LIGHTER_BACKGROUND);
+
+	draw_scaled_blits(&mut screen, generate_bitmap, |scale_x, scale_y| ScaledTinted {
+		scale_x,
+		scale_y,
+		tint_color: RGBA::from_rgba([155, 242, 21, 127]),
+	});
+
+	let path = reference_file(Path::new("scaled_tinted_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn blended_scaled_blits() {
+	use RgbaBlitMethod::*;
+
+	let mut screen = setup_for_blending();
+
+	draw_scaled_blits(&mut screen, generate_solid_bitmap_with_varied_alpha, |scale_x, scale_y| ScaledBlended {
+		scale_x,
+		scale_y,
+		blend: BlendFunction::Blend,
+	});
+
+	let path = reference_file(Path::new("blended_scaled_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn scaled_flipped_blits() {
+	use RgbaBlitMethod::*;
+
+	let mut screen = setup();
+	screen.clear(LIGHTER_BACKGROUND);
+
+	draw_scaled_blits(&mut screen, generate_bitmap, |scale_x, scale_y| ScaledFlipped {
+		scale_x,
+		scale_y,
+		horizontal_flip: true,
+		vertical_flip: false,
+	});
+
+	let path = reference_file(Path::new("scaled_flipped_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn scaled_transparent_blits() {
+	use RgbaBlitMethod::*;
+
+	let mut screen = setup();
+	screen.clear(LIGHTER_BACKGROUND);
+
+	draw_scaled_blits(&mut screen, generate_bitmap, |scale_x, scale_y| ScaledTransparent {
+		scale_x,
+		scale_y,
+		transparent_color: RGBA::from_rgb([0, 0, 0]),
+	});
+
+	let path = reference_file(Path::new("scaled_transparent_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn scaled_transparent_flipped_blits() {
+	use RgbaBlitMethod::*;
+
+	let mut screen = setup();
+	screen.clear(LIGHTER_BACKGROUND);
+
+	draw_scaled_blits(&mut screen, generate_bitmap, |scale_x, scale_y| ScaledTransparentFlipped {
+		scale_x,
+		scale_y,
+		transparent_color: RGBA::from_rgb([0, 0, 0]),
+		horizontal_flip: false,
+		vertical_flip: true,
+	});
+
+	let path = reference_file(Path::new("scaled_transparent_flipped_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn nine_slice_blits() {
+	use RgbaBlitMethod::*;
+
+	let mut screen = setup_for_blending();
+
+	let bmp = generate_bitmap(21, 21);
+	let stretched = NineSlice::uniform(7);
+	let tiled = NineSlice::uniform(7).with_fill(NineSliceFill::Tile, NineSliceFill::Tile);
+	let tiled_edges = NineSlice::new(7, 7, 7, 7).with_fill(NineSliceFill::Tile, NineSliceFill::Stretch);
+
+	screen.blit_nine_slice(Solid, &bmp, &stretched, &Rect::new(10, 10, 90, 50));
+	screen.blit_nine_slice(Solid, &bmp, &tiled, &Rect::new(115, 10, 90, 50));
+	screen.blit_nine_slice(Solid, &bmp, &tiled_edges, &Rect::new(220, 10, 90, 50));
+
+	screen.blit_nine_slice(Transparent(RGBA::from_rgb([0, 0, 0])), &bmp, &stretched, &Rect::new(10, 70, 90, 50));
+	screen.blit_nine_slice(
+		TransparentFlipped {
+			transparent_color: RGBA::from_rgb([0, 0, 0]),
+			horizontal_flip: true,
+			vertical_flip: false,
+		},
+		&bmp,
+		&tiled,
+		&Rect::new(115, 70, 90, 50),
+	);
+	screen.blit_nine_slice(
+		SolidTinted(RGBA::from_rgba([155, 242, 21, 127])),
+		&bmp,
+		&tiled_edges,
+		&Rect::new(220, 70, 90, 50),
+	);
+	screen.blit_nine_slice(
+		TransparentBlended {
+			transparent_color: RGBA::from_rgb([0, 0, 0]),
+			blend: BlendFunction::BlendSourceWithAlpha(128),
+		},
+		&bmp,
+		&tiled,
+		&Rect::new(10, 130, 90, 50),
+	);
+
+	// destinations too small to fit the corners
+	screen.blit_nine_slice(Solid, &bmp, &stretched, &Rect::new(115, 130, 10, 9));
+	screen.blit_nine_slice(Solid, &bmp, &stretched, &Rect::new(135, 130, 30, 5));
+
+	let mut atlas = BitmapAtlas::new(generate_bitmap(42, 21));
+	atlas.add(Rect::new(21, 0, 21, 21)).unwrap();
+	screen.blit_atlas_nine_slice(Solid, &atlas, 0, &NineSlice::new(7, 3, 3, 7), &Rect::new(220, 130, 60, 40));
+
+	// clipped
+	screen.blit_nine_slice(Solid, &bmp, &tiled, &Rect::new(-20, 200, 70, 60));
+	screen.blit_nine_slice(Solid, &bmp, &stretched, &Rect::new(280, 195, 60, 60));
+
+	let path = reference_file(Path::new("nine_slice_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn tiled_blits() {
+	use RgbaBlitMethod::*;
+
+	let mut screen = setup();
+	screen.clear(LIGHTER_BACKGROUND);
+
+	let bmp = generate_bitmap(16, 12);
+
+	screen.blit_tiled(Solid, &bmp, &Rect::new(10, 10, 90, 50), 0, 0);
+	screen.blit_tiled(Solid, &bmp, &Rect::new(115, 10, 90, 50), 5, 3);
+	screen.blit_tiled(Solid, &bmp, &Rect::new(220, 10, 90, 50), -21, -40);
+
+	screen.blit_tiled(Transparent(RGBA::from_rgb([0, 0, 0])), &bmp, &Rect::new(10, 70, 90, 50), 0, 0);
+	screen.blit_tiled(
+		TransparentFlipped {
+			transparent_color: RGBA::from_rgb([0, 0, 0]),
+			horizontal_flip: true,
+			vertical_flip: false,
+		},
+		&bmp,
+		&Rect::new(115, 70, 90, 50),
+		8,
+		0,
+	);
+	screen.blit_tiled(SolidTinted(RGBA::from_rgba([155, 242, 21, 127])), &bmp, &Rect::new(220, 70, 90, 50), 0, 6);
+
+	let mut atlas = BitmapAtlas::new(generate_bitmap(32, 32));
+	atlas.add(Rect::new(4, 4, 12, 12)).unwrap();
+	screen.blit_atlas_tiled(Solid, &atlas, 0, &Rect::new(10, 130, 90, 50), 0, 0);
+	screen.blit_tiled_region(Solid, &bmp, &Rect::new(0, 0, 8, 6), &Rect::new(115, 130, 90, 50), 2, 2);
+
+	// clipped
+	screen.blit_tiled(Solid, &bmp, &Rect::new(-30, 190, 100, 80), 0, 0);
+	screen.blit_tiled(Solid, &bmp, &Rect::new(250, -30, 100, 300), 3, 7);
+
+	let path = reference_file(Path::new("tiled_blits.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn view_drawing() {
+	let mut screen = setup();
+	screen.clear(LIGHTER_BACKGROUND);
+
+	let font = BitmaskFont::new_vga_font().unwrap();
+	let bmp = generate_bitmap(16, 16);
+	let message = "Hello, world!\nTesting 123";
+
+	// two side-by-side "split-screen" views, drawn to with the same local coordinates
+	for (index, region) in [Rect::new(10, 10, 145, 140), Rect::new(165, 10, 145, 140)].iter().enumerate() {
+		let mut view = screen.view_mut(region).unwrap();
+		view.clear(if index == 0 { COLOR_BLUE } else { COLOR_RED });
+		view.rect(0, 0, view.width() as i32 - 1, view.height() as i32 - 1, COLOR_BRIGHT_WHITE);
+		view.line(-20, -20, 200, 100, COLOR_BRIGHT_YELLOW);
+		view.filled_circle(130, 120, 25, COLOR_GREEN);
+		view.print_string(message, 10, 10, FontRenderOpts::Color(COLOR_BRIGHT_WHITE), &font);
+		view.blit(RgbaBlitMethod::Solid, &bmp, -8, 40);
+		view.blit(RgbaBlitMethod::Scaled { scale_x: 3.0, scale_y: 2.0 }, &bmp, 100, 40);
+		view.triangle_2d(&RgbaTriangle2d::Solid {
+			position: [Vector2::new(20.0, 80.0), Vector2::new(10.0, 130.0), Vector2::new(60.0, 110.0)],
+			color: COLOR_MAGENTA,
+		});
+
+		// nested view with its own clipping region
+		let mut panel = view.view_mut(&Rect::new(70, 75, 50, 30)).unwrap();
+		panel.clear(COLOR_DARK_GRAY);
+		panel.set_clip_region(&Rect::new(2, 2, 46, 26));
+		panel.print_string("clipped!", 0, 12, FontRenderOpts::Color(COLOR_BRIGHT_CYAN), &font);
+	}
+
+	// views used as blit sources
+	let copy = screen.clone();
+	let view = copy.view(&Rect::new(20, 20, 60, 30)).unwrap();
+	screen.blit_view(RgbaBlitMethod::Solid, &view, 10, 170);
+	let mut dest = screen.view_mut(&Rect::new(100, 160, 100, 70)).unwrap();
+	dest.blit_view(RgbaBlitMethod::Scaled { scale_x: 2.0, scale_y: 2.0 }, &view, -20, 10);
+
+	let path = reference_file(Path::new("view_drawing.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
 #[test]
 fn blend_function_blend() {
 	let mut screen = setup_for_blending_half_solid_half_semi_transparent();
@@ -2243,6 +2561,163 @@ fn blend_function_multiplied_blend() {
 	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
 }
 
+fn draw_with_blend_function(screen: &mut RgbaBitmap, blend: BlendFunction, premultiplied: bool) {
+	let mut bmp_solid = generate_bitmap(32, 32);
+	let mut bmp_solid_with_varied_alpha = generate_solid_bitmap_with_varied_alpha(32, 32);
+	let mut bmp_with_varied_alpha = generate_bitmap_with_varied_alpha(32, 32);
+	let mut color = COLOR_BRIGHT_YELLOW_HALF_ALPHA;
+	if premultiplied {
+		for bmp in [&mut bmp_solid, &mut bmp_solid_with_varied_alpha, &mut bmp_with_varied_alpha] {
+			bmp.pixels_mut().iter_mut().for_each(|pixel| *pixel = pixel.premultiplied());
+		}
+		color = color.premultiplied();
+	}
+
+	// the same blend function is applied the same way by blits, primitives and triangles
+	for y in [5, 125] {
+		let method = RgbaBlitMethod::SolidBlended(blend);
+		screen.blit(method.clone(), &bmp_solid, 10, y);
+		screen.blit(method.clone(), &bmp_solid_with_varied_alpha, 100, y);
+		screen.blit(method.clone(), &bmp_with_varied_alpha, 200, y);
+
+		let method = RgbaBlitMethod::SolidFlippedBlended { horizontal_flip: true, vertical_flip: false, blend };
+		screen.blit(method.clone(), &bmp_with_varied_alpha, 250, y);
+
+		let method = RgbaBlitMethod::RotoZoomBlended { angle: 0.5, scale_x: 1.2, scale_y: 1.2, blend };
+		screen.blit(method.clone(), &bmp_solid_with_varied_alpha, 130, y + 50);
+
+		screen.blended_filled_rect(10, y + 45, 60, y + 70, color, blend);
+		screen.blended_line(10, y + 80, 100, y + 110, color, blend);
+		screen.blended_filled_ellipse(220, y + 80, 30, 20, color, blend);
+		screen.solid_blended_triangle_2d(
+			&[
+				Vector2::new(60.0, y as f32 + 50.0),
+				Vector2::new(40.0, y as f32 + 105.0),
+				Vector2::new(110.0, y as f32 + 100.0),
+			],
+			color,
+			blend,
+		);
+	}
+}
+
+#[test]
+fn blend_function_additive() {
+	let mut screen = setup_for_blending_half_solid_half_semi_transparent();
+
+	draw_with_blend_function(&mut screen, BlendFunction::Additive, false);
+
+	let path = reference_file(Path::new("blend_function_additive.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn blend_function_subtractive() {
+	let mut screen = setup_for_blending_half_solid_half_semi_transparent();
+
+	draw_with_blend_function(&mut screen, BlendFunction::Subtractive, false);
+
+	let path = reference_file(Path::new("blend_function_subtractive.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn blend_function_multiply() {
+	let mut screen = setup_for_blending_half_solid_half_semi_transparent();
+
+	draw_with_blend_function(&mut screen, BlendFunction::Multiply, false);
+
+	let path = reference_file(Path::new("blend_function_multiply.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn blend_function_screen() {
+	let mut screen = setup_for_blending_half_solid_half_semi_transparent();
+
+	draw_with_blend_function(&mut screen, BlendFunction::Screen, false);
+
+	let path = reference_file(Path::new("blend_function_screen.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn blend_function_overlay() {
+	let mut screen = setup_for_blending_half_solid_half_semi_transparent();
+
+	draw_with_blend_function(&mut screen, BlendFunction::Overlay, false);
+
+	let path = reference_file(Path::new("blend_function_overlay.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn blend_function_darken() {
+	let mut screen = setup_for_blending_half_solid_half_semi_transparent();
+
+	draw_with_blend_function(&mut screen, BlendFunction::Darken, false);
+
+	let path = reference_file(Path::new("blend_function_darken.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn blend_function_lighten() {
+	let mut screen = setup_for_blending_half_solid_half_semi_transparent();
+
+	draw_with_blend_function(&mut screen, BlendFunction::Lighten, false);
+
+	let path = reference_file(Path::new("blend_function_lighten.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn blend_function_premultiplied_blend() {
+	let mut screen = setup_for_blending_half_solid_half_semi_transparent();
+
+	draw_with_blend_function(&mut screen, BlendFunction::PremultipliedBlend, true);
+
+	let path = reference_file(Path::new("blend_function_premultiplied_blend.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
+#[test]
+fn blend_function_premultiplied_blend_source_with_alpha() {
+	let mut screen = setup_for_blending_half_solid_half_semi_transparent();
+
+	draw_with_blend_function(&mut screen, BlendFunction::PremultipliedBlendSourceWithAlpha(127), true);
+
+	let path = reference_file(Path::new("blend_function_premultiplied_blend_source_with_alpha.png"));
+	if cfg!(recreate_ref_test_images) {
+		screen.to_png_file(path.as_path(), PngFormat::RGBA).unwrap();
+	}
+	assert!(verify_visual(&screen, &path), "bitmap differs from source image: {:?}", path);
+}
+
 #[test]
 fn triangle_2d() {
 	use RgbaTriangle2d::*;